@@ -51,26 +51,24 @@ async fn decode_instruction(Json(payload): Json<DecodeInstruction>) -> impl Into
     let instruction: UiInstruction = Into::into(payload);
     match DECODE_MATCHER.try_new_decoder(instruction) {
         Ok(decoder) => match decoder.decode() {
-            Ok(decoded) => return (StatusCode::OK, Json(decoded)).into_response(),
-            Err(err) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(Error {
-                        msg: format!("failed to decode instruction {err:#?}"),
-                    }),
-                )
-                    .into_response()
-            }
+            Ok(decoded) => (StatusCode::OK, Json(decoded)).into_response(),
+            Err(err) => (
+                StatusCode::BAD_REQUEST,
+                Json(Error {
+                    msg: format!("failed to decode instruction {err:#?}"),
+                }),
+            )
+                .into_response(),
         },
         Err(err) => {
             // log
-            return (
+            (
                 StatusCode::BAD_REQUEST,
                 Json(Error {
                     msg: err.to_string(),
                 }),
             )
-                .into_response();
+                .into_response()
         }
     }
 }
@@ -18,20 +18,20 @@ pub struct Error {
     pub msg: String,
 }
 
-impl Into<UiPartiallyDecodedInstruction> for DecodeInstruction {
-    fn into(self) -> UiPartiallyDecodedInstruction {
+impl From<DecodeInstruction> for UiPartiallyDecodedInstruction {
+    fn from(value: DecodeInstruction) -> Self {
         UiPartiallyDecodedInstruction {
-            data: self.data,
-            accounts: self.accounts,
-            program_id: self.program_id,
-            stack_height: self.stack_height
+            data: value.data,
+            accounts: value.accounts,
+            program_id: value.program_id,
+            stack_height: value.stack_height
         }
     }
 }
 
-impl Into<UiInstruction> for DecodeInstruction {
-    fn into(self) -> UiInstruction {
-        let partial: UiPartiallyDecodedInstruction = Into::into(self);
+impl From<DecodeInstruction> for UiInstruction {
+    fn from(value: DecodeInstruction) -> Self {
+        let partial: UiPartiallyDecodedInstruction = Into::into(value);
         UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(partial))
     }
 }
\ No newline at end of file
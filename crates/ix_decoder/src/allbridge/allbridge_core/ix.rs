@@ -0,0 +1,66 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use super::address_format;
+use crate::{
+    anchor,
+    types::{format_address, serialize_hex, BridgeTransfer},
+};
+
+pub const SWAP_AND_BRIDGE: [u8; 8] = [204, 63, 169, 171, 186, 125, 86, 159];
+pub const RECEIVE_TOKENS: [u8; 8] = [229, 73, 222, 185, 57, 227, 213, 67];
+
+/// Swaps `amount` of the pool token into the bridge's virtual usd and sends it to be
+/// swapped into `receive_token` on the destination chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapAndBridgeData {
+    pub amount: u64,
+    #[serde(serialize_with = "serialize_hex")]
+    pub recipient: [u8; 32],
+    pub destination_chain_id: u8,
+    #[serde(serialize_with = "serialize_hex")]
+    pub receive_token: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub nonce: [u8; 32],
+}
+
+impl SwapAndBridgeData {
+    pub fn bridge_transfer(&self) -> BridgeTransfer {
+        let format = address_format(self.destination_chain_id);
+        BridgeTransfer {
+            destination_chain_id: self.destination_chain_id.into(),
+            destination_address: format_address(format, &self.recipient),
+            token: format_address(format, &self.receive_token),
+            amount: self.amount.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapAndBridgeInstruction {
+    pub args: SwapAndBridgeData,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AllbridgeCoreInstruction {
+    SwapAndBridge(SwapAndBridgeInstruction),
+    /// the transfer is read from the message received by the messenger
+    ReceiveTokens,
+}
+
+impl AllbridgeCoreInstruction {
+    /// Unpacks a byte buffer into a [AllbridgeCoreInstruction](enum.AllbridgeCoreInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            SWAP_AND_BRIDGE => Self::SwapAndBridge(anchor::deserialize(rest)?),
+            RECEIVE_TOKENS => Self::ReceiveTokens,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
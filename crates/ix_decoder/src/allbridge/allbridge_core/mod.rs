@@ -0,0 +1,129 @@
+//! Instruction decoding functions for the allbridge core program
+//!
+//! Transfers leave solana through `swap_and_bridge` and arrive through `receive_tokens`,
+//! the pools on either side swapping to and from a virtual usd.
+
+use anyhow::Context;
+use ix::AllbridgeCoreInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, AddressFormat, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("BrdgN2RPzEMWF96ZbnnJaUtQDQx7VRXYaHHbYCBvceWB");
+
+/// the chain id allbridge assigns to solana
+pub const SOLANA_CHAIN_ID: u8 = 4;
+
+/// Returns the address format of a chain, by its allbridge chain id
+pub fn address_format(chain_id: u8) -> AddressFormat {
+    match chain_id {
+        SOLANA_CHAIN_ID => AddressFormat::Solana,
+        // ethereum, bsc, polygon, arbitrum, avalanche, base, optimism and celo
+        1 | 2 | 5 | 6 | 8 | 9 | 10 | 11 => AddressFormat::Evm,
+        _ => AddressFormat::Hex,
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AllbridgeCoreDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl AllbridgeCoreDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = AllbridgeCoreInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            AllbridgeCoreInstruction::SwapAndBridge(ix) => {
+                let mut data = to_data_map(&ix)?;
+                data.extend(to_data_map(&ix.args.bridge_transfer())?);
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&[
+                        "mint",
+                        "user",
+                        "config",
+                        "lock",
+                        "pool",
+                        "gasUsage",
+                        "bridgeAuthority",
+                        "userToken",
+                        "bridgeToken",
+                        "chainBridge",
+                        "messenger",
+                        "messengerGasUsage",
+                        "messengerConfig",
+                        "sentMessageAccount",
+                        "otherBridgeToken",
+                        "systemProgram",
+                        "tokenProgram",
+                    ]),
+                    name: "swapAndBridge".to_string(),
+                })
+            }
+            AllbridgeCoreInstruction::ReceiveTokens => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "mint",
+                    "user",
+                    "config",
+                    "lock",
+                    "pool",
+                    "bridgeAuthority",
+                    "recipient",
+                    "recipientToken",
+                    "bridgeToken",
+                    "chainBridge",
+                    "receivedMessageAccount",
+                    "messenger",
+                    "systemProgram",
+                    "tokenProgram",
+                ]),
+                name: "receiveTokens".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_swap_and_bridge() {
+        let user = Pubkey::new_unique();
+        let mut recipient = [0u8; 32];
+        recipient[12..].copy_from_slice(&[0xbb; 20]);
+        let mut receive_token = [0u8; 32];
+        receive_token[12..].copy_from_slice(&[0xaa; 20]);
+        let mut data = ix::SWAP_AND_BRIDGE.to_vec();
+        data.extend(to_vec(&(25_000_000u64, recipient, 2u8, receive_token, [1u8; 32])).unwrap());
+        let decoded = AllbridgeCoreDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique(), user],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "swapAndBridge");
+        assert_eq!(decoded.accounts["user"], user.to_string());
+        assert_eq!(decoded.data["destinationChainId"], 2);
+        assert_eq!(
+            decoded.data["destinationAddress"],
+            format!("0x{}", hex::encode([0xbb; 20]))
+        );
+        assert_eq!(
+            decoded.data["token"],
+            format!("0x{}", hex::encode([0xaa; 20]))
+        );
+        assert_eq!(decoded.data["amount"], "25000000");
+        assert_eq!(decoded.data["args"]["amount"], 25_000_000);
+    }
+}
@@ -0,0 +1,47 @@
+//! Instruction decoding functions for the allbridge programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use allbridge_core::AllbridgeCoreDecoder;
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod allbridge_core;
+
+pub static ALLBRIDGE_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(AllbridgeProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Core(AllbridgeCoreDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct AllbridgeProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Core(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Allbridge::Core"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Core(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for AllbridgeProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            allbridge_core::PROGRAM_ID => Some(Box::new(Programs::Core(
+                AllbridgeCoreDecoder::new(ix.clone()),
+            ))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Helpers for decoding the instructions, events and accounts of anchor programs
+//!
+//! Anchor prefixes instruction data with the first 8 bytes of `sha256("global:<instruction_name>")`,
+//! account data with the first 8 bytes of `sha256("account:<AccountName>")` and events with the
+//! first 8 bytes of `sha256("event:<EventName>")`.
+
+use anyhow::{anyhow, Context};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshDeserialize;
+use solana_sdk::{hash::hash, pubkey::Pubkey};
+
+/// Prefix of the instruction data used by anchor's `emit_cpi!` to log events through a self cpi
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+/// Returns the discriminator of the instruction with the given snake case name
+pub fn instruction_discriminator(name: &str) -> [u8; 8] {
+    discriminator("global", name)
+}
+
+/// Returns the discriminator of the account with the given name
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    discriminator("account", name)
+}
+
+/// Returns the discriminator of the event with the given name
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    discriminator("event", name)
+}
+
+fn discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("{namespace}:{name}").as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Splits instruction, event or account data into its discriminator and remaining bytes
+pub fn split_discriminator(data: &[u8]) -> anyhow::Result<([u8; 8], &[u8])> {
+    if data.len() < 8 {
+        return Err(anyhow!("data too short to contain a discriminator"));
+    }
+    let (discriminator, rest) = data.split_at(8);
+    Ok((discriminator.try_into()?, rest))
+}
+
+/// Returns the event data of a self cpi event instruction, or None if the data isn't an event
+pub fn strip_event_tag(data: &[u8]) -> Option<&[u8]> {
+    data.strip_prefix(&EVENT_IX_TAG)
+}
+
+/// Returns the event data logged by the program through anchor's `emit!`, in log order.
+///
+/// `emit!` writes events as base64 encoded `Program data:` lines, which are attributed
+/// to the program at the top of the invocation stack tracked through the invoke logs.
+pub fn log_events(logs: &[String], program_id: &Pubkey) -> Vec<Vec<u8>> {
+    program_logs(logs, program_id, "Program data: ")
+        .into_iter()
+        .flat_map(|data| data.split(' '))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .collect()
+}
+
+/// Returns the messages logged by the program through `msg!`, in log order
+pub fn log_messages<'a>(logs: &'a [String], program_id: &Pubkey) -> Vec<&'a str> {
+    program_logs(logs, program_id, "Program log: ")
+}
+
+/// Returns the logs starting with `prefix` written while the program was at the top of
+/// the invocation stack, with the prefix removed
+fn program_logs<'a>(logs: &'a [String], program_id: &Pubkey, prefix: &str) -> Vec<&'a str> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut lines = Vec::new();
+    for log in logs {
+        if let Some(line) = log.strip_prefix(prefix) {
+            if stack.last() == Some(&program_id.as_str()) {
+                lines.push(line);
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split(' ');
+            match (parts.next(), parts.next()) {
+                (Some(program), Some("invoke")) => stack.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+    lines
+}
+
+/// Borsh deserializes `T` from the start of `data`.
+///
+/// Trailing bytes are ignored so that arguments appended by newer program versions
+/// don't prevent decoding the arguments we know about.
+pub fn deserialize<T: BorshDeserialize>(data: &[u8]) -> anyhow::Result<T> {
+    let mut data = data;
+    T::deserialize(&mut data).with_context(|| "failed to deserialize")
+}
+
+/// Deserializes the account data, checking it carries the expected discriminator
+pub fn unpack_account<T: BorshDeserialize>(
+    data: &[u8],
+    expected_discriminator: [u8; 8],
+) -> anyhow::Result<T> {
+    let (discriminator, rest) = split_discriminator(data)?;
+    if discriminator != expected_discriminator {
+        return Err(anyhow!("invalid account discriminator"));
+    }
+    deserialize(rest)
+}
@@ -0,0 +1,89 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    debridge::Offer,
+    types::{serialize_hex, serialize_option_hex, serialize_option_pubkey, BridgeTransfer},
+};
+
+pub const FULFILL_ORDER: [u8; 8] = [61, 214, 39, 248, 65, 212, 153, 36];
+pub const SEND_UNLOCK: [u8; 8] = [197, 114, 196, 249, 170, 75, 173, 204];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalCallParams {
+    #[serde(serialize_with = "serialize_hex")]
+    pub external_call_shortcut: [u8; 32],
+}
+
+/// An order created on another chain, addresses being in the format of the chain they
+/// belong to
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub maker_order_nonce: u64,
+    #[serde(serialize_with = "serialize_hex")]
+    pub maker_src: Vec<u8>,
+    pub give: Offer,
+    pub take: Offer,
+    #[serde(serialize_with = "serialize_hex")]
+    pub receiver_dst: Vec<u8>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub give_patch_authority_src: Vec<u8>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub order_authority_address_dst: Vec<u8>,
+    #[serde(serialize_with = "serialize_option_hex")]
+    pub allowed_taker_dst: Option<Vec<u8>>,
+    #[serde(serialize_with = "serialize_option_hex")]
+    pub allowed_cancel_beneficiary_src: Option<Vec<u8>>,
+    pub external_call: Option<ExternalCallParams>,
+}
+
+/// A taker filling an order, the order being validated against its id
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FulfillOrderInstruction {
+    pub unvalidated_order: Order,
+    #[serde(serialize_with = "serialize_hex")]
+    pub order_id: [u8; 32],
+    /// receives the unlock on the source chain, the taker when not set
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub unlock_authority: Option<Pubkey>,
+}
+
+impl FulfillOrderInstruction {
+    pub fn bridge_transfer(&self) -> anyhow::Result<BridgeTransfer> {
+        let order = &self.unvalidated_order;
+        order.take.to_bridge_transfer(&order.receiver_dst)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendUnlockInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub order_id: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DlnDestinationInstruction {
+    FulfillOrder(Box<FulfillOrderInstruction>),
+    SendUnlock(SendUnlockInstruction),
+}
+
+impl DlnDestinationInstruction {
+    /// Unpacks a byte buffer into a [DlnDestinationInstruction](enum.DlnDestinationInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            FULFILL_ORDER => Self::FulfillOrder(Box::new(anchor::deserialize(rest)?)),
+            SEND_UNLOCK => Self::SendUnlock(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,111 @@
+//! Instruction decoding functions for the dln destination program, where orders arriving
+//! on solana are fulfilled and the unlocks for their takers sent back
+
+use anyhow::Context;
+use ix::DlnDestinationInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("dst5MGcFPoBeREFAA5E3tU5ij8m5uVYwkzkSAbsLbNo");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DlnDestinationDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DlnDestinationDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DlnDestinationInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DlnDestinationInstruction::FulfillOrder(ix) => {
+                let mut data = to_data_map(&ix)?;
+                data.extend(to_data_map(&ix.bridge_transfer()?)?);
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&[
+                        "takeOrderState",
+                        "taker",
+                        "takerWallet",
+                        "receiverDst",
+                        "authorizedSrcContract",
+                        "takeOrderPatch",
+                        "state",
+                        "splTokenProgram",
+                        "systemProgram",
+                    ]),
+                    name: "fulfillOrder".to_string(),
+                })
+            }
+            DlnDestinationInstruction::SendUnlock(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "takeOrderState",
+                    "authorizedSrcContract",
+                    "unlocker",
+                    "state",
+                    "debridgeProgram",
+                ]),
+                name: "sendUnlock".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debridge::SOLANA_CHAIN_ID;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_fulfill_order() {
+        let taker = Pubkey::new_unique();
+        let receiver = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut give_chain = [0u8; 32];
+        give_chain[31] = 1;
+        let mut take_chain = [0u8; 32];
+        take_chain[24..].copy_from_slice(&SOLANA_CHAIN_ID.to_be_bytes());
+        let mut amount = [0u8; 32];
+        amount[24..].copy_from_slice(&5_000_000u64.to_be_bytes());
+        let mut data = ix::FULFILL_ORDER.to_vec();
+        data.extend(
+            to_vec(&(
+                3u64,
+                vec![0xccu8; 20],
+                (give_chain, vec![0xaau8; 20], amount),
+                (take_chain, mint.to_bytes().to_vec(), amount),
+                receiver.to_bytes().to_vec(),
+                vec![0xccu8; 20],
+                receiver.to_bytes().to_vec(),
+                None::<Vec<u8>>,
+                None::<Vec<u8>>,
+                None::<[u8; 32]>,
+            ))
+            .unwrap(),
+        );
+        data.extend(to_vec(&([9u8; 32], None::<Pubkey>)).unwrap());
+        let decoded = DlnDestinationDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique(), taker],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "fulfillOrder");
+        assert_eq!(decoded.accounts["taker"], taker.to_string());
+        assert_eq!(decoded.data["destinationChainId"], SOLANA_CHAIN_ID);
+        assert_eq!(decoded.data["destinationAddress"], receiver.to_string());
+        assert_eq!(decoded.data["token"], mint.to_string());
+        assert_eq!(decoded.data["amount"], "5000000");
+        assert_eq!(decoded.data["orderId"], hex::encode([9u8; 32]));
+    }
+}
@@ -0,0 +1,101 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    debridge::Offer,
+    types::{
+        serialize_hex, serialize_option_hex, serialize_option_pubkey, serialize_pubkey,
+        BridgeTransfer,
+    },
+};
+
+pub const CREATE_ORDER: [u8; 8] = [141, 54, 37, 207, 237, 210, 250, 215];
+pub const CREATE_ORDER_WITH_NONCE: [u8; 8] = [130, 131, 98, 190, 40, 206, 68, 50];
+pub const CLAIM_UNLOCK: [u8; 8] = [89, 81, 180, 79, 142, 144, 66, 251];
+pub const CLAIM_ORDER_CANCEL: [u8; 8] = [19, 97, 126, 238, 204, 141, 69, 76];
+
+/// The order being placed, giving `give_original_amount` of the mint for the `take`
+/// offer on the destination chain
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderArgs {
+    pub give_original_amount: u64,
+    pub take: Offer,
+    #[serde(serialize_with = "serialize_hex")]
+    pub receiver_dst: Vec<u8>,
+    #[serde(serialize_with = "serialize_option_hex")]
+    pub external_call: Option<Vec<u8>>,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub give_patch_authority_src: Pubkey,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub allowed_cancel_beneficiary_src: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub order_authority_address_dst: Vec<u8>,
+    #[serde(serialize_with = "serialize_option_hex")]
+    pub allowed_taker_dst: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffiliateFee {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderInstruction {
+    pub order_args: CreateOrderArgs,
+    pub affiliate_fee: Option<AffiliateFee>,
+    pub referral_code: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderWithNonceInstruction {
+    pub order_args: CreateOrderArgs,
+    pub affiliate_fee: Option<AffiliateFee>,
+    pub referral_code: Option<u32>,
+    pub nonce: u64,
+}
+
+impl CreateOrderArgs {
+    pub fn bridge_transfer(&self) -> anyhow::Result<BridgeTransfer> {
+        self.take.to_bridge_transfer(&self.receiver_dst)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderIdInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub order_id: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DlnSourceInstruction {
+    CreateOrder(CreateOrderInstruction),
+    CreateOrderWithNonce(CreateOrderWithNonceInstruction),
+    ClaimUnlock(OrderIdInstruction),
+    ClaimOrderCancel(OrderIdInstruction),
+}
+
+impl DlnSourceInstruction {
+    /// Unpacks a byte buffer into a [DlnSourceInstruction](enum.DlnSourceInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE_ORDER => Self::CreateOrder(anchor::deserialize(rest)?),
+            CREATE_ORDER_WITH_NONCE => Self::CreateOrderWithNonce(anchor::deserialize(rest)?),
+            CLAIM_UNLOCK => Self::ClaimUnlock(anchor::deserialize(rest)?),
+            CLAIM_ORDER_CANCEL => Self::ClaimOrderCancel(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
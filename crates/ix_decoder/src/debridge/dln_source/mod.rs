@@ -0,0 +1,139 @@
+//! Instruction decoding functions for the dln source program, where orders leaving solana
+//! are created and their unlocks claimed
+
+use anyhow::Context;
+use ix::DlnSourceInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("src5qyZHqTqecJV4aY6Cb6zDZLMDzrDKKezs22MPHr4");
+
+const CREATE_ORDER_ACCOUNTS: [&str; 12] = [
+    "maker",
+    "state",
+    "tokenMint",
+    "giveOrderState",
+    "authorizedNativeSender",
+    "makerWallet",
+    "giveOrderWallet",
+    "nonceMaster",
+    "feeLedgerWallet",
+    "systemProgram",
+    "splTokenProgram",
+    "associatedSplTokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DlnSourceDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DlnSourceDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DlnSourceInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DlnSourceInstruction::CreateOrder(ix) => {
+                let mut data = to_data_map(&ix)?;
+                data.extend(to_data_map(&ix.order_args.bridge_transfer()?)?);
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&CREATE_ORDER_ACCOUNTS),
+                    name: "createOrder".to_string(),
+                })
+            }
+            DlnSourceInstruction::CreateOrderWithNonce(ix) => {
+                let mut data = to_data_map(&ix)?;
+                data.extend(to_data_map(&ix.order_args.bridge_transfer()?)?);
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&CREATE_ORDER_ACCOUNTS),
+                    name: "createOrderWithNonce".to_string(),
+                })
+            }
+            DlnSourceInstruction::ClaimUnlock(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "authorizedSrcContract",
+                    "state",
+                    "giveOrderState",
+                    "giveOrderWallet",
+                    "unlockBeneficiaryWallet",
+                    "splTokenProgram",
+                ]),
+                name: "claimUnlock".to_string(),
+            }),
+            DlnSourceInstruction::ClaimOrderCancel(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "authorizedSrcContract",
+                    "state",
+                    "giveOrderState",
+                    "giveOrderWallet",
+                    "cancelBeneficiaryWallet",
+                    "splTokenProgram",
+                ]),
+                name: "claimOrderCancel".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_create_order() {
+        let maker = Pubkey::new_unique();
+        let token = [0xaau8; 20];
+        let receiver = [0xbbu8; 20];
+        let mut chain_id = [0u8; 32];
+        chain_id[30..].copy_from_slice(&42161u16.to_be_bytes());
+        let mut amount = [0u8; 32];
+        amount[16..].copy_from_slice(&(10u128.pow(21)).to_be_bytes());
+        let mut data = ix::CREATE_ORDER.to_vec();
+        data.extend(
+            to_vec(&(
+                1_000_000u64,
+                chain_id,
+                token.to_vec(),
+                amount,
+                receiver.to_vec(),
+                None::<Vec<u8>>,
+                maker,
+                None::<Pubkey>,
+                receiver.to_vec(),
+                None::<Vec<u8>>,
+            ))
+            .unwrap(),
+        );
+        data.extend(to_vec(&(None::<(Pubkey, u64)>, Some(7u32))).unwrap());
+        let decoded = DlnSourceDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![maker],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "createOrder");
+        assert_eq!(decoded.accounts["maker"], maker.to_string());
+        assert_eq!(decoded.data["destinationChainId"], 42161);
+        assert_eq!(
+            decoded.data["destinationAddress"],
+            format!("0x{}", hex::encode(receiver))
+        );
+        assert_eq!(decoded.data["token"], format!("0x{}", hex::encode(token)));
+        assert_eq!(decoded.data["amount"], "1000000000000000000000");
+        assert_eq!(decoded.data["orderArgs"]["giveOriginalAmount"], 1_000_000);
+        assert_eq!(decoded.data["referralCode"], 7);
+    }
+}
@@ -0,0 +1,129 @@
+//! Instruction decoding functions for the debridge liquidity network (dln) programs
+//!
+//! Orders are created on the source chain and fulfilled by takers on the destination
+//! chain, which then sends an unlock back for the taker to claim the given tokens.
+//! Chain ids and amounts are big endian u256, addresses are raw bytes of the chain they
+//! belong to.
+
+use crate::types::{
+    format_address, serialize_hex, AddressFormat, BridgeTransfer, PartiallyDecodedInstruction,
+    ProgramDecoder, ProgramDecoderMatcher,
+};
+use anyhow::{anyhow, Context};
+use borsh::BorshDeserialize;
+use dln_destination::DlnDestinationDecoder;
+use dln_source::DlnSourceDecoder;
+use once_cell::sync::Lazy;
+use serde::{Serialize, Serializer};
+use std::sync::Arc;
+
+pub mod dln_destination;
+pub mod dln_source;
+
+/// the chain id dln assigns to solana, other chains using their evm chain id
+pub const SOLANA_CHAIN_ID: u64 = 7565164;
+
+pub static DEBRIDGE_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(DebridgeProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    DlnSource(DlnSourceDecoder),
+    DlnDestination(DlnDestinationDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct DebridgeProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::DlnSource(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Debridge::DlnSource"),
+            Self::DlnDestination(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Debridge::DlnDestination"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::DlnSource(decoder) => format!("{:#?}", decoder),
+            Self::DlnDestination(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for DebridgeProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            dln_source::PROGRAM_ID => Some(Box::new(Programs::DlnSource(DlnSourceDecoder::new(
+                ix.clone(),
+            )))),
+            dln_destination::PROGRAM_ID => Some(Box::new(Programs::DlnDestination(
+                DlnDestinationDecoder::new(ix.clone()),
+            ))),
+            _ => None,
+        }
+    }
+}
+
+/// An amount of a token on a given chain, the give or take side of an order
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Offer {
+    #[serde(serialize_with = "serialize_u256")]
+    pub chain_id: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub token_address: Vec<u8>,
+    #[serde(serialize_with = "serialize_u256")]
+    pub amount: [u8; 32],
+}
+
+impl Offer {
+    pub fn chain_id(&self) -> anyhow::Result<u64> {
+        let (high, low) = self.chain_id.split_at(24);
+        if high.iter().any(|&b| b != 0) {
+            return Err(anyhow!("chain id overflows u64"));
+        }
+        Ok(u64::from_be_bytes(low.try_into()?))
+    }
+    /// Returns the transfer of the offer to the given address on the offer's chain
+    pub fn to_bridge_transfer(&self, receiver: &[u8]) -> anyhow::Result<BridgeTransfer> {
+        let chain_id = self.chain_id()?;
+        let format = match chain_id {
+            SOLANA_CHAIN_ID => AddressFormat::Solana,
+            _ => AddressFormat::Evm,
+        };
+        Ok(BridgeTransfer {
+            destination_chain_id: chain_id,
+            destination_address: format_address(format, receiver),
+            token: format_address(format, &self.token_address),
+            amount: u256_to_string(&self.amount),
+        })
+    }
+}
+
+/// Formats a big endian u256 as a decimal string
+pub fn u256_to_string(value: &[u8; 32]) -> String {
+    let mut value = *value;
+    let mut digits = vec![];
+    while value.iter().any(|&b| b != 0) {
+        let mut remainder = 0u32;
+        for byte in value.iter_mut() {
+            let acc = (remainder << 8) | *byte as u32;
+            *byte = (acc / 10) as u8;
+            remainder = acc % 10;
+        }
+        digits.push(char::from(b'0' + remainder as u8));
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().collect()
+}
+
+pub fn serialize_u256<S: Serializer>(value: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&u256_to_string(value))
+}
@@ -0,0 +1,45 @@
+//! Instruction decoding functions for the drift programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use v2::DriftV2Decoder;
+
+pub mod v2;
+
+pub static DRIFT_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(DriftProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    V2(DriftV2Decoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct DriftProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::V2(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Drift::V2"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::V2(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for DriftProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            v2::PROGRAM_ID => Some(Box::new(Programs::V2(DriftV2Decoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
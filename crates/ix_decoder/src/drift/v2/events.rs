@@ -0,0 +1,313 @@
+//! Event types, emitted by the program through logs
+//!
+//! Explanations are kept as the index of the program's explanation enums, which grow
+//! with every program release.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::ix::{MarketType, PositionDirection};
+use crate::{
+    anchor,
+    types::{
+        serialize_i128, serialize_option_pubkey, serialize_option_u128, serialize_pubkey,
+        serialize_u128, to_data_map, DecodedInstruction,
+    },
+};
+
+pub const ORDER_ACTION_RECORD: [u8; 8] = [224, 52, 67, 71, 194, 237, 109, 1];
+pub const DEPOSIT_RECORD: [u8; 8] = [180, 241, 218, 207, 102, 135, 44, 134];
+pub const SETTLE_PNL_RECORD: [u8; 8] = [57, 68, 105, 26, 119, 198, 213, 89];
+pub const FUNDING_PAYMENT_RECORD: [u8; 8] = [8, 59, 96, 20, 137, 201, 56, 95];
+pub const LIQUIDATION_RECORD: [u8; 8] = [127, 17, 0, 108, 182, 13, 231, 53];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderAction {
+    Place,
+    Cancel,
+    Fill,
+    Trigger,
+    Expire,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DepositDirection {
+    Deposit,
+    Withdraw,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiquidationType {
+    LiquidatePerp,
+    LiquidateSpot,
+    LiquidateBorrowForPerpPnl,
+    LiquidatePerpPnlForDeposit,
+    PerpBankruptcy,
+    SpotBankruptcy,
+}
+
+/// An order being placed, cancelled, filled, triggered or expiring. Fill fields are only
+/// set for fills, and the taker and maker fields for the sides taking part in the action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderActionRecord {
+    pub ts: i64,
+    pub action: OrderAction,
+    pub action_explanation: u8,
+    pub market_index: u16,
+    pub market_type: MarketType,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub filler: Option<Pubkey>,
+    pub filler_reward: Option<u64>,
+    pub fill_record_id: Option<u64>,
+    pub base_asset_amount_filled: Option<u64>,
+    pub quote_asset_amount_filled: Option<u64>,
+    pub taker_fee: Option<u64>,
+    /// negative for maker rebates
+    pub maker_fee: Option<i64>,
+    pub referrer_reward: Option<u32>,
+    pub quote_asset_amount_surplus: Option<i64>,
+    pub spot_fulfillment_method_fee: Option<u64>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub taker: Option<Pubkey>,
+    pub taker_order_id: Option<u32>,
+    pub taker_order_direction: Option<PositionDirection>,
+    pub taker_order_base_asset_amount: Option<u64>,
+    pub taker_order_cumulative_base_asset_amount_filled: Option<u64>,
+    pub taker_order_cumulative_quote_asset_amount_filled: Option<u64>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub maker: Option<Pubkey>,
+    pub maker_order_id: Option<u32>,
+    pub maker_order_direction: Option<PositionDirection>,
+    pub maker_order_base_asset_amount: Option<u64>,
+    pub maker_order_cumulative_base_asset_amount_filled: Option<u64>,
+    pub maker_order_cumulative_quote_asset_amount_filled: Option<u64>,
+    pub oracle_price: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositRecord {
+    pub ts: i64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_authority: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    pub direction: DepositDirection,
+    pub deposit_record_id: u64,
+    /// in native units of the market's mint
+    pub amount: u64,
+    pub market_index: u16,
+    pub oracle_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_deposit_balance: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_withdraw_balance: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_cumulative_deposit_interest: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_cumulative_borrow_interest: u128,
+    pub total_deposits_after: u64,
+    pub total_withdraws_after: u64,
+    pub explanation: u8,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub transfer_user: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlePnlRecord {
+    pub ts: i64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    pub market_index: u16,
+    /// in quote units, with 6 decimals
+    #[serde(serialize_with = "serialize_i128")]
+    pub pnl: i128,
+    pub base_asset_amount: i64,
+    pub quote_asset_amount_after: i64,
+    pub quote_entry_amount: i64,
+    pub settle_price: i64,
+    pub explanation: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingPaymentRecord {
+    pub ts: i64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_authority: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    pub market_index: u16,
+    pub funding_payment: i64,
+    pub base_asset_amount: i64,
+    pub user_last_cumulative_funding: i64,
+    #[serde(serialize_with = "serialize_i128")]
+    pub amm_cumulative_funding_long: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub amm_cumulative_funding_short: i128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidatePerpRecord {
+    pub market_index: u16,
+    pub oracle_price: i64,
+    pub base_asset_amount: i64,
+    pub quote_asset_amount: i64,
+    pub lp_shares: u64,
+    pub fill_record_id: u64,
+    pub user_order_id: u32,
+    pub liquidator_order_id: u32,
+    pub liquidator_fee: u64,
+    pub if_fee: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidateSpotRecord {
+    pub asset_market_index: u16,
+    pub asset_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub asset_transfer: u128,
+    pub liability_market_index: u16,
+    pub liability_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liability_transfer: u128,
+    pub if_fee: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidateBorrowForPerpPnlRecord {
+    pub perp_market_index: u16,
+    pub market_oracle_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub pnl_transfer: u128,
+    pub liability_market_index: u16,
+    pub liability_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liability_transfer: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidatePerpPnlForDepositRecord {
+    pub perp_market_index: u16,
+    pub market_oracle_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub pnl_transfer: u128,
+    pub asset_market_index: u16,
+    pub asset_price: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub asset_transfer: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpBankruptcyRecord {
+    pub market_index: u16,
+    #[serde(serialize_with = "serialize_i128")]
+    pub pnl: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub if_payment: u128,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub clawback_user: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_u128")]
+    pub clawback_user_payment: Option<u128>,
+    #[serde(serialize_with = "serialize_i128")]
+    pub cumulative_funding_rate_delta: i128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotBankruptcyRecord {
+    pub market_index: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrow_amount: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub if_payment: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub cumulative_deposit_interest_delta: u128,
+}
+
+/// A liquidation step, only the record matching `liquidation_type` is meaningful
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidationRecord {
+    pub ts: i64,
+    pub liquidation_type: LiquidationType,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub liquidator: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub margin_requirement: u128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub total_collateral: i128,
+    pub margin_freed: u64,
+    pub liquidation_id: u16,
+    pub bankrupt: bool,
+    pub canceled_order_ids: Vec<u32>,
+    pub liquidate_perp: LiquidatePerpRecord,
+    pub liquidate_spot: LiquidateSpotRecord,
+    pub liquidate_borrow_for_perp_pnl: LiquidateBorrowForPerpPnlRecord,
+    pub liquidate_perp_pnl_for_deposit: LiquidatePerpPnlForDepositRecord,
+    pub perp_bankruptcy: PerpBankruptcyRecord,
+    pub spot_bankruptcy: SpotBankruptcyRecord,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DriftEvent {
+    OrderAction(OrderActionRecord),
+    Deposit(DepositRecord),
+    SettlePnl(SettlePnlRecord),
+    FundingPayment(FundingPaymentRecord),
+    Liquidation(Box<LiquidationRecord>),
+}
+
+impl DriftEvent {
+    /// Unpacks the logged event data
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            ORDER_ACTION_RECORD => Self::OrderAction(anchor::deserialize(rest)?),
+            DEPOSIT_RECORD => Self::Deposit(anchor::deserialize(rest)?),
+            SETTLE_PNL_RECORD => Self::SettlePnl(anchor::deserialize(rest)?),
+            FUNDING_PAYMENT_RECORD => Self::FundingPayment(anchor::deserialize(rest)?),
+            LIQUIDATION_RECORD => Self::Liquidation(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+
+    /// Converts the event to a decoded instruction named after the event, without accounts
+    pub fn to_decoded_instruction(&self) -> anyhow::Result<DecodedInstruction> {
+        let (name, data) = match self {
+            Self::OrderAction(event) => ("orderActionRecord", to_data_map(event)?),
+            Self::Deposit(event) => ("depositRecord", to_data_map(event)?),
+            Self::SettlePnl(event) => ("settlePnlRecord", to_data_map(event)?),
+            Self::FundingPayment(event) => ("fundingPaymentRecord", to_data_map(event)?),
+            Self::Liquidation(event) => ("liquidationRecord", to_data_map(event)?),
+        };
+        Ok(DecodedInstruction {
+            data,
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Decodes the events the program logged during a transaction, skipping events
+/// which aren't supported
+pub fn log_events(logs: &[String]) -> Vec<DriftEvent> {
+    anchor::log_events(logs, &super::PROGRAM_ID)
+        .iter()
+        .filter_map(|data| DriftEvent::unpack(data).ok())
+        .collect()
+}
@@ -0,0 +1,327 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{anchor, types::serialize_u128};
+
+pub const INITIALIZE_USER: [u8; 8] = [111, 17, 185, 250, 60, 122, 38, 254];
+pub const INITIALIZE_USER_STATS: [u8; 8] = [254, 243, 72, 98, 251, 130, 168, 213];
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const TRANSFER_DEPOSIT: [u8; 8] = [20, 20, 147, 223, 41, 63, 204, 111];
+pub const PLACE_PERP_ORDER: [u8; 8] = [69, 161, 93, 202, 120, 126, 76, 185];
+pub const PLACE_SPOT_ORDER: [u8; 8] = [45, 79, 81, 160, 248, 90, 91, 220];
+pub const PLACE_ORDERS: [u8; 8] = [60, 63, 50, 123, 12, 197, 60, 190];
+pub const PLACE_AND_TAKE_PERP_ORDER: [u8; 8] = [213, 51, 1, 187, 108, 220, 230, 224];
+pub const PLACE_AND_MAKE_PERP_ORDER: [u8; 8] = [149, 117, 11, 237, 47, 95, 89, 237];
+pub const PLACE_AND_TAKE_SPOT_ORDER: [u8; 8] = [191, 3, 138, 71, 114, 198, 202, 100];
+pub const CANCEL_ORDER: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+pub const CANCEL_ORDER_BY_USER_ID: [u8; 8] = [107, 211, 250, 133, 18, 37, 57, 100];
+pub const CANCEL_ORDERS: [u8; 8] = [238, 225, 95, 158, 227, 103, 8, 194];
+pub const CANCEL_ORDERS_BY_IDS: [u8; 8] = [134, 19, 144, 165, 94, 240, 210, 94];
+pub const FILL_PERP_ORDER: [u8; 8] = [13, 188, 248, 103, 134, 217, 106, 240];
+pub const FILL_SPOT_ORDER: [u8; 8] = [212, 206, 130, 173, 21, 34, 199, 40];
+pub const TRIGGER_ORDER: [u8; 8] = [63, 112, 51, 233, 232, 47, 240, 199];
+pub const SETTLE_PNL: [u8; 8] = [43, 61, 234, 45, 15, 95, 152, 153];
+pub const SETTLE_MULTIPLE_PNLS: [u8; 8] = [127, 66, 117, 57, 40, 50, 152, 127];
+pub const SETTLE_FUNDING_PAYMENT: [u8; 8] = [222, 90, 202, 94, 28, 45, 115, 183];
+pub const LIQUIDATE_PERP: [u8; 8] = [75, 35, 119, 247, 191, 18, 139, 2];
+pub const LIQUIDATE_SPOT: [u8; 8] = [107, 0, 128, 41, 35, 229, 251, 18];
+pub const LIQUIDATE_BORROW_FOR_PERP_PNL: [u8; 8] = [169, 17, 32, 90, 207, 148, 209, 27];
+pub const LIQUIDATE_PERP_PNL_FOR_DEPOSIT: [u8; 8] = [237, 75, 198, 235, 233, 186, 75, 35];
+pub const RESOLVE_PERP_BANKRUPTCY: [u8; 8] = [224, 16, 176, 214, 162, 213, 183, 222];
+pub const RESOLVE_SPOT_BANKRUPTCY: [u8; 8] = [124, 194, 240, 254, 198, 213, 52, 122];
+pub const UPDATE_FUNDING_RATE: [u8; 8] = [201, 178, 116, 212, 166, 144, 72, 238];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MarketType {
+    Spot,
+    Perp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PositionDirection {
+    Long,
+    Short,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderType {
+    Market,
+    Limit,
+    TriggerMarket,
+    TriggerLimit,
+    Oracle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderTriggerCondition {
+    Above,
+    Below,
+    TriggeredAbove,
+    TriggeredBelow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PostOnlyParam {
+    None,
+    MustPostOnly,
+    TryPostOnly,
+    Slide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpotFulfillmentType {
+    SerumV3,
+    Match,
+    PhoenixV1,
+    OpenbookV2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SettlePnlMode {
+    MustSettle,
+    TrySettle,
+}
+
+/// Prices use 6 decimals, perp base amounts 9 decimals and spot base amounts the
+/// decimals of the market's mint
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderParams {
+    pub order_type: OrderType,
+    pub market_type: MarketType,
+    pub direction: PositionDirection,
+    pub user_order_id: u8,
+    pub base_asset_amount: u64,
+    pub price: u64,
+    pub market_index: u16,
+    pub reduce_only: bool,
+    pub post_only: PostOnlyParam,
+    /// immediate or cancel in the lowest bit, a bool in older program versions
+    pub bit_flags: u8,
+    pub max_ts: Option<i64>,
+    pub trigger_price: Option<u64>,
+    pub trigger_condition: OrderTriggerCondition,
+    pub oracle_price_offset: Option<i32>,
+    pub auction_duration: Option<u8>,
+    pub auction_start_price: Option<i64>,
+    pub auction_end_price: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeUserInstruction {
+    pub sub_account_id: u16,
+    pub name: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInstruction {
+    pub market_index: u16,
+    pub amount: u64,
+    pub reduce_only: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferDepositInstruction {
+    pub market_index: u16,
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceAndTakePerpOrderInstruction {
+    pub params: OrderParams,
+    pub success_condition: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceAndMakePerpOrderInstruction {
+    pub params: OrderParams,
+    pub taker_order_id: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceAndTakeSpotOrderInstruction {
+    pub params: OrderParams,
+    pub fulfillment_type: Option<SpotFulfillmentType>,
+    pub maker_order_id: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderInstruction {
+    /// the user's last order is cancelled when not set
+    pub order_id: Option<u32>,
+}
+
+/// Orders matching every filter which is set are cancelled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrdersInstruction {
+    pub market_type: Option<MarketType>,
+    pub market_index: Option<u16>,
+    pub direction: Option<PositionDirection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrdersByIdsInstruction {
+    pub order_ids: Vec<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillPerpOrderInstruction {
+    pub order_id: Option<u32>,
+    pub maker_order_id: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillSpotOrderInstruction {
+    pub order_id: Option<u32>,
+    pub fulfillment_type: Option<SpotFulfillmentType>,
+    pub maker_order_id: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettleMultiplePnlsInstruction {
+    pub market_indexes: Vec<u16>,
+    pub mode: SettlePnlMode,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidatePerpInstruction {
+    pub market_index: u16,
+    pub liquidator_max_base_asset_amount: u64,
+    pub limit_price: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidateSpotInstruction {
+    pub asset_market_index: u16,
+    pub liability_market_index: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidator_max_liability_transfer: u128,
+    pub limit_price: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidateBorrowForPerpPnlInstruction {
+    pub perp_market_index: u16,
+    pub spot_market_index: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidator_max_liability_transfer: u128,
+    pub limit_price: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidatePerpPnlForDepositInstruction {
+    pub perp_market_index: u16,
+    pub spot_market_index: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidator_max_pnl_transfer: u128,
+    pub limit_price: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvePerpBankruptcyInstruction {
+    pub quote_spot_market_index: u16,
+    pub market_index: u16,
+}
+
+/// Instructions supported by the drift v2 program
+#[derive(Clone, Debug, PartialEq)]
+pub enum DriftInstruction {
+    InitializeUser(InitializeUserInstruction),
+    InitializeUserStats,
+    Deposit(DepositInstruction),
+    Withdraw(DepositInstruction),
+    TransferDeposit(TransferDepositInstruction),
+    PlacePerpOrder(OrderParams),
+    PlaceSpotOrder(OrderParams),
+    PlaceOrders(Vec<OrderParams>),
+    PlaceAndTakePerpOrder(PlaceAndTakePerpOrderInstruction),
+    PlaceAndMakePerpOrder(PlaceAndMakePerpOrderInstruction),
+    PlaceAndTakeSpotOrder(PlaceAndTakeSpotOrderInstruction),
+    CancelOrder(CancelOrderInstruction),
+    CancelOrderByUserId(u8),
+    CancelOrders(CancelOrdersInstruction),
+    CancelOrdersByIds(CancelOrdersByIdsInstruction),
+    FillPerpOrder(FillPerpOrderInstruction),
+    FillSpotOrder(FillSpotOrderInstruction),
+    TriggerOrder(u32),
+    SettlePnl(u16),
+    SettleMultiplePnls(SettleMultiplePnlsInstruction),
+    SettleFundingPayment,
+    LiquidatePerp(LiquidatePerpInstruction),
+    LiquidateSpot(LiquidateSpotInstruction),
+    LiquidateBorrowForPerpPnl(LiquidateBorrowForPerpPnlInstruction),
+    LiquidatePerpPnlForDeposit(LiquidatePerpPnlForDepositInstruction),
+    ResolvePerpBankruptcy(ResolvePerpBankruptcyInstruction),
+    ResolveSpotBankruptcy(u16),
+    UpdateFundingRate(u16),
+}
+
+impl DriftInstruction {
+    /// Unpacks a byte buffer into a [DriftInstruction](enum.DriftInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_USER => Self::InitializeUser(anchor::deserialize(rest)?),
+            INITIALIZE_USER_STATS => Self::InitializeUserStats,
+            DEPOSIT => Self::Deposit(anchor::deserialize(rest)?),
+            WITHDRAW => Self::Withdraw(anchor::deserialize(rest)?),
+            TRANSFER_DEPOSIT => Self::TransferDeposit(anchor::deserialize(rest)?),
+            PLACE_PERP_ORDER => Self::PlacePerpOrder(anchor::deserialize(rest)?),
+            PLACE_SPOT_ORDER => Self::PlaceSpotOrder(anchor::deserialize(rest)?),
+            PLACE_ORDERS => Self::PlaceOrders(anchor::deserialize(rest)?),
+            PLACE_AND_TAKE_PERP_ORDER => Self::PlaceAndTakePerpOrder(anchor::deserialize(rest)?),
+            PLACE_AND_MAKE_PERP_ORDER => Self::PlaceAndMakePerpOrder(anchor::deserialize(rest)?),
+            PLACE_AND_TAKE_SPOT_ORDER => Self::PlaceAndTakeSpotOrder(anchor::deserialize(rest)?),
+            CANCEL_ORDER => Self::CancelOrder(anchor::deserialize(rest)?),
+            CANCEL_ORDER_BY_USER_ID => Self::CancelOrderByUserId(anchor::deserialize(rest)?),
+            CANCEL_ORDERS => Self::CancelOrders(anchor::deserialize(rest)?),
+            CANCEL_ORDERS_BY_IDS => Self::CancelOrdersByIds(anchor::deserialize(rest)?),
+            FILL_PERP_ORDER => Self::FillPerpOrder(anchor::deserialize(rest)?),
+            FILL_SPOT_ORDER => Self::FillSpotOrder(anchor::deserialize(rest)?),
+            TRIGGER_ORDER => Self::TriggerOrder(anchor::deserialize(rest)?),
+            SETTLE_PNL => Self::SettlePnl(anchor::deserialize(rest)?),
+            SETTLE_MULTIPLE_PNLS => Self::SettleMultiplePnls(anchor::deserialize(rest)?),
+            SETTLE_FUNDING_PAYMENT => Self::SettleFundingPayment,
+            LIQUIDATE_PERP => Self::LiquidatePerp(anchor::deserialize(rest)?),
+            LIQUIDATE_SPOT => Self::LiquidateSpot(anchor::deserialize(rest)?),
+            LIQUIDATE_BORROW_FOR_PERP_PNL => {
+                Self::LiquidateBorrowForPerpPnl(anchor::deserialize(rest)?)
+            }
+            LIQUIDATE_PERP_PNL_FOR_DEPOSIT => {
+                Self::LiquidatePerpPnlForDeposit(anchor::deserialize(rest)?)
+            }
+            RESOLVE_PERP_BANKRUPTCY => Self::ResolvePerpBankruptcy(anchor::deserialize(rest)?),
+            RESOLVE_SPOT_BANKRUPTCY => Self::ResolveSpotBankruptcy(anchor::deserialize(rest)?),
+            UPDATE_FUNDING_RATE => Self::UpdateFundingRate(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
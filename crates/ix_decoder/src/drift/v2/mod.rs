@@ -0,0 +1,354 @@
+//! Instruction decoding functions for the drift v2 program
+//!
+//! Oracles, markets and maker accounts are passed as remaining accounts and are not named.
+//! Order and liquidation records are logged rather than emitted through self cpi,
+//! [events::log_events] decodes them from the logs of a transaction.
+
+use anyhow::Context;
+use ix::DriftInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+
+const ORDER_ACCOUNTS: [&str; 3] = ["state", "user", "authority"];
+
+const TAKE_ORDER_ACCOUNTS: [&str; 4] = ["state", "user", "userStats", "authority"];
+
+const FILL_ORDER_ACCOUNTS: [&str; 6] = [
+    "state",
+    "authority",
+    "filler",
+    "fillerStats",
+    "user",
+    "userStats",
+];
+
+const LIQUIDATE_ACCOUNTS: [&str; 6] = [
+    "state",
+    "authority",
+    "liquidator",
+    "liquidatorStats",
+    "user",
+    "userStats",
+];
+
+const RESOLVE_BANKRUPTCY_ACCOUNTS: [&str; 10] = [
+    "state",
+    "authority",
+    "liquidator",
+    "liquidatorStats",
+    "user",
+    "userStats",
+    "spotMarketVault",
+    "insuranceFundVault",
+    "driftSigner",
+    "tokenProgram",
+];
+
+const SETTLE_PNL_ACCOUNTS: [&str; 4] = ["state", "user", "authority", "spotMarketVault"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DriftV2Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DriftV2Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DriftInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DriftInstruction::InitializeUser(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "user",
+                    "userStats",
+                    "state",
+                    "authority",
+                    "payer",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "initializeUser".to_string(),
+            }),
+            DriftInstruction::InitializeUserStats => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "userStats",
+                    "state",
+                    "authority",
+                    "payer",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "initializeUserStats".to_string(),
+                ..Default::default()
+            }),
+            DriftInstruction::Deposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "user",
+                    "userStats",
+                    "authority",
+                    "spotMarketVault",
+                    "userTokenAccount",
+                    "tokenProgram",
+                ]),
+                name: "deposit".to_string(),
+            }),
+            DriftInstruction::Withdraw(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "user",
+                    "userStats",
+                    "authority",
+                    "spotMarketVault",
+                    "driftSigner",
+                    "userTokenAccount",
+                    "tokenProgram",
+                ]),
+                name: "withdraw".to_string(),
+            }),
+            DriftInstruction::TransferDeposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "fromUser",
+                    "toUser",
+                    "userStats",
+                    "authority",
+                    "state",
+                    "spotMarketVault",
+                ]),
+                name: "transferDeposit".to_string(),
+            }),
+            DriftInstruction::PlacePerpOrder(params) => Ok(DecodedInstruction {
+                data: to_data_map(&params)?,
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "placePerpOrder".to_string(),
+            }),
+            DriftInstruction::PlaceSpotOrder(params) => Ok(DecodedInstruction {
+                data: to_data_map(&params)?,
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "placeSpotOrder".to_string(),
+            }),
+            DriftInstruction::PlaceOrders(params) => Ok(DecodedInstruction {
+                data: [("params".to_string(), serde_json::to_value(params)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "placeOrders".to_string(),
+            }),
+            DriftInstruction::PlaceAndTakePerpOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TAKE_ORDER_ACCOUNTS),
+                name: "placeAndTakePerpOrder".to_string(),
+            }),
+            DriftInstruction::PlaceAndMakePerpOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "user",
+                    "userStats",
+                    "taker",
+                    "takerStats",
+                    "authority",
+                ]),
+                name: "placeAndMakePerpOrder".to_string(),
+            }),
+            DriftInstruction::PlaceAndTakeSpotOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TAKE_ORDER_ACCOUNTS),
+                name: "placeAndTakeSpotOrder".to_string(),
+            }),
+            DriftInstruction::CancelOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "cancelOrder".to_string(),
+            }),
+            DriftInstruction::CancelOrderByUserId(user_order_id) => Ok(DecodedInstruction {
+                data: [(
+                    "userOrderId".to_string(),
+                    serde_json::to_value(user_order_id)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "cancelOrderByUserId".to_string(),
+            }),
+            DriftInstruction::CancelOrders(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "cancelOrders".to_string(),
+            }),
+            DriftInstruction::CancelOrdersByIds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ORDER_ACCOUNTS),
+                name: "cancelOrdersByIds".to_string(),
+            }),
+            DriftInstruction::FillPerpOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FILL_ORDER_ACCOUNTS),
+                name: "fillPerpOrder".to_string(),
+            }),
+            DriftInstruction::FillSpotOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FILL_ORDER_ACCOUNTS),
+                name: "fillSpotOrder".to_string(),
+            }),
+            DriftInstruction::TriggerOrder(order_id) => Ok(DecodedInstruction {
+                data: [("orderId".to_string(), serde_json::to_value(order_id)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["state", "authority", "filler", "user"]),
+                name: "triggerOrder".to_string(),
+            }),
+            DriftInstruction::SettlePnl(market_index) => Ok(DecodedInstruction {
+                data: [(
+                    "marketIndex".to_string(),
+                    serde_json::to_value(market_index)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&SETTLE_PNL_ACCOUNTS),
+                name: "settlePnl".to_string(),
+            }),
+            DriftInstruction::SettleMultiplePnls(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SETTLE_PNL_ACCOUNTS),
+                name: "settleMultiplePnls".to_string(),
+            }),
+            DriftInstruction::SettleFundingPayment => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["state", "user"]),
+                name: "settleFundingPayment".to_string(),
+                ..Default::default()
+            }),
+            DriftInstruction::LiquidatePerp(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDATE_ACCOUNTS),
+                name: "liquidatePerp".to_string(),
+            }),
+            DriftInstruction::LiquidateSpot(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDATE_ACCOUNTS),
+                name: "liquidateSpot".to_string(),
+            }),
+            DriftInstruction::LiquidateBorrowForPerpPnl(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDATE_ACCOUNTS),
+                name: "liquidateBorrowForPerpPnl".to_string(),
+            }),
+            DriftInstruction::LiquidatePerpPnlForDeposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDATE_ACCOUNTS),
+                name: "liquidatePerpPnlForDeposit".to_string(),
+            }),
+            DriftInstruction::ResolvePerpBankruptcy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&RESOLVE_BANKRUPTCY_ACCOUNTS),
+                name: "resolvePerpBankruptcy".to_string(),
+            }),
+            DriftInstruction::ResolveSpotBankruptcy(market_index) => Ok(DecodedInstruction {
+                data: [(
+                    "marketIndex".to_string(),
+                    serde_json::to_value(market_index)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&RESOLVE_BANKRUPTCY_ACCOUNTS),
+                name: "resolveSpotBankruptcy".to_string(),
+            }),
+            DriftInstruction::UpdateFundingRate(perp_market_index) => Ok(DecodedInstruction {
+                data: [(
+                    "perpMarketIndex".to_string(),
+                    serde_json::to_value(perp_market_index)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&["state", "perpMarket", "oracle"]),
+                name: "updateFundingRate".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_place_perp_order_and_fill_record() {
+        let user = Pubkey::new_unique();
+        let mut data = ix::PLACE_PERP_ORDER.to_vec();
+        // limit short of 1.5 SOL-PERP at $150, post only with a 60s expiry
+        data.extend([1, 1, 1, 7]);
+        data.extend(to_vec(&(1_500_000_000u64, 150_000_000u64, 0u16, false)).unwrap());
+        data.extend([1, 0]);
+        data.extend(to_vec(&Some(1_700_000_060i64)).unwrap());
+        data.extend([0, 0, 0, 0, 0, 0]);
+        let decoded = DriftV2Decoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique(), user, Pubkey::new_unique()],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "placePerpOrder");
+        assert_eq!(decoded.accounts["user"], user.to_string());
+        assert_eq!(decoded.data["orderType"], "limit");
+        assert_eq!(decoded.data["direction"], "short");
+        assert_eq!(decoded.data["postOnly"], "mustPostOnly");
+        assert_eq!(decoded.data["baseAssetAmount"], 1_500_000_000u64);
+        assert_eq!(decoded.data["maxTs"], 1_700_000_060i64);
+
+        let taker = Pubkey::new_unique();
+        let mut record = events::ORDER_ACTION_RECORD.to_vec();
+        record.extend(to_vec(&(1_700_000_000i64, 2u8, 0u8, 0u16, 1u8)).unwrap());
+        record.extend(to_vec(&(None::<Pubkey>, None::<u64>, Some(9u64))).unwrap());
+        record.extend(to_vec(&(Some(1_500_000_000u64), Some(225_000_000u64))).unwrap());
+        record.extend(to_vec(&(Some(67_500u64), Some(-5_625i64), None::<u32>)).unwrap());
+        record.extend(to_vec(&(None::<i64>, None::<u64>, Some(taker), Some(12u32))).unwrap());
+        record.extend(to_vec(&(Some(0u8), Some(1_500_000_000u64))).unwrap());
+        record.extend(to_vec(&(Some(1_500_000_000u64), Some(225_000_000u64))).unwrap());
+        record.extend(to_vec(&(None::<Pubkey>, None::<u32>, None::<u8>)).unwrap());
+        record.extend(to_vec(&(None::<u64>, None::<u64>, None::<u64>)).unwrap());
+        record.extend(to_vec(&150_010_000i64).unwrap());
+        let logs = vec![
+            format!("Program {} invoke [1]", PROGRAM_ID),
+            "Program log: Instruction: FillPerpOrder".to_string(),
+            format!("Program data: {}", STANDARD.encode(&record)),
+            format!("Program {} success", PROGRAM_ID),
+        ];
+        let events = events::log_events(&logs);
+        assert_eq!(events.len(), 1);
+        let events::DriftEvent::OrderAction(action) = &events[0] else {
+            panic!("expected an order action record");
+        };
+        assert_eq!(action.action, events::OrderAction::Fill);
+        assert_eq!(action.market_type, ix::MarketType::Perp);
+        assert_eq!(action.taker, Some(taker));
+        assert_eq!(
+            action.taker_order_direction,
+            Some(ix::PositionDirection::Long)
+        );
+        assert_eq!(action.maker_fee, Some(-5_625));
+        let decoded = events[0].to_decoded_instruction().unwrap();
+        assert_eq!(decoded.name, "orderActionRecord");
+        assert_eq!(decoded.data["action"], "fill");
+        assert_eq!(decoded.data["taker"], taker.to_string());
+        assert_eq!(decoded.data["quoteAssetAmountFilled"], 225_000_000u64);
+    }
+}
@@ -0,0 +1,493 @@
+//! Account types
+//!
+//! The accounts are zero copy, padding fields are kept to preserve the layout. Markets are
+//! decoded up to their market index and status, the fields which follow are not decoded.
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::ix::{MarketType, OrderTriggerCondition, OrderType, PositionDirection};
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_i128, serialize_pubkey, serialize_u128},
+};
+
+pub const USER_DISCRIMINATOR: [u8; 8] = [159, 117, 95, 227, 239, 151, 58, 236];
+pub const PERP_MARKET_DISCRIMINATOR: [u8; 8] = [10, 223, 12, 44, 107, 245, 55, 247];
+pub const SPOT_MARKET_DISCRIMINATOR: [u8; 8] = [100, 177, 8, 107, 168, 65, 65, 39];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpotBalanceType {
+    #[default]
+    Deposit,
+    Borrow,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderStatus {
+    #[default]
+    Init,
+    Open,
+    Filled,
+    Canceled,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPosition {
+    /// balance scaled by the market's cumulative interest, see [SpotMarket::token_amount]
+    pub scaled_balance: u64,
+    pub open_bids: i64,
+    pub open_asks: i64,
+    pub cumulative_deposits: i64,
+    pub market_index: u16,
+    pub balance_type: SpotBalanceType,
+    pub open_orders: u8,
+    #[serde(skip)]
+    pub padding: [u8; 4],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpPosition {
+    pub last_cumulative_funding_rate: i64,
+    /// with 9 decimals, negative for shorts
+    pub base_asset_amount: i64,
+    /// with 6 decimals
+    pub quote_asset_amount: i64,
+    pub quote_break_even_amount: i64,
+    pub quote_entry_amount: i64,
+    pub open_bids: i64,
+    pub open_asks: i64,
+    pub settled_pnl: i64,
+    pub lp_shares: u64,
+    pub last_base_asset_amount_per_lp: i64,
+    pub last_quote_asset_amount_per_lp: i64,
+    pub remainder_base_asset_amount: i32,
+    pub market_index: u16,
+    pub open_orders: u8,
+    pub per_lp_base: i8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub slot: u64,
+    pub price: u64,
+    pub base_asset_amount: u64,
+    pub base_asset_amount_filled: u64,
+    pub quote_asset_amount_filled: u64,
+    pub trigger_price: u64,
+    pub auction_start_price: i64,
+    pub auction_end_price: i64,
+    pub max_ts: i64,
+    pub oracle_price_offset: i32,
+    pub order_id: u32,
+    pub market_index: u16,
+    pub status: OrderStatus,
+    pub order_type: OrderType,
+    pub market_type: MarketType,
+    pub user_order_id: u8,
+    pub existing_position_direction: PositionDirection,
+    pub direction: PositionDirection,
+    pub reduce_only: bool,
+    pub post_only: bool,
+    pub immediate_or_cancel: bool,
+    pub trigger_condition: OrderTriggerCondition,
+    pub auction_duration: u8,
+    #[serde(skip)]
+    pub padding: [u8; 3],
+}
+
+/// A drift sub account, holding its positions and open orders
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub authority: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub delegate: Pubkey,
+    #[serde(serialize_with = "serialize_hex")]
+    pub name: [u8; 32],
+    pub spot_positions: [SpotPosition; 8],
+    pub perp_positions: [PerpPosition; 8],
+    pub orders: [Order; 32],
+    pub last_add_perp_lp_shares_ts: i64,
+    pub total_deposits: u64,
+    pub total_withdraws: u64,
+    pub total_social_loss: u64,
+    pub settled_perp_pnl: i64,
+    pub cumulative_spot_fees: i64,
+    pub cumulative_perp_funding: i64,
+    pub liquidation_margin_freed: u64,
+    pub last_active_slot: u64,
+    pub next_order_id: u32,
+    pub max_margin_ratio: u32,
+    pub next_liquidation_id: u16,
+    pub sub_account_id: u16,
+    /// bit flags, being liquidated in the lowest bit and bankrupt in the next
+    pub status: u8,
+    pub is_margin_trading_enabled: bool,
+    pub idle: bool,
+    pub open_orders: u8,
+    pub has_open_order: bool,
+    pub open_auctions: u8,
+    pub has_open_auction: bool,
+}
+
+impl User {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, USER_DISCRIMINATOR)
+    }
+
+    /// Name of the sub account, with the padding removed
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name).trim_end().to_string()
+    }
+
+    /// Spot positions which are in use
+    pub fn active_spot_positions(&self) -> impl Iterator<Item = &SpotPosition> {
+        self.spot_positions
+            .iter()
+            .filter(|position| position.scaled_balance != 0 || position.open_orders != 0)
+    }
+
+    /// Perp positions which are in use
+    pub fn active_perp_positions(&self) -> impl Iterator<Item = &PerpPosition> {
+        self.perp_positions.iter().filter(|position| {
+            position.base_asset_amount != 0
+                || position.quote_asset_amount != 0
+                || position.open_orders != 0
+                || position.lp_shares != 0
+        })
+    }
+
+    /// Orders which are resting or waiting for a trigger
+    pub fn open_orders(&self) -> impl Iterator<Item = &Order> {
+        self.orders
+            .iter()
+            .filter(|order| order.status == OrderStatus::Open)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalOracleData {
+    pub last_oracle_price: i64,
+    pub last_oracle_conf: u64,
+    pub last_oracle_delay: i64,
+    pub last_oracle_price_twap: i64,
+    pub last_oracle_price_twap_5min: i64,
+    pub last_oracle_price_twap_ts: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalIndexData {
+    pub last_index_bid_price: u64,
+    pub last_index_ask_price: u64,
+    pub last_index_price_twap: u64,
+    pub last_index_price_twap_5min: u64,
+    pub last_index_price_twap_ts: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolBalance {
+    #[serde(serialize_with = "serialize_u128")]
+    pub scaled_balance: u128,
+    pub market_index: u16,
+    #[serde(skip)]
+    pub padding: [u8; 6],
+}
+
+/// The market's amm, reserves and amounts use 9 decimals and the peg multiplier 3
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Amm {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub oracle: Pubkey,
+    pub historical_oracle_data: HistoricalOracleData,
+    #[serde(serialize_with = "serialize_i128")]
+    pub base_asset_amount_per_lp: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub quote_asset_amount_per_lp: i128,
+    pub fee_pool: PoolBalance,
+    #[serde(serialize_with = "serialize_u128")]
+    pub base_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub quote_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub concentration_coef: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub min_base_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub max_base_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub sqrt_k: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub peg_multiplier: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub terminal_quote_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub base_asset_amount_long: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub base_asset_amount_short: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub base_asset_amount_with_amm: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub base_asset_amount_with_unsettled_lp: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub max_open_interest: u128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub quote_asset_amount: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub quote_entry_amount_long: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub quote_entry_amount_short: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub quote_break_even_amount_long: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub quote_break_even_amount_short: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub user_lp_shares: u128,
+    pub last_funding_rate: i64,
+    pub last_funding_rate_long: i64,
+    pub last_funding_rate_short: i64,
+    pub last_24h_avg_funding_rate: i64,
+    #[serde(serialize_with = "serialize_i128")]
+    pub total_fee: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub total_mm_fee: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_exchange_fee: u128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub total_fee_minus_distributions: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_fee_withdrawn: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_liquidation_fee: u128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub cumulative_funding_rate_long: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub cumulative_funding_rate_short: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_social_loss: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub ask_base_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub ask_quote_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub bid_base_asset_reserve: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub bid_quote_asset_reserve: u128,
+    pub last_oracle_normalised_price: i64,
+    pub last_oracle_reserve_price_spread_pct: i64,
+    pub last_bid_price_twap: u64,
+    pub last_ask_price_twap: u64,
+    pub last_mark_price_twap: u64,
+    pub last_mark_price_twap_5min: u64,
+    pub last_update_slot: u64,
+    pub last_oracle_conf_pct: u64,
+    pub net_revenue_since_last_funding: i64,
+    pub last_funding_rate_ts: i64,
+    pub funding_period: i64,
+    pub order_step_size: u64,
+    pub order_tick_size: u64,
+    pub min_order_size: u64,
+    pub max_position_size: u64,
+    pub volume_24h: u64,
+    pub long_intensity_volume: u64,
+    pub short_intensity_volume: u64,
+    pub last_trade_ts: i64,
+    pub mark_std: u64,
+    pub oracle_std: u64,
+    pub last_mark_price_twap_ts: i64,
+    pub base_spread: u32,
+    pub max_spread: u32,
+    pub long_spread: u32,
+    pub short_spread: u32,
+    pub long_intensity_count: u32,
+    pub short_intensity_count: u32,
+    pub max_fill_reserve_fraction: u16,
+    pub max_slippage_ratio: u16,
+    pub curve_update_intensity: u8,
+    pub amm_jit_intensity: u8,
+    pub oracle_source: u8,
+    pub last_oracle_valid: bool,
+    pub target_base_asset_amount_per_lp: i32,
+    pub per_lp_base: i8,
+    #[serde(skip)]
+    pub padding1: u8,
+    #[serde(skip)]
+    pub padding2: u16,
+    pub total_fee_earned_per_lp: u64,
+    pub net_unsettled_funding_pnl: i64,
+    pub quote_asset_amount_with_unsettled_lp: i64,
+    pub reference_price_offset: i32,
+    #[serde(skip)]
+    pub padding: [u8; 12],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsuranceClaim {
+    pub revenue_withdraw_since_last_settle: i64,
+    pub max_revenue_withdraw_per_period: u64,
+    pub quote_max_insurance: u64,
+    pub quote_settled_insurance: u64,
+    pub last_revenue_withdraw_ts: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpMarket {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pubkey: Pubkey,
+    pub amm: Amm,
+    pub pnl_pool: PoolBalance,
+    #[serde(serialize_with = "serialize_hex")]
+    pub name: [u8; 32],
+    pub insurance_claim: InsuranceClaim,
+    pub unrealized_pnl_max_imbalance: u64,
+    pub expiry_ts: i64,
+    pub expiry_price: i64,
+    pub next_fill_record_id: u64,
+    pub next_funding_rate_record_id: u64,
+    pub next_curve_record_id: u64,
+    pub imf_factor: u32,
+    pub unrealized_pnl_imf_factor: u32,
+    pub liquidator_fee: u32,
+    pub if_liquidation_fee: u32,
+    pub margin_ratio_initial: u32,
+    pub margin_ratio_maintenance: u32,
+    pub unrealized_pnl_initial_asset_weight: u32,
+    pub unrealized_pnl_maintenance_asset_weight: u32,
+    pub number_of_users_with_base: u32,
+    pub number_of_users: u32,
+    pub market_index: u16,
+    pub status: u8,
+    pub contract_type: u8,
+    pub contract_tier: u8,
+    pub paused_operations: u8,
+    pub quote_spot_market_index: u16,
+    pub fee_adjustment: i16,
+}
+
+impl PerpMarket {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, PERP_MARKET_DISCRIMINATOR)
+    }
+
+    /// Name of the market, with the padding removed
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name).trim_end().to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsuranceFund {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub vault: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_shares: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub user_shares: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub shares_base: u128,
+    pub unstaking_period: i64,
+    pub last_revenue_settle_ts: i64,
+    pub revenue_settle_period: i64,
+    pub total_factor: u32,
+    pub user_factor: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotMarket {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pubkey: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub oracle: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub vault: Pubkey,
+    #[serde(serialize_with = "serialize_hex")]
+    pub name: [u8; 32],
+    pub historical_oracle_data: HistoricalOracleData,
+    pub historical_index_data: HistoricalIndexData,
+    pub revenue_pool: PoolBalance,
+    pub spot_fee_pool: PoolBalance,
+    pub insurance_fund: InsuranceFund,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_spot_fee: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub deposit_balance: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrow_balance: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub cumulative_deposit_interest: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub cumulative_borrow_interest: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_social_loss: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub total_quote_social_loss: u128,
+    pub withdraw_guard_threshold: u64,
+    pub max_token_deposits: u64,
+    pub deposit_token_twap: u64,
+    pub borrow_token_twap: u64,
+    pub utilization_twap: u64,
+    pub last_interest_ts: u64,
+    pub last_twap_ts: u64,
+    pub expiry_ts: i64,
+    pub order_step_size: u64,
+    pub order_tick_size: u64,
+    pub min_order_size: u64,
+    pub max_position_size: u64,
+    pub next_fill_record_id: u64,
+    pub next_deposit_record_id: u64,
+    pub initial_asset_weight: u32,
+    pub maintenance_asset_weight: u32,
+    pub initial_liability_weight: u32,
+    pub maintenance_liability_weight: u32,
+    pub imf_factor: u32,
+    pub liquidator_fee: u32,
+    pub if_liquidation_fee: u32,
+    pub optimal_utilization: u32,
+    pub optimal_borrow_rate: u32,
+    pub max_borrow_rate: u32,
+    pub decimals: u32,
+    pub market_index: u16,
+    pub orders_enabled: bool,
+    pub oracle_source: u8,
+    pub status: u8,
+    pub asset_tier: u8,
+}
+
+impl SpotMarket {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, SPOT_MARKET_DISCRIMINATOR)
+    }
+
+    /// Name of the market, with the padding removed
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name).trim_end().to_string()
+    }
+
+    /// Native token amount of a position in this market, as of the last interest update
+    pub fn token_amount(&self, position: &SpotPosition) -> u128 {
+        let cumulative_interest = match position.balance_type {
+            SpotBalanceType::Deposit => self.cumulative_deposit_interest,
+            SpotBalanceType::Borrow => self.cumulative_borrow_interest,
+        };
+        // scaled balances use 9 decimals and the cumulative interest 10
+        let precision_decrease = 10u128.pow(19 - self.decimals);
+        position.scaled_balance as u128 * cumulative_interest / precision_decrease
+    }
+}
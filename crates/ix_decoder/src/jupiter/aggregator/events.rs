@@ -0,0 +1,55 @@
+//! Event types, emitted by the program through self cpi
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const SWAP_EVENT: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+pub const FEE_EVENT: [u8; 8] = [73, 79, 78, 127, 184, 213, 13, 220];
+
+/// Emitted after each step of the route with the amounts swapped by the amm
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapEvent {
+    /// program id of the amm which executed the step
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub amm: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub input_mint: Pubkey,
+    pub input_amount: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub output_mint: Pubkey,
+    pub output_amount: u64,
+}
+
+/// Emitted when a platform fee is taken
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEvent {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AggregatorEvent {
+    Swap(SwapEvent),
+    Fee(FeeEvent),
+}
+
+impl AggregatorEvent {
+    /// Unpacks the event data following the self cpi event tag
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            SWAP_EVENT => Self::Swap(anchor::deserialize(rest)?),
+            FEE_EVENT => Self::Fee(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+}
@@ -0,0 +1,318 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use super::events::AggregatorEvent;
+use crate::anchor;
+
+pub const ROUTE: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+pub const ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [150, 86, 71, 116, 167, 93, 14, 104];
+pub const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+pub const SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] =
+    [230, 121, 143, 80, 119, 159, 106, 170];
+pub const EXACT_OUT_ROUTE: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+pub const SHARED_ACCOUNTS_EXACT_OUT_ROUTE: [u8; 8] = [176, 209, 105, 168, 154, 125, 69, 62];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemainingAccountsSlice {
+    /// the kind of accounts in the slice, as defined by the underlying amm
+    pub accounts_type: u8,
+    pub length: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemainingAccountsInfo {
+    pub slices: Vec<RemainingAccountsSlice>,
+}
+
+/// The amm used by a step of the route, along with any amm specific arguments.
+///
+/// Variants are borsh encoded by their index, so the order must match the program.
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Swap {
+    Saber,
+    SaberAddDecimalsDeposit,
+    SaberAddDecimalsWithdraw,
+    TokenSwap,
+    Sencha,
+    Step,
+    Cropper,
+    Raydium,
+    Crema {
+        a_to_b: bool,
+    },
+    Lifinity,
+    Mercurial,
+    Cykura,
+    Serum {
+        side: Side,
+    },
+    MarinadeDeposit,
+    MarinadeUnstake,
+    Aldrin {
+        side: Side,
+    },
+    AldrinV2 {
+        side: Side,
+    },
+    Whirlpool {
+        a_to_b: bool,
+    },
+    Invariant {
+        x_to_y: bool,
+    },
+    Meteora,
+    GooseFX,
+    DeltaFi {
+        stable: bool,
+    },
+    Balansol,
+    MarcoPolo {
+        x_to_y: bool,
+    },
+    Dradex {
+        side: Side,
+    },
+    LifinityV2,
+    RaydiumClmm,
+    Openbook {
+        side: Side,
+    },
+    Phoenix {
+        side: Side,
+    },
+    Symmetry {
+        from_token_id: u64,
+        to_token_id: u64,
+    },
+    TokenSwapV2,
+    HeliumTreasuryManagementRedeemV0,
+    StakeDexStakeWrappedSol,
+    StakeDexSwapViaStake {
+        bridge_stake_seed: u32,
+    },
+    GooseFXV2,
+    Perps,
+    PerpsAddLiquidity,
+    PerpsRemoveLiquidity,
+    MeteoraDlmm,
+    OpenBookV2 {
+        side: Side,
+    },
+    RaydiumClmmV2,
+    StakeDexPrefundWithdrawStakeAndDepositStake {
+        bridge_stake_seed: u32,
+    },
+    Clone {
+        pool_index: u8,
+        quantity_is_input: bool,
+        quantity_is_collateral: bool,
+    },
+    SanctumS {
+        src_lst_value_calc_accs: u8,
+        dst_lst_value_calc_accs: u8,
+        src_lst_index: u32,
+        dst_lst_index: u32,
+    },
+    SanctumSAddLiquidity {
+        lst_value_calc_accs: u8,
+        lst_index: u32,
+    },
+    SanctumSRemoveLiquidity {
+        lst_value_calc_accs: u8,
+        lst_index: u32,
+    },
+    RaydiumCP,
+    WhirlpoolSwapV2 {
+        a_to_b: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    },
+    OneIntro,
+    PumpdotfunWrappedBuy,
+    PumpdotfunWrappedSell,
+    PerpsV2,
+    PerpsV2AddLiquidity,
+    PerpsV2RemoveLiquidity,
+    MoonshotWrappedBuy,
+    MoonshotWrappedSell,
+    StabbleStableSwap,
+    StabbleWeightedSwap,
+    Obric {
+        x_to_y: bool,
+    },
+    FoxBuyFromEstimatedCost,
+    FoxClaimPartial {
+        is_y: bool,
+    },
+    SolFi {
+        is_quote_to_base: bool,
+    },
+    SolayerDelegateNoInit,
+    SolayerUndelegateNoInit,
+    TokenMill {
+        side: Side,
+    },
+    DaosFunBuy,
+    DaosFunSell,
+    ZeroFi,
+    StakeDexWithdrawWrappedSol,
+    VirtualsBuy,
+    VirtualsSell,
+    Perena {
+        in_index: u8,
+        out_index: u8,
+    },
+    PumpdotfunAmmBuy,
+    PumpdotfunAmmSell,
+    Gamma,
+    MeteoraDlmmSwapV2 {
+        remaining_accounts_info: RemainingAccountsInfo,
+    },
+    Woofi,
+    MeteoraDammV2,
+    MeteoraDynamicBondingCurveSwap,
+    StabbleStableSwapV2,
+    StabbleWeightedSwapV2,
+    RaydiumLaunchlabBuy {
+        share_fee_rate: u64,
+    },
+    RaydiumLaunchlabSell {
+        share_fee_rate: u64,
+    },
+    BoopdotfunWrappedBuy,
+    BoopdotfunWrappedSell,
+    Plasma {
+        side: Side,
+    },
+    GoonFi {
+        is_bid: bool,
+        blacklist_bump: u8,
+    },
+    HumidiFi {
+        swap_id: u64,
+        is_base_to_quote: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlanStep {
+    pub swap: Swap,
+    /// share of the input amount routed through this step
+    pub percent: u8,
+    /// index of the step's input amount, where 0 is the route's input
+    pub input_index: u8,
+    /// index the step's output amount is stored at, for use by later steps
+    pub output_index: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteInstruction {
+    pub route_plan: Vec<RoutePlanStep>,
+    pub in_amount: u64,
+    pub quoted_out_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+/// The input amount is read from the token ledger at execution time
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteWithTokenLedgerInstruction {
+    pub route_plan: Vec<RoutePlanStep>,
+    pub quoted_out_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedAccountsRouteInstruction {
+    /// id of the program authority holding the shared token accounts
+    pub id: u8,
+    pub route_plan: Vec<RoutePlanStep>,
+    pub in_amount: u64,
+    pub quoted_out_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedAccountsRouteWithTokenLedgerInstruction {
+    pub id: u8,
+    pub route_plan: Vec<RoutePlanStep>,
+    pub quoted_out_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExactOutRouteInstruction {
+    pub route_plan: Vec<RoutePlanStep>,
+    pub out_amount: u64,
+    pub quoted_in_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedAccountsExactOutRouteInstruction {
+    pub id: u8,
+    pub route_plan: Vec<RoutePlanStep>,
+    pub out_amount: u64,
+    pub quoted_in_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+/// Instructions supported by the jupiter v6 aggregator program, along with the
+/// events it emits through self cpi.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AggregatorInstruction {
+    Route(RouteInstruction),
+    RouteWithTokenLedger(RouteWithTokenLedgerInstruction),
+    SharedAccountsRoute(SharedAccountsRouteInstruction),
+    SharedAccountsRouteWithTokenLedger(SharedAccountsRouteWithTokenLedgerInstruction),
+    ExactOutRoute(ExactOutRouteInstruction),
+    SharedAccountsExactOutRoute(SharedAccountsExactOutRouteInstruction),
+    Event(AggregatorEvent),
+}
+
+impl AggregatorInstruction {
+    /// Unpacks a byte buffer into a [AggregatorInstruction](enum.AggregatorInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        if let Some(event) = anchor::strip_event_tag(input) {
+            return Ok(Self::Event(AggregatorEvent::unpack(event)?));
+        }
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            ROUTE => Self::Route(anchor::deserialize(rest)?),
+            ROUTE_WITH_TOKEN_LEDGER => Self::RouteWithTokenLedger(anchor::deserialize(rest)?),
+            SHARED_ACCOUNTS_ROUTE => Self::SharedAccountsRoute(anchor::deserialize(rest)?),
+            SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER => {
+                Self::SharedAccountsRouteWithTokenLedger(anchor::deserialize(rest)?)
+            }
+            EXACT_OUT_ROUTE => Self::ExactOutRoute(anchor::deserialize(rest)?),
+            SHARED_ACCOUNTS_EXACT_OUT_ROUTE => {
+                Self::SharedAccountsExactOutRoute(anchor::deserialize(rest)?)
+            }
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
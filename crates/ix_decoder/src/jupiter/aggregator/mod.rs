@@ -0,0 +1,296 @@
+//! Instruction decoding functions for the jupiter v6 aggregator program
+
+use anyhow::Context;
+use events::AggregatorEvent;
+use ix::AggregatorInstruction;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    transaction::DecodedTransaction,
+    types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction},
+};
+
+pub mod events;
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+const ROUTE_ACCOUNTS: [&str; 9] = [
+    "tokenProgram",
+    "userTransferAuthority",
+    "userSourceTokenAccount",
+    "userDestinationTokenAccount",
+    "destinationTokenAccount",
+    "destinationMint",
+    "platformFeeAccount",
+    "eventAuthority",
+    "program",
+];
+
+const ROUTE_WITH_TOKEN_LEDGER_ACCOUNTS: [&str; 10] = [
+    "tokenProgram",
+    "userTransferAuthority",
+    "userSourceTokenAccount",
+    "userDestinationTokenAccount",
+    "destinationTokenAccount",
+    "destinationMint",
+    "platformFeeAccount",
+    "tokenLedger",
+    "eventAuthority",
+    "program",
+];
+
+const SHARED_ACCOUNTS: [&str; 13] = [
+    "tokenProgram",
+    "programAuthority",
+    "userTransferAuthority",
+    "sourceTokenAccount",
+    "programSourceTokenAccount",
+    "programDestinationTokenAccount",
+    "destinationTokenAccount",
+    "sourceMint",
+    "destinationMint",
+    "platformFeeAccount",
+    "token2022Program",
+    "eventAuthority",
+    "program",
+];
+
+const SHARED_ACCOUNTS_WITH_TOKEN_LEDGER: [&str; 14] = [
+    "tokenProgram",
+    "programAuthority",
+    "userTransferAuthority",
+    "sourceTokenAccount",
+    "programSourceTokenAccount",
+    "programDestinationTokenAccount",
+    "destinationTokenAccount",
+    "sourceMint",
+    "destinationMint",
+    "platformFeeAccount",
+    "token2022Program",
+    "tokenLedger",
+    "eventAuthority",
+    "program",
+];
+
+const EXACT_OUT_ROUTE_ACCOUNTS: [&str; 11] = [
+    "tokenProgram",
+    "userTransferAuthority",
+    "userSourceTokenAccount",
+    "userDestinationTokenAccount",
+    "destinationTokenAccount",
+    "sourceMint",
+    "destinationMint",
+    "platformFeeAccount",
+    "token2022Program",
+    "eventAuthority",
+    "program",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AggregatorDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl AggregatorDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = AggregatorInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            AggregatorInstruction::Route(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ROUTE_ACCOUNTS),
+                name: "route".to_string(),
+            }),
+            AggregatorInstruction::RouteWithTokenLedger(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ROUTE_WITH_TOKEN_LEDGER_ACCOUNTS),
+                name: "routeWithTokenLedger".to_string(),
+            }),
+            AggregatorInstruction::SharedAccountsRoute(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SHARED_ACCOUNTS),
+                name: "sharedAccountsRoute".to_string(),
+            }),
+            AggregatorInstruction::SharedAccountsRouteWithTokenLedger(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&SHARED_ACCOUNTS_WITH_TOKEN_LEDGER),
+                    name: "sharedAccountsRouteWithTokenLedger".to_string(),
+                })
+            }
+            AggregatorInstruction::ExactOutRoute(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&EXACT_OUT_ROUTE_ACCOUNTS),
+                name: "exactOutRoute".to_string(),
+            }),
+            AggregatorInstruction::SharedAccountsExactOutRoute(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SHARED_ACCOUNTS),
+                name: "sharedAccountsExactOutRoute".to_string(),
+            }),
+            AggregatorInstruction::Event(event) => {
+                let (name, data) = match event {
+                    AggregatorEvent::Swap(event) => ("swapEvent", to_data_map(&event)?),
+                    AggregatorEvent::Fee(event) => ("feeEvent", to_data_map(&event)?),
+                };
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&["eventAuthority"]),
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// A single step of a jupiter route, pairing a `swapEvent` with the amm instruction
+/// which executed it
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapHop {
+    pub outer_index: usize,
+    /// index of the `swapEvent` within the transaction's instructions
+    pub event_index: usize,
+    /// index of the amm instruction within the transaction's instructions, None if the
+    /// amm instruction could not be found
+    pub instruction_index: Option<usize>,
+    /// program id of the amm reported by the event
+    pub amm: String,
+}
+
+/// Matches each jupiter `swapEvent` of the transaction to the amm instruction it reports.
+///
+/// The aggregator invokes the amm and then emits the event, so both sit at the same
+/// stack height and the amm instruction is the closest preceding invocation of `amm`.
+pub fn swap_hops(tx: &DecodedTransaction) -> Vec<SwapHop> {
+    let program_id = PROGRAM_ID.to_string();
+    tx.instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(event_index, event)| {
+            let decoded = event.decoded.as_ref()?;
+            if event.program_id != program_id || decoded.name != "swapEvent" {
+                return None;
+            }
+            let amm = decoded.data.get("amm")?.as_str()?.to_string();
+            let instruction_index = tx.instructions[..event_index].iter().rposition(|ix| {
+                ix.outer_index == event.outer_index
+                    && ix.stack_height == event.stack_height
+                    && ix.program_id == amm
+            });
+            Some(SwapHop {
+                outer_index: event.outer_index,
+                event_index,
+                instruction_index,
+                amm,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{anchor, transaction::DecodedTransactionInstruction};
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_route_plan() {
+        let mut data = ix::ROUTE.to_vec();
+        // two steps, whirlpool a to b followed by a raydium clmm swap
+        data.extend(2u32.to_le_bytes());
+        data.extend([17, 1, 100, 0, 1]);
+        data.extend([26, 100, 1, 2]);
+        data.extend(to_vec(&(1_000_000u64, 2_500_000u64, 50u16, 0u8)).unwrap());
+        let decoded = AggregatorDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: (0..9).map(|_| Pubkey::new_unique()).collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "route");
+        assert_eq!(decoded.data["inAmount"], 1_000_000u64);
+        assert_eq!(decoded.data["slippageBps"], 50);
+        let route_plan = decoded.data["routePlan"].as_array().unwrap();
+        assert_eq!(route_plan.len(), 2);
+        assert_eq!(route_plan[0]["swap"]["whirlpool"]["aToB"], true);
+        assert_eq!(route_plan[0]["percent"], 100);
+        assert_eq!(route_plan[1]["swap"], "raydiumClmm");
+        assert_eq!(route_plan[1]["inputIndex"], 1);
+        assert_eq!(route_plan[1]["outputIndex"], 2);
+        assert!(decoded.accounts.contains_key("destinationMint"));
+
+        // unknown swap variants fail to decode rather than misreading the route
+        let mut data = ix::ROUTE.to_vec();
+        data.extend(1u32.to_le_bytes());
+        data.extend([255, 100, 0, 1]);
+        assert!(ix::AggregatorInstruction::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn test_swap_hops() {
+        let amm = Pubkey::new_unique();
+        let decode_event = |amm: Pubkey| {
+            let mut data = anchor::EVENT_IX_TAG.to_vec();
+            data.extend(events::SWAP_EVENT);
+            data.extend(
+                to_vec(&(
+                    amm,
+                    Pubkey::new_unique(),
+                    10u64,
+                    Pubkey::new_unique(),
+                    20u64,
+                ))
+                .unwrap(),
+            );
+            AggregatorDecoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![Pubkey::new_unique()],
+                data,
+                stack_height: Some(2),
+            })
+            .decode()
+            .unwrap()
+        };
+        let instruction = |program_id: &Pubkey, inner_index, stack_height, decoded| {
+            DecodedTransactionInstruction {
+                program_id: program_id.to_string(),
+                outer_index: 0,
+                inner_index,
+                stack_height,
+                decoded,
+                ..Default::default()
+            }
+        };
+        let tx = DecodedTransaction {
+            instructions: vec![
+                instruction(&PROGRAM_ID, None, Some(1), None),
+                instruction(&amm, Some(0), Some(2), None),
+                // token transfer made by the amm
+                instruction(&Pubkey::new_unique(), Some(1), Some(3), None),
+                instruction(&PROGRAM_ID, Some(2), Some(2), Some(decode_event(amm))),
+                // event for an amm which wasn't invoked
+                instruction(
+                    &PROGRAM_ID,
+                    Some(3),
+                    Some(2),
+                    Some(decode_event(Pubkey::new_unique())),
+                ),
+            ],
+            ..Default::default()
+        };
+        let hops = swap_hops(&tx);
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].event_index, 3);
+        assert_eq!(hops[0].instruction_index, Some(1));
+        assert_eq!(hops[0].amm, amm.to_string());
+        assert_eq!(hops[1].instruction_index, None);
+    }
+}
@@ -0,0 +1,107 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const OPEN_DCA: [u8; 8] = [36, 65, 185, 54, 1, 210, 100, 163];
+pub const OPEN_DCA_V2: [u8; 8] = [142, 119, 43, 109, 162, 52, 11, 177];
+pub const CLOSE_DCA: [u8; 8] = [22, 7, 33, 98, 168, 183, 34, 243];
+pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const INITIATE_FLASH_FILL: [u8; 8] = [143, 205, 3, 191, 162, 215, 245, 49];
+pub const FULFILL_FLASH_FILL: [u8; 8] = [115, 64, 226, 78, 33, 211, 105, 162];
+pub const INITIATE_DLMM_FILL: [u8; 8] = [155, 193, 80, 121, 91, 147, 254, 187];
+pub const FULFILL_DLMM_FILL: [u8; 8] = [1, 230, 118, 251, 45, 177, 101, 187];
+pub const END_AND_CLOSE: [u8; 8] = [83, 125, 166, 69, 247, 252, 103, 133];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenDcaInstruction {
+    /// seed of the dca account, allowing a user to hold several dcas
+    pub application_idx: u64,
+    pub in_amount: u64,
+    pub in_amount_per_cycle: u64,
+    /// seconds between each cycle
+    pub cycle_frequency: i64,
+    pub min_price: Option<u64>,
+    pub max_price: Option<u64>,
+    pub start_at: Option<i64>,
+    pub close_wsol_in_ata: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenDcaV2Instruction {
+    pub application_idx: u64,
+    pub in_amount: u64,
+    pub in_amount_per_cycle: u64,
+    pub cycle_frequency: i64,
+    pub min_out_amount: Option<u64>,
+    pub max_out_amount: Option<u64>,
+    pub start_at: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Withdrawal {
+    In,
+    Out,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawInstruction {
+    pub withdraw_amount: u64,
+    /// whether the unused input or the received output is withdrawn
+    pub withdrawal: Withdrawal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInstruction {
+    pub deposit_in: u64,
+}
+
+/// Completes a keeper fill, repaying the output of the swap to the dca
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FulfillFillInstruction {
+    pub repay_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DcaInstruction {
+    OpenDca(OpenDcaInstruction),
+    OpenDcaV2(OpenDcaV2Instruction),
+    CloseDca,
+    Withdraw(WithdrawInstruction),
+    Deposit(DepositInstruction),
+    InitiateFlashFill,
+    FulfillFlashFill(FulfillFillInstruction),
+    InitiateDlmmFill,
+    FulfillDlmmFill(FulfillFillInstruction),
+    EndAndClose,
+}
+
+impl DcaInstruction {
+    /// Unpacks a byte buffer into a [DcaInstruction](enum.DcaInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            OPEN_DCA => Self::OpenDca(anchor::deserialize(rest)?),
+            OPEN_DCA_V2 => Self::OpenDcaV2(anchor::deserialize(rest)?),
+            CLOSE_DCA => Self::CloseDca,
+            WITHDRAW => Self::Withdraw(anchor::deserialize(rest)?),
+            DEPOSIT => Self::Deposit(anchor::deserialize(rest)?),
+            INITIATE_FLASH_FILL => Self::InitiateFlashFill,
+            FULFILL_FLASH_FILL => Self::FulfillFlashFill(anchor::deserialize(rest)?),
+            INITIATE_DLMM_FILL => Self::InitiateDlmmFill,
+            FULFILL_DLMM_FILL => Self::FulfillDlmmFill(anchor::deserialize(rest)?),
+            END_AND_CLOSE => Self::EndAndClose,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
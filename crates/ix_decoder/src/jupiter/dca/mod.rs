@@ -0,0 +1,247 @@
+//! Instruction decoding functions for the jupiter dca program
+
+use anyhow::Context;
+use ix::DcaInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M");
+
+const FLASH_FILL_ACCOUNTS: [&str; 15] = [
+    "keeper",
+    "dca",
+    "inputMint",
+    "outputMint",
+    "keeperInAta",
+    "inAta",
+    "outAta",
+    "feeAuthority",
+    "feeAta",
+    "instructionsSysvar",
+    "systemProgram",
+    "tokenProgram",
+    "associatedTokenProgram",
+    "eventAuthority",
+    "program",
+];
+
+const INITIATE_FILL_ACCOUNTS: [&str; 10] = [
+    "keeper",
+    "dca",
+    "inputMint",
+    "keeperInAta",
+    "inAta",
+    "outAta",
+    "instructionsSysvar",
+    "systemProgram",
+    "tokenProgram",
+    "associatedTokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DcaDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DcaDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DcaInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DcaInstruction::OpenDca(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "dca",
+                    "user",
+                    "inputMint",
+                    "outputMint",
+                    "userAta",
+                    "inAta",
+                    "outAta",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "openDca".to_string(),
+            }),
+            DcaInstruction::OpenDcaV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "dca",
+                    "user",
+                    "payer",
+                    "inputMint",
+                    "outputMint",
+                    "userAta",
+                    "inAta",
+                    "outAta",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "openDcaV2".to_string(),
+            }),
+            DcaInstruction::CloseDca => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "user",
+                    "dca",
+                    "inputMint",
+                    "outputMint",
+                    "inAta",
+                    "outAta",
+                    "userInAta",
+                    "userOutAta",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "closeDca".to_string(),
+                ..Default::default()
+            }),
+            DcaInstruction::Withdraw(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "user",
+                    "dca",
+                    "inputMint",
+                    "outputMint",
+                    "dcaAta",
+                    "userInAta",
+                    "userOutAta",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "withdraw".to_string(),
+            }),
+            DcaInstruction::Deposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "user",
+                    "dca",
+                    "inAta",
+                    "userInAta",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "deposit".to_string(),
+            }),
+            DcaInstruction::InitiateFlashFill => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&INITIATE_FILL_ACCOUNTS),
+                name: "initiateFlashFill".to_string(),
+                ..Default::default()
+            }),
+            DcaInstruction::FulfillFlashFill(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FLASH_FILL_ACCOUNTS),
+                name: "fulfillFlashFill".to_string(),
+            }),
+            DcaInstruction::InitiateDlmmFill => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&INITIATE_FILL_ACCOUNTS),
+                name: "initiateDlmmFill".to_string(),
+                ..Default::default()
+            }),
+            DcaInstruction::FulfillDlmmFill(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FLASH_FILL_ACCOUNTS),
+                name: "fulfillDlmmFill".to_string(),
+            }),
+            DcaInstruction::EndAndClose => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "keeper",
+                    "dca",
+                    "inputMint",
+                    "outputMint",
+                    "inAta",
+                    "outAta",
+                    "user",
+                    "userOutAta",
+                    "initUserOutAta",
+                    "intermediateAccount",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "endAndClose".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_open_and_close_dca() {
+        let decode = |data: Vec<u8>, accounts: &[Pubkey]| {
+            DcaDecoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: accounts.to_vec(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        let accounts: Vec<Pubkey> = (0..13).map(|_| Pubkey::new_unique()).collect();
+        assert_eq!(ix::OPEN_DCA, anchor::instruction_discriminator("open_dca"));
+        let mut data = ix::OPEN_DCA.to_vec();
+        data.extend(to_vec(&(3u64, 10_000_000u64, 1_000_000u64, 3_600i64)).unwrap());
+        data.extend(to_vec(&(None::<u64>, Some(250u64), None::<i64>, Some(true))).unwrap());
+        let decoded = decode(data, &accounts[..12]);
+        assert_eq!(decoded.name, "openDca");
+        assert_eq!(decoded.data["applicationIdx"], 3);
+        assert_eq!(decoded.data["inAmountPerCycle"], 1_000_000);
+        assert_eq!(decoded.data["cycleFrequency"], 3_600);
+        assert!(decoded.data["minPrice"].is_null());
+        assert_eq!(decoded.data["maxPrice"], 250);
+        assert_eq!(decoded.data["closeWsolInAta"], true);
+        assert_eq!(decoded.accounts["user"], accounts[1].to_string());
+        assert_eq!(decoded.accounts["outAta"], accounts[6].to_string());
+
+        assert_eq!(
+            ix::OPEN_DCA_V2,
+            anchor::instruction_discriminator("open_dca_v2")
+        );
+        let mut data = ix::OPEN_DCA_V2.to_vec();
+        data.extend(to_vec(&(4u64, 5_000_000u64, 500_000u64, 60i64)).unwrap());
+        data.extend(to_vec(&(Some(1_000u64), None::<u64>, Some(1_700_000_000i64))).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "openDcaV2");
+        assert_eq!(decoded.data["minOutAmount"], 1_000);
+        assert_eq!(decoded.data["startAt"], 1_700_000_000i64);
+        assert_eq!(decoded.accounts["payer"], accounts[2].to_string());
+
+        assert_eq!(
+            ix::CLOSE_DCA,
+            anchor::instruction_discriminator("close_dca")
+        );
+        let decoded = decode(ix::CLOSE_DCA.to_vec(), &accounts);
+        assert_eq!(decoded.name, "closeDca");
+        assert!(decoded.data.is_empty());
+        assert_eq!(decoded.accounts["user"], accounts[0].to_string());
+        assert_eq!(decoded.accounts["userOutAta"], accounts[7].to_string());
+    }
+}
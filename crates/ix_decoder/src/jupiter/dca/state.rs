@@ -0,0 +1,49 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const DCA_DISCRIMINATOR: [u8; 8] = [82, 93, 90, 127, 40, 101, 145, 154];
+
+/// An open dca, closed once every cycle has been filled or the user closes it
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dca {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub input_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub output_mint: Pubkey,
+    pub idx: u64,
+    pub next_cycle_at: i64,
+    pub in_deposited: u64,
+    pub in_withdrawn: u64,
+    pub out_withdrawn: u64,
+    pub in_used: u64,
+    pub out_received: u64,
+    pub in_amount_per_cycle: u64,
+    pub cycle_frequency: i64,
+    pub next_cycle_amount_left: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub in_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub out_account: Pubkey,
+    /// zero when no minimum was set
+    pub min_out_amount: u64,
+    /// zero when no maximum was set
+    pub max_out_amount: u64,
+    pub keeper_in_balance_before_borrow: u64,
+    pub dca_out_balance_before_swap: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Dca {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, DCA_DISCRIMINATOR)
+    }
+}
@@ -0,0 +1,68 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const INITIALIZE_ORDER: [u8; 8] = [133, 110, 74, 175, 112, 159, 245, 159];
+pub const FILL_ORDER: [u8; 8] = [232, 122, 115, 25, 199, 143, 136, 162];
+pub const PRE_FLASH_FILL_ORDER: [u8; 8] = [240, 47, 153, 68, 13, 190, 225, 42];
+pub const FLASH_FILL_ORDER: [u8; 8] = [252, 104, 18, 134, 164, 78, 18, 140];
+pub const CANCEL_ORDER: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+pub const CANCEL_EXPIRED_ORDER: [u8; 8] = [216, 120, 64, 235, 155, 19, 229, 99];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeOrderInstruction {
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub expired_at: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillOrderInstruction {
+    pub making_amount: u64,
+    pub max_taking_amount: u64,
+}
+
+/// Borrows the input tokens from the order reserve ahead of a flash fill
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreFlashFillOrderInstruction {
+    pub making_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlashFillOrderInstruction {
+    pub max_taking_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitOrderInstruction {
+    InitializeOrder(InitializeOrderInstruction),
+    FillOrder(FillOrderInstruction),
+    PreFlashFillOrder(PreFlashFillOrderInstruction),
+    FlashFillOrder(FlashFillOrderInstruction),
+    CancelOrder,
+    CancelExpiredOrder,
+}
+
+impl LimitOrderInstruction {
+    /// Unpacks a byte buffer into a [LimitOrderInstruction](enum.LimitOrderInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_ORDER => Self::InitializeOrder(anchor::deserialize(rest)?),
+            FILL_ORDER => Self::FillOrder(anchor::deserialize(rest)?),
+            PRE_FLASH_FILL_ORDER => Self::PreFlashFillOrder(anchor::deserialize(rest)?),
+            FLASH_FILL_ORDER => Self::FlashFillOrder(anchor::deserialize(rest)?),
+            CANCEL_ORDER => Self::CancelOrder,
+            CANCEL_EXPIRED_ORDER => Self::CancelExpiredOrder,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,181 @@
+//! Instruction decoding functions for the jupiter limit order v1 program
+
+use anyhow::Context;
+use ix::LimitOrderInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("jupoNjAxXgZ4rjzxzPMP4oxduvQsQtZzyknqvzYNrNu");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LimitOrderDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl LimitOrderDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = LimitOrderInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            LimitOrderInstruction::InitializeOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "base",
+                    "maker",
+                    "order",
+                    "reserve",
+                    "makerInputAccount",
+                    "inputMint",
+                    "makerOutputAccount",
+                    "referral",
+                    "outputMint",
+                    "systemProgram",
+                    "tokenProgram",
+                    "rent",
+                ]),
+                name: "initializeOrder".to_string(),
+            }),
+            LimitOrderInstruction::FillOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "order",
+                    "reserve",
+                    "maker",
+                    "taker",
+                    "takerOutputAccount",
+                    "makerOutputAccount",
+                    "takerInputAccount",
+                    "feeAuthority",
+                    "programFeeAccount",
+                    "referral",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "fillOrder".to_string(),
+            }),
+            LimitOrderInstruction::PreFlashFillOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "order",
+                    "reserve",
+                    "taker",
+                    "takerOutputAccount",
+                    "inputMint",
+                    "inputMintTokenProgram",
+                    "instruction",
+                ]),
+                name: "preFlashFillOrder".to_string(),
+            }),
+            LimitOrderInstruction::FlashFillOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "order",
+                    "reserve",
+                    "maker",
+                    "taker",
+                    "makerOutputAccount",
+                    "takerInputAccount",
+                    "feeAuthority",
+                    "programFeeAccount",
+                    "referral",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "flashFillOrder".to_string(),
+            }),
+            LimitOrderInstruction::CancelOrder => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "order",
+                    "reserve",
+                    "maker",
+                    "makerInputAccount",
+                    "systemProgram",
+                    "tokenProgram",
+                    "inputMint",
+                ]),
+                name: "cancelOrder".to_string(),
+                ..Default::default()
+            }),
+            LimitOrderInstruction::CancelExpiredOrder => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "order",
+                    "reserve",
+                    "maker",
+                    "makerInputAccount",
+                    "tokenProgram",
+                    "inputMint",
+                ]),
+                name: "cancelExpiredOrder".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_initialize_fill_and_cancel_order() {
+        let decode = |data: Vec<u8>, accounts: &[Pubkey]| {
+            LimitOrderDecoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: accounts.to_vec(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        let accounts: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
+        assert_eq!(
+            ix::INITIALIZE_ORDER,
+            anchor::instruction_discriminator("initialize_order")
+        );
+        let mut data = ix::INITIALIZE_ORDER.to_vec();
+        data.extend(to_vec(&(1_000u64, 2_000u64, None::<i64>)).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "initializeOrder");
+        assert_eq!(decoded.data["makingAmount"], 1_000);
+        assert_eq!(decoded.data["takingAmount"], 2_000);
+        assert!(decoded.data["expiredAt"].is_null());
+        assert_eq!(decoded.accounts["maker"], accounts[1].to_string());
+        assert_eq!(decoded.accounts["outputMint"], accounts[8].to_string());
+
+        assert_eq!(
+            ix::FILL_ORDER,
+            anchor::instruction_discriminator("fill_order")
+        );
+        let mut data = ix::FILL_ORDER.to_vec();
+        data.extend(to_vec(&(400u64, 810u64)).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "fillOrder");
+        assert_eq!(decoded.data["makingAmount"], 400);
+        assert_eq!(decoded.data["maxTakingAmount"], 810);
+        assert_eq!(decoded.accounts["taker"], accounts[3].to_string());
+        assert_eq!(decoded.accounts["referral"], accounts[9].to_string());
+
+        assert_eq!(
+            ix::CANCEL_ORDER,
+            anchor::instruction_discriminator("cancel_order")
+        );
+        let decoded = decode(ix::CANCEL_ORDER.to_vec(), &accounts[..7]);
+        assert_eq!(decoded.name, "cancelOrder");
+        assert!(decoded.data.is_empty());
+        assert_eq!(
+            decoded.accounts["makerInputAccount"],
+            accounts[3].to_string()
+        );
+        assert_eq!(decoded.accounts["inputMint"], accounts[6].to_string());
+    }
+}
@@ -0,0 +1,50 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey},
+};
+
+pub const ORDER_DISCRIMINATOR: [u8; 8] = [134, 173, 223, 185, 77, 86, 28, 51];
+
+/// An open limit order, closed once fully filled or cancelled
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub input_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub output_mint: Pubkey,
+    /// set between a pre flash fill and the flash fill completing it
+    pub waiting: bool,
+    pub ori_making_amount: u64,
+    pub ori_taking_amount: u64,
+    /// amount of the input mint still to be sold
+    pub making_amount: u64,
+    /// amount of the output mint still to be bought
+    pub taking_amount: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker_input_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker_output_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub reserve: Pubkey,
+    pub borrow_making_amount: u64,
+    pub expired_at: Option<i64>,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub base: Pubkey,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub referral: Option<Pubkey>,
+}
+
+impl Order {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, ORDER_DISCRIMINATOR)
+    }
+}
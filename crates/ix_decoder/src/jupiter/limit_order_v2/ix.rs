@@ -0,0 +1,80 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const INITIALIZE_ORDER: [u8; 8] = [133, 110, 74, 175, 112, 159, 245, 159];
+pub const PRE_FLASH_FILL_ORDER: [u8; 8] = [240, 47, 153, 68, 13, 190, 225, 42];
+pub const FLASH_FILL_ORDER: [u8; 8] = [252, 104, 18, 134, 164, 78, 18, 140];
+pub const CANCEL_ORDER: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeOrderInstruction {
+    /// seed of the order account, allowing a maker to hold several orders
+    pub unique_id: u64,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub expired_at: Option<i64>,
+    pub fee_bps: Option<u16>,
+    /// only present in orders created since slippage was introduced
+    pub slippage_bps: Option<u16>,
+}
+
+impl InitializeOrderInstruction {
+    fn unpack(mut input: &[u8]) -> anyhow::Result<Self> {
+        let (unique_id, making_amount, taking_amount, expired_at, fee_bps) =
+            <(u64, u64, u64, Option<i64>, Option<u16>)>::deserialize(&mut input)?;
+        let slippage_bps = if input.is_empty() {
+            None
+        } else {
+            Option::<u16>::deserialize(&mut input)?
+        };
+        Ok(Self {
+            unique_id,
+            making_amount,
+            taking_amount,
+            expired_at,
+            fee_bps,
+            slippage_bps,
+        })
+    }
+}
+
+/// Borrows the input tokens from the order reserve ahead of a flash fill
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreFlashFillOrderInstruction {
+    pub making_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlashFillOrderInstruction {
+    pub max_taking_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitOrderInstruction {
+    InitializeOrder(InitializeOrderInstruction),
+    PreFlashFillOrder(PreFlashFillOrderInstruction),
+    FlashFillOrder(FlashFillOrderInstruction),
+    CancelOrder,
+}
+
+impl LimitOrderInstruction {
+    /// Unpacks a byte buffer into a [LimitOrderInstruction](enum.LimitOrderInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_ORDER => Self::InitializeOrder(InitializeOrderInstruction::unpack(rest)?),
+            PRE_FLASH_FILL_ORDER => Self::PreFlashFillOrder(anchor::deserialize(rest)?),
+            FLASH_FILL_ORDER => Self::FlashFillOrder(anchor::deserialize(rest)?),
+            CANCEL_ORDER => Self::CancelOrder,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,136 @@
+//! Instruction decoding functions for the jupiter limit order v2 program
+
+use anyhow::Context;
+use ix::LimitOrderInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LimitOrderV2Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl LimitOrderV2Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = LimitOrderInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            LimitOrderInstruction::InitializeOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "maker",
+                    "order",
+                    "inputMintReserve",
+                    "makerInputMintAccount",
+                    "feeAccount",
+                    "referral",
+                    "inputMint",
+                    "outputMint",
+                    "inputTokenProgram",
+                    "outputTokenProgram",
+                    "systemProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializeOrder".to_string(),
+            }),
+            LimitOrderInstruction::PreFlashFillOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "taker",
+                    "order",
+                    "inputMintReserve",
+                    "takerInputMintAccount",
+                    "inputMint",
+                    "inputTokenProgram",
+                    "instruction",
+                ]),
+                name: "preFlashFillOrder".to_string(),
+            }),
+            LimitOrderInstruction::FlashFillOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "taker",
+                    "maker",
+                    "order",
+                    "makerOutputMintAccount",
+                    "takerOutputMintAccount",
+                    "feeAccount",
+                    "outputTokenProgram",
+                    "outputMint",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "flashFillOrder".to_string(),
+            }),
+            LimitOrderInstruction::CancelOrder => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "signer",
+                    "maker",
+                    "order",
+                    "inputMintReserve",
+                    "makerInputMintAccount",
+                    "inputMint",
+                    "inputTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "cancelOrder".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_initialize_order_with_and_without_slippage() {
+        let mut data = ix::INITIALIZE_ORDER.to_vec();
+        data.extend(
+            to_vec(&(
+                7u64,
+                1_000u64,
+                2_000u64,
+                Some(1_700_000_000i64),
+                None::<u16>,
+            ))
+            .unwrap(),
+        );
+        let decoder = |data: Vec<u8>| {
+            LimitOrderV2Decoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: (0..15).map(|_| Pubkey::new_unique()).collect(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        let decoded = decoder(data.clone());
+        assert_eq!(decoded.name, "initializeOrder");
+        assert_eq!(decoded.data["uniqueId"], 7);
+        assert_eq!(decoded.data["expiredAt"], 1_700_000_000i64);
+        assert!(decoded.data["slippageBps"].is_null());
+
+        data.extend(to_vec(&Some(50u16)).unwrap());
+        let decoded = decoder(data);
+        assert_eq!(decoded.data["slippageBps"], 50);
+        assert_eq!(decoded.accounts.len(), 15);
+    }
+}
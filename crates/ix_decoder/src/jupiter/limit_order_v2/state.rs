@@ -0,0 +1,48 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const ORDER_DISCRIMINATOR: [u8; 8] = [134, 173, 223, 185, 77, 86, 28, 51];
+
+/// An open limit order, closed once fully filled or cancelled
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub input_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub output_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub input_token_program: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub output_token_program: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub input_mint_reserve: Pubkey,
+    pub unique_id: u64,
+    pub ori_making_amount: u64,
+    pub ori_taking_amount: u64,
+    /// amount of the input mint still to be sold
+    pub making_amount: u64,
+    /// amount of the output mint still to be bought
+    pub taking_amount: u64,
+    pub borrow_making_amount: u64,
+    pub expired_at: Option<i64>,
+    pub fee_bps: u16,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_account: Pubkey,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl Order {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, ORDER_DISCRIMINATOR)
+    }
+}
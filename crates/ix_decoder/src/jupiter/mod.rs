@@ -0,0 +1,83 @@
+//! Instruction decoding functions for the jupiter programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use aggregator::AggregatorDecoder;
+use anyhow::Context;
+use dca::DcaDecoder;
+use limit_order::LimitOrderDecoder;
+use limit_order_v2::LimitOrderV2Decoder;
+use once_cell::sync::Lazy;
+use perps::PerpsDecoder;
+use std::sync::Arc;
+
+pub mod aggregator;
+pub mod dca;
+pub mod limit_order;
+pub mod limit_order_v2;
+pub mod perps;
+
+pub static JUPITER_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(JupiterProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Aggregator(AggregatorDecoder),
+    LimitOrder(LimitOrderDecoder),
+    LimitOrderV2(LimitOrderV2Decoder),
+    Dca(DcaDecoder),
+    Perps(PerpsDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct JupiterProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Aggregator(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Jupiter::Aggregator"),
+            Self::LimitOrder(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Jupiter::LimitOrder"),
+            Self::LimitOrderV2(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Jupiter::LimitOrderV2"),
+            Self::Dca(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Jupiter::Dca"),
+            Self::Perps(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Jupiter::Perps"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Aggregator(decoder) => format!("{:#?}", decoder),
+            Self::LimitOrder(decoder) => format!("{:#?}", decoder),
+            Self::LimitOrderV2(decoder) => format!("{:#?}", decoder),
+            Self::Dca(decoder) => format!("{:#?}", decoder),
+            Self::Perps(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for JupiterProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            aggregator::PROGRAM_ID => Some(Box::new(Programs::Aggregator(AggregatorDecoder::new(
+                ix.clone(),
+            )))),
+            limit_order::PROGRAM_ID => Some(Box::new(Programs::LimitOrder(
+                LimitOrderDecoder::new(ix.clone()),
+            ))),
+            limit_order_v2::PROGRAM_ID => Some(Box::new(Programs::LimitOrderV2(
+                LimitOrderV2Decoder::new(ix.clone()),
+            ))),
+            dca::PROGRAM_ID => Some(Box::new(Programs::Dca(DcaDecoder::new(ix.clone())))),
+            perps::PROGRAM_ID => Some(Box::new(Programs::Perps(PerpsDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use super::state::Side;
+use crate::anchor;
+
+pub const CREATE_INCREASE_POSITION_MARKET_REQUEST: [u8; 8] = [184, 85, 199, 24, 105, 171, 156, 56];
+pub const CREATE_DECREASE_POSITION_MARKET_REQUEST: [u8; 8] = [74, 198, 195, 86, 193, 99, 1, 79];
+pub const CLOSE_POSITION_REQUEST: [u8; 8] = [40, 105, 217, 188, 220, 45, 109, 110];
+pub const INCREASE_POSITION_PRE_SWAP: [u8; 8] = [26, 136, 225, 217, 22, 21, 83, 20];
+pub const INCREASE_POSITION4: [u8; 8] = [67, 147, 53, 23, 43, 57, 16, 67];
+pub const DECREASE_POSITION4: [u8; 8] = [185, 161, 114, 175, 96, 148, 3, 170];
+pub const LIQUIDATE_FULL_POSITION4: [u8; 8] = [64, 176, 88, 51, 168, 188, 156, 175];
+
+/// Requests a keeper to open or increase a position at the market price
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateIncreasePositionMarketRequestInstruction {
+    /// usd amounts use 6 decimals
+    pub size_usd_delta: u64,
+    pub collateral_token_delta: u64,
+    pub side: Side,
+    pub price_slippage: u64,
+    /// set when the collateral is swapped from the input mint through jupiter
+    pub jupiter_minimum_out: Option<u64>,
+    /// seed of the position request account
+    pub counter: u64,
+}
+
+/// Requests a keeper to decrease or close a position at the market price
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDecreasePositionMarketRequestInstruction {
+    pub collateral_usd_delta: u64,
+    pub size_usd_delta: u64,
+    pub price_slippage: u64,
+    pub jupiter_minimum_out: Option<u64>,
+    pub entire_position: Option<bool>,
+    pub counter: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PerpsInstruction {
+    CreateIncreasePositionMarketRequest(CreateIncreasePositionMarketRequestInstruction),
+    CreateDecreasePositionMarketRequest(CreateDecreasePositionMarketRequestInstruction),
+    ClosePositionRequest,
+    IncreasePositionPreSwap,
+    IncreasePosition4,
+    DecreasePosition4,
+    LiquidateFullPosition4,
+}
+
+impl PerpsInstruction {
+    /// Unpacks a byte buffer into a [PerpsInstruction](enum.PerpsInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE_INCREASE_POSITION_MARKET_REQUEST => {
+                Self::CreateIncreasePositionMarketRequest(anchor::deserialize(rest)?)
+            }
+            CREATE_DECREASE_POSITION_MARKET_REQUEST => {
+                Self::CreateDecreasePositionMarketRequest(anchor::deserialize(rest)?)
+            }
+            CLOSE_POSITION_REQUEST => Self::ClosePositionRequest,
+            INCREASE_POSITION_PRE_SWAP => Self::IncreasePositionPreSwap,
+            INCREASE_POSITION4 => Self::IncreasePosition4,
+            DECREASE_POSITION4 => Self::DecreasePosition4,
+            LIQUIDATE_FULL_POSITION4 => Self::LiquidateFullPosition4,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
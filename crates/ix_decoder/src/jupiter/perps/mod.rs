@@ -0,0 +1,231 @@
+//! Instruction decoding functions for the jupiter perpetuals program
+//!
+//! Positions are changed through position requests, created by the trader and then
+//! fulfilled by a keeper which executes the change at the oracle price.
+
+use anyhow::Context;
+use ix::PerpsInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("PERPHjGBqRHArX4DySjwM6UJHiR3sWAatqfdBS2qQJu");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PerpsDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl PerpsDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = PerpsInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            PerpsInstruction::CreateIncreasePositionMarketRequest(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "fundingAccount",
+                    "perpetuals",
+                    "pool",
+                    "position",
+                    "positionRequest",
+                    "positionRequestAta",
+                    "custody",
+                    "collateralCustody",
+                    "inputMint",
+                    "referral",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "createIncreasePositionMarketRequest".to_string(),
+            }),
+            PerpsInstruction::CreateDecreasePositionMarketRequest(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "receivingAccount",
+                    "perpetuals",
+                    "pool",
+                    "position",
+                    "positionRequest",
+                    "positionRequestAta",
+                    "custody",
+                    "collateralCustody",
+                    "desiredMint",
+                    "referral",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "createDecreasePositionMarketRequest".to_string(),
+            }),
+            PerpsInstruction::ClosePositionRequest => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "keeper",
+                    "owner",
+                    "ownerAta",
+                    "pool",
+                    "positionRequest",
+                    "positionRequestAta",
+                    "position",
+                    "tokenProgram",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "closePositionRequest".to_string(),
+                ..Default::default()
+            }),
+            PerpsInstruction::IncreasePositionPreSwap => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "keeper",
+                    "keeperAta",
+                    "positionRequest",
+                    "positionRequestAta",
+                    "position",
+                    "collateralCustody",
+                    "collateralCustodyTokenAccount",
+                    "instruction",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "increasePositionPreSwap".to_string(),
+                ..Default::default()
+            }),
+            PerpsInstruction::IncreasePosition4 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "keeper",
+                    "perpetuals",
+                    "pool",
+                    "positionRequest",
+                    "positionRequestAta",
+                    "position",
+                    "custody",
+                    "custodyDovesPriceAccount",
+                    "custodyPythnetPriceAccount",
+                    "collateralCustody",
+                    "collateralCustodyDovesPriceAccount",
+                    "collateralCustodyPythnetPriceAccount",
+                    "collateralCustodyTokenAccount",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "increasePosition4".to_string(),
+                ..Default::default()
+            }),
+            PerpsInstruction::DecreasePosition4 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "keeper",
+                    "owner",
+                    "transferAuthority",
+                    "perpetuals",
+                    "pool",
+                    "positionRequest",
+                    "positionRequestAta",
+                    "position",
+                    "custody",
+                    "custodyDovesPriceAccount",
+                    "custodyPythnetPriceAccount",
+                    "collateralCustody",
+                    "collateralCustodyDovesPriceAccount",
+                    "collateralCustodyPythnetPriceAccount",
+                    "collateralCustodyTokenAccount",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "decreasePosition4".to_string(),
+                ..Default::default()
+            }),
+            PerpsInstruction::LiquidateFullPosition4 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "signer",
+                    "perpetuals",
+                    "pool",
+                    "position",
+                    "custody",
+                    "custodyDovesPriceAccount",
+                    "custodyPythnetPriceAccount",
+                    "collateralCustody",
+                    "collateralCustodyDovesPriceAccount",
+                    "collateralCustodyPythnetPriceAccount",
+                    "collateralCustodyTokenAccount",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "liquidateFullPosition4".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_increase_request_and_position() {
+        let mut data = ix::CREATE_INCREASE_POSITION_MARKET_REQUEST.to_vec();
+        data.extend(
+            to_vec(&(
+                100_000_000u64,
+                50_000_000u64,
+                2u8,
+                150_000_000u64,
+                None::<u64>,
+                3u64,
+            ))
+            .unwrap(),
+        );
+        let decoded = PerpsDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: (0..16).map(|_| Pubkey::new_unique()).collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "createIncreasePositionMarketRequest");
+        assert_eq!(decoded.data["side"], "short");
+        assert_eq!(decoded.data["counter"], 3);
+        assert!(decoded.data["jupiterMinimumOut"].is_null());
+
+        let owner = Pubkey::new_unique();
+        let mut data = state::POSITION_DISCRIMINATOR.to_vec();
+        data.extend(owner.to_bytes());
+        data.extend([0u8; 32 * 3]);
+        data.extend(to_vec(&(1i64, 2i64, 1u8, 150_000_000u64, 100_000_000u64)).unwrap());
+        data.extend(to_vec(&(10_000_000u64, -5i64, 7u128, 0u64, 254u8)).unwrap());
+        let position = state::Position::unpack(&data).unwrap();
+        assert_eq!(position.owner, owner);
+        assert_eq!(position.side, state::Side::Long);
+        assert_eq!(position.realised_pnl_usd, -5);
+        assert_eq!(position.bump, 254);
+
+        assert_eq!(
+            anchor::account_discriminator("PositionRequest"),
+            state::POSITION_REQUEST_DISCRIMINATOR
+        );
+        assert_eq!(
+            anchor::instruction_discriminator("liquidate_full_position4"),
+            ix::LIQUIDATE_FULL_POSITION4
+        );
+    }
+}
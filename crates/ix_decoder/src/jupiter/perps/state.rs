@@ -0,0 +1,113 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey, serialize_u128},
+};
+
+pub const POSITION_DISCRIMINATOR: [u8; 8] = [170, 188, 143, 228, 122, 64, 247, 208];
+pub const POSITION_REQUEST_DISCRIMINATOR: [u8; 8] = [12, 38, 250, 199, 46, 154, 32, 216];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    /// a closed position
+    #[default]
+    None,
+    Long,
+    Short,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RequestChange {
+    #[default]
+    None,
+    Increase,
+    Decrease,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RequestType {
+    #[default]
+    Market,
+    Trigger,
+}
+
+/// A trader's position in a custody, the account is kept and reused once the position is closed
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub custody: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub collateral_custody: Pubkey,
+    pub open_time: i64,
+    pub update_time: i64,
+    pub side: Side,
+    /// average entry price, with 6 decimals
+    pub price: u64,
+    pub size_usd: u64,
+    pub collateral_usd: u64,
+    pub realised_pnl_usd: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub cumulative_interest_snapshot: u128,
+    pub locked_amount: u64,
+    pub bump: u8,
+}
+
+impl Position {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, POSITION_DISCRIMINATOR)
+    }
+}
+
+/// A pending change to a position, executed by a keeper and then closed
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRequest {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub custody: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub position: Pubkey,
+    /// mint deposited or received by the owner
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    pub open_time: i64,
+    pub update_time: i64,
+    pub size_usd_delta: u64,
+    pub collateral_delta: u64,
+    pub request_change: RequestChange,
+    pub request_type: RequestType,
+    pub side: Side,
+    pub price_slippage: Option<u64>,
+    pub jupiter_minimum_out: Option<u64>,
+    pub pre_swap_amount: Option<u64>,
+    pub trigger_price: Option<u64>,
+    pub trigger_above_threshold: Option<bool>,
+    pub entire_position: Option<bool>,
+    pub executed: bool,
+    pub counter: u64,
+    pub bump: u8,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub referral: Option<Pubkey>,
+}
+
+impl PositionRequest {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, POSITION_REQUEST_DISCRIMINATOR)
+    }
+}
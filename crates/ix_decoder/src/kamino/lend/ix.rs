@@ -0,0 +1,158 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_pubkey},
+};
+
+pub const INIT_LENDING_MARKET: [u8; 8] = [34, 162, 116, 14, 101, 137, 94, 239];
+pub const INIT_RESERVE: [u8; 8] = [138, 245, 71, 225, 153, 4, 3, 43];
+pub const INIT_OBLIGATION: [u8; 8] = [251, 10, 231, 76, 27, 11, 159, 96];
+pub const INIT_USER_METADATA: [u8; 8] = [117, 169, 176, 69, 197, 23, 15, 162];
+pub const REFRESH_RESERVE: [u8; 8] = [2, 218, 138, 235, 79, 201, 25, 102];
+pub const REFRESH_OBLIGATION: [u8; 8] = [33, 132, 147, 228, 151, 192, 72, 89];
+pub const DEPOSIT_RESERVE_LIQUIDITY: [u8; 8] = [169, 201, 30, 126, 6, 205, 102, 68];
+pub const REDEEM_RESERVE_COLLATERAL: [u8; 8] = [234, 117, 181, 125, 185, 142, 220, 29];
+pub const DEPOSIT_OBLIGATION_COLLATERAL: [u8; 8] = [108, 209, 4, 72, 21, 22, 118, 133];
+pub const WITHDRAW_OBLIGATION_COLLATERAL: [u8; 8] = [37, 116, 205, 103, 243, 192, 92, 198];
+pub const BORROW_OBLIGATION_LIQUIDITY: [u8; 8] = [121, 127, 18, 204, 73, 245, 225, 65];
+pub const REPAY_OBLIGATION_LIQUIDITY: [u8; 8] = [145, 178, 13, 225, 76, 240, 147, 72];
+pub const DEPOSIT_RESERVE_LIQUIDITY_AND_OBLIGATION_COLLATERAL: [u8; 8] =
+    [129, 199, 4, 2, 222, 39, 26, 46];
+pub const WITHDRAW_OBLIGATION_COLLATERAL_AND_REDEEM_RESERVE_COLLATERAL: [u8; 8] =
+    [75, 93, 93, 220, 34, 150, 218, 196];
+pub const LIQUIDATE_OBLIGATION_AND_REDEEM_RESERVE_COLLATERAL: [u8; 8] =
+    [177, 71, 154, 188, 226, 133, 74, 55];
+pub const FLASH_BORROW_RESERVE_LIQUIDITY: [u8; 8] = [135, 231, 52, 167, 7, 52, 212, 193];
+pub const FLASH_REPAY_RESERVE_LIQUIDITY: [u8; 8] = [185, 117, 0, 203, 96, 245, 180, 186];
+pub const REDEEM_FEES: [u8; 8] = [215, 39, 180, 41, 173, 46, 248, 220];
+pub const SOCIALIZE_LOSS: [u8; 8] = [245, 75, 91, 0, 236, 97, 19, 3];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitLendingMarketInstruction {
+    /// name of the currency prices are quoted in, padded with zeroes
+    #[serde(serialize_with = "serialize_hex")]
+    pub quote_currency: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitObligationInstruction {
+    /// obligation type, 0 for a vanilla obligation
+    pub tag: u8,
+    pub id: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitUserMetadataInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_lookup_table: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityAmountInstruction {
+    pub liquidity_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollateralAmountInstruction {
+    pub collateral_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidateInstruction {
+    /// debt repaid by the liquidator
+    pub liquidity_amount: u64,
+    pub min_acceptable_received_liquidity_amount: u64,
+    pub max_allowed_ltv_override_percent: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlashRepayInstruction {
+    pub liquidity_amount: u64,
+    /// index of the matching flash borrow within the transaction
+    pub borrow_instruction_index: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LendInstruction {
+    InitLendingMarket(InitLendingMarketInstruction),
+    InitReserve,
+    InitObligation(InitObligationInstruction),
+    InitUserMetadata(InitUserMetadataInstruction),
+    RefreshReserve,
+    RefreshObligation,
+    DepositReserveLiquidity(LiquidityAmountInstruction),
+    RedeemReserveCollateral(CollateralAmountInstruction),
+    DepositObligationCollateral(CollateralAmountInstruction),
+    WithdrawObligationCollateral(CollateralAmountInstruction),
+    BorrowObligationLiquidity(LiquidityAmountInstruction),
+    RepayObligationLiquidity(LiquidityAmountInstruction),
+    DepositReserveLiquidityAndObligationCollateral(LiquidityAmountInstruction),
+    WithdrawObligationCollateralAndRedeemReserveCollateral(CollateralAmountInstruction),
+    LiquidateObligationAndRedeemReserveCollateral(LiquidateInstruction),
+    FlashBorrowReserveLiquidity(LiquidityAmountInstruction),
+    FlashRepayReserveLiquidity(FlashRepayInstruction),
+    RedeemFees,
+    SocializeLoss(LiquidityAmountInstruction),
+}
+
+impl LendInstruction {
+    /// Unpacks a byte buffer into a [LendInstruction](enum.LendInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INIT_LENDING_MARKET => Self::InitLendingMarket(anchor::deserialize(rest)?),
+            INIT_RESERVE => Self::InitReserve,
+            INIT_OBLIGATION => Self::InitObligation(anchor::deserialize(rest)?),
+            INIT_USER_METADATA => Self::InitUserMetadata(anchor::deserialize(rest)?),
+            REFRESH_RESERVE => Self::RefreshReserve,
+            REFRESH_OBLIGATION => Self::RefreshObligation,
+            DEPOSIT_RESERVE_LIQUIDITY => Self::DepositReserveLiquidity(anchor::deserialize(rest)?),
+            REDEEM_RESERVE_COLLATERAL => Self::RedeemReserveCollateral(anchor::deserialize(rest)?),
+            DEPOSIT_OBLIGATION_COLLATERAL => {
+                Self::DepositObligationCollateral(anchor::deserialize(rest)?)
+            }
+            WITHDRAW_OBLIGATION_COLLATERAL => {
+                Self::WithdrawObligationCollateral(anchor::deserialize(rest)?)
+            }
+            BORROW_OBLIGATION_LIQUIDITY => {
+                Self::BorrowObligationLiquidity(anchor::deserialize(rest)?)
+            }
+            REPAY_OBLIGATION_LIQUIDITY => {
+                Self::RepayObligationLiquidity(anchor::deserialize(rest)?)
+            }
+            DEPOSIT_RESERVE_LIQUIDITY_AND_OBLIGATION_COLLATERAL => {
+                Self::DepositReserveLiquidityAndObligationCollateral(anchor::deserialize(rest)?)
+            }
+            WITHDRAW_OBLIGATION_COLLATERAL_AND_REDEEM_RESERVE_COLLATERAL => {
+                Self::WithdrawObligationCollateralAndRedeemReserveCollateral(anchor::deserialize(
+                    rest,
+                )?)
+            }
+            LIQUIDATE_OBLIGATION_AND_REDEEM_RESERVE_COLLATERAL => {
+                Self::LiquidateObligationAndRedeemReserveCollateral(anchor::deserialize(rest)?)
+            }
+            FLASH_BORROW_RESERVE_LIQUIDITY => {
+                Self::FlashBorrowReserveLiquidity(anchor::deserialize(rest)?)
+            }
+            FLASH_REPAY_RESERVE_LIQUIDITY => {
+                Self::FlashRepayReserveLiquidity(anchor::deserialize(rest)?)
+            }
+            REDEEM_FEES => Self::RedeemFees,
+            SOCIALIZE_LOSS => Self::SocializeLoss(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,376 @@
+//! Instruction decoding functions for the kamino lend program
+
+use anyhow::Context;
+use ix::LendInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+
+const FLASH_LOAN_ACCOUNTS: [&str; 12] = [
+    "userTransferAuthority",
+    "lendingMarketAuthority",
+    "lendingMarket",
+    "reserve",
+    "reserveLiquidityMint",
+    "reserveLiquidity",
+    "userLiquidity",
+    "reserveLiquidityFeeReceiver",
+    "referrerTokenState",
+    "referrerAccount",
+    "sysvarInfo",
+    "tokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KaminoLendDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl KaminoLendDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = LendInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            LendInstruction::InitLendingMarket(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lendingMarketOwner",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "initLendingMarket".to_string(),
+            }),
+            LendInstruction::InitReserve => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "lendingMarketOwner",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "reserve",
+                    "reserveLiquidityMint",
+                    "reserveLiquiditySupply",
+                    "feeReceiver",
+                    "reserveCollateralMint",
+                    "reserveCollateralSupply",
+                    "rent",
+                    "liquidityTokenProgram",
+                    "collateralTokenProgram",
+                    "systemProgram",
+                ]),
+                name: "initReserve".to_string(),
+                ..Default::default()
+            }),
+            LendInstruction::InitObligation(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "obligationOwner",
+                    "feePayer",
+                    "obligation",
+                    "lendingMarket",
+                    "seed1Account",
+                    "seed2Account",
+                    "ownerUserMetadata",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "initObligation".to_string(),
+            }),
+            LendInstruction::InitUserMetadata(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "feePayer",
+                    "userMetadata",
+                    "referrerUserMetadata",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "initUserMetadata".to_string(),
+            }),
+            LendInstruction::RefreshReserve => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "reserve",
+                    "lendingMarket",
+                    "pythOracle",
+                    "switchboardPriceOracle",
+                    "switchboardTwapOracle",
+                    "scopePrices",
+                ]),
+                name: "refreshReserve".to_string(),
+                ..Default::default()
+            }),
+            // the reserves of the obligation's deposits and borrows follow as remaining accounts
+            LendInstruction::RefreshObligation => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["lendingMarket", "obligation"]),
+                name: "refreshObligation".to_string(),
+                ..Default::default()
+            }),
+            LendInstruction::DepositReserveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "reserve",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "reserveLiquidityMint",
+                    "reserveLiquiditySupply",
+                    "reserveCollateralMint",
+                    "userSourceLiquidity",
+                    "userDestinationCollateral",
+                    "collateralTokenProgram",
+                    "liquidityTokenProgram",
+                    "instructionSysvarAccount",
+                ]),
+                name: "depositReserveLiquidity".to_string(),
+            }),
+            LendInstruction::RedeemReserveCollateral(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "lendingMarket",
+                    "reserve",
+                    "lendingMarketAuthority",
+                    "reserveLiquidityMint",
+                    "reserveCollateralMint",
+                    "reserveLiquiditySupply",
+                    "userSourceCollateral",
+                    "userDestinationLiquidity",
+                    "collateralTokenProgram",
+                    "liquidityTokenProgram",
+                    "instructionSysvarAccount",
+                ]),
+                name: "redeemReserveCollateral".to_string(),
+            }),
+            LendInstruction::DepositObligationCollateral(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "obligation",
+                    "lendingMarket",
+                    "depositReserve",
+                    "reserveDestinationCollateral",
+                    "userSourceCollateral",
+                    "tokenProgram",
+                    "instructionSysvarAccount",
+                ]),
+                name: "depositObligationCollateral".to_string(),
+            }),
+            LendInstruction::WithdrawObligationCollateral(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "obligation",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "withdrawReserve",
+                    "reserveSourceCollateral",
+                    "userDestinationCollateral",
+                    "tokenProgram",
+                    "instructionSysvarAccount",
+                ]),
+                name: "withdrawObligationCollateral".to_string(),
+            }),
+            LendInstruction::BorrowObligationLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "obligation",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "borrowReserve",
+                    "borrowReserveLiquidityMint",
+                    "reserveSourceLiquidity",
+                    "borrowReserveLiquidityFeeReceiver",
+                    "userDestinationLiquidity",
+                    "referrerTokenState",
+                    "tokenProgram",
+                    "instructionSysvarAccount",
+                ]),
+                name: "borrowObligationLiquidity".to_string(),
+            }),
+            LendInstruction::RepayObligationLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "obligation",
+                    "lendingMarket",
+                    "repayReserve",
+                    "reserveLiquidityMint",
+                    "reserveDestinationLiquidity",
+                    "userSourceLiquidity",
+                    "tokenProgram",
+                    "instructionSysvarAccount",
+                ]),
+                name: "repayObligationLiquidity".to_string(),
+            }),
+            LendInstruction::DepositReserveLiquidityAndObligationCollateral(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "owner",
+                        "obligation",
+                        "lendingMarket",
+                        "lendingMarketAuthority",
+                        "reserve",
+                        "reserveLiquidityMint",
+                        "reserveLiquiditySupply",
+                        "reserveCollateralMint",
+                        "reserveDestinationDepositCollateral",
+                        "userSourceLiquidity",
+                        "placeholderUserDestinationCollateral",
+                        "collateralTokenProgram",
+                        "liquidityTokenProgram",
+                        "instructionSysvarAccount",
+                    ]),
+                    name: "depositReserveLiquidityAndObligationCollateral".to_string(),
+                })
+            }
+            LendInstruction::WithdrawObligationCollateralAndRedeemReserveCollateral(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "owner",
+                        "obligation",
+                        "lendingMarket",
+                        "lendingMarketAuthority",
+                        "withdrawReserve",
+                        "reserveLiquidityMint",
+                        "reserveSourceCollateral",
+                        "reserveCollateralMint",
+                        "reserveLiquiditySupply",
+                        "userDestinationLiquidity",
+                        "placeholderUserDestinationCollateral",
+                        "collateralTokenProgram",
+                        "liquidityTokenProgram",
+                        "instructionSysvarAccount",
+                    ]),
+                    name: "withdrawObligationCollateralAndRedeemReserveCollateral".to_string(),
+                })
+            }
+            LendInstruction::LiquidateObligationAndRedeemReserveCollateral(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "liquidator",
+                        "obligation",
+                        "lendingMarket",
+                        "lendingMarketAuthority",
+                        "repayReserve",
+                        "repayReserveLiquidityMint",
+                        "repayReserveLiquiditySupply",
+                        "withdrawReserve",
+                        "withdrawReserveLiquidityMint",
+                        "withdrawReserveCollateralMint",
+                        "withdrawReserveCollateralSupply",
+                        "withdrawReserveLiquiditySupply",
+                        "withdrawReserveLiquidityFeeReceiver",
+                        "userSourceLiquidity",
+                        "userDestinationCollateral",
+                        "userDestinationLiquidity",
+                        "collateralTokenProgram",
+                        "repayLiquidityTokenProgram",
+                        "withdrawLiquidityTokenProgram",
+                        "instructionSysvarAccount",
+                    ]),
+                    name: "liquidateObligationAndRedeemReserveCollateral".to_string(),
+                })
+            }
+            LendInstruction::FlashBorrowReserveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FLASH_LOAN_ACCOUNTS),
+                name: "flashBorrowReserveLiquidity".to_string(),
+            }),
+            LendInstruction::FlashRepayReserveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FLASH_LOAN_ACCOUNTS),
+                name: "flashRepayReserveLiquidity".to_string(),
+            }),
+            LendInstruction::RedeemFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "reserve",
+                    "reserveLiquidityMint",
+                    "reserveLiquidityFeeReceiver",
+                    "reserveSupplyLiquidity",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "tokenProgram",
+                ]),
+                name: "redeemFees".to_string(),
+                ..Default::default()
+            }),
+            LendInstruction::SocializeLoss(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "riskCouncil",
+                    "obligation",
+                    "lendingMarket",
+                    "reserve",
+                    "instructionSysvarAccount",
+                ]),
+                name: "socializeLoss".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_liquidation_and_obligation() {
+        let mut data = ix::LIQUIDATE_OBLIGATION_AND_REDEEM_RESERVE_COLLATERAL.to_vec();
+        data.extend(to_vec(&(1_000_000u64, 990_000u64, 0u64)).unwrap());
+        let accounts: Vec<Pubkey> = (0..20).map(|_| Pubkey::new_unique()).collect();
+        let decoded = KaminoLendDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(
+            decoded.name,
+            "liquidateObligationAndRedeemReserveCollateral"
+        );
+        assert_eq!(decoded.data["liquidityAmount"], 1_000_000);
+        assert_eq!(decoded.accounts["obligation"], accounts[1].to_string());
+        assert_eq!(decoded.accounts["withdrawReserve"], accounts[7].to_string());
+
+        let reserve = Pubkey::new_unique();
+        let mut obligation = state::OBLIGATION_DISCRIMINATOR.to_vec();
+        obligation.extend(0u64.to_le_bytes());
+        obligation.extend([0u8; 16]);
+        obligation.extend(Pubkey::new_unique().to_bytes());
+        obligation.extend(Pubkey::new_unique().to_bytes());
+        obligation.extend(reserve.to_bytes());
+        obligation.extend(5_000u64.to_le_bytes());
+        obligation.extend([0u8; 136 - 40]);
+        obligation.extend([0u8; 136 * 7 + 8 + 16]);
+        obligation.extend(reserve.to_bytes());
+        obligation.extend([0u8; 48 + 8]);
+        // 2.5 in 60 bit fixed point
+        obligation.extend((5u128 << 59).to_le_bytes());
+        obligation.extend([0u8; 200 - 32 - 48 - 8 - 16]);
+        obligation.extend([0u8; 200 * 4 + 16 * 4 + 8 + 5 + 3 + 32 + 1]);
+        let obligation = state::Obligation::unpack(&obligation).unwrap();
+        let deposits: Vec<_> = obligation.active_deposits().collect();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].deposited_amount, 5_000);
+        let borrows: Vec<_> = obligation.active_borrows().collect();
+        assert_eq!(borrows.len(), 1);
+        assert_eq!(borrows[0].borrow_reserve, reserve);
+        assert_eq!(borrows[0].borrowed_amount(), 2.5);
+    }
+}
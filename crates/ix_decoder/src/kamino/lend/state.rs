@@ -0,0 +1,225 @@
+//! Account types
+//!
+//! Values suffixed with `_sf` are fixed point numbers with 60 fractional bits, see
+//! [scaled_fraction_to_f64].
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_pubkey, serialize_u128},
+};
+
+pub const RESERVE_DISCRIMINATOR: [u8; 8] = [43, 242, 204, 202, 26, 247, 59, 127];
+pub const OBLIGATION_DISCRIMINATOR: [u8; 8] = [168, 206, 141, 106, 88, 76, 172, 167];
+
+const FRACTION_BITS: u32 = 60;
+
+/// Converts a `_sf` value to a float
+pub fn scaled_fraction_to_f64(value: u128) -> f64 {
+    value as f64 / (1u128 << FRACTION_BITS) as f64
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastUpdate {
+    pub slot: u64,
+    pub stale: u8,
+    pub price_status: u8,
+    #[serde(skip)]
+    pub placeholder: [u8; 6],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BigFractionBytes {
+    pub value: [u64; 4],
+    #[serde(skip)]
+    pub padding: [u64; 2],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReserveLiquidity {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint_pubkey: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub supply_vault: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_vault: Pubkey,
+    /// liquidity held by the supply vault
+    pub available_amount: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrowed_amount_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_price_sf: u128,
+    pub market_price_last_updated_ts: u64,
+    pub mint_decimals: u64,
+    pub deposit_limit_crossed_timestamp: u64,
+    pub borrow_limit_crossed_timestamp: u64,
+    pub cumulative_borrow_rate_bsf: BigFractionBytes,
+    #[serde(serialize_with = "serialize_u128")]
+    pub accumulated_protocol_fees_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub accumulated_referrer_fees_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub pending_referrer_fees_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub absolute_referral_rate_sf: u128,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_program: Pubkey,
+    #[serde(skip)]
+    pub padding2: [u64; 51],
+    #[serde(skip)]
+    pub padding3: [u128; 32],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReserveCollateral {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint_pubkey: Pubkey,
+    pub mint_total_supply: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub supply_vault: Pubkey,
+    #[serde(skip)]
+    pub padding1: [u128; 32],
+    #[serde(skip)]
+    pub padding2: [u128; 32],
+}
+
+/// A reserve of a lending market, the reserve config which follows the collateral is not decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reserve {
+    pub version: u64,
+    pub last_update: LastUpdate,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lending_market: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub farm_collateral: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub farm_debt: Pubkey,
+    pub liquidity: ReserveLiquidity,
+    #[serde(skip)]
+    pub reserve_liquidity_padding: [u64; 150],
+    pub collateral: ReserveCollateral,
+}
+
+impl Reserve {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, RESERVE_DISCRIMINATOR)
+    }
+
+    /// Liquidity owed to the reserve, available or lent out, in native units
+    pub fn total_liquidity(&self) -> f64 {
+        self.liquidity.available_amount as f64
+            + scaled_fraction_to_f64(self.liquidity.borrowed_amount_sf)
+            - scaled_fraction_to_f64(self.liquidity.accumulated_protocol_fees_sf)
+            - scaled_fraction_to_f64(self.liquidity.accumulated_referrer_fees_sf)
+            - scaled_fraction_to_f64(self.liquidity.pending_referrer_fees_sf)
+    }
+
+    /// Converts an amount of collateral tokens to the liquidity they can be redeemed for
+    pub fn collateral_to_liquidity(&self, collateral_amount: u64) -> f64 {
+        if self.collateral.mint_total_supply == 0 {
+            return collateral_amount as f64;
+        }
+        collateral_amount as f64 * self.total_liquidity() / self.collateral.mint_total_supply as f64
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObligationCollateral {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub deposit_reserve: Pubkey,
+    /// collateral tokens of the deposit reserve
+    pub deposited_amount: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_value_sf: u128,
+    pub borrowed_amount_against_this_collateral_in_elevation_group: u64,
+    #[serde(skip)]
+    pub padding: [u64; 9],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObligationLiquidity {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub borrow_reserve: Pubkey,
+    pub cumulative_borrow_rate_bsf: BigFractionBytes,
+    #[serde(skip)]
+    pub padding: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrowed_amount_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_value_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrow_factor_adjusted_market_value_sf: u128,
+    pub borrowed_amount_outside_elevation_groups: u64,
+    #[serde(skip)]
+    pub padding2: [u64; 7],
+}
+
+impl ObligationLiquidity {
+    /// Debt in native units of the borrowed liquidity, as of the last refresh
+    pub fn borrowed_amount(&self) -> f64 {
+        scaled_fraction_to_f64(self.borrowed_amount_sf)
+    }
+}
+
+/// A user's deposits and borrows within a lending market, empty slots have a default reserve
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Obligation {
+    pub tag: u64,
+    pub last_update: LastUpdate,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lending_market: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    pub deposits: [ObligationCollateral; 8],
+    pub lowest_reserve_deposit_liquidation_ltv: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub deposited_value_sf: u128,
+    pub borrows: [ObligationLiquidity; 5],
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrow_factor_adjusted_debt_value_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrowed_assets_market_value_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub allowed_borrow_value_sf: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub unhealthy_borrow_value_sf: u128,
+    pub deposits_asset_tiers: [u8; 8],
+    pub borrows_asset_tiers: [u8; 5],
+    pub elevation_group: u8,
+    pub num_of_obsolete_reserves: u8,
+    pub has_debt: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub referrer: Pubkey,
+    pub borrowing_disabled: u8,
+}
+
+impl Obligation {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, OBLIGATION_DISCRIMINATOR)
+    }
+
+    /// Deposits which are in use
+    pub fn active_deposits(&self) -> impl Iterator<Item = &ObligationCollateral> {
+        self.deposits
+            .iter()
+            .filter(|deposit| deposit.deposit_reserve != Pubkey::default())
+    }
+
+    /// Borrows which are in use
+    pub fn active_borrows(&self) -> impl Iterator<Item = &ObligationLiquidity> {
+        self.borrows
+            .iter()
+            .filter(|borrow| borrow.borrow_reserve != Pubkey::default())
+    }
+}
@@ -0,0 +1,45 @@
+//! Instruction decoding functions for the kamino programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use lend::KaminoLendDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod lend;
+
+pub static KAMINO_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(KaminoProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Lend(KaminoLendDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct KaminoProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Lend(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Kamino::Lend"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Lend(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for KaminoProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            lend::PROGRAM_ID => Some(Box::new(Programs::Lend(KaminoLendDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
@@ -1,16 +1,35 @@
-use std::str::FromStr;
-
 use anyhow::{anyhow, Context, Result};
-use solana_sdk::pubkey::Pubkey;
 use solana_transaction_status::{
     UiInstruction, UiParsedInstruction, UiPartiallyDecodedInstruction,
 };
-use types::{
-    DecodedInstruction, PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher,
-    PROGRAM_DECODER_MATCHERS,
-};
+use types::{PartiallyDecodedInstruction, ProgramDecoder, PROGRAM_DECODER_MATCHERS};
+pub mod allbridge;
+pub mod anchor;
+pub mod debridge;
+pub mod drift;
+pub mod jupiter;
+pub mod kamino;
+pub mod magic_eden;
+pub mod mango;
+pub mod marginfi;
+pub mod marinade;
+pub mod mayan;
+pub mod metaplex;
+pub mod meteora;
+pub mod native;
+pub mod openbook;
+pub mod orca;
+pub mod phoenix;
+pub mod pump;
 pub mod raydium;
+pub mod sanctum;
+pub mod solend;
+pub mod spl;
+pub mod squads;
+pub mod tensor;
+pub mod transaction;
 pub mod types;
+pub mod wormhole;
 
 #[derive(Clone, Copy)]
 pub struct DecodeMatcher {}
@@ -19,42 +38,12 @@ impl DecodeMatcher {
     /// Attemps to decode the given instruction, returning an error if we failed to decode
     pub fn try_new_decoder(self, ix: UiInstruction) -> Result<Box<dyn ProgramDecoder>> {
         match ix {
-            //
-            UiInstruction::Compiled(ix) => {
-                return Err(anyhow!("compiled instructions not supported"))
-            }
+            UiInstruction::Compiled(_) => Err(anyhow!("compiled instructions not supported")),
             UiInstruction::Parsed(ix) => match ix {
                 UiParsedInstruction::PartiallyDecoded(ix) => {
-                    return try_program_decoder(ix).with_context(|| "unrecognized instruction");
-                    /*
-                                        {
-                      "accounts": [
-                        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
-                        "3cdeiXyxedfzki8HTZ1DomU1HU1SbGFnv5Pttkmc8n8E",
-                        "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1",
-                        "DkhYtHGiBFBRK9priwezu16JbJjnVZGFMKxkgq69advx",
-                        "2Vb8om5Ewed5LJ1X2KsRtd8p3mZX5ocBhZMJ2VDDzRmv",
-                        "ESmdjKtmsUbkKV7XcNz6TrZBFKeaa5K9sVRVbGBYywVy",
-                        "HyH2qkQn5fLndZvzUE3mizN99WH3rJPQCVPiJNQBMDcq",
-                        "srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX",
-                        "EzEiX9G3oZrWMHp7m4aKqGNCGx77RPXMt9C7BCxfsPhR",
-                        "qPFqZduU2PE9pDkcK6f4Dkr76uVSsoe8BPL7Jm9h2u4",
-                        "3GTbXkHYFH4bP1SffyM1QH4HkwEnfcVz6HezA8sjRj2z",
-                        "9wk9xcQhVTaZ4BQ2xRMbvhx7upXyeDgJQrvhg5nEUkBA",
-                        "GQ3K2SPnznyhE4H7G6Anp5CW67UhLnCZt9XXCiog4rhL",
-                        "9zh3hw8khtcgzvTrvnGp6kkJ7QYXTLdrkC75i7z13tpQ",
-                        "Fz6FwoEErD69qv5Jo1wPsLuLMroG2DoAPHGyRoixDg2W",
-                        "FKbm7rasypkm8YZAqMZmWD3GcbFYYU5eEnddM13tmZoj",
-                        "JDzGczMYV3338frU4GGusTKbsRD4qvCiCsD7ax5LMtnU",
-                        "AjVKSwyZGeGeyvmCr1FBciR6pLcaErdLEEiJGAnbq1ct"
-                      ],
-                      "data": "5ub2y2Px1fytRf4QS8Qy3VH",
-                      "programId": "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
-                      "stackHeight": null
-                    }
-                                         */
+                    try_program_decoder(ix).with_context(|| "unrecognized instruction")
                 }
-                UiParsedInstruction::Parsed(ix) => return Err(anyhow!("unsupported instruction format"))
+                UiParsedInstruction::Parsed(_) => Err(anyhow!("unsupported instruction format")),
             },
         }
     }
@@ -64,12 +53,16 @@ pub fn try_program_decoder(
     ix: UiPartiallyDecodedInstruction,
 ) -> anyhow::Result<Box<dyn ProgramDecoder>> {
     let ix: PartiallyDecodedInstruction = TryFrom::try_from(ix)?;
+    try_instruction_decoder(&ix)
+}
+
+/// Returns the first decoder which is able to decode the given instruction
+pub fn try_instruction_decoder(
+    ix: &PartiallyDecodedInstruction,
+) -> anyhow::Result<Box<dyn ProgramDecoder>> {
     for decoder_matcher in &PROGRAM_DECODER_MATCHERS {
-        match decoder_matcher.try_new(&ix) {
-            Some(decoder) => {
-                return Ok(decoder);
-            }
-            None => (),
+        if let Some(decoder) = decoder_matcher.try_new(ix) {
+            return Ok(decoder);
         }
     }
     Err(anyhow!("failed to fnid a decoder"))
@@ -0,0 +1,107 @@
+//! Instruction types
+//!
+//! The pda bumps passed to the older instructions are not decoded.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+pub const CANCEL_SELL: [u8; 8] = [198, 198, 130, 203, 163, 95, 175, 75];
+pub const BUY_V2: [u8; 8] = [184, 23, 238, 97, 103, 197, 211, 61];
+pub const CANCEL_BUY: [u8; 8] = [238, 76, 36, 218, 132, 177, 224, 233];
+pub const EXECUTE_SALE_V2: [u8; 8] = [91, 220, 49, 223, 204, 129, 53, 193];
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+/// Arguments of `sell` and `cancel_sell`, the listing price being `buyer_price`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellInstruction {
+    pub buyer_price: u64,
+    pub token_size: u64,
+    pub seller_state_expiry: i64,
+}
+
+impl SellInstruction {
+    /// `sell` prefixes the arguments with bumps, which are skipped by reading the
+    /// arguments from the end of the data
+    fn unpack_suffix(input: &[u8]) -> anyhow::Result<Self> {
+        let start = input
+            .len()
+            .checked_sub(24)
+            .ok_or_else(|| anyhow!("instruction data too short"))?;
+        anchor::deserialize(&input[start..])
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyV2Instruction {
+    pub buyer_price: u64,
+    pub token_size: u64,
+    pub buyer_state_expiry: i64,
+    /// share of the royalties the buyer agrees to pay
+    pub buyer_creator_royalty_bp: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelBuyInstruction {
+    pub buyer_price: u64,
+    pub token_size: u64,
+    pub buyer_state_expiry: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteSaleV2Instruction {
+    #[serde(skip)]
+    pub escrow_payment_bump: u8,
+    #[serde(skip)]
+    pub program_as_signer_bump: u8,
+    pub buyer_price: u64,
+    pub token_size: u64,
+    pub buyer_state_expiry: i64,
+    pub seller_state_expiry: i64,
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EscrowInstruction {
+    #[serde(skip)]
+    pub escrow_payment_bump: u8,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum M2Instruction {
+    Sell(SellInstruction),
+    CancelSell(SellInstruction),
+    BuyV2(BuyV2Instruction),
+    CancelBuy(CancelBuyInstruction),
+    ExecuteSaleV2(ExecuteSaleV2Instruction),
+    Deposit(EscrowInstruction),
+    Withdraw(EscrowInstruction),
+}
+
+impl M2Instruction {
+    /// Unpacks a byte buffer into a [M2Instruction](enum.M2Instruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            SELL => Self::Sell(SellInstruction::unpack_suffix(rest)?),
+            CANCEL_SELL => Self::CancelSell(anchor::deserialize(rest)?),
+            BUY_V2 => Self::BuyV2(anchor::deserialize(rest)?),
+            CANCEL_BUY => Self::CancelBuy(anchor::deserialize(rest)?),
+            EXECUTE_SALE_V2 => Self::ExecuteSaleV2(anchor::deserialize(rest)?),
+            DEPOSIT => Self::Deposit(anchor::deserialize(rest)?),
+            WITHDRAW => Self::Withdraw(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
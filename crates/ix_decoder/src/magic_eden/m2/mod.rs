@@ -0,0 +1,212 @@
+//! Instruction decoding functions for the magic eden m2 program
+//!
+//! Listings are `sell` and bids `buy_v2`, both settled by `execute_sale_v2`. The sale
+//! price is logged as json, see [log_events].
+
+use anyhow::Context;
+use ix::M2Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use super::JsonLog;
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K");
+
+const ESCROW_ACCOUNTS: [&str; 6] = [
+    "wallet",
+    "notary",
+    "escrowPaymentAccount",
+    "authority",
+    "auctionHouse",
+    "systemProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct M2Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl M2Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix =
+            M2Instruction::unpack(&self.ix.data).with_context(|| "failed to decode instruction")?;
+        match ix {
+            M2Instruction::Sell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "wallet",
+                    "notary",
+                    "tokenAccount",
+                    "tokenAta",
+                    "tokenMint",
+                    "metadata",
+                    "authority",
+                    "auctionHouse",
+                    "sellerTradeState",
+                    "sellerReferral",
+                    "tokenProgram",
+                    "systemProgram",
+                    "programAsSigner",
+                    "rent",
+                ]),
+                name: "sell".to_string(),
+            }),
+            M2Instruction::CancelSell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "wallet",
+                    "notary",
+                    "tokenAccount",
+                    "tokenAta",
+                    "tokenMint",
+                    "authority",
+                    "auctionHouse",
+                    "sellerTradeState",
+                    "sellerReferral",
+                    "tokenProgram",
+                    "programAsSigner",
+                ]),
+                name: "cancelSell".to_string(),
+            }),
+            M2Instruction::BuyV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "wallet",
+                    "notary",
+                    "tokenMint",
+                    "metadata",
+                    "escrowPaymentAccount",
+                    "authority",
+                    "auctionHouse",
+                    "buyerTradeState",
+                    "buyerReferral",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "buyV2".to_string(),
+            }),
+            M2Instruction::CancelBuy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "wallet",
+                    "notary",
+                    "tokenMint",
+                    "authority",
+                    "auctionHouse",
+                    "buyerTradeState",
+                    "buyerReferral",
+                ]),
+                name: "cancelBuy".to_string(),
+            }),
+            M2Instruction::ExecuteSaleV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "buyer",
+                    "seller",
+                    "notary",
+                    "programAsSigner",
+                    "tokenAccount",
+                    "buyerReceiptTokenAccount",
+                    "tokenMint",
+                    "metadata",
+                    "escrowPaymentAccount",
+                    "authority",
+                    "auctionHouse",
+                    "auctionHouseTreasury",
+                    "sellerTradeState",
+                    "buyerTradeState",
+                    "buyerReferral",
+                    "sellerReferral",
+                    "tokenProgram",
+                    "systemProgram",
+                    "associatedTokenProgram",
+                    "rent",
+                ]),
+                name: "executeSaleV2".to_string(),
+            }),
+            M2Instruction::Deposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ESCROW_ACCOUNTS),
+                name: "deposit".to_string(),
+            }),
+            M2Instruction::Withdraw(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ESCROW_ACCOUNTS),
+                name: "withdraw".to_string(),
+            }),
+        }
+    }
+}
+
+/// Decodes the json objects the program logged during a transaction, sales log their
+/// price along with the expiries of the listing and bid
+pub fn log_events(logs: &[String]) -> Vec<JsonLog> {
+    super::json_logs(logs, &PROGRAM_ID)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_execute_sale_and_price_log() {
+        let buyer = Pubkey::new_unique();
+        let seller = Pubkey::new_unique();
+        let mut data = ix::EXECUTE_SALE_V2.to_vec();
+        data.extend([254, 253]);
+        data.extend(to_vec(&(2_500_000_000u64, 1u64, 0i64, -1i64, 0i16, 250u16)).unwrap());
+        let decoded = M2Decoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: [Pubkey::new_unique(), buyer, seller]
+                .into_iter()
+                .chain((0..18).map(|_| Pubkey::new_unique()))
+                .collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "executeSaleV2");
+        assert_eq!(decoded.accounts["buyer"], buyer.to_string());
+        assert_eq!(decoded.accounts["seller"], seller.to_string());
+        assert_eq!(decoded.data["buyerPrice"], 2_500_000_000u64);
+        assert_eq!(decoded.data["takerFeeBp"], 250);
+        assert!(!decoded.data.contains_key("escrowPaymentBump"));
+
+        // the bumps preceding the price of a listing are skipped
+        let mut data = ix::SELL.to_vec();
+        data.extend([255, 254]);
+        data.extend(to_vec(&(2_500_000_000u64, 1u64, -1i64)).unwrap());
+        let decoded = M2Decoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![seller],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "sell");
+        assert_eq!(decoded.data["buyerPrice"], 2_500_000_000u64);
+        assert_eq!(decoded.data["sellerStateExpiry"], -1);
+
+        let logs = vec![
+            format!("Program {} invoke [1]", PROGRAM_ID),
+            "Program log: Instruction: ExecuteSaleV2".to_string(),
+            "Program log: {\"price\":2500000000,\"seller_expiry\":-1,\"buyer_expiry\":0}"
+                .to_string(),
+            format!("Program {} success", PROGRAM_ID),
+        ];
+        let events = log_events(&logs);
+        assert_eq!(events.len(), 1);
+        let decoded = events[0].to_decoded_instruction();
+        assert_eq!(decoded.name, "jsonLog");
+        assert_eq!(decoded.data["price"], 2_500_000_000u64);
+    }
+}
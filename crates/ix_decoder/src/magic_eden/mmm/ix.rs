@@ -0,0 +1,72 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const SOL_FULFILL_BUY: [u8; 8] = [92, 16, 226, 79, 31, 242, 53, 118];
+pub const SOL_FULFILL_SELL: [u8; 8] = [164, 180, 96, 192, 103, 225, 105, 232];
+pub const DEPOSIT_SELL: [u8; 8] = [144, 131, 44, 156, 197, 10, 197, 43];
+pub const WITHDRAW_SELL: [u8; 8] = [47, 42, 156, 228, 249, 163, 254, 185];
+pub const SOL_DEPOSIT_BUY: [u8; 8] = [66, 229, 11, 54, 109, 164, 85, 238];
+pub const SOL_WITHDRAW_BUY: [u8; 8] = [154, 41, 80, 232, 174, 48, 246, 35];
+
+/// A seller selling into the bids of a pool
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolFulfillBuyInstruction {
+    pub asset_amount: u64,
+    pub min_payment_amount: u64,
+    pub allowlist_aux: Option<String>,
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: i16,
+}
+
+/// A buyer buying from the listings of a pool
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolFulfillSellInstruction {
+    pub asset_amount: u64,
+    pub max_payment_amount: u64,
+    /// share of the royalties the buyer agrees to pay
+    pub buyside_creator_royalty_bp: u16,
+    pub allowlist_aux: Option<String>,
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: i16,
+}
+
+/// Arguments of `deposit_sell` and `withdraw_sell`, listing or delisting assets
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellSideInstruction {
+    pub asset_amount: u64,
+    pub allowlist_aux: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MmmInstruction {
+    SolFulfillBuy(SolFulfillBuyInstruction),
+    SolFulfillSell(SolFulfillSellInstruction),
+    DepositSell(SellSideInstruction),
+    WithdrawSell(SellSideInstruction),
+    SolDepositBuy(u64),
+    SolWithdrawBuy(u64),
+}
+
+impl MmmInstruction {
+    /// Unpacks a byte buffer into a [MmmInstruction](enum.MmmInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            SOL_FULFILL_BUY => Self::SolFulfillBuy(anchor::deserialize(rest)?),
+            SOL_FULFILL_SELL => Self::SolFulfillSell(anchor::deserialize(rest)?),
+            DEPOSIT_SELL => Self::DepositSell(anchor::deserialize(rest)?),
+            WITHDRAW_SELL => Self::WithdrawSell(anchor::deserialize(rest)?),
+            SOL_DEPOSIT_BUY => Self::SolDepositBuy(anchor::deserialize(rest)?),
+            SOL_WITHDRAW_BUY => Self::SolWithdrawBuy(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
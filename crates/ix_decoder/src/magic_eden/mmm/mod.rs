@@ -0,0 +1,185 @@
+//! Instruction decoding functions for the magic eden mmm program
+//!
+//! Pools list assets through `deposit_sell` and bid through `sol_deposit_buy`. The
+//! price, lp fee and royalties of a fill are logged as json, see [log_events].
+
+use anyhow::Context;
+use ix::MmmInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use super::JsonLog;
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc");
+
+const SOL_BUY_ACCOUNTS: [&str; 5] = [
+    "owner",
+    "cosigner",
+    "pool",
+    "buysideSolEscrowAccount",
+    "systemProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MmmDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl MmmDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = MmmInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            MmmInstruction::SolFulfillBuy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "owner",
+                    "cosigner",
+                    "referral",
+                    "pool",
+                    "buysideSolEscrowAccount",
+                    "assetMetadata",
+                    "assetMasterEdition",
+                    "assetMint",
+                    "payerAssetAccount",
+                    "sellsideEscrowTokenAccount",
+                    "ownerTokenAccount",
+                    "allowlistAuxAccount",
+                    "sellState",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "rent",
+                ]),
+                name: "solFulfillBuy".to_string(),
+            }),
+            MmmInstruction::SolFulfillSell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "owner",
+                    "cosigner",
+                    "referral",
+                    "pool",
+                    "buysideSolEscrowAccount",
+                    "assetMetadata",
+                    "assetMasterEdition",
+                    "assetMint",
+                    "sellsideEscrowTokenAccount",
+                    "payerAssetAccount",
+                    "allowlistAuxAccount",
+                    "sellState",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "rent",
+                ]),
+                name: "solFulfillSell".to_string(),
+            }),
+            MmmInstruction::DepositSell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "cosigner",
+                    "pool",
+                    "assetMetadata",
+                    "assetMasterEdition",
+                    "assetMint",
+                    "assetTokenAccount",
+                    "sellsideEscrowTokenAccount",
+                    "sellState",
+                    "allowlistAuxAccount",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "rent",
+                ]),
+                name: "depositSell".to_string(),
+            }),
+            MmmInstruction::WithdrawSell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "cosigner",
+                    "pool",
+                    "assetMint",
+                    "assetTokenAccount",
+                    "sellsideEscrowTokenAccount",
+                    "buysideSolEscrowAccount",
+                    "allowlistAuxAccount",
+                    "sellState",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "rent",
+                ]),
+                name: "withdrawSell".to_string(),
+            }),
+            MmmInstruction::SolDepositBuy(payment_amount) => Ok(DecodedInstruction {
+                data: [(
+                    "paymentAmount".to_string(),
+                    serde_json::to_value(payment_amount)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&SOL_BUY_ACCOUNTS),
+                name: "solDepositBuy".to_string(),
+            }),
+            MmmInstruction::SolWithdrawBuy(payment_amount) => Ok(DecodedInstruction {
+                data: [(
+                    "paymentAmount".to_string(),
+                    serde_json::to_value(payment_amount)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&SOL_BUY_ACCOUNTS),
+                name: "solWithdrawBuy".to_string(),
+            }),
+        }
+    }
+}
+
+/// Decodes the json objects the program logged during a transaction, fills log their
+/// total price, lp fee and the royalties paid
+pub fn log_events(logs: &[String]) -> Vec<JsonLog> {
+    super::json_logs(logs, &PROGRAM_ID)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_sol_fulfill_sell() {
+        let payer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut data = ix::SOL_FULFILL_SELL.to_vec();
+        data.extend(to_vec(&(1u64, 3_000_000_000u64, 10_000u16, None::<String>)).unwrap());
+        data.extend(to_vec(&(0i16, 200i16)).unwrap());
+        let decoded = MmmDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: [payer]
+                .into_iter()
+                .chain((0..7).map(|_| Pubkey::new_unique()))
+                .chain([mint])
+                .collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "solFulfillSell");
+        assert_eq!(decoded.accounts["payer"], payer.to_string());
+        assert_eq!(decoded.accounts["assetMint"], mint.to_string());
+        assert_eq!(decoded.data["maxPaymentAmount"], 3_000_000_000u64);
+        assert_eq!(decoded.data["buysideCreatorRoyaltyBp"], 10_000);
+        assert_eq!(decoded.data["takerFeeBp"], 200);
+    }
+}
@@ -0,0 +1,85 @@
+//! Instruction decoding functions for the magic eden programs
+
+use crate::{
+    anchor,
+    types::{
+        DecodedInstruction, PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher,
+    },
+};
+use anyhow::Context;
+use m2::M2Decoder;
+use mmm::MmmDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod m2;
+pub mod mmm;
+
+pub static MAGIC_EDEN_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(MagicEdenProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    M2(M2Decoder),
+    Mmm(MmmDecoder),
+}
+
+/// A json object logged by a magic eden program, which is how the programs log the
+/// price and fees of trades
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonLog(pub serde_json::Map<String, serde_json::Value>);
+
+impl JsonLog {
+    /// Converts the log to a decoded instruction, without accounts, whose data are the
+    /// logged fields as named by the program
+    pub fn to_decoded_instruction(&self) -> DecodedInstruction {
+        DecodedInstruction {
+            data: self.0.clone().into_iter().collect(),
+            name: "jsonLog".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the json objects the program logged, skipping any other messages
+fn json_logs(logs: &[String], program_id: &solana_sdk::pubkey::Pubkey) -> Vec<JsonLog> {
+    anchor::log_messages(logs, program_id)
+        .into_iter()
+        .filter(|message| message.starts_with('{'))
+        .filter_map(|message| serde_json::from_str(message).ok())
+        .map(JsonLog)
+        .collect()
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct MagicEdenProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::M2(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode MagicEden::M2"),
+            Self::Mmm(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode MagicEden::Mmm"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::M2(decoder) => format!("{:#?}", decoder),
+            Self::Mmm(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for MagicEdenProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            m2::PROGRAM_ID => Some(Box::new(Programs::M2(M2Decoder::new(ix.clone())))),
+            mmm::PROGRAM_ID => Some(Box::new(Programs::Mmm(MmmDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
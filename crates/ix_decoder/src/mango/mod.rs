@@ -0,0 +1,45 @@
+//! Instruction decoding functions for the mango programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use v4::MangoV4Decoder;
+
+pub mod v4;
+
+pub static MANGO_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(MangoProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    V4(MangoV4Decoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct MangoProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::V4(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Mango::V4"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::V4(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for MangoProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            v4::PROGRAM_ID => Some(Box::new(Programs::V4(MangoV4Decoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Event types, emitted by the program through logs
+//!
+//! Prices and transfers which the program tracks as I80F48 are kept as their raw
+//! i128 value, see [i80f48_to_f64](crate::marginfi::v2::state::i80f48_to_f64).
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_i128, serialize_pubkey, to_data_map, DecodedInstruction},
+};
+
+pub const FILL_LOG_V3: [u8; 8] = [175, 13, 227, 17, 190, 150, 87, 4];
+pub const DEPOSIT_LOG: [u8; 8] = [141, 186, 168, 252, 108, 141, 72, 94];
+pub const WITHDRAW_LOG: [u8; 8] = [235, 69, 115, 62, 185, 172, 126, 223];
+pub const PERP_SETTLE_PNL_LOG: [u8; 8] = [5, 115, 223, 4, 144, 143, 161, 21];
+pub const TOKEN_LIQ_WITH_TOKEN_LOG: [u8; 8] = [131, 216, 33, 79, 60, 6, 240, 109];
+
+/// A perp fill between a taker and a resting maker order, quantities are in lots
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillLogV3 {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_group: Pubkey,
+    pub market_index: u16,
+    /// 0 when the taker bought, 1 when it sold
+    pub taker_side: u8,
+    pub maker_slot: u8,
+    /// true when the maker order was completely filled
+    pub maker_out: bool,
+    pub timestamp: u64,
+    pub seq_num: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    pub maker_client_order_id: u64,
+    /// fee rate, negative for rebates
+    pub maker_fee: f32,
+    pub maker_timestamp: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub taker: Pubkey,
+    pub taker_client_order_id: u64,
+    pub taker_fee: f32,
+    /// in quote lots per base lot
+    pub price: i64,
+    /// in base lots
+    pub quantity: i64,
+    pub maker_closed_pnl: f64,
+    pub taker_closed_pnl: f64,
+}
+
+/// A token deposit or withdrawal, quantities are native
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransferLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_group: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub signer: Pubkey,
+    pub token_index: u16,
+    pub quantity: u64,
+    /// oracle price in native quote per native token
+    #[serde(serialize_with = "serialize_i128")]
+    pub price: i128,
+}
+
+/// A perp pnl settlement between two accounts, in native quote
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpSettlePnlLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_group: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_account_a: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_account_b: Pubkey,
+    pub perp_market_index: u16,
+    #[serde(serialize_with = "serialize_i128")]
+    pub settlement: i128,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub settler: Pubkey,
+    #[serde(serialize_with = "serialize_i128")]
+    pub fee: i128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLiqWithTokenLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mango_group: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub liqee: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub liqor: Pubkey,
+    pub asset_token_index: u16,
+    pub liab_token_index: u16,
+    #[serde(serialize_with = "serialize_i128")]
+    pub asset_transfer: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub liab_transfer: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub asset_price: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub liab_price: i128,
+    pub bankruptcy: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MangoEvent {
+    Fill(FillLogV3),
+    Deposit(TokenTransferLog),
+    Withdraw(TokenTransferLog),
+    PerpSettlePnl(PerpSettlePnlLog),
+    TokenLiqWithToken(TokenLiqWithTokenLog),
+}
+
+impl MangoEvent {
+    /// Unpacks the logged event data
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            FILL_LOG_V3 => Self::Fill(anchor::deserialize(rest)?),
+            DEPOSIT_LOG => Self::Deposit(anchor::deserialize(rest)?),
+            WITHDRAW_LOG => Self::Withdraw(anchor::deserialize(rest)?),
+            PERP_SETTLE_PNL_LOG => Self::PerpSettlePnl(anchor::deserialize(rest)?),
+            TOKEN_LIQ_WITH_TOKEN_LOG => Self::TokenLiqWithToken(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+
+    /// Converts the event to a decoded instruction named after the event, without accounts
+    pub fn to_decoded_instruction(&self) -> anyhow::Result<DecodedInstruction> {
+        let (name, data) = match self {
+            Self::Fill(event) => ("fillLogV3", to_data_map(event)?),
+            Self::Deposit(event) => ("depositLog", to_data_map(event)?),
+            Self::Withdraw(event) => ("withdrawLog", to_data_map(event)?),
+            Self::PerpSettlePnl(event) => ("perpSettlePnlLog", to_data_map(event)?),
+            Self::TokenLiqWithToken(event) => ("tokenLiqWithTokenLog", to_data_map(event)?),
+        };
+        Ok(DecodedInstruction {
+            data,
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Decodes the events the program logged during a transaction, skipping events
+/// which aren't supported
+pub fn log_events(logs: &[String]) -> Vec<MangoEvent> {
+    anchor::log_events(logs, &super::PROGRAM_ID)
+        .iter()
+        .filter_map(|data| MangoEvent::unpack(data).ok())
+        .collect()
+}
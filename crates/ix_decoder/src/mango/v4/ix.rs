@@ -0,0 +1,182 @@
+//! Instruction types
+//!
+//! I80F48 fixed point arguments are kept as their raw i128 value.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{
+    anchor,
+    types::{serialize_i128, serialize_u128},
+};
+
+pub const ACCOUNT_CREATE: [u8; 8] = [198, 95, 39, 197, 41, 214, 157, 18];
+pub const TOKEN_DEPOSIT: [u8; 8] = [117, 255, 154, 71, 245, 58, 95, 89];
+pub const TOKEN_WITHDRAW: [u8; 8] = [63, 223, 42, 59, 15, 128, 102, 66];
+pub const PERP_PLACE_ORDER: [u8; 8] = [189, 196, 225, 201, 114, 172, 25, 166];
+pub const PERP_PLACE_ORDER_PEGGED: [u8; 8] = [192, 60, 153, 162, 246, 200, 50, 87];
+pub const PERP_CANCEL_ORDER: [u8; 8] = [233, 9, 189, 68, 224, 163, 245, 193];
+pub const PERP_CANCEL_ORDER_BY_CLIENT_ORDER_ID: [u8; 8] = [74, 250, 56, 79, 206, 173, 163, 102];
+pub const PERP_CANCEL_ALL_ORDERS: [u8; 8] = [96, 16, 226, 181, 107, 145, 224, 213];
+pub const PERP_CONSUME_EVENTS: [u8; 8] = [158, 85, 29, 209, 56, 235, 32, 37];
+pub const PERP_SETTLE_PNL: [u8; 8] = [245, 98, 85, 179, 230, 215, 130, 57];
+pub const PERP_SETTLE_FEES: [u8; 8] = [223, 237, 227, 72, 152, 185, 234, 115];
+pub const PERP_UPDATE_FUNDING: [u8; 8] = [28, 18, 184, 70, 7, 245, 14, 46];
+pub const PERP_LIQ_BASE_OR_POSITIVE_PNL: [u8; 8] = [107, 170, 93, 139, 192, 141, 121, 205];
+pub const PERP_LIQ_NEGATIVE_PNL_OR_BANKRUPTCY: [u8; 8] = [31, 175, 214, 180, 117, 227, 152, 53];
+pub const TOKEN_LIQ_WITH_TOKEN: [u8; 8] = [6, 52, 83, 20, 216, 127, 64, 102];
+pub const TOKEN_LIQ_BANKRUPTCY: [u8; 8] = [122, 110, 203, 15, 8, 117, 164, 70];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlaceOrderType {
+    Limit,
+    ImmediateOrCancel,
+    PostOnly,
+    Market,
+    PostOnlySlide,
+    FillOrKill,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCreateInstruction {
+    pub account_num: u32,
+    pub token_count: u8,
+    pub serum3_count: u8,
+    pub perp_count: u8,
+    pub perp_oo_count: u8,
+    pub name: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDepositInstruction {
+    pub amount: u64,
+    pub reduce_only: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenWithdrawInstruction {
+    pub amount: u64,
+    pub allow_borrow: bool,
+}
+
+/// Prices are in quote lots per base lot
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpPlaceOrderInstruction {
+    pub side: Side,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots: i64,
+    pub client_order_id: u64,
+    pub order_type: PlaceOrderType,
+    pub reduce_only: bool,
+    /// unix timestamp after which the order expires, 0 if it never expires
+    pub expiry_timestamp: u64,
+    /// maximum number of orders to match against
+    pub limit: u8,
+}
+
+/// An order whose price follows the oracle price, offset by `price_offset_lots`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpPlaceOrderPeggedInstruction {
+    pub side: Side,
+    pub price_offset_lots: i64,
+    pub peg_limit: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots: i64,
+    pub client_order_id: u64,
+    pub order_type: PlaceOrderType,
+    pub reduce_only: bool,
+    pub expiry_timestamp: u64,
+    pub limit: u8,
+    pub max_oracle_staleness_slots: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpCancelOrderInstruction {
+    #[serde(serialize_with = "serialize_u128")]
+    pub order_id: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpLiqBaseOrPositivePnlInstruction {
+    pub max_base_transfer: i64,
+    pub max_pnl_transfer: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLiqWithTokenInstruction {
+    pub asset_token_index: u16,
+    pub liab_token_index: u16,
+    #[serde(serialize_with = "serialize_i128")]
+    pub max_liab_transfer: i128,
+}
+
+/// Instructions supported by the mango v4 program
+#[derive(Clone, Debug, PartialEq)]
+pub enum MangoInstruction {
+    AccountCreate(AccountCreateInstruction),
+    TokenDeposit(TokenDepositInstruction),
+    TokenWithdraw(TokenWithdrawInstruction),
+    PerpPlaceOrder(PerpPlaceOrderInstruction),
+    PerpPlaceOrderPegged(PerpPlaceOrderPeggedInstruction),
+    PerpCancelOrder(PerpCancelOrderInstruction),
+    PerpCancelOrderByClientOrderId(u64),
+    PerpCancelAllOrders(u8),
+    PerpConsumeEvents(u64),
+    PerpSettlePnl,
+    PerpSettleFees(u64),
+    PerpUpdateFunding,
+    PerpLiqBaseOrPositivePnl(PerpLiqBaseOrPositivePnlInstruction),
+    PerpLiqNegativePnlOrBankruptcy(u64),
+    TokenLiqWithToken(TokenLiqWithTokenInstruction),
+    TokenLiqBankruptcy(i128),
+}
+
+impl MangoInstruction {
+    /// Unpacks a byte buffer into a [MangoInstruction](enum.MangoInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            ACCOUNT_CREATE => Self::AccountCreate(anchor::deserialize(rest)?),
+            TOKEN_DEPOSIT => Self::TokenDeposit(anchor::deserialize(rest)?),
+            TOKEN_WITHDRAW => Self::TokenWithdraw(anchor::deserialize(rest)?),
+            PERP_PLACE_ORDER => Self::PerpPlaceOrder(anchor::deserialize(rest)?),
+            PERP_PLACE_ORDER_PEGGED => Self::PerpPlaceOrderPegged(anchor::deserialize(rest)?),
+            PERP_CANCEL_ORDER => Self::PerpCancelOrder(anchor::deserialize(rest)?),
+            PERP_CANCEL_ORDER_BY_CLIENT_ORDER_ID => {
+                Self::PerpCancelOrderByClientOrderId(anchor::deserialize(rest)?)
+            }
+            PERP_CANCEL_ALL_ORDERS => Self::PerpCancelAllOrders(anchor::deserialize(rest)?),
+            PERP_CONSUME_EVENTS => Self::PerpConsumeEvents(anchor::deserialize(rest)?),
+            PERP_SETTLE_PNL => Self::PerpSettlePnl,
+            PERP_SETTLE_FEES => Self::PerpSettleFees(anchor::deserialize(rest)?),
+            PERP_UPDATE_FUNDING => Self::PerpUpdateFunding,
+            PERP_LIQ_BASE_OR_POSITIVE_PNL => {
+                Self::PerpLiqBaseOrPositivePnl(anchor::deserialize(rest)?)
+            }
+            PERP_LIQ_NEGATIVE_PNL_OR_BANKRUPTCY => {
+                Self::PerpLiqNegativePnlOrBankruptcy(anchor::deserialize(rest)?)
+            }
+            TOKEN_LIQ_WITH_TOKEN => Self::TokenLiqWithToken(anchor::deserialize(rest)?),
+            TOKEN_LIQ_BANKRUPTCY => Self::TokenLiqBankruptcy(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,298 @@
+//! Instruction decoding functions for the mango v4 program
+//!
+//! Banks, oracles and perp markets needed for health checks are passed as remaining
+//! accounts and are not named. Fills and transfers are logged rather than emitted through
+//! self cpi, [events::log_events] decodes them from the logs of a transaction.
+
+use anyhow::Context;
+use ix::MangoInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("4MangoMjqJ2firMokCjjGgoK8d4MXcrgL7XJaL3w6fVg");
+
+const PERP_PLACE_ORDER_ACCOUNTS: [&str; 8] = [
+    "group",
+    "account",
+    "owner",
+    "perpMarket",
+    "bids",
+    "asks",
+    "eventQueue",
+    "oracle",
+];
+
+const PERP_CANCEL_ORDER_ACCOUNTS: [&str; 6] =
+    ["group", "account", "owner", "perpMarket", "bids", "asks"];
+
+const TOKEN_LIQ_ACCOUNTS: [&str; 4] = ["group", "liqor", "liqorOwner", "liqee"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MangoV4Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl MangoV4Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = MangoInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            MangoInstruction::AccountCreate(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "account",
+                    "owner",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "accountCreate".to_string(),
+            }),
+            MangoInstruction::TokenDeposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "account",
+                    "owner",
+                    "bank",
+                    "vault",
+                    "oracle",
+                    "tokenAccount",
+                    "tokenAuthority",
+                    "tokenProgram",
+                ]),
+                name: "tokenDeposit".to_string(),
+            }),
+            MangoInstruction::TokenWithdraw(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "account",
+                    "owner",
+                    "bank",
+                    "vault",
+                    "oracle",
+                    "tokenAccount",
+                    "tokenProgram",
+                ]),
+                name: "tokenWithdraw".to_string(),
+            }),
+            MangoInstruction::PerpPlaceOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PERP_PLACE_ORDER_ACCOUNTS),
+                name: "perpPlaceOrder".to_string(),
+            }),
+            MangoInstruction::PerpPlaceOrderPegged(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PERP_PLACE_ORDER_ACCOUNTS),
+                name: "perpPlaceOrderPegged".to_string(),
+            }),
+            MangoInstruction::PerpCancelOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PERP_CANCEL_ORDER_ACCOUNTS),
+                name: "perpCancelOrder".to_string(),
+            }),
+            MangoInstruction::PerpCancelOrderByClientOrderId(client_order_id) => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "clientOrderId".to_string(),
+                        serde_json::to_value(client_order_id)?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&PERP_CANCEL_ORDER_ACCOUNTS),
+                    name: "perpCancelOrderByClientOrderId".to_string(),
+                })
+            }
+            MangoInstruction::PerpCancelAllOrders(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&PERP_CANCEL_ORDER_ACCOUNTS),
+                name: "perpCancelAllOrders".to_string(),
+            }),
+            MangoInstruction::PerpConsumeEvents(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["group", "perpMarket", "eventQueue"]),
+                name: "perpConsumeEvents".to_string(),
+            }),
+            MangoInstruction::PerpSettlePnl => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "settler",
+                    "settlerOwner",
+                    "perpMarket",
+                    "accountA",
+                    "accountB",
+                    "oracle",
+                    "settleBank",
+                    "settleOracle",
+                ]),
+                name: "perpSettlePnl".to_string(),
+                ..Default::default()
+            }),
+            MangoInstruction::PerpSettleFees(max_settle_amount) => Ok(DecodedInstruction {
+                data: [(
+                    "maxSettleAmount".to_string(),
+                    serde_json::to_value(max_settle_amount)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "perpMarket",
+                    "account",
+                    "oracle",
+                    "settleBank",
+                    "settleOracle",
+                ]),
+                name: "perpSettleFees".to_string(),
+            }),
+            MangoInstruction::PerpUpdateFunding => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "perpMarket",
+                    "bids",
+                    "asks",
+                    "oracle",
+                ]),
+                name: "perpUpdateFunding".to_string(),
+                ..Default::default()
+            }),
+            MangoInstruction::PerpLiqBaseOrPositivePnl(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "perpMarket",
+                    "oracle",
+                    "liqor",
+                    "liqorOwner",
+                    "liqee",
+                    "settleBank",
+                    "settleVault",
+                    "settleOracle",
+                ]),
+                name: "perpLiqBaseOrPositivePnl".to_string(),
+            }),
+            MangoInstruction::PerpLiqNegativePnlOrBankruptcy(max_liab_transfer) => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "maxLiabTransfer".to_string(),
+                        serde_json::to_value(max_liab_transfer)?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&[
+                        "group",
+                        "liqor",
+                        "liqorOwner",
+                        "liqee",
+                        "perpMarket",
+                        "oracle",
+                        "settleBank",
+                        "settleVault",
+                        "settleOracle",
+                        "insuranceVault",
+                        "tokenProgram",
+                    ]),
+                    name: "perpLiqNegativePnlOrBankruptcy".to_string(),
+                })
+            }
+            MangoInstruction::TokenLiqWithToken(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TOKEN_LIQ_ACCOUNTS),
+                name: "tokenLiqWithToken".to_string(),
+            }),
+            MangoInstruction::TokenLiqBankruptcy(max_liab_transfer) => Ok(DecodedInstruction {
+                data: [(
+                    "maxLiabTransfer".to_string(),
+                    serde_json::to_value(max_liab_transfer.to_string())?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "group",
+                    "liqor",
+                    "liqorOwner",
+                    "liqee",
+                    "liabMintInfo",
+                    "quoteVault",
+                    "insuranceVault",
+                    "tokenProgram",
+                ]),
+                name: "tokenLiqBankruptcy".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_deposit_and_deposit_log() {
+        let account = Pubkey::new_unique();
+        let mut data = ix::TOKEN_DEPOSIT.to_vec();
+        data.extend(to_vec(&(2_500_000u64, false)).unwrap());
+        let decoded = MangoV4Decoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: [Pubkey::new_unique(), account]
+                .into_iter()
+                .chain((0..7).map(|_| Pubkey::new_unique()))
+                .collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "tokenDeposit");
+        assert_eq!(decoded.accounts["account"], account.to_string());
+        assert_eq!(decoded.data["amount"], 2_500_000u64);
+        assert_eq!(decoded.data["reduceOnly"], false);
+
+        let mut log = events::DEPOSIT_LOG.to_vec();
+        log.extend(
+            to_vec(&(
+                Pubkey::new_unique(),
+                account,
+                Pubkey::new_unique(),
+                0u16,
+                2_500_000u64,
+                1i128 << 48,
+            ))
+            .unwrap(),
+        );
+        let logs = vec![
+            format!("Program {} invoke [1]", PROGRAM_ID),
+            "Program log: Instruction: TokenDeposit".to_string(),
+            format!("Program data: {}", STANDARD.encode(&log)),
+            format!(
+                "Program {} consumed 40000 of 200000 compute units",
+                PROGRAM_ID
+            ),
+            format!("Program {} success", PROGRAM_ID),
+        ];
+        let events = events::log_events(&logs);
+        assert_eq!(events.len(), 1);
+        let decoded = events[0].to_decoded_instruction().unwrap();
+        assert_eq!(decoded.name, "depositLog");
+        assert_eq!(decoded.data["mangoAccount"], account.to_string());
+        assert_eq!(decoded.data["quantity"], 2_500_000u64);
+        assert_eq!(decoded.data["price"], (1i128 << 48).to_string());
+    }
+}
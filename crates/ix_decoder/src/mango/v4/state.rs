@@ -0,0 +1,108 @@
+//! Account types
+//!
+//! Mango accounts have a fixed part followed by dynamically sized position lists, only the
+//! token positions, which come first, are decoded.
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    marginfi::v2::state::i80f48_to_f64,
+    types::{serialize_hex, serialize_i128, serialize_pubkey},
+};
+
+pub const MANGO_ACCOUNT_DISCRIMINATOR: [u8; 8] = [243, 228, 247, 3, 169, 52, 175, 31];
+
+/// Token index of unused token positions
+pub const INACTIVE_TOKEN_INDEX: u16 = u16::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPosition {
+    /// I80F48 position, scaled by the bank's deposit or borrow index
+    #[serde(serialize_with = "serialize_i128")]
+    pub indexed_position: i128,
+    pub token_index: u16,
+    pub in_use_count: u16,
+    #[serde(skip)]
+    pub padding: [u8; 4],
+    #[serde(serialize_with = "serialize_i128")]
+    pub previous_index: i128,
+    pub cumulative_deposit_interest: f64,
+    pub cumulative_borrow_interest: f64,
+    #[serde(skip)]
+    pub reserved: [u8; 128],
+}
+
+impl TokenPosition {
+    /// Native amount of the position given the bank's I80F48 indexes, negative for borrows
+    pub fn native(&self, deposit_index: i128, borrow_index: i128) -> f64 {
+        let index = if self.indexed_position >= 0 {
+            deposit_index
+        } else {
+            borrow_index
+        };
+        i80f48_to_f64(self.indexed_position) * i80f48_to_f64(index)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MangoAccount {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub group: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    #[serde(serialize_with = "serialize_hex")]
+    pub name: [u8; 32],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub delegate: Pubkey,
+    pub account_num: u32,
+    pub being_liquidated: u8,
+    pub in_health_region: u8,
+    pub bump: u8,
+    pub sequence_number: u8,
+    /// native quote deposited minus withdrawn, at the oracle price of the time
+    pub net_deposits: i64,
+    pub perp_spot_transfers: i64,
+    pub health_region_begin_init_health: i64,
+    pub frozen_until: u64,
+    pub buyback_fees_accrued_current: u64,
+    pub buyback_fees_accrued_previous: u64,
+    pub buyback_fees_expiry_timestamp: u64,
+    pub next_token_conditional_swap_id: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub temporary_delegate: Pubkey,
+    pub temporary_delegate_expiry: u64,
+    pub last_collateral_fee_charge: u64,
+    #[serde(skip)]
+    pub reserved: [u8; 152],
+    pub header_version: u8,
+    #[serde(skip)]
+    pub padding3: [u8; 7],
+    #[serde(skip)]
+    pub padding4: u32,
+    pub tokens: Vec<TokenPosition>,
+}
+
+impl MangoAccount {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, MANGO_ACCOUNT_DISCRIMINATOR)
+    }
+
+    /// Name of the account, with the padding removed
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+
+    /// Token positions which are in use
+    pub fn active_token_positions(&self) -> impl Iterator<Item = &TokenPosition> {
+        self.tokens
+            .iter()
+            .filter(|position| position.token_index != INACTIVE_TOKEN_INDEX)
+    }
+}
@@ -0,0 +1,145 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const MARGINFI_GROUP_INITIALIZE: [u8; 8] = [255, 67, 67, 26, 94, 31, 34, 20];
+pub const MARGINFI_ACCOUNT_INITIALIZE: [u8; 8] = [43, 78, 61, 255, 148, 52, 249, 154];
+pub const MARGINFI_ACCOUNT_CLOSE: [u8; 8] = [186, 221, 93, 34, 50, 97, 194, 241];
+pub const LENDING_POOL_ADD_BANK: [u8; 8] = [215, 68, 72, 78, 208, 218, 103, 182];
+pub const LENDING_POOL_ACCRUE_BANK_INTEREST: [u8; 8] = [108, 201, 30, 87, 47, 65, 97, 188];
+pub const LENDING_POOL_COLLECT_BANK_FEES: [u8; 8] = [201, 5, 215, 116, 230, 92, 75, 150];
+pub const LENDING_POOL_HANDLE_BANKRUPTCY: [u8; 8] = [162, 11, 56, 139, 90, 128, 70, 173];
+pub const LENDING_ACCOUNT_DEPOSIT: [u8; 8] = [171, 94, 235, 103, 82, 64, 212, 140];
+pub const LENDING_ACCOUNT_REPAY: [u8; 8] = [79, 209, 172, 177, 222, 51, 173, 151];
+pub const LENDING_ACCOUNT_WITHDRAW: [u8; 8] = [36, 72, 74, 19, 210, 210, 192, 192];
+pub const LENDING_ACCOUNT_BORROW: [u8; 8] = [4, 126, 116, 53, 48, 5, 212, 31];
+pub const LENDING_ACCOUNT_CLOSE_BALANCE: [u8; 8] = [245, 54, 41, 4, 243, 202, 31, 17];
+pub const LENDING_ACCOUNT_LIQUIDATE: [u8; 8] = [214, 169, 151, 213, 251, 167, 86, 219];
+pub const LENDING_ACCOUNT_START_FLASHLOAN: [u8; 8] = [14, 131, 33, 220, 81, 186, 180, 107];
+pub const LENDING_ACCOUNT_END_FLASHLOAN: [u8; 8] = [105, 124, 201, 106, 153, 2, 8, 156];
+pub const LENDING_ACCOUNT_SETTLE_EMISSIONS: [u8; 8] = [161, 58, 136, 174, 242, 223, 156, 176];
+pub const LENDING_ACCOUNT_WITHDRAW_EMISSIONS: [u8; 8] = [234, 22, 84, 214, 118, 176, 140, 170];
+
+/// Reads an amount followed by a flag which older program versions didn't take
+fn unpack_amount_and_flag(mut input: &[u8]) -> anyhow::Result<(u64, Option<bool>)> {
+    let amount = u64::deserialize(&mut input)?;
+    let flag = if input.is_empty() {
+        None
+    } else {
+        Option::<bool>::deserialize(&mut input)?
+    };
+    Ok((amount, flag))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInstruction {
+    pub amount: u64,
+    pub deposit_up_to_limit: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepayInstruction {
+    pub amount: u64,
+    /// repays the whole liability, closing the balance
+    pub repay_all: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawInstruction {
+    pub amount: u64,
+    /// withdraws the whole deposit, closing the balance
+    pub withdraw_all: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BorrowInstruction {
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidateInstruction {
+    /// collateral seized from the liquidatee, in native units of the asset bank
+    pub asset_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartFlashloanInstruction {
+    /// index of the instruction ending the flash loan
+    pub end_index: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarginfiInstruction {
+    MarginfiGroupInitialize,
+    MarginfiAccountInitialize,
+    MarginfiAccountClose,
+    /// the bank config argument is not decoded
+    LendingPoolAddBank,
+    LendingPoolAccrueBankInterest,
+    LendingPoolCollectBankFees,
+    LendingPoolHandleBankruptcy,
+    LendingAccountDeposit(DepositInstruction),
+    LendingAccountRepay(RepayInstruction),
+    LendingAccountWithdraw(WithdrawInstruction),
+    LendingAccountBorrow(BorrowInstruction),
+    LendingAccountCloseBalance,
+    LendingAccountLiquidate(LiquidateInstruction),
+    LendingAccountStartFlashloan(StartFlashloanInstruction),
+    LendingAccountEndFlashloan,
+    LendingAccountSettleEmissions,
+    LendingAccountWithdrawEmissions,
+}
+
+impl MarginfiInstruction {
+    /// Unpacks a byte buffer into a [MarginfiInstruction](enum.MarginfiInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            MARGINFI_GROUP_INITIALIZE => Self::MarginfiGroupInitialize,
+            MARGINFI_ACCOUNT_INITIALIZE => Self::MarginfiAccountInitialize,
+            MARGINFI_ACCOUNT_CLOSE => Self::MarginfiAccountClose,
+            LENDING_POOL_ADD_BANK => Self::LendingPoolAddBank,
+            LENDING_POOL_ACCRUE_BANK_INTEREST => Self::LendingPoolAccrueBankInterest,
+            LENDING_POOL_COLLECT_BANK_FEES => Self::LendingPoolCollectBankFees,
+            LENDING_POOL_HANDLE_BANKRUPTCY => Self::LendingPoolHandleBankruptcy,
+            LENDING_ACCOUNT_DEPOSIT => {
+                let (amount, deposit_up_to_limit) = unpack_amount_and_flag(rest)?;
+                Self::LendingAccountDeposit(DepositInstruction {
+                    amount,
+                    deposit_up_to_limit,
+                })
+            }
+            LENDING_ACCOUNT_REPAY => {
+                let (amount, repay_all) = unpack_amount_and_flag(rest)?;
+                Self::LendingAccountRepay(RepayInstruction { amount, repay_all })
+            }
+            LENDING_ACCOUNT_WITHDRAW => {
+                let (amount, withdraw_all) = unpack_amount_and_flag(rest)?;
+                Self::LendingAccountWithdraw(WithdrawInstruction {
+                    amount,
+                    withdraw_all,
+                })
+            }
+            LENDING_ACCOUNT_BORROW => Self::LendingAccountBorrow(anchor::deserialize(rest)?),
+            LENDING_ACCOUNT_CLOSE_BALANCE => Self::LendingAccountCloseBalance,
+            LENDING_ACCOUNT_LIQUIDATE => Self::LendingAccountLiquidate(anchor::deserialize(rest)?),
+            LENDING_ACCOUNT_START_FLASHLOAN => {
+                Self::LendingAccountStartFlashloan(anchor::deserialize(rest)?)
+            }
+            LENDING_ACCOUNT_END_FLASHLOAN => Self::LendingAccountEndFlashloan,
+            LENDING_ACCOUNT_SETTLE_EMISSIONS => Self::LendingAccountSettleEmissions,
+            LENDING_ACCOUNT_WITHDRAW_EMISSIONS => Self::LendingAccountWithdrawEmissions,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,254 @@
+//! Instruction decoding functions for the marginfi v2 program
+
+use anyhow::Context;
+use ix::MarginfiInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MFv2hWf31Z9kbCa1snEPYctwafyhdvnV7FZnsebVacA");
+
+const DEPOSIT_ACCOUNTS: [&str; 7] = [
+    "marginfiGroup",
+    "marginfiAccount",
+    "signer",
+    "bank",
+    "signerTokenAccount",
+    "bankLiquidityVault",
+    "tokenProgram",
+];
+
+const WITHDRAW_ACCOUNTS: [&str; 8] = [
+    "marginfiGroup",
+    "marginfiAccount",
+    "signer",
+    "bank",
+    "destinationTokenAccount",
+    "bankLiquidityVaultAuthority",
+    "bankLiquidityVault",
+    "tokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MarginfiV2Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl MarginfiV2Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = MarginfiInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            MarginfiInstruction::MarginfiGroupInitialize => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["marginfiGroup", "admin", "systemProgram"]),
+                name: "marginfiGroupInitialize".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::MarginfiAccountInitialize => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "marginfiAccount",
+                    "authority",
+                    "feePayer",
+                    "systemProgram",
+                ]),
+                name: "marginfiAccountInitialize".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::MarginfiAccountClose => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["marginfiAccount", "authority", "feePayer"]),
+                name: "marginfiAccountClose".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingPoolAddBank => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "admin",
+                    "feePayer",
+                    "bankMint",
+                    "bank",
+                    "liquidityVaultAuthority",
+                    "liquidityVault",
+                    "insuranceVaultAuthority",
+                    "insuranceVault",
+                    "feeVaultAuthority",
+                    "feeVault",
+                    "rent",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "lendingPoolAddBank".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingPoolAccrueBankInterest => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["marginfiGroup", "bank"]),
+                name: "lendingPoolAccrueBankInterest".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingPoolCollectBankFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "bank",
+                    "liquidityVaultAuthority",
+                    "liquidityVault",
+                    "insuranceVault",
+                    "feeVault",
+                    "tokenProgram",
+                ]),
+                name: "lendingPoolCollectBankFees".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingPoolHandleBankruptcy => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "signer",
+                    "bank",
+                    "marginfiAccount",
+                    "liquidityVault",
+                    "insuranceVault",
+                    "insuranceVaultAuthority",
+                    "tokenProgram",
+                ]),
+                name: "lendingPoolHandleBankruptcy".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingAccountDeposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_ACCOUNTS),
+                name: "lendingAccountDeposit".to_string(),
+            }),
+            MarginfiInstruction::LendingAccountRepay(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_ACCOUNTS),
+                name: "lendingAccountRepay".to_string(),
+            }),
+            MarginfiInstruction::LendingAccountWithdraw(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_ACCOUNTS),
+                name: "lendingAccountWithdraw".to_string(),
+            }),
+            MarginfiInstruction::LendingAccountBorrow(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_ACCOUNTS),
+                name: "lendingAccountBorrow".to_string(),
+            }),
+            MarginfiInstruction::LendingAccountCloseBalance => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "marginfiAccount",
+                    "signer",
+                    "bank",
+                ]),
+                name: "lendingAccountCloseBalance".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingAccountLiquidate(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "assetBank",
+                    "liabBank",
+                    "liquidatorMarginfiAccount",
+                    "signer",
+                    "liquidateeMarginfiAccount",
+                    "bankLiquidityVaultAuthority",
+                    "bankLiquidityVault",
+                    "bankInsuranceVault",
+                    "tokenProgram",
+                ]),
+                name: "lendingAccountLiquidate".to_string(),
+            }),
+            MarginfiInstruction::LendingAccountStartFlashloan(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["marginfiAccount", "signer", "ixsSysvar"]),
+                name: "lendingAccountStartFlashloan".to_string(),
+            }),
+            MarginfiInstruction::LendingAccountEndFlashloan => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["marginfiAccount", "signer"]),
+                name: "lendingAccountEndFlashloan".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingAccountSettleEmissions => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["marginfiAccount", "bank"]),
+                name: "lendingAccountSettleEmissions".to_string(),
+                ..Default::default()
+            }),
+            MarginfiInstruction::LendingAccountWithdrawEmissions => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "marginfiGroup",
+                    "marginfiAccount",
+                    "signer",
+                    "bank",
+                    "emissionsMint",
+                    "emissionsAuth",
+                    "emissionsVault",
+                    "destinationAccount",
+                    "tokenProgram",
+                ]),
+                name: "lendingAccountWithdrawEmissions".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_deposit_and_balances() {
+        let decode = |data: Vec<u8>| {
+            MarginfiV2Decoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: (0..7).map(|_| Pubkey::new_unique()).collect(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        // deposits made before the limit flag was introduced only carry the amount
+        let mut data = ix::LENDING_ACCOUNT_DEPOSIT.to_vec();
+        data.extend(250u64.to_le_bytes());
+        let decoded = decode(data.clone());
+        assert_eq!(decoded.name, "lendingAccountDeposit");
+        assert_eq!(decoded.data["amount"], 250);
+        assert!(decoded.data["depositUpToLimit"].is_null());
+        data.extend([1, 1]);
+        assert_eq!(decode(data).data["depositUpToLimit"], true);
+
+        let one = 1i128 << 48;
+        let bank_pk = Pubkey::new_unique();
+        let mut account = state::MARGINFI_ACCOUNT_DISCRIMINATOR.to_vec();
+        account.extend([0u8; 64]);
+        account.push(1);
+        account.extend(bank_pk.to_bytes());
+        account.extend([0u8; 7]);
+        account.extend((one * 3).to_le_bytes());
+        account.extend([0u8; 16 * 2 + 16]);
+        account.extend([0u8; 104 * 15 + 8]);
+        let account = state::MarginfiAccount::unpack(&account).unwrap();
+        let balances: Vec<_> = account.active_balances().collect();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].bank_pk, bank_pk);
+
+        let bank = state::Bank {
+            asset_share_value: one + one / 2,
+            ..Default::default()
+        };
+        assert_eq!(bank.asset_amount(balances[0].asset_shares), 4.5);
+    }
+}
@@ -0,0 +1,124 @@
+//! Account types
+//!
+//! Share values and share amounts are I80F48 fixed point numbers, see [i80f48_to_f64].
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_i128, serialize_pubkey},
+};
+
+pub const BANK_DISCRIMINATOR: [u8; 8] = [142, 49, 166, 242, 50, 66, 97, 188];
+pub const MARGINFI_ACCOUNT_DISCRIMINATOR: [u8; 8] = [67, 178, 130, 109, 126, 114, 28, 42];
+
+const FRACTION_BITS: u32 = 48;
+
+/// Converts an I80F48 value to a float
+pub fn i80f48_to_f64(value: i128) -> f64 {
+    value as f64 / (1u128 << FRACTION_BITS) as f64
+}
+
+/// A lending pool bank, the bank config which follows `last_update` is not decoded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bank {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    pub mint_decimals: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub group: Pubkey,
+    #[serde(skip)]
+    pub padding0: [u8; 7],
+    /// native amount per asset share
+    #[serde(serialize_with = "serialize_i128")]
+    pub asset_share_value: i128,
+    /// native amount per liability share
+    #[serde(serialize_with = "serialize_i128")]
+    pub liability_share_value: i128,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub liquidity_vault: Pubkey,
+    pub liquidity_vault_bump: u8,
+    pub liquidity_vault_authority_bump: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub insurance_vault: Pubkey,
+    pub insurance_vault_bump: u8,
+    pub insurance_vault_authority_bump: u8,
+    #[serde(skip)]
+    pub padding1: [u8; 4],
+    #[serde(serialize_with = "serialize_i128")]
+    pub collected_insurance_fees_outstanding: i128,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_vault: Pubkey,
+    pub fee_vault_bump: u8,
+    pub fee_vault_authority_bump: u8,
+    #[serde(skip)]
+    pub padding2: [u8; 6],
+    #[serde(serialize_with = "serialize_i128")]
+    pub collected_group_fees_outstanding: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub total_liability_shares: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub total_asset_shares: i128,
+    pub last_update: i64,
+}
+
+impl Bank {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, BANK_DISCRIMINATOR)
+    }
+
+    /// Converts asset shares to native units of the bank's mint
+    pub fn asset_amount(&self, shares: i128) -> f64 {
+        i80f48_to_f64(shares) * i80f48_to_f64(self.asset_share_value)
+    }
+
+    /// Converts liability shares to native units of the bank's mint
+    pub fn liability_amount(&self, shares: i128) -> f64 {
+        i80f48_to_f64(shares) * i80f48_to_f64(self.liability_share_value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub active: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub bank_pk: Pubkey,
+    #[serde(skip)]
+    pub padding0: [u8; 7],
+    #[serde(serialize_with = "serialize_i128")]
+    pub asset_shares: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub liability_shares: i128,
+    #[serde(serialize_with = "serialize_i128")]
+    pub emissions_outstanding: i128,
+    pub last_update: u64,
+    #[serde(skip)]
+    pub padding: u64,
+}
+
+/// A user's balances within a marginfi group
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginfiAccount {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub group: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub authority: Pubkey,
+    pub balances: [Balance; 16],
+    pub account_flags: u64,
+}
+
+impl MarginfiAccount {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, MARGINFI_ACCOUNT_DISCRIMINATOR)
+    }
+
+    /// Balances which are in use
+    pub fn active_balances(&self) -> impl Iterator<Item = &Balance> {
+        self.balances.iter().filter(|balance| balance.active != 0)
+    }
+}
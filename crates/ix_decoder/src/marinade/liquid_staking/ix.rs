@@ -0,0 +1,108 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const DEPOSIT_STAKE_ACCOUNT: [u8; 8] = [110, 130, 115, 41, 164, 102, 2, 59];
+pub const LIQUID_UNSTAKE: [u8; 8] = [30, 30, 119, 240, 191, 227, 12, 16];
+pub const ADD_LIQUIDITY: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+pub const REMOVE_LIQUIDITY: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+pub const ORDER_UNSTAKE: [u8; 8] = [97, 167, 144, 107, 117, 190, 128, 36];
+pub const CLAIM: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+pub const WITHDRAW_STAKE_ACCOUNT: [u8; 8] = [211, 85, 184, 65, 183, 177, 233, 217];
+pub const STAKE_RESERVE: [u8; 8] = [87, 217, 23, 179, 205, 25, 113, 129];
+pub const UPDATE_ACTIVE: [u8; 8] = [4, 67, 81, 64, 136, 245, 93, 152];
+pub const UPDATE_DEACTIVATED: [u8; 8] = [16, 232, 131, 115, 156, 100, 239, 50];
+pub const DEACTIVATE_STAKE: [u8; 8] = [165, 158, 229, 97, 168, 220, 187, 225];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LamportsInstruction {
+    pub lamports: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MsolAmountInstruction {
+    pub msol_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveLiquidityInstruction {
+    /// liquidity pool tokens burned
+    pub tokens: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorIndexInstruction {
+    pub validator_index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeIndexInstruction {
+    pub stake_index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeAndValidatorIndexInstruction {
+    pub stake_index: u32,
+    pub validator_index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawStakeAccountInstruction {
+    pub stake_index: u32,
+    pub validator_index: u32,
+    pub msol_amount: u64,
+    /// receives the split stake account
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub beneficiary: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarinadeInstruction {
+    Deposit(LamportsInstruction),
+    DepositStakeAccount(ValidatorIndexInstruction),
+    LiquidUnstake(MsolAmountInstruction),
+    AddLiquidity(LamportsInstruction),
+    RemoveLiquidity(RemoveLiquidityInstruction),
+    OrderUnstake(MsolAmountInstruction),
+    Claim,
+    WithdrawStakeAccount(WithdrawStakeAccountInstruction),
+    StakeReserve(ValidatorIndexInstruction),
+    UpdateActive(StakeAndValidatorIndexInstruction),
+    UpdateDeactivated(StakeIndexInstruction),
+    DeactivateStake(StakeAndValidatorIndexInstruction),
+}
+
+impl MarinadeInstruction {
+    /// Unpacks a byte buffer into a [MarinadeInstruction](enum.MarinadeInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            DEPOSIT => Self::Deposit(anchor::deserialize(rest)?),
+            DEPOSIT_STAKE_ACCOUNT => Self::DepositStakeAccount(anchor::deserialize(rest)?),
+            LIQUID_UNSTAKE => Self::LiquidUnstake(anchor::deserialize(rest)?),
+            ADD_LIQUIDITY => Self::AddLiquidity(anchor::deserialize(rest)?),
+            REMOVE_LIQUIDITY => Self::RemoveLiquidity(anchor::deserialize(rest)?),
+            ORDER_UNSTAKE => Self::OrderUnstake(anchor::deserialize(rest)?),
+            CLAIM => Self::Claim,
+            WITHDRAW_STAKE_ACCOUNT => Self::WithdrawStakeAccount(anchor::deserialize(rest)?),
+            STAKE_RESERVE => Self::StakeReserve(anchor::deserialize(rest)?),
+            UPDATE_ACTIVE => Self::UpdateActive(anchor::deserialize(rest)?),
+            UPDATE_DEACTIVATED => Self::UpdateDeactivated(anchor::deserialize(rest)?),
+            DEACTIVATE_STAKE => Self::DeactivateStake(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
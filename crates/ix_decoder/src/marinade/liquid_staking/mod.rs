@@ -0,0 +1,267 @@
+//! Instruction decoding functions for the marinade liquid staking program
+
+use anyhow::Context;
+use ix::MarinadeInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+
+const UPDATE_ACCOUNTS: [&str; 12] = [
+    "state",
+    "stakeList",
+    "stakeAccount",
+    "stakeWithdrawAuthority",
+    "reservePda",
+    "msolMint",
+    "msolMintAuthority",
+    "treasuryMsolAccount",
+    "clock",
+    "stakeHistory",
+    "stakeProgram",
+    "tokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MarinadeDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl MarinadeDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = MarinadeInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            MarinadeInstruction::Deposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "msolMint",
+                    "liqPoolSolLegPda",
+                    "liqPoolMsolLeg",
+                    "liqPoolMsolLegAuthority",
+                    "reservePda",
+                    "transferFrom",
+                    "mintTo",
+                    "msolMintAuthority",
+                    "systemProgram",
+                    "tokenProgram",
+                ]),
+                name: "deposit".to_string(),
+            }),
+            MarinadeInstruction::DepositStakeAccount(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "validatorList",
+                    "stakeList",
+                    "stakeAccount",
+                    "stakeAuthority",
+                    "duplicationFlag",
+                    "rentPayer",
+                    "msolMint",
+                    "mintTo",
+                    "msolMintAuthority",
+                    "clock",
+                    "rent",
+                    "systemProgram",
+                    "tokenProgram",
+                    "stakeProgram",
+                ]),
+                name: "depositStakeAccount".to_string(),
+            }),
+            MarinadeInstruction::LiquidUnstake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "msolMint",
+                    "liqPoolSolLegPda",
+                    "liqPoolMsolLeg",
+                    "treasuryMsolAccount",
+                    "getMsolFrom",
+                    "getMsolFromAuthority",
+                    "transferSolTo",
+                    "systemProgram",
+                    "tokenProgram",
+                ]),
+                name: "liquidUnstake".to_string(),
+            }),
+            MarinadeInstruction::AddLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "lpMint",
+                    "lpMintAuthority",
+                    "liqPoolMsolLeg",
+                    "liqPoolSolLegPda",
+                    "transferFrom",
+                    "mintTo",
+                    "systemProgram",
+                    "tokenProgram",
+                ]),
+                name: "addLiquidity".to_string(),
+            }),
+            MarinadeInstruction::RemoveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "lpMint",
+                    "burnFrom",
+                    "burnFromAuthority",
+                    "transferSolTo",
+                    "transferMsolTo",
+                    "liqPoolSolLegPda",
+                    "liqPoolMsolLeg",
+                    "liqPoolMsolLegAuthority",
+                    "systemProgram",
+                    "tokenProgram",
+                ]),
+                name: "removeLiquidity".to_string(),
+            }),
+            MarinadeInstruction::OrderUnstake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "msolMint",
+                    "burnMsolFrom",
+                    "burnMsolAuthority",
+                    "newTicketAccount",
+                    "clock",
+                    "rent",
+                    "tokenProgram",
+                ]),
+                name: "orderUnstake".to_string(),
+            }),
+            MarinadeInstruction::Claim => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "reservePda",
+                    "ticketAccount",
+                    "transferSolTo",
+                    "clock",
+                    "systemProgram",
+                ]),
+                name: "claim".to_string(),
+                ..Default::default()
+            }),
+            MarinadeInstruction::WithdrawStakeAccount(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "msolMint",
+                    "burnMsolFrom",
+                    "burnMsolAuthority",
+                    "treasuryMsolAccount",
+                    "validatorList",
+                    "stakeList",
+                    "stakeWithdrawAuthority",
+                    "stakeDepositAuthority",
+                    "stakeAccount",
+                    "splitStakeAccount",
+                    "splitStakeRentPayer",
+                    "clock",
+                    "systemProgram",
+                    "tokenProgram",
+                    "stakeProgram",
+                ]),
+                name: "withdrawStakeAccount".to_string(),
+            }),
+            MarinadeInstruction::StakeReserve(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "validatorList",
+                    "stakeList",
+                    "validatorVote",
+                    "reservePda",
+                    "stakeAccount",
+                    "stakeDepositAuthority",
+                    "rentPayer",
+                    "clock",
+                    "epochSchedule",
+                    "rent",
+                    "stakeHistory",
+                    "stakeConfig",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "stakeReserve".to_string(),
+            }),
+            MarinadeInstruction::UpdateActive(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&[&UPDATE_ACCOUNTS[..], &["validatorList"]].concat()),
+                name: "updateActive".to_string(),
+            }),
+            MarinadeInstruction::UpdateDeactivated(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(
+                    &[
+                        &UPDATE_ACCOUNTS[..],
+                        &["operationalSolAccount", "systemProgram"],
+                    ]
+                    .concat(),
+                ),
+                name: "updateDeactivated".to_string(),
+            }),
+            MarinadeInstruction::DeactivateStake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "reservePda",
+                    "validatorList",
+                    "stakeList",
+                    "stakeAccount",
+                    "stakeDepositAuthority",
+                    "splitStakeAccount",
+                    "splitStakeRentPayer",
+                    "clock",
+                    "rent",
+                    "epochSchedule",
+                    "stakeHistory",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "deactivateStake".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_liquid_unstake_and_state() {
+        let mut data = ix::LIQUID_UNSTAKE.to_vec();
+        data.extend(2_000_000_000u64.to_le_bytes());
+        let accounts: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let decoded = MarinadeDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "liquidUnstake");
+        assert_eq!(decoded.data["msolAmount"], 2_000_000_000u64);
+        assert_eq!(decoded.accounts["transferSolTo"], accounts[7].to_string());
+
+        let state = state::State {
+            msol_price: 5 << 31,
+            ..Default::default()
+        };
+        assert_eq!(state.exchange_rate(), 2.5);
+        assert_eq!(state.msol_to_lamports(1_000_000_000), 2_500_000_000);
+    }
+}
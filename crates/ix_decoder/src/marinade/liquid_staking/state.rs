@@ -0,0 +1,147 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+pub const TICKET_ACCOUNT_DATA_DISCRIMINATOR: [u8; 8] = [133, 77, 18, 98, 211, 1, 231, 3];
+
+/// `msol_price` is scaled by 2^32
+const PRICE_DENOMINATOR: u128 = 1 << 32;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fee {
+    pub basis_points: u32,
+}
+
+/// An account holding a list of fixed size items
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct List {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub account: Pubkey,
+    pub item_size: u32,
+    pub count: u32,
+    #[serde(skip)]
+    pub reserved1: Pubkey,
+    #[serde(skip)]
+    pub reserved2: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeSystem {
+    pub stake_list: List,
+    pub delayed_unstake_cooling_down: u64,
+    pub stake_deposit_bump_seed: u8,
+    pub stake_withdraw_bump_seed: u8,
+    pub slots_for_stake_delta: u64,
+    pub last_stake_delta_epoch: u64,
+    pub min_stake: u64,
+    pub extra_stake_delta_runs: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSystem {
+    pub validator_list: List,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub manager_authority: Pubkey,
+    pub total_validator_score: u32,
+    /// lamports delegated to validators
+    pub total_active_balance: u64,
+    pub auto_add_validator_enabled: u8,
+}
+
+/// The msol/sol pool used for liquid unstakes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiqPool {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lp_mint: Pubkey,
+    pub lp_mint_authority_bump_seed: u8,
+    pub sol_leg_bump_seed: u8,
+    pub msol_leg_authority_bump_seed: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub msol_leg: Pubkey,
+    pub lp_liquidity_target: u64,
+    pub lp_max_fee: Fee,
+    pub lp_min_fee: Fee,
+    pub treasury_cut: Fee,
+    pub lp_supply: u64,
+    pub lent_from_sol_leg: u64,
+    pub liquidity_sol_cap: u64,
+}
+
+/// The marinade state, fields added after `emergency_cooling_down` are not decoded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct State {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub msol_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub admin_authority: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub operational_sol_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub treasury_msol_account: Pubkey,
+    pub reserve_bump_seed: u8,
+    pub msol_mint_authority_bump_seed: u8,
+    pub rent_exempt_for_token_acc: u64,
+    pub reward_fee: Fee,
+    pub stake_system: StakeSystem,
+    pub validator_system: ValidatorSystem,
+    pub liq_pool: LiqPool,
+    pub available_reserve_balance: u64,
+    pub msol_supply: u64,
+    /// lamports per msol, scaled by 2^32
+    pub msol_price: u64,
+    pub circulating_ticket_count: u64,
+    /// lamports owed to unclaimed tickets
+    pub circulating_ticket_balance: u64,
+    pub lent_from_reserve: u64,
+    pub min_deposit: u64,
+    pub min_withdraw: u64,
+    pub staking_sol_cap: u64,
+    pub emergency_cooling_down: u64,
+}
+
+impl State {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, STATE_DISCRIMINATOR)
+    }
+
+    /// Sol per msol as of the last update
+    pub fn exchange_rate(&self) -> f64 {
+        self.msol_price as f64 / PRICE_DENOMINATOR as f64
+    }
+
+    /// Lamports msol can be redeemed for through a delayed unstake
+    pub fn msol_to_lamports(&self, msol_amount: u64) -> u64 {
+        (msol_amount as u128 * self.msol_price as u128 / PRICE_DENOMINATOR)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+}
+
+/// A delayed unstake ticket, claimable once the stake has been deactivated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketAccountData {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub state_address: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub beneficiary: Pubkey,
+    pub lamports_amount: u64,
+    pub created_epoch: u64,
+}
+
+impl TicketAccountData {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, TICKET_ACCOUNT_DATA_DISCRIMINATOR)
+    }
+}
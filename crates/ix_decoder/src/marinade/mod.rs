@@ -0,0 +1,47 @@
+//! Instruction decoding functions for the marinade programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use liquid_staking::MarinadeDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod liquid_staking;
+
+pub static MARINADE_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(MarinadeProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    LiquidStaking(MarinadeDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct MarinadeProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::LiquidStaking(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Marinade::LiquidStaking"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::LiquidStaking(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for MarinadeProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            liquid_staking::PROGRAM_ID => Some(Box::new(Programs::LiquidStaking(
+                MarinadeDecoder::new(ix.clone()),
+            ))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Instruction decoding functions for the mayan programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use swift::SwiftDecoder;
+
+pub mod swift;
+
+pub static MAYAN_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(MayanProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Swift(SwiftDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct MayanProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Swift(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Mayan::Swift"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Swift(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for MayanProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            swift::PROGRAM_ID => Some(Box::new(Programs::Swift(SwiftDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
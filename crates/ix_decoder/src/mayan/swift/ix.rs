@@ -0,0 +1,104 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{
+    anchor,
+    types::{format_address, serialize_hex, BridgeTransfer},
+    wormhole,
+};
+
+pub const INIT_ORDER: [u8; 8] = [32, 76, 41, 12, 39, 162, 132, 219];
+pub const FULFILL: [u8; 8] = [143, 2, 52, 206, 174, 164, 247, 72];
+pub const SETTLE: [u8; 8] = [175, 42, 185, 87, 144, 131, 102, 212];
+pub const UNLOCK: [u8; 8] = [101, 155, 40, 21, 158, 189, 56, 203];
+pub const CANCEL: [u8; 8] = [232, 219, 223, 41, 219, 236, 220, 190];
+pub const REFUND: [u8; 8] = [2, 96, 183, 251, 63, 208, 46, 46];
+
+/// An order swapping the tokens locked in the order state for at least `amount_out_min`
+/// of `token_out` on `chain_dest`. Chains are wormhole chain ids, addresses are left
+/// padded to 32 bytes and amounts are normalized to at most 8 decimals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitOrderParams {
+    #[serde(serialize_with = "serialize_hex")]
+    pub trader: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub token_out: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub addr_dest: [u8; 32],
+    pub chain_dest: u16,
+    pub amount_out_min: u64,
+    pub gas_drop: u64,
+    pub fee_cancel: u64,
+    pub fee_refund: u64,
+    pub deadline: u64,
+    #[serde(serialize_with = "serialize_hex")]
+    pub addr_ref: [u8; 32],
+    pub fee_rate_ref: u8,
+    pub fee_rate_mayan: u8,
+    pub auction_mode: u8,
+    #[serde(serialize_with = "serialize_hex")]
+    pub key_rnd: [u8; 32],
+}
+
+impl InitOrderParams {
+    pub fn bridge_transfer(&self) -> BridgeTransfer {
+        let format = wormhole::address_format(self.chain_dest);
+        BridgeTransfer {
+            destination_chain_id: self.chain_dest.into(),
+            destination_address: format_address(format, &self.addr_dest),
+            token: format_address(format, &self.token_out),
+            amount: self.amount_out_min.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitOrderInstruction {
+    pub params: InitOrderParams,
+}
+
+/// A driver filling an order on solana
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FulfillInstruction {
+    /// receives the locked tokens on the source chain
+    #[serde(serialize_with = "serialize_hex")]
+    pub addr_unlocker: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettleInstruction {
+    pub try_close_ata: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SwiftInstruction {
+    InitOrder(InitOrderInstruction),
+    Fulfill(FulfillInstruction),
+    Settle(SettleInstruction),
+    Unlock,
+    Cancel,
+    Refund,
+}
+
+impl SwiftInstruction {
+    /// Unpacks a byte buffer into a [SwiftInstruction](enum.SwiftInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INIT_ORDER => Self::InitOrder(anchor::deserialize(rest)?),
+            FULFILL => Self::Fulfill(anchor::deserialize(rest)?),
+            SETTLE => Self::Settle(anchor::deserialize(rest)?),
+            UNLOCK => Self::Unlock,
+            CANCEL => Self::Cancel,
+            REFUND => Self::Refund,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,163 @@
+//! Instruction decoding functions for the mayan swift program
+//!
+//! Orders leaving solana lock the input tokens through `init_order` and are unlocked to
+//! the driver filling them on the destination chain. Orders arriving on solana are filled
+//! through `fulfill` and paid out by `settle`.
+
+use anyhow::Context;
+use ix::SwiftInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("BLZRi6frs4X4DNLw56V4EXai1b6QVESN1BhHBTYM9VcY");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SwiftDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl SwiftDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = SwiftInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            SwiftInstruction::InitOrder(ix) => {
+                let mut data = to_data_map(&ix)?;
+                data.extend(to_data_map(&ix.params.bridge_transfer())?);
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&[
+                        "trader",
+                        "relayer",
+                        "state",
+                        "stateFromAcc",
+                        "relayerFeeAcc",
+                        "mintFrom",
+                        "feeManagerProgram",
+                        "tokenProgram",
+                        "systemProgram",
+                    ]),
+                    name: "initOrder".to_string(),
+                })
+            }
+            SwiftInstruction::Fulfill(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "driver",
+                    "stateToAcc",
+                    "mintTo",
+                    "dest",
+                    "systemProgram",
+                ]),
+                name: "fulfill".to_string(),
+            }),
+            SwiftInstruction::Settle(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "stateToAcc",
+                    "relayer",
+                    "mintTo",
+                    "dest",
+                    "destAcc",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "settle".to_string(),
+            }),
+            SwiftInstruction::Unlock => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "vaaUnlock",
+                    "state",
+                    "stateFromAcc",
+                    "mintFrom",
+                    "unlockerAcc",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "unlock".to_string(),
+                ..Default::default()
+            }),
+            SwiftInstruction::Cancel => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "state",
+                    "relayer",
+                    "stateFromAcc",
+                    "relayerAcc",
+                    "mintFrom",
+                    "trader",
+                    "traderAcc",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "cancel".to_string(),
+                ..Default::default()
+            }),
+            SwiftInstruction::Refund => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "vaaCancel",
+                    "state",
+                    "stateFromAcc",
+                    "relayer",
+                    "relayerAcc",
+                    "mintFrom",
+                    "trader",
+                    "traderAcc",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "refund".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_init_order() {
+        let trader = Pubkey::new_unique();
+        let mut token_out = [0u8; 32];
+        token_out[12..].copy_from_slice(&[0xaa; 20]);
+        let mut addr_dest = [0u8; 32];
+        addr_dest[12..].copy_from_slice(&[0xbb; 20]);
+        let mut data = ix::INIT_ORDER.to_vec();
+        data.extend(to_vec(&(trader.to_bytes(), token_out, addr_dest, 30u16)).unwrap());
+        data.extend(
+            to_vec(&(99_000_000u64, 0u64, 10_000u64, 10_000u64, 1_800_000_000u64)).unwrap(),
+        );
+        data.extend(to_vec(&([0u8; 32], 0u8, 3u8, 2u8, [5u8; 32])).unwrap());
+        let decoded = SwiftDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![trader],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "initOrder");
+        assert_eq!(decoded.accounts["trader"], trader.to_string());
+        assert_eq!(decoded.data["destinationChainId"], 30);
+        assert_eq!(
+            decoded.data["destinationAddress"],
+            format!("0x{}", hex::encode([0xbb; 20]))
+        );
+        assert_eq!(
+            decoded.data["token"],
+            format!("0x{}", hex::encode([0xaa; 20]))
+        );
+        assert_eq!(decoded.data["amount"], "99000000");
+        assert_eq!(decoded.data["params"]["feeRateMayan"], 3);
+    }
+}
@@ -0,0 +1,88 @@
+//! Events logged through the noop program
+//!
+//! Bubblegum logs the schema of every leaf it writes as application data, followed by
+//! the change log the compression program logs for the tree modification.
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    spl::account_compression::events::{
+        AccountCompressionEvent, ApplicationDataEvent, ApplicationDataEventV1,
+    },
+    types::{serialize_hex, serialize_pubkey, to_data_map, DecodedInstruction},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize)]
+pub enum BubblegumEventType {
+    Uninitialized,
+    LeafSchemaEvent,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize)]
+pub enum Version {
+    V1,
+}
+
+/// Contents of a compressed nft leaf
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(untagged)]
+pub enum LeafSchema {
+    #[serde(rename_all = "camelCase")]
+    V1 {
+        #[serde(rename = "assetId", serialize_with = "serialize_pubkey")]
+        id: Pubkey,
+        #[serde(serialize_with = "serialize_pubkey")]
+        owner: Pubkey,
+        #[serde(serialize_with = "serialize_pubkey")]
+        delegate: Pubkey,
+        nonce: u64,
+        /// hash of the metadata args
+        #[serde(serialize_with = "serialize_hex")]
+        data_hash: [u8; 32],
+        #[serde(serialize_with = "serialize_hex")]
+        creator_hash: [u8; 32],
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeafSchemaEvent {
+    #[serde(skip)]
+    pub event_type: BubblegumEventType,
+    #[serde(skip)]
+    pub version: Version,
+    #[serde(flatten)]
+    pub schema: LeafSchema,
+    /// the leaf written to the tree
+    #[serde(serialize_with = "serialize_hex")]
+    pub leaf_hash: [u8; 32],
+}
+
+impl LeafSchemaEvent {
+    pub fn unpack(application_data: &[u8]) -> anyhow::Result<Self> {
+        anchor::deserialize(application_data)
+    }
+
+    /// Returns the event as a [DecodedInstruction] named after the event
+    pub fn to_decoded_instruction(&self) -> anyhow::Result<DecodedInstruction> {
+        Ok(DecodedInstruction {
+            data: to_data_map(self)?,
+            name: "leafSchemaEvent".to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Decodes a noop payload logged while executing a bubblegum instruction, application
+/// data being decoded as the schema of the leaf written by the instruction
+pub fn decode_noop_event(data: &[u8]) -> anyhow::Result<DecodedInstruction> {
+    match AccountCompressionEvent::unpack(data)? {
+        AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1(
+            ApplicationDataEventV1 { application_data },
+        )) => LeafSchemaEvent::unpack(&application_data)?.to_decoded_instruction(),
+        event => event.to_decoded_instruction(),
+    }
+}
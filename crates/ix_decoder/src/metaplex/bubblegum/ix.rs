@@ -0,0 +1,178 @@
+//! Instruction types
+//!
+//! Instructions acting on an existing leaf identify it by its index in the tree along
+//! with the hashes making up the leaf, the proof is passed as remaining accounts.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    metaplex::token_metadata::state::{Collection, Creator, TokenStandard, Uses},
+    types::{serialize_hex, serialize_pubkey},
+};
+
+pub const CREATE_TREE: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+pub const MINT_V1: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+pub const MINT_TO_COLLECTION_V1: [u8; 8] = [153, 18, 178, 47, 197, 158, 86, 15];
+pub const TRANSFER: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+pub const BURN: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+pub const DELEGATE: [u8; 8] = [90, 147, 75, 178, 85, 88, 4, 137];
+pub const REDEEM: [u8; 8] = [184, 12, 86, 149, 70, 196, 97, 225];
+pub const CANCEL_REDEEM: [u8; 8] = [111, 76, 232, 50, 39, 175, 48, 242];
+pub const DECOMPRESS_V1: [u8; 8] = [54, 85, 76, 70, 228, 250, 164, 81];
+pub const VERIFY_CREATOR: [u8; 8] = [52, 17, 96, 132, 71, 4, 85, 194];
+pub const VERIFY_COLLECTION: [u8; 8] = [56, 113, 101, 253, 79, 55, 122, 169];
+pub const UNVERIFY_COLLECTION: [u8; 8] = [250, 251, 42, 106, 41, 137, 186, 168];
+pub const SET_AND_VERIFY_COLLECTION: [u8; 8] = [235, 242, 121, 216, 158, 234, 180, 234];
+pub const UPDATE_METADATA: [u8; 8] = [170, 182, 43, 239, 97, 78, 225, 186];
+pub const SET_TREE_DELEGATE: [u8; 8] = [253, 118, 66, 37, 190, 49, 154, 102];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+/// Metadata of a compressed nft
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateArgs {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub creators: Option<Vec<Creator>>,
+    pub seller_fee_basis_points: Option<u16>,
+    pub primary_sale_happened: Option<bool>,
+    pub is_mutable: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTreeInstruction {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    /// anyone may mint to a public tree
+    pub public: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeafInstruction {
+    /// root of the tree the proof was generated against
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub data_hash: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub creator_hash: [u8; 32],
+    /// nonce the asset id is derived from
+    pub nonce: u64,
+    /// index of the leaf in the tree
+    pub index: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub data_hash: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub message: MetadataArgs,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAndVerifyCollectionInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub data_hash: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub message: MetadataArgs,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub collection: Pubkey,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMetadataInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub current_metadata: MetadataArgs,
+    pub update_args: UpdateArgs,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BubblegumInstruction {
+    CreateTree(CreateTreeInstruction),
+    MintV1(MetadataArgs),
+    MintToCollectionV1(MetadataArgs),
+    Transfer(LeafInstruction),
+    Burn(LeafInstruction),
+    Delegate(LeafInstruction),
+    Redeem(LeafInstruction),
+    CancelRedeem([u8; 32]),
+    DecompressV1(MetadataArgs),
+    VerifyCreator(VerifyInstruction),
+    VerifyCollection(VerifyInstruction),
+    UnverifyCollection(VerifyInstruction),
+    SetAndVerifyCollection(SetAndVerifyCollectionInstruction),
+    UpdateMetadata(UpdateMetadataInstruction),
+    SetTreeDelegate,
+}
+
+impl BubblegumInstruction {
+    /// Unpacks a byte buffer into a [BubblegumInstruction](enum.BubblegumInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE_TREE => Self::CreateTree(anchor::deserialize(rest)?),
+            MINT_V1 => Self::MintV1(anchor::deserialize(rest)?),
+            MINT_TO_COLLECTION_V1 => Self::MintToCollectionV1(anchor::deserialize(rest)?),
+            TRANSFER => Self::Transfer(anchor::deserialize(rest)?),
+            BURN => Self::Burn(anchor::deserialize(rest)?),
+            DELEGATE => Self::Delegate(anchor::deserialize(rest)?),
+            REDEEM => Self::Redeem(anchor::deserialize(rest)?),
+            CANCEL_REDEEM => Self::CancelRedeem(anchor::deserialize(rest)?),
+            DECOMPRESS_V1 => Self::DecompressV1(anchor::deserialize(rest)?),
+            VERIFY_CREATOR => Self::VerifyCreator(anchor::deserialize(rest)?),
+            VERIFY_COLLECTION => Self::VerifyCollection(anchor::deserialize(rest)?),
+            UNVERIFY_COLLECTION => Self::UnverifyCollection(anchor::deserialize(rest)?),
+            SET_AND_VERIFY_COLLECTION => Self::SetAndVerifyCollection(anchor::deserialize(rest)?),
+            UPDATE_METADATA => Self::UpdateMetadata(anchor::deserialize(rest)?),
+            SET_TREE_DELEGATE => Self::SetTreeDelegate,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,279 @@
+//! Instruction decoding functions for the metaplex bubblegum program
+//!
+//! Compressed nfts are leaves of a concurrent merkle tree owned by the account
+//! compression program, the proof of the leaf is passed as remaining accounts and is
+//! not named. Use [state::get_asset_id] to derive the asset id of a leaf, the leaf
+//! itself is logged through the noop program and decoded by [events::decode_noop_event].
+
+use anyhow::Context;
+use ix::BubblegumInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+const LEAF_ACCOUNTS: [&str; 7] = [
+    "treeAuthority",
+    "leafOwner",
+    "leafDelegate",
+    "merkleTree",
+    "logWrapper",
+    "compressionProgram",
+    "systemProgram",
+];
+
+const COLLECTION_ACCOUNTS: [&str; 16] = [
+    "treeAuthority",
+    "leafOwner",
+    "leafDelegate",
+    "merkleTree",
+    "payer",
+    "treeDelegate",
+    "collectionAuthority",
+    "collectionAuthorityRecordPda",
+    "collectionMint",
+    "collectionMetadata",
+    "editionAccount",
+    "bubblegumSigner",
+    "logWrapper",
+    "compressionProgram",
+    "tokenMetadataProgram",
+    "systemProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BubblegumDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl BubblegumDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = BubblegumInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            BubblegumInstruction::CreateTree(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "merkleTree",
+                    "payer",
+                    "treeCreator",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "createTree".to_string(),
+            }),
+            BubblegumInstruction::MintV1(message) => Ok(DecodedInstruction {
+                data: to_data_map(&message)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "leafOwner",
+                    "leafDelegate",
+                    "merkleTree",
+                    "payer",
+                    "treeDelegate",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "mintV1".to_string(),
+            }),
+            BubblegumInstruction::MintToCollectionV1(message) => Ok(DecodedInstruction {
+                data: to_data_map(&message)?,
+                accounts: self.ix.named_accounts(&COLLECTION_ACCOUNTS),
+                name: "mintToCollectionV1".to_string(),
+            }),
+            BubblegumInstruction::Transfer(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "leafOwner",
+                    "leafDelegate",
+                    "newLeafOwner",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "transfer".to_string(),
+            }),
+            BubblegumInstruction::Burn(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LEAF_ACCOUNTS),
+                name: "burn".to_string(),
+            }),
+            BubblegumInstruction::Delegate(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "leafOwner",
+                    "previousLeafDelegate",
+                    "newLeafDelegate",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "delegate".to_string(),
+            }),
+            BubblegumInstruction::Redeem(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "leafOwner",
+                    "leafDelegate",
+                    "merkleTree",
+                    "voucher",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "redeem".to_string(),
+            }),
+            BubblegumInstruction::CancelRedeem(root) => Ok(DecodedInstruction {
+                data: [("root".to_string(), serde_json::to_value(hex::encode(root))?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "leafOwner",
+                    "merkleTree",
+                    "voucher",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "cancelRedeem".to_string(),
+            }),
+            BubblegumInstruction::DecompressV1(message) => Ok(DecodedInstruction {
+                data: to_data_map(&message)?,
+                accounts: self.ix.named_accounts(&[
+                    "voucher",
+                    "leafOwner",
+                    "tokenAccount",
+                    "mint",
+                    "mintAuthority",
+                    "metadata",
+                    "masterEdition",
+                    "systemProgram",
+                    "sysvarRent",
+                    "tokenMetadataProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "logWrapper",
+                ]),
+                name: "decompressV1".to_string(),
+            }),
+            BubblegumInstruction::VerifyCreator(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "leafOwner",
+                    "leafDelegate",
+                    "merkleTree",
+                    "payer",
+                    "creator",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                ]),
+                name: "verifyCreator".to_string(),
+            }),
+            BubblegumInstruction::VerifyCollection(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&COLLECTION_ACCOUNTS),
+                name: "verifyCollection".to_string(),
+            }),
+            BubblegumInstruction::UnverifyCollection(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&COLLECTION_ACCOUNTS),
+                name: "unverifyCollection".to_string(),
+            }),
+            BubblegumInstruction::SetAndVerifyCollection(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&COLLECTION_ACCOUNTS),
+                name: "setAndVerifyCollection".to_string(),
+            }),
+            BubblegumInstruction::UpdateMetadata(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "authority",
+                    "collectionMint",
+                    "collectionMetadata",
+                    "collectionAuthorityRecordPda",
+                    "leafOwner",
+                    "leafDelegate",
+                    "payer",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "tokenMetadataProgram",
+                    "systemProgram",
+                ]),
+                name: "updateMetadata".to_string(),
+            }),
+            BubblegumInstruction::SetTreeDelegate => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "treeAuthority",
+                    "treeCreator",
+                    "newTreeDelegate",
+                    "merkleTree",
+                    "systemProgram",
+                ]),
+                name: "setTreeDelegate".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_transfer() {
+        let tree = Pubkey::new_unique();
+        let new_leaf_owner = Pubkey::new_unique();
+        let mut data = ix::TRANSFER.to_vec();
+        data.extend([7u8; 32]);
+        data.extend([1u8; 32]);
+        data.extend([2u8; 32]);
+        data.extend(to_vec(&(42u64, 42u32)).unwrap());
+        let decoded = BubblegumDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                new_leaf_owner,
+                tree,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                // proof
+                Pubkey::new_unique(),
+            ],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "transfer");
+        assert_eq!(decoded.accounts["newLeafOwner"], new_leaf_owner.to_string());
+        assert_eq!(decoded.accounts["merkleTree"], tree.to_string());
+        assert_eq!(decoded.data["root"], hex::encode([7u8; 32]));
+        assert_eq!(decoded.data["nonce"], 42);
+    }
+}
@@ -0,0 +1,45 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [122, 245, 175, 248, 171, 34, 0, 207];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DecompressibleState {
+    Enabled,
+    Disabled,
+}
+
+/// Configuration of a merkle tree, stored at the tree authority pda
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeConfig {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub tree_creator: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub tree_delegate: Pubkey,
+    pub total_mint_capacity: u64,
+    pub num_minted: u64,
+    pub is_public: bool,
+    pub is_decompressible: DecompressibleState,
+}
+
+impl TreeConfig {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, TREE_CONFIG_DISCRIMINATOR)
+    }
+}
+
+/// Returns the id of the compressed nft minted to the tree with the given nonce
+pub fn get_asset_id(tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", tree.as_ref(), &nonce.to_le_bytes()],
+        &super::PROGRAM_ID,
+    )
+    .0
+}
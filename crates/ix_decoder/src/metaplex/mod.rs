@@ -0,0 +1,65 @@
+//! Instruction decoding functions for the metaplex programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use bubblegum::BubblegumDecoder;
+use mpl_core::CoreDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use token_metadata::TokenMetadataDecoder;
+
+pub mod bubblegum;
+pub mod mpl_core;
+pub mod token_metadata;
+
+pub static METAPLEX_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(MetaplexProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    TokenMetadata(TokenMetadataDecoder),
+    Core(CoreDecoder),
+    Bubblegum(BubblegumDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct MetaplexProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::TokenMetadata(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Metaplex::TokenMetadata"),
+            Self::Core(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Metaplex::Core"),
+            Self::Bubblegum(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Metaplex::Bubblegum"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::TokenMetadata(decoder) => format!("{:#?}", decoder),
+            Self::Core(decoder) => format!("{:#?}", decoder),
+            Self::Bubblegum(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for MetaplexProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            token_metadata::PROGRAM_ID => Some(Box::new(Programs::TokenMetadata(
+                TokenMetadataDecoder::new(ix.clone()),
+            ))),
+            mpl_core::PROGRAM_ID => Some(Box::new(Programs::Core(CoreDecoder::new(ix.clone())))),
+            bubblegum::PROGRAM_ID => Some(Box::new(Programs::Bubblegum(BubblegumDecoder::new(
+                ix.clone(),
+            )))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Instruction types
+//!
+//! Instructions are borsh encoded, the variant index being the leading byte. Plugins
+//! passed on creation and the compression proofs of compressed assets are not decoded.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use super::state::UpdateAuthority;
+use crate::anchor;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DataState {
+    AccountState,
+    LedgerState,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInstruction {
+    pub data_state: DataState,
+    pub name: String,
+    pub uri: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCollectionInstruction {
+    pub name: String,
+    pub uri: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInstruction {
+    pub new_name: Option<String>,
+    pub new_uri: Option<String>,
+    pub new_update_authority: Option<UpdateAuthority>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCollectionInstruction {
+    pub new_name: Option<String>,
+    pub new_uri: Option<String>,
+}
+
+/// Instructions supported by the core program, plugin management instructions are
+/// not supported
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoreInstruction {
+    CreateV1(CreateInstruction),
+    CreateCollectionV1(CreateCollectionInstruction),
+    BurnV1,
+    BurnCollectionV1,
+    TransferV1,
+    UpdateV1(UpdateInstruction),
+    UpdateCollectionV1(UpdateCollectionInstruction),
+    CreateV2(CreateInstruction),
+}
+
+impl CoreInstruction {
+    /// Unpacks a byte buffer into a [CoreInstruction](enum.CoreInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::CreateV1(anchor::deserialize(rest)?),
+            1 => Self::CreateCollectionV1(anchor::deserialize(rest)?),
+            12 => Self::BurnV1,
+            13 => Self::BurnCollectionV1,
+            14 => Self::TransferV1,
+            15 => Self::UpdateV1(anchor::deserialize(rest)?),
+            16 => Self::UpdateCollectionV1(anchor::deserialize(rest)?),
+            20 => Self::CreateV2(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
@@ -0,0 +1,175 @@
+//! Instruction decoding functions for the metaplex core program
+
+use anyhow::Context;
+use ix::CoreInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+const CREATE_ACCOUNTS: [&str; 8] = [
+    "asset",
+    "collection",
+    "authority",
+    "payer",
+    "owner",
+    "updateAuthority",
+    "systemProgram",
+    "logWrapper",
+];
+
+const UPDATE_ACCOUNTS: [&str; 6] = [
+    "asset",
+    "collection",
+    "payer",
+    "authority",
+    "systemProgram",
+    "logWrapper",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CoreDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl CoreDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = CoreInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            CoreInstruction::CreateV1(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CREATE_ACCOUNTS),
+                name: "createV1".to_string(),
+            }),
+            CoreInstruction::CreateCollectionV1(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "collection",
+                    "updateAuthority",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "createCollectionV1".to_string(),
+            }),
+            CoreInstruction::BurnV1 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&UPDATE_ACCOUNTS),
+                name: "burnV1".to_string(),
+                ..Default::default()
+            }),
+            CoreInstruction::BurnCollectionV1 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "collection",
+                    "payer",
+                    "authority",
+                    "logWrapper",
+                ]),
+                name: "burnCollectionV1".to_string(),
+                ..Default::default()
+            }),
+            CoreInstruction::TransferV1 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "asset",
+                    "collection",
+                    "payer",
+                    "authority",
+                    "newOwner",
+                    "systemProgram",
+                    "logWrapper",
+                ]),
+                name: "transferV1".to_string(),
+                ..Default::default()
+            }),
+            CoreInstruction::UpdateV1(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&UPDATE_ACCOUNTS),
+                name: "updateV1".to_string(),
+            }),
+            CoreInstruction::UpdateCollectionV1(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "collection",
+                    "payer",
+                    "authority",
+                    "newUpdateAuthority",
+                    "systemProgram",
+                    "logWrapper",
+                ]),
+                name: "updateCollectionV1".to_string(),
+            }),
+            CoreInstruction::CreateV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CREATE_ACCOUNTS),
+                name: "createV2".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_transfer_and_asset() {
+        let new_owner = Pubkey::new_unique();
+        let decoded = CoreDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                Pubkey::new_unique(),
+                PROGRAM_ID,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                new_owner,
+            ],
+            data: vec![14, 0],
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "transferV1");
+        assert_eq!(decoded.accounts["newOwner"], new_owner.to_string());
+
+        let mut data = vec![15, 0, 1];
+        data.extend(to_vec("https://example.com/1.json").unwrap());
+        data.extend([1, 1]);
+        data.extend(new_owner.to_bytes());
+        let decoded = CoreDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique(); 6],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "updateV1");
+        assert_eq!(decoded.data["newName"], serde_json::Value::Null);
+        assert_eq!(decoded.data["newUri"], "https://example.com/1.json");
+        assert_eq!(
+            decoded.data["newUpdateAuthority"]["address"],
+            new_owner.to_string()
+        );
+
+        let collection = Pubkey::new_unique();
+        let mut account = vec![1];
+        account.extend(new_owner.to_bytes());
+        account.push(2);
+        account.extend(collection.to_bytes());
+        account.extend(to_vec(&("Asset #1", "https://example.com/1.json", None::<u64>)).unwrap());
+        // plugin header
+        account.extend([3, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let asset = state::BaseAssetV1::unpack(&account).unwrap();
+        assert_eq!(asset.owner, new_owner);
+        assert_eq!(asset.name, "Asset #1");
+        assert_eq!(asset.collection(), Some(collection));
+        assert!(state::BaseCollectionV1::unpack(&account).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+//! Account types
+//!
+//! Assets and collections are tagged by a leading [Key], the plugins which follow the
+//! base account are not decoded.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Key {
+    Uninitialized,
+    AssetV1,
+    HashedAssetV1,
+    PluginHeaderV1,
+    PluginRegistryV1,
+    CollectionV1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateAuthority {
+    None,
+    Address(#[serde(serialize_with = "serialize_pubkey")] Pubkey),
+    /// the asset is updated by the update authority of the collection
+    Collection(#[serde(serialize_with = "serialize_pubkey")] Pubkey),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseAssetV1 {
+    pub key: Key,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    pub update_authority: UpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    /// set once the asset has been compressed
+    pub seq: Option<u64>,
+}
+
+impl BaseAssetV1 {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let asset: Self = anchor::deserialize(data)?;
+        if asset.key != Key::AssetV1 {
+            return Err(anyhow!("account is not an asset"));
+        }
+        Ok(asset)
+    }
+
+    /// Returns the collection of the asset, if any
+    pub fn collection(&self) -> Option<Pubkey> {
+        match self.update_authority {
+            UpdateAuthority::Collection(collection) => Some(collection),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseCollectionV1 {
+    pub key: Key,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub uri: String,
+    pub num_minted: u32,
+    pub current_size: u32,
+}
+
+impl BaseCollectionV1 {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let collection: Self = anchor::deserialize(data)?;
+        if collection.key != Key::CollectionV1 {
+            return Err(anyhow!("account is not a collection"));
+        }
+        Ok(collection)
+    }
+}
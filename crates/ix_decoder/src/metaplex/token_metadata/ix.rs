@@ -0,0 +1,338 @@
+//! Instruction types
+//!
+//! Instructions are borsh encoded, the variant index being the leading byte. The
+//! authorization data which trails the arguments of programmable nft instructions is
+//! not decoded.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::state::{Collection, CollectionDetails, Creator, Data, DataV2, TokenStandard, Uses};
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMetadataAccountV2Instruction {
+    pub data: Option<DataV2>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub update_authority: Option<Pubkey>,
+    pub primary_sale_happened: Option<bool>,
+    pub is_mutable: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMetadataAccountV2Instruction {
+    pub data: DataV2,
+    pub is_mutable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMetadataAccountV3Instruction {
+    pub data: DataV2,
+    pub is_mutable: bool,
+    /// set when the mint is a collection nft
+    pub collection_details: Option<CollectionDetails>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMasterEditionInstruction {
+    /// maximum number of prints, unlimited when not set
+    pub max_supply: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintNewEditionInstruction {
+    pub edition: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCollectionSizeInstruction {
+    pub size: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrintSupply {
+    Zero,
+    Limited(u64),
+    Unlimited,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetData {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub token_standard: TokenStandard,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub collection_details: Option<CollectionDetails>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub rule_set: Option<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CreateArgs {
+    #[serde(rename_all = "camelCase")]
+    V1 {
+        asset_data: AssetData,
+        decimals: Option<u8>,
+        print_supply: Option<PrintSupply>,
+    },
+}
+
+/// Arguments of the instructions whose only versioned argument is an amount
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AmountArgs {
+    V1 { amount: u64 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegateArgs {
+    CollectionV1,
+    SaleV1 {
+        amount: u64,
+    },
+    TransferV1 {
+        amount: u64,
+    },
+    DataV1,
+    UtilityV1 {
+        amount: u64,
+    },
+    StakingV1 {
+        amount: u64,
+    },
+    StandardV1 {
+        amount: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    LockedTransferV1 {
+        amount: u64,
+        /// the only address the token can be transferred to
+        #[serde(serialize_with = "serialize_pubkey")]
+        locked_address: Pubkey,
+    },
+    ProgrammableConfigV1,
+    AuthorityItemV1,
+    DataItemV1,
+    CollectionItemV1,
+    ProgrammableConfigItemV1,
+    PrintDelegateV1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RevokeArgs {
+    CollectionV1,
+    SaleV1,
+    TransferV1,
+    DataV1,
+    UtilityV1,
+    StakingV1,
+    StandardV1,
+    LockedTransferV1,
+    ProgrammableConfigV1,
+    MigrationV1,
+    AuthorityItemV1,
+    DataItemV1,
+    CollectionItemV1,
+    ProgrammableConfigItemV1,
+    PrintDelegateV1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationArgs {
+    CreatorV1,
+    CollectionV1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CollectionToggle {
+    None,
+    Clear,
+    Set(Collection),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CollectionDetailsToggle {
+    None,
+    Clear,
+    Set(CollectionDetails),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UsesToggle {
+    None,
+    Clear,
+    Set(Uses),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleSetToggle {
+    None,
+    Clear,
+    Set(#[serde(serialize_with = "serialize_pubkey")] Pubkey),
+}
+
+/// Update arguments, the variant depends on the authority performing the update
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateArgs {
+    #[serde(rename_all = "camelCase")]
+    V1 {
+        #[serde(serialize_with = "serialize_option_pubkey")]
+        new_update_authority: Option<Pubkey>,
+        data: Option<Data>,
+        primary_sale_happened: Option<bool>,
+        is_mutable: Option<bool>,
+        collection: CollectionToggle,
+        collection_details: CollectionDetailsToggle,
+        uses: UsesToggle,
+        rule_set: RuleSetToggle,
+    },
+    #[serde(rename_all = "camelCase")]
+    AsUpdateAuthorityV2 {
+        #[serde(serialize_with = "serialize_option_pubkey")]
+        new_update_authority: Option<Pubkey>,
+        data: Option<Data>,
+        primary_sale_happened: Option<bool>,
+        is_mutable: Option<bool>,
+        collection: CollectionToggle,
+        collection_details: CollectionDetailsToggle,
+        uses: UsesToggle,
+        rule_set: RuleSetToggle,
+        token_standard: Option<TokenStandard>,
+    },
+    #[serde(rename_all = "camelCase")]
+    AsAuthorityItemDelegateV2 {
+        #[serde(serialize_with = "serialize_option_pubkey")]
+        new_update_authority: Option<Pubkey>,
+        primary_sale_happened: Option<bool>,
+        is_mutable: Option<bool>,
+        token_standard: Option<TokenStandard>,
+    },
+    AsCollectionDelegateV2 {
+        collection: CollectionToggle,
+    },
+    AsDataDelegateV2 {
+        data: Option<Data>,
+    },
+    #[serde(rename_all = "camelCase")]
+    AsProgrammableConfigDelegateV2 {
+        rule_set: RuleSetToggle,
+    },
+    AsDataItemDelegateV2 {
+        data: Option<Data>,
+    },
+    AsCollectionItemDelegateV2 {
+        collection: CollectionToggle,
+    },
+    #[serde(rename_all = "camelCase")]
+    AsProgrammableConfigItemDelegateV2 {
+        rule_set: RuleSetToggle,
+    },
+}
+
+/// Instructions supported by the token metadata program, deprecated instructions
+/// are not supported
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenMetadataInstruction {
+    UpdatePrimarySaleHappenedViaToken,
+    SignMetadata,
+    MintNewEditionFromMasterEditionViaToken(MintNewEditionInstruction),
+    UpdateMetadataAccountV2(UpdateMetadataAccountV2Instruction),
+    CreateMetadataAccountV2(CreateMetadataAccountV2Instruction),
+    CreateMasterEditionV3(CreateMasterEditionInstruction),
+    VerifyCollection,
+    UnverifyCollection,
+    ApproveCollectionAuthority,
+    RevokeCollectionAuthority,
+    SetAndVerifyCollection,
+    FreezeDelegatedAccount,
+    ThawDelegatedAccount,
+    RemoveCreatorVerification,
+    BurnNft,
+    VerifySizedCollectionItem,
+    UnverifySizedCollectionItem,
+    SetAndVerifySizedCollectionItem,
+    CreateMetadataAccountV3(CreateMetadataAccountV3Instruction),
+    SetCollectionSize(SetCollectionSizeInstruction),
+    Burn(AmountArgs),
+    Create(CreateArgs),
+    Mint(AmountArgs),
+    Delegate(DelegateArgs),
+    Revoke(RevokeArgs),
+    Lock,
+    Unlock,
+    Transfer(AmountArgs),
+    Update(UpdateArgs),
+    Verify(VerificationArgs),
+    Unverify(VerificationArgs),
+}
+
+impl TokenMetadataInstruction {
+    /// Unpacks a byte buffer into a [TokenMetadataInstruction](enum.TokenMetadataInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            4 => Self::UpdatePrimarySaleHappenedViaToken,
+            7 => Self::SignMetadata,
+            11 => Self::MintNewEditionFromMasterEditionViaToken(anchor::deserialize(rest)?),
+            15 => Self::UpdateMetadataAccountV2(anchor::deserialize(rest)?),
+            16 => Self::CreateMetadataAccountV2(anchor::deserialize(rest)?),
+            17 => Self::CreateMasterEditionV3(anchor::deserialize(rest)?),
+            18 => Self::VerifyCollection,
+            22 => Self::UnverifyCollection,
+            23 => Self::ApproveCollectionAuthority,
+            24 => Self::RevokeCollectionAuthority,
+            25 => Self::SetAndVerifyCollection,
+            26 => Self::FreezeDelegatedAccount,
+            27 => Self::ThawDelegatedAccount,
+            28 => Self::RemoveCreatorVerification,
+            29 => Self::BurnNft,
+            30 => Self::VerifySizedCollectionItem,
+            31 => Self::UnverifySizedCollectionItem,
+            32 => Self::SetAndVerifySizedCollectionItem,
+            33 => Self::CreateMetadataAccountV3(anchor::deserialize(rest)?),
+            34 => Self::SetCollectionSize(anchor::deserialize(rest)?),
+            41 => Self::Burn(anchor::deserialize(rest)?),
+            42 => Self::Create(anchor::deserialize(rest)?),
+            43 => Self::Mint(anchor::deserialize(rest)?),
+            44 => Self::Delegate(anchor::deserialize(rest)?),
+            45 => Self::Revoke(anchor::deserialize(rest)?),
+            46 => Self::Lock,
+            47 => Self::Unlock,
+            49 => Self::Transfer(anchor::deserialize(rest)?),
+            50 => Self::Update(anchor::deserialize(rest)?),
+            52 => Self::Verify(anchor::deserialize(rest)?),
+            53 => Self::Unverify(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
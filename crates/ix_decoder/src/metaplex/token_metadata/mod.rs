@@ -0,0 +1,480 @@
+//! Instruction decoding functions for the metaplex token metadata program
+//!
+//! Optional accounts which aren't used are passed as the program id.
+
+use anyhow::Context;
+use ix::TokenMetadataInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const CREATE_METADATA_ACCOUNTS: [&str; 7] = [
+    "metadata",
+    "mint",
+    "mintAuthority",
+    "payer",
+    "updateAuthority",
+    "systemProgram",
+    "rent",
+];
+
+const VERIFY_COLLECTION_ACCOUNTS: [&str; 7] = [
+    "metadata",
+    "collectionAuthority",
+    "payer",
+    "collectionMint",
+    "collection",
+    "collectionMasterEditionAccount",
+    "collectionAuthorityRecord",
+];
+
+const SET_AND_VERIFY_COLLECTION_ACCOUNTS: [&str; 8] = [
+    "metadata",
+    "collectionAuthority",
+    "payer",
+    "updateAuthority",
+    "collectionMint",
+    "collection",
+    "collectionMasterEditionAccount",
+    "collectionAuthorityRecord",
+];
+
+const FREEZE_DELEGATED_ACCOUNTS: [&str; 5] = [
+    "delegate",
+    "tokenAccount",
+    "edition",
+    "mint",
+    "tokenProgram",
+];
+
+const DELEGATE_ACCOUNTS: [&str; 14] = [
+    "delegateRecord",
+    "delegate",
+    "metadata",
+    "masterEdition",
+    "tokenRecord",
+    "mint",
+    "token",
+    "authority",
+    "payer",
+    "systemProgram",
+    "sysvarInstructions",
+    "splTokenProgram",
+    "authorizationRulesProgram",
+    "authorizationRules",
+];
+
+const LOCK_ACCOUNTS: [&str; 13] = [
+    "authority",
+    "tokenOwner",
+    "token",
+    "mint",
+    "metadata",
+    "edition",
+    "tokenRecord",
+    "payer",
+    "systemProgram",
+    "sysvarInstructions",
+    "splTokenProgram",
+    "authorizationRulesProgram",
+    "authorizationRules",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TokenMetadataDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl TokenMetadataDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = TokenMetadataInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            TokenMetadataInstruction::UpdatePrimarySaleHappenedViaToken => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["metadata", "owner", "token"]),
+                name: "updatePrimarySaleHappenedViaToken".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::SignMetadata => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["metadata", "creator"]),
+                name: "signMetadata".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::MintNewEditionFromMasterEditionViaToken(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "newMetadata",
+                        "newEdition",
+                        "masterEdition",
+                        "newMint",
+                        "editionMarkPda",
+                        "newMintAuthority",
+                        "payer",
+                        "tokenAccountOwner",
+                        "tokenAccount",
+                        "newMetadataUpdateAuthority",
+                        "metadata",
+                        "tokenProgram",
+                        "systemProgram",
+                        "rent",
+                    ]),
+                    name: "mintNewEditionFromMasterEditionViaToken".to_string(),
+                })
+            }
+            TokenMetadataInstruction::UpdateMetadataAccountV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&["metadata", "updateAuthority"]),
+                name: "updateMetadataAccountV2".to_string(),
+            }),
+            TokenMetadataInstruction::CreateMetadataAccountV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CREATE_METADATA_ACCOUNTS),
+                name: "createMetadataAccountV2".to_string(),
+            }),
+            TokenMetadataInstruction::CreateMasterEditionV3(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "edition",
+                    "mint",
+                    "updateAuthority",
+                    "mintAuthority",
+                    "payer",
+                    "metadata",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "createMasterEditionV3".to_string(),
+            }),
+            TokenMetadataInstruction::VerifyCollection => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&VERIFY_COLLECTION_ACCOUNTS),
+                name: "verifyCollection".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::UnverifyCollection => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "metadata",
+                    "collectionAuthority",
+                    "collectionMint",
+                    "collection",
+                    "collectionMasterEditionAccount",
+                    "collectionAuthorityRecord",
+                ]),
+                name: "unverifyCollection".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::ApproveCollectionAuthority => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "collectionAuthorityRecord",
+                    "newCollectionAuthority",
+                    "updateAuthority",
+                    "payer",
+                    "metadata",
+                    "mint",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "approveCollectionAuthority".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::RevokeCollectionAuthority => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "collectionAuthorityRecord",
+                    "delegateAuthority",
+                    "revokeAuthority",
+                    "metadata",
+                    "mint",
+                ]),
+                name: "revokeCollectionAuthority".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::SetAndVerifyCollection => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&SET_AND_VERIFY_COLLECTION_ACCOUNTS),
+                name: "setAndVerifyCollection".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::FreezeDelegatedAccount => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&FREEZE_DELEGATED_ACCOUNTS),
+                name: "freezeDelegatedAccount".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::ThawDelegatedAccount => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&FREEZE_DELEGATED_ACCOUNTS),
+                name: "thawDelegatedAccount".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::RemoveCreatorVerification => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["metadata", "creator"]),
+                name: "removeCreatorVerification".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::BurnNft => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "metadata",
+                    "owner",
+                    "mint",
+                    "tokenAccount",
+                    "masterEditionAccount",
+                    "splTokenProgram",
+                    "collectionMetadata",
+                ]),
+                name: "burnNft".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::VerifySizedCollectionItem => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&VERIFY_COLLECTION_ACCOUNTS),
+                name: "verifySizedCollectionItem".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::UnverifySizedCollectionItem => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&VERIFY_COLLECTION_ACCOUNTS),
+                name: "unverifySizedCollectionItem".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::SetAndVerifySizedCollectionItem => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&SET_AND_VERIFY_COLLECTION_ACCOUNTS),
+                name: "setAndVerifySizedCollectionItem".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::CreateMetadataAccountV3(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CREATE_METADATA_ACCOUNTS),
+                name: "createMetadataAccountV3".to_string(),
+            }),
+            TokenMetadataInstruction::SetCollectionSize(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "collectionMetadata",
+                    "collectionAuthority",
+                    "collectionMint",
+                    "collectionAuthorityRecord",
+                ]),
+                name: "setCollectionSize".to_string(),
+            }),
+            TokenMetadataInstruction::Burn(args) => Ok(DecodedInstruction {
+                data: to_data_map(&args)?,
+                accounts: self.ix.named_accounts(&[
+                    "authority",
+                    "collectionMetadata",
+                    "metadata",
+                    "edition",
+                    "mint",
+                    "token",
+                    "masterEdition",
+                    "masterEditionMint",
+                    "masterEditionToken",
+                    "editionMarker",
+                    "tokenRecord",
+                    "systemProgram",
+                    "sysvarInstructions",
+                    "splTokenProgram",
+                ]),
+                name: "burn".to_string(),
+            }),
+            TokenMetadataInstruction::Create(args) => Ok(DecodedInstruction {
+                data: to_data_map(&args)?,
+                accounts: self.ix.named_accounts(&[
+                    "metadata",
+                    "masterEdition",
+                    "mint",
+                    "authority",
+                    "payer",
+                    "updateAuthority",
+                    "systemProgram",
+                    "sysvarInstructions",
+                    "splTokenProgram",
+                ]),
+                name: "create".to_string(),
+            }),
+            TokenMetadataInstruction::Mint(args) => Ok(DecodedInstruction {
+                data: to_data_map(&args)?,
+                accounts: self.ix.named_accounts(&[
+                    "token",
+                    "tokenOwner",
+                    "metadata",
+                    "masterEdition",
+                    "tokenRecord",
+                    "mint",
+                    "authority",
+                    "delegateRecord",
+                    "payer",
+                    "systemProgram",
+                    "sysvarInstructions",
+                    "splTokenProgram",
+                    "splAtaProgram",
+                    "authorizationRulesProgram",
+                    "authorizationRules",
+                ]),
+                name: "mint".to_string(),
+            }),
+            TokenMetadataInstruction::Delegate(args) => Ok(DecodedInstruction {
+                data: [("args".to_string(), serde_json::to_value(args)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&DELEGATE_ACCOUNTS),
+                name: "delegate".to_string(),
+            }),
+            TokenMetadataInstruction::Revoke(args) => Ok(DecodedInstruction {
+                data: [("args".to_string(), serde_json::to_value(args)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&DELEGATE_ACCOUNTS),
+                name: "revoke".to_string(),
+            }),
+            TokenMetadataInstruction::Lock => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&LOCK_ACCOUNTS),
+                name: "lock".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::Unlock => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&LOCK_ACCOUNTS),
+                name: "unlock".to_string(),
+                ..Default::default()
+            }),
+            TokenMetadataInstruction::Transfer(args) => Ok(DecodedInstruction {
+                data: to_data_map(&args)?,
+                accounts: self.ix.named_accounts(&[
+                    "token",
+                    "tokenOwner",
+                    "destination",
+                    "destinationOwner",
+                    "mint",
+                    "metadata",
+                    "edition",
+                    "ownerTokenRecord",
+                    "destinationTokenRecord",
+                    "authority",
+                    "payer",
+                    "systemProgram",
+                    "sysvarInstructions",
+                    "splTokenProgram",
+                    "splAtaProgram",
+                    "authorizationRulesProgram",
+                    "authorizationRules",
+                ]),
+                name: "transfer".to_string(),
+            }),
+            TokenMetadataInstruction::Update(args) => Ok(DecodedInstruction {
+                data: to_data_map(&args)?,
+                accounts: self.ix.named_accounts(&[
+                    "authority",
+                    "delegateRecord",
+                    "token",
+                    "mint",
+                    "metadata",
+                    "edition",
+                    "payer",
+                    "systemProgram",
+                    "sysvarInstructions",
+                    "authorizationRulesProgram",
+                    "authorizationRules",
+                ]),
+                name: "update".to_string(),
+            }),
+            TokenMetadataInstruction::Verify(args) => Ok(DecodedInstruction {
+                data: [("args".to_string(), serde_json::to_value(args)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "authority",
+                    "delegateRecord",
+                    "metadata",
+                    "collectionMint",
+                    "collectionMetadata",
+                    "collectionMasterEdition",
+                    "systemProgram",
+                    "sysvarInstructions",
+                ]),
+                name: "verify".to_string(),
+            }),
+            TokenMetadataInstruction::Unverify(args) => Ok(DecodedInstruction {
+                data: [("args".to_string(), serde_json::to_value(args)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "authority",
+                    "delegateRecord",
+                    "metadata",
+                    "collectionMint",
+                    "collectionMetadata",
+                    "systemProgram",
+                    "sysvarInstructions",
+                ]),
+                name: "unverify".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_update_and_metadata() {
+        let new_update_authority = Pubkey::new_unique();
+        let mut data = vec![15, 1];
+        data.extend(to_vec(&("Drainer".to_string(), "FREE".to_string())).unwrap());
+        data.extend(to_vec(&("https://claim.example".to_string(), 0u16)).unwrap());
+        data.extend([0, 0, 0, 1]);
+        data.extend(new_update_authority.to_bytes());
+        data.extend([0, 0]);
+        let metadata = Pubkey::new_unique();
+        let decoded = TokenMetadataDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![metadata, Pubkey::new_unique()],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "updateMetadataAccountV2");
+        assert_eq!(decoded.accounts["metadata"], metadata.to_string());
+        assert_eq!(decoded.data["data"]["uri"], "https://claim.example");
+        assert_eq!(
+            decoded.data["updateAuthority"],
+            new_update_authority.to_string()
+        );
+        assert_eq!(decoded.data["isMutable"], serde_json::Value::Null);
+
+        // names are padded to their maximum length, and accounts created before the
+        // optional fields were added end after `is_mutable`
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let mut account = vec![4];
+        account.extend(Pubkey::new_unique().to_bytes());
+        account.extend(mint.to_bytes());
+        account.extend(to_vec(&format!("{:\0<32}", "Mad Lad #42")).unwrap());
+        account.extend(to_vec(&format!("{:\0<10}", "MAD")).unwrap());
+        account.extend(to_vec(&format!("{:\0<200}", "https://madlads.example/42.json")).unwrap());
+        account.extend(to_vec(&(500u16, Some(vec![(creator, true, 100u8)]))).unwrap());
+        account.extend([1, 1]);
+        let metadata = state::Metadata::unpack(&account).unwrap();
+        assert_eq!(metadata.mint, mint);
+        assert_eq!(metadata.name, "Mad Lad #42");
+        assert_eq!(metadata.symbol, "MAD");
+        assert_eq!(metadata.uri, "https://madlads.example/42.json");
+        assert_eq!(metadata.creators.unwrap()[0].address, creator);
+        assert_eq!(metadata.token_standard, None);
+
+        account.extend([1, 255, 1, 4, 1, 1]);
+        account.extend(Pubkey::new_unique().to_bytes());
+        let metadata = state::Metadata::unpack(&account).unwrap();
+        assert_eq!(metadata.edition_nonce, Some(255));
+        assert_eq!(
+            metadata.token_standard,
+            Some(state::TokenStandard::ProgrammableNonFungible)
+        );
+        assert!(metadata.collection.unwrap().verified);
+    }
+}
@@ -0,0 +1,189 @@
+//! Account types, along with the metadata types shared with the instructions
+//!
+//! Accounts are tagged by a leading [Key] rather than an anchor discriminator.
+
+use anyhow::{anyhow, Context};
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{serialize_option_pubkey, serialize_pubkey};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Key {
+    Uninitialized,
+    EditionV1,
+    MasterEditionV1,
+    ReservationListV1,
+    MetadataV1,
+    ReservationListV2,
+    MasterEditionV2,
+    EditionMarker,
+    UseAuthorityRecord,
+    CollectionAuthorityRecord,
+    TokenOwnedEscrow,
+    TokenRecord,
+    MetadataDelegate,
+    EditionMarkerV2,
+    HolderDelegate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+    ProgrammableNonFungible,
+    ProgrammableNonFungibleEdition,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Creator {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub address: Pubkey,
+    pub verified: bool,
+    /// percentage of the royalties paid to the creator
+    pub share: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    pub verified: bool,
+    /// mint of the collection nft
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub key: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CollectionDetails {
+    V1 {
+        size: u64,
+    },
+    V2 {
+        #[serde(skip)]
+        padding: [u8; 8],
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgrammableConfig {
+    V1 {
+        /// token auth rule set checked on transfers
+        #[serde(serialize_with = "serialize_option_pubkey")]
+        rule_set: Option<Pubkey>,
+    },
+}
+
+/// Metadata of a legacy create or update
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Data {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// royalties paid to the creators on secondary sales
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+/// Metadata of a mint, its name, symbol and uri have their null padding removed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub update_authority: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub collection_details: Option<CollectionDetails>,
+    pub programmable_config: Option<ProgrammableConfig>,
+}
+
+/// Deserializes a field added by a later program version, which older accounts may lack
+fn optional_field<T: BorshDeserialize>(input: &mut &[u8]) -> Option<T> {
+    Option::<T>::deserialize(input).ok().flatten()
+}
+
+impl Metadata {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let mut input = data;
+        let key = Key::deserialize(&mut input).with_context(|| "failed to deserialize key")?;
+        if key != Key::MetadataV1 {
+            return Err(anyhow!("account is not a metadata account"));
+        }
+        let (update_authority, mint, data, primary_sale_happened, is_mutable) =
+            <(Pubkey, Pubkey, Data, bool, bool)>::deserialize(&mut input)
+                .with_context(|| "failed to deserialize metadata")?;
+        Ok(Self {
+            update_authority,
+            mint,
+            name: data.name.trim_end_matches('\0').to_string(),
+            symbol: data.symbol.trim_end_matches('\0').to_string(),
+            uri: data.uri.trim_end_matches('\0').to_string(),
+            seller_fee_basis_points: data.seller_fee_basis_points,
+            creators: data.creators,
+            primary_sale_happened,
+            is_mutable,
+            edition_nonce: optional_field(&mut input),
+            token_standard: optional_field(&mut input),
+            collection: optional_field(&mut input),
+            uses: optional_field(&mut input),
+            collection_details: optional_field(&mut input),
+            programmable_config: optional_field(&mut input),
+        })
+    }
+}
+
+/// Returns the address of the metadata account of a mint
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", super::PROGRAM_ID.as_ref(), mint.as_ref()],
+        &super::PROGRAM_ID,
+    )
+    .0
+}
@@ -0,0 +1,87 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{anchor, types::serialize_u128};
+
+pub const INITIALIZE_POOL: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+pub const INITIALIZE_CUSTOMIZABLE_POOL: [u8; 8] = [20, 161, 241, 24, 189, 221, 180, 2];
+pub const ADD_LIQUIDITY: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+pub const REMOVE_LIQUIDITY: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+pub const REMOVE_ALL_LIQUIDITY: [u8; 8] = [10, 51, 61, 35, 112, 105, 24, 85];
+pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const CREATE_POSITION: [u8; 8] = [48, 215, 197, 153, 96, 203, 180, 133];
+pub const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+pub const CLAIM_POSITION_FEE: [u8; 8] = [180, 38, 154, 17, 133, 33, 162, 211];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePoolInstruction {
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub sqrt_price: u128,
+    pub activation_point: Option<u64>,
+}
+
+/// Arguments of both the add and remove liquidity instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyLiquidityInstruction {
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity_delta: u128,
+    pub token_a_amount_threshold: u64,
+    pub token_b_amount_threshold: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveAllLiquidityInstruction {
+    pub token_a_amount_threshold: u64,
+    pub token_b_amount_threshold: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstruction {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+/// Instructions supported by the damm v2 program.
+///
+/// The fee parameters of customizable pools have changed layout between
+/// program versions, so only the instruction's accounts are decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DammV2Instruction {
+    InitializePool(InitializePoolInstruction),
+    InitializeCustomizablePool,
+    AddLiquidity(ModifyLiquidityInstruction),
+    RemoveLiquidity(ModifyLiquidityInstruction),
+    RemoveAllLiquidity(RemoveAllLiquidityInstruction),
+    Swap(SwapInstruction),
+    CreatePosition,
+    ClosePosition,
+    ClaimPositionFee,
+}
+
+impl DammV2Instruction {
+    /// Unpacks a byte buffer into a [DammV2Instruction](enum.DammV2Instruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_POOL => Self::InitializePool(anchor::deserialize(rest)?),
+            INITIALIZE_CUSTOMIZABLE_POOL => Self::InitializeCustomizablePool,
+            ADD_LIQUIDITY => Self::AddLiquidity(anchor::deserialize(rest)?),
+            REMOVE_LIQUIDITY => Self::RemoveLiquidity(anchor::deserialize(rest)?),
+            REMOVE_ALL_LIQUIDITY => Self::RemoveAllLiquidity(anchor::deserialize(rest)?),
+            SWAP => Self::Swap(anchor::deserialize(rest)?),
+            CREATE_POSITION => Self::CreatePosition,
+            CLOSE_POSITION => Self::ClosePosition,
+            CLAIM_POSITION_FEE => Self::ClaimPositionFee,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
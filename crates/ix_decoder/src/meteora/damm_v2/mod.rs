@@ -0,0 +1,250 @@
+//! Instruction decoding functions for the meteora damm v2 (cp-amm) program
+
+use anyhow::Context;
+use ix::DammV2Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+const ADD_LIQUIDITY_ACCOUNTS: [&str; 14] = [
+    "pool",
+    "position",
+    "tokenAAccount",
+    "tokenBAccount",
+    "tokenAVault",
+    "tokenBVault",
+    "tokenAMint",
+    "tokenBMint",
+    "positionNftAccount",
+    "owner",
+    "tokenAProgram",
+    "tokenBProgram",
+    "eventAuthority",
+    "program",
+];
+
+/// shared by the remove liquidity and fee claim instructions
+const WITHDRAW_ACCOUNTS: [&str; 15] = [
+    "poolAuthority",
+    "pool",
+    "position",
+    "tokenAAccount",
+    "tokenBAccount",
+    "tokenAVault",
+    "tokenBVault",
+    "tokenAMint",
+    "tokenBMint",
+    "positionNftAccount",
+    "owner",
+    "tokenAProgram",
+    "tokenBProgram",
+    "eventAuthority",
+    "program",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DammV2Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DammV2Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DammV2Instruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DammV2Instruction::InitializePool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "creator",
+                    "positionNftMint",
+                    "positionNftAccount",
+                    "payer",
+                    "config",
+                    "poolAuthority",
+                    "pool",
+                    "position",
+                    "tokenAMint",
+                    "tokenBMint",
+                    "tokenAVault",
+                    "tokenBVault",
+                    "payerTokenA",
+                    "payerTokenB",
+                    "tokenAProgram",
+                    "tokenBProgram",
+                    "token2022Program",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializePool".to_string(),
+            }),
+            DammV2Instruction::InitializeCustomizablePool => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "creator",
+                    "positionNftMint",
+                    "positionNftAccount",
+                    "payer",
+                    "poolAuthority",
+                    "pool",
+                    "position",
+                    "tokenAMint",
+                    "tokenBMint",
+                    "tokenAVault",
+                    "tokenBVault",
+                    "payerTokenA",
+                    "payerTokenB",
+                    "tokenAProgram",
+                    "tokenBProgram",
+                    "token2022Program",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializeCustomizablePool".to_string(),
+                ..Default::default()
+            }),
+            DammV2Instruction::AddLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ADD_LIQUIDITY_ACCOUNTS),
+                name: "addLiquidity".to_string(),
+            }),
+            DammV2Instruction::RemoveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_ACCOUNTS),
+                name: "removeLiquidity".to_string(),
+            }),
+            DammV2Instruction::RemoveAllLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_ACCOUNTS),
+                name: "removeAllLiquidity".to_string(),
+            }),
+            DammV2Instruction::Swap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "poolAuthority",
+                    "pool",
+                    "inputTokenAccount",
+                    "outputTokenAccount",
+                    "tokenAVault",
+                    "tokenBVault",
+                    "tokenAMint",
+                    "tokenBMint",
+                    "payer",
+                    "tokenAProgram",
+                    "tokenBProgram",
+                    "referralTokenAccount",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "swap".to_string(),
+            }),
+            DammV2Instruction::CreatePosition => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "positionNftMint",
+                    "positionNftAccount",
+                    "pool",
+                    "position",
+                    "poolAuthority",
+                    "payer",
+                    "tokenProgram",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "createPosition".to_string(),
+                ..Default::default()
+            }),
+            DammV2Instruction::ClosePosition => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "positionNftMint",
+                    "positionNftAccount",
+                    "pool",
+                    "position",
+                    "poolAuthority",
+                    "rentReceiver",
+                    "owner",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "closePosition".to_string(),
+                ..Default::default()
+            }),
+            DammV2Instruction::ClaimPositionFee => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&WITHDRAW_ACCOUNTS),
+                name: "claimPositionFee".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_swap_and_modify_liquidity() {
+        let decode = |data: Vec<u8>, accounts: &[Pubkey]| {
+            DammV2Decoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: accounts.to_vec(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        let accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+        assert_eq!(ix::SWAP, anchor::instruction_discriminator("swap"));
+        let mut data = ix::SWAP.to_vec();
+        data.extend(to_vec(&(2_500_000u64, 2_400_000u64)).unwrap());
+        let decoded = decode(data, &accounts[..14]);
+        assert_eq!(decoded.name, "swap");
+        assert_eq!(decoded.data["amountIn"], 2_500_000);
+        assert_eq!(decoded.data["minimumAmountOut"], 2_400_000);
+        assert_eq!(
+            decoded.accounts["inputTokenAccount"],
+            accounts[2].to_string()
+        );
+        assert_eq!(
+            decoded.accounts["referralTokenAccount"],
+            accounts[11].to_string()
+        );
+
+        assert_eq!(
+            ix::ADD_LIQUIDITY,
+            anchor::instruction_discriminator("add_liquidity")
+        );
+        let mut data = ix::ADD_LIQUIDITY.to_vec();
+        data.extend(to_vec(&(1u128 << 70, 1_000u64, 2_000u64)).unwrap());
+        let decoded = decode(data, &accounts[..14]);
+        assert_eq!(decoded.name, "addLiquidity");
+        assert_eq!(decoded.data["liquidityDelta"], (1u128 << 70).to_string());
+        assert_eq!(decoded.data["tokenAAmountThreshold"], 1_000);
+        assert_eq!(decoded.accounts["position"], accounts[1].to_string());
+        assert_eq!(decoded.accounts["owner"], accounts[9].to_string());
+
+        assert_eq!(
+            ix::REMOVE_LIQUIDITY,
+            anchor::instruction_discriminator("remove_liquidity")
+        );
+        let mut data = ix::REMOVE_LIQUIDITY.to_vec();
+        data.extend(to_vec(&(1u128 << 70, 900u64, 1_900u64)).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "removeLiquidity");
+        assert_eq!(decoded.data["tokenBAmountThreshold"], 1_900);
+        assert_eq!(decoded.accounts["poolAuthority"], accounts[0].to_string());
+        assert_eq!(decoded.accounts["owner"], accounts[10].to_string());
+    }
+}
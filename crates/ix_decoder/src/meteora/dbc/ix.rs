@@ -0,0 +1,87 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN: [u8; 8] = [140, 85, 215, 176, 102, 54, 104, 79];
+pub const INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022: [u8; 8] = [169, 118, 51, 78, 145, 110, 220, 155];
+pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const SWAP2: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+pub const MIGRATE_METEORA_DAMM: [u8; 8] = [27, 1, 48, 22, 180, 63, 118, 217];
+pub const MIGRATION_DAMM_V2: [u8; 8] = [156, 169, 230, 103, 53, 228, 80, 64];
+pub const MIGRATION_METEORA_DAMM_CREATE_METADATA: [u8; 8] = [47, 94, 126, 115, 221, 226, 194, 133];
+pub const MIGRATION_DAMM_V2_CREATE_METADATA: [u8; 8] = [109, 189, 19, 36, 195, 183, 222, 82];
+
+/// Arguments of both the spl token and token-2022 virtual pool initialization
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeVirtualPoolInstruction {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstruction {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SwapMode {
+    #[default]
+    ExactIn,
+    PartialFill,
+    ExactOut,
+}
+
+/// The meaning of the amounts depends on the swap mode, for exact in and partial
+/// fill swaps they are the amount in and minimum amount out, for exact out swaps
+/// they are the amount out and maximum amount in
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Swap2Instruction {
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub swap_mode: SwapMode,
+}
+
+/// Instructions supported by the dynamic bonding curve program.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DbcInstruction {
+    InitializeVirtualPoolWithSplToken(InitializeVirtualPoolInstruction),
+    InitializeVirtualPoolWithToken2022(InitializeVirtualPoolInstruction),
+    Swap(SwapInstruction),
+    Swap2(Swap2Instruction),
+    MigrateMeteoraDamm,
+    MigrationDammV2,
+    MigrationMeteoraDammCreateMetadata,
+    MigrationDammV2CreateMetadata,
+}
+
+impl DbcInstruction {
+    /// Unpacks a byte buffer into a [DbcInstruction](enum.DbcInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN => {
+                Self::InitializeVirtualPoolWithSplToken(anchor::deserialize(rest)?)
+            }
+            INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022 => {
+                Self::InitializeVirtualPoolWithToken2022(anchor::deserialize(rest)?)
+            }
+            SWAP => Self::Swap(anchor::deserialize(rest)?),
+            SWAP2 => Self::Swap2(anchor::deserialize(rest)?),
+            MIGRATE_METEORA_DAMM => Self::MigrateMeteoraDamm,
+            MIGRATION_DAMM_V2 => Self::MigrationDammV2,
+            MIGRATION_METEORA_DAMM_CREATE_METADATA => Self::MigrationMeteoraDammCreateMetadata,
+            MIGRATION_DAMM_V2_CREATE_METADATA => Self::MigrationDammV2CreateMetadata,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
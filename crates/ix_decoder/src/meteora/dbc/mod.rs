@@ -0,0 +1,272 @@
+//! Instruction decoding functions for the meteora dynamic bonding curve program
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use ix::DbcInstruction;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN");
+
+const SWAP_ACCOUNTS: [&str; 15] = [
+    "poolAuthority",
+    "config",
+    "pool",
+    "inputTokenAccount",
+    "outputTokenAccount",
+    "baseVault",
+    "quoteVault",
+    "baseMint",
+    "quoteMint",
+    "payer",
+    "tokenBaseProgram",
+    "tokenQuoteProgram",
+    "referralTokenAccount",
+    "eventAuthority",
+    "program",
+];
+
+const CREATE_METADATA_ACCOUNTS: [&str; 7] = [
+    "virtualPool",
+    "config",
+    "migrationMetadata",
+    "payer",
+    "systemProgram",
+    "eventAuthority",
+    "program",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationDestination {
+    /// the dynamic amm program, also known as damm v1
+    DynamicAmm,
+    DammV2,
+}
+
+/// Links a bonding curve to the amm pool its liquidity was migrated to
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Migration {
+    pub destination: MigrationDestination,
+    pub destination_program_id: String,
+    /// the bonding curve's virtual pool
+    pub virtual_pool: Option<String>,
+    /// the amm pool created by the migration
+    pub pool: Option<String>,
+    pub base_mint: Option<String>,
+    pub quote_mint: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DbcDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DbcDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DbcInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DbcInstruction::InitializeVirtualPoolWithSplToken(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "config",
+                    "poolAuthority",
+                    "creator",
+                    "baseMint",
+                    "quoteMint",
+                    "pool",
+                    "baseVault",
+                    "quoteVault",
+                    "mintMetadata",
+                    "metadataProgram",
+                    "payer",
+                    "tokenQuoteProgram",
+                    "tokenProgram",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializeVirtualPoolWithSplToken".to_string(),
+            }),
+            DbcInstruction::InitializeVirtualPoolWithToken2022(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "config",
+                    "poolAuthority",
+                    "creator",
+                    "baseMint",
+                    "quoteMint",
+                    "pool",
+                    "baseVault",
+                    "quoteVault",
+                    "payer",
+                    "tokenQuoteProgram",
+                    "tokenProgram",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializeVirtualPoolWithToken2022".to_string(),
+            }),
+            DbcInstruction::Swap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swap".to_string(),
+            }),
+            DbcInstruction::Swap2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swap2".to_string(),
+            }),
+            DbcInstruction::MigrateMeteoraDamm => {
+                let accounts = self.ix.named_accounts(&[
+                    "virtualPool",
+                    "migrationMetadata",
+                    "config",
+                    "poolAuthority",
+                    "pool",
+                    "dammConfig",
+                    "lpMint",
+                    "tokenAMint",
+                    "tokenBMint",
+                    "aVault",
+                    "bVault",
+                    "aTokenVault",
+                    "bTokenVault",
+                    "aVaultLpMint",
+                    "bVaultLpMint",
+                    "aVaultLp",
+                    "bVaultLp",
+                    "baseVault",
+                    "quoteVault",
+                    "virtualPoolLp",
+                    "protocolTokenAFee",
+                    "protocolTokenBFee",
+                    "payer",
+                    "rent",
+                    "mintMetadata",
+                    "metadataProgram",
+                    "ammProgram",
+                    "vaultProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "systemProgram",
+                ]);
+                let migration = Migration {
+                    destination: MigrationDestination::DynamicAmm,
+                    destination_program_id: super::dynamic_amm::PROGRAM_ID.to_string(),
+                    virtual_pool: account(&accounts, "virtualPool"),
+                    pool: account(&accounts, "pool"),
+                    base_mint: account(&accounts, "tokenAMint"),
+                    quote_mint: account(&accounts, "tokenBMint"),
+                };
+                Ok(DecodedInstruction {
+                    data: [("migration".to_string(), serde_json::to_value(migration)?)]
+                        .into_iter()
+                        .collect(),
+                    accounts,
+                    name: "migrateMeteoraDamm".to_string(),
+                })
+            }
+            DbcInstruction::MigrationDammV2 => {
+                let accounts = self.ix.named_accounts(&[
+                    "virtualPool",
+                    "migrationMetadata",
+                    "config",
+                    "poolAuthority",
+                    "pool",
+                    "firstPositionNftMint",
+                    "firstPositionNftAccount",
+                    "firstPosition",
+                    "secondPositionNftMint",
+                    "secondPositionNftAccount",
+                    "secondPosition",
+                    "dammPoolAuthority",
+                    "ammProgram",
+                    "baseMint",
+                    "quoteMint",
+                    "tokenAVault",
+                    "tokenBVault",
+                    "baseVault",
+                    "quoteVault",
+                    "payer",
+                    "tokenBaseProgram",
+                    "tokenQuoteProgram",
+                    "token2022Program",
+                    "dammEventAuthority",
+                    "systemProgram",
+                ]);
+                let migration = Migration {
+                    destination: MigrationDestination::DammV2,
+                    destination_program_id: super::damm_v2::PROGRAM_ID.to_string(),
+                    virtual_pool: account(&accounts, "virtualPool"),
+                    pool: account(&accounts, "pool"),
+                    base_mint: account(&accounts, "baseMint"),
+                    quote_mint: account(&accounts, "quoteMint"),
+                };
+                Ok(DecodedInstruction {
+                    data: [("migration".to_string(), serde_json::to_value(migration)?)]
+                        .into_iter()
+                        .collect(),
+                    accounts,
+                    name: "migrationDammV2".to_string(),
+                })
+            }
+            DbcInstruction::MigrationMeteoraDammCreateMetadata => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&CREATE_METADATA_ACCOUNTS),
+                name: "migrationMeteoraDammCreateMetadata".to_string(),
+                ..Default::default()
+            }),
+            DbcInstruction::MigrationDammV2CreateMetadata => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&CREATE_METADATA_ACCOUNTS),
+                name: "migrationDammV2CreateMetadata".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+fn account(accounts: &HashMap<String, serde_json::Value>, name: &str) -> Option<String> {
+    accounts
+        .get(name)
+        .and_then(|account| account.as_str())
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_migration_damm_v2() {
+        let accounts = (0..25).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let decoded = DbcDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data: ix::MIGRATION_DAMM_V2.to_vec(),
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "migrationDammV2");
+        let migration = &decoded.data["migration"];
+        assert_eq!(migration["destination"], "dammV2");
+        assert_eq!(
+            migration["destinationProgramId"],
+            super::super::damm_v2::PROGRAM_ID.to_string()
+        );
+        assert_eq!(migration["virtualPool"], accounts[0].to_string());
+        assert_eq!(migration["pool"], accounts[4].to_string());
+        assert_eq!(migration["baseMint"], accounts[13].to_string());
+    }
+}
@@ -0,0 +1,265 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{anchor, types::serialize_hex};
+
+pub const INITIALIZE_LB_PAIR: [u8; 8] = [45, 154, 237, 210, 221, 15, 166, 92];
+pub const INITIALIZE_BIN_ARRAY_BITMAP_EXTENSION: [u8; 8] = [47, 157, 226, 180, 12, 240, 33, 71];
+pub const INITIALIZE_BIN_ARRAY: [u8; 8] = [35, 86, 19, 185, 78, 212, 75, 211];
+pub const ADD_LIQUIDITY: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+pub const ADD_LIQUIDITY_BY_WEIGHT: [u8; 8] = [28, 140, 238, 99, 231, 162, 21, 149];
+pub const ADD_LIQUIDITY_BY_STRATEGY: [u8; 8] = [7, 3, 150, 127, 148, 40, 61, 200];
+pub const ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE: [u8; 8] = [41, 5, 238, 175, 100, 225, 6, 205];
+pub const ADD_LIQUIDITY_ONE_SIDE: [u8; 8] = [94, 155, 103, 151, 70, 95, 220, 165];
+pub const REMOVE_LIQUIDITY: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+pub const REMOVE_LIQUIDITY_BY_RANGE: [u8; 8] = [26, 82, 102, 152, 240, 74, 105, 26];
+pub const REMOVE_ALL_LIQUIDITY: [u8; 8] = [10, 51, 61, 35, 112, 105, 24, 85];
+pub const INITIALIZE_POSITION: [u8; 8] = [219, 192, 234, 71, 190, 191, 102, 80];
+pub const INITIALIZE_POSITION_PDA: [u8; 8] = [46, 82, 125, 146, 85, 141, 228, 153];
+pub const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const SWAP_EXACT_OUT: [u8; 8] = [250, 73, 101, 33, 38, 207, 75, 184];
+pub const SWAP_WITH_PRICE_IMPACT: [u8; 8] = [56, 173, 230, 208, 173, 228, 156, 205];
+pub const SWAP2: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+pub const SWAP_EXACT_OUT2: [u8; 8] = [43, 215, 247, 132, 137, 60, 243, 81];
+pub const SWAP_WITH_PRICE_IMPACT2: [u8; 8] = [74, 98, 192, 214, 177, 51, 75, 51];
+pub const CLAIM_FEE: [u8; 8] = [169, 32, 79, 137, 136, 232, 70, 137];
+pub const CLAIM_REWARD: [u8; 8] = [149, 95, 181, 242, 94, 90, 158, 162];
+pub const WITHDRAW_PROTOCOL_FEE: [u8; 8] = [158, 201, 158, 189, 33, 93, 162, 103];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeLbPairInstruction {
+    pub active_id: i32,
+    pub bin_step: u16,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeBinArrayInstruction {
+    pub index: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinLiquidityDistribution {
+    pub bin_id: i32,
+    /// share of amount x deposited into the bin, in basis points
+    pub distribution_x: u16,
+    pub distribution_y: u16,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddLiquidityInstruction {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub bin_liquidity_dist: Vec<BinLiquidityDistribution>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinLiquidityDistributionByWeight {
+    pub bin_id: i32,
+    pub weight: u16,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddLiquidityByWeightInstruction {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub bin_liquidity_dist: Vec<BinLiquidityDistributionByWeight>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddLiquidityOneSideInstruction {
+    pub amount: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub bin_liquidity_dist: Vec<BinLiquidityDistributionByWeight>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StrategyType {
+    #[default]
+    SpotOneSide,
+    CurveOneSide,
+    BidAskOneSide,
+    SpotBalanced,
+    CurveBalanced,
+    BidAskBalanced,
+    SpotImBalanced,
+    CurveImBalanced,
+    BidAskImBalanced,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyParameters {
+    pub min_bin_id: i32,
+    pub max_bin_id: i32,
+    pub strategy_type: StrategyType,
+    /// strategy specific parameters, unused by the current strategies
+    #[serde(serialize_with = "serialize_hex")]
+    pub parameteres: [u8; 64],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddLiquidityByStrategyInstruction {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub strategy_parameters: StrategyParameters,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddLiquidityByStrategyOneSideInstruction {
+    pub amount: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub strategy_parameters: StrategyParameters,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinLiquidityReduction {
+    pub bin_id: i32,
+    pub bps_to_remove: u16,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveLiquidityInstruction {
+    pub bin_liquidity_removal: Vec<BinLiquidityReduction>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveLiquidityByRangeInstruction {
+    pub from_bin_id: i32,
+    pub to_bin_id: i32,
+    pub bps_to_remove: u16,
+}
+
+/// Arguments of both initialize position instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePositionInstruction {
+    pub lower_bin_id: i32,
+    pub width: i32,
+}
+
+/// Arguments of both the v1 and v2 swap instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstruction {
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+}
+
+/// Arguments of both the v1 and v2 swap exact out instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapExactOutInstruction {
+    pub max_in_amount: u64,
+    pub out_amount: u64,
+}
+
+/// Arguments of both the v1 and v2 swap with price impact instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapWithPriceImpactInstruction {
+    pub amount_in: u64,
+    pub active_id: Option<i32>,
+    pub max_price_impact_bps: u16,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimRewardInstruction {
+    pub reward_index: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawProtocolFeeInstruction {
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+/// Instructions supported by the dlmm program.
+///
+/// The v2 swap instructions support token-2022 mints, their trailing
+/// `remaining_accounts_info` argument is not decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DlmmInstruction {
+    InitializeLbPair(InitializeLbPairInstruction),
+    InitializeBinArrayBitmapExtension,
+    InitializeBinArray(InitializeBinArrayInstruction),
+    AddLiquidity(AddLiquidityInstruction),
+    AddLiquidityByWeight(AddLiquidityByWeightInstruction),
+    AddLiquidityByStrategy(AddLiquidityByStrategyInstruction),
+    AddLiquidityByStrategyOneSide(AddLiquidityByStrategyOneSideInstruction),
+    AddLiquidityOneSide(AddLiquidityOneSideInstruction),
+    RemoveLiquidity(RemoveLiquidityInstruction),
+    RemoveLiquidityByRange(RemoveLiquidityByRangeInstruction),
+    RemoveAllLiquidity,
+    InitializePosition(InitializePositionInstruction),
+    InitializePositionPda(InitializePositionInstruction),
+    ClosePosition,
+    Swap(SwapInstruction),
+    SwapExactOut(SwapExactOutInstruction),
+    SwapWithPriceImpact(SwapWithPriceImpactInstruction),
+    Swap2(SwapInstruction),
+    SwapExactOut2(SwapExactOutInstruction),
+    SwapWithPriceImpact2(SwapWithPriceImpactInstruction),
+    ClaimFee,
+    ClaimReward(ClaimRewardInstruction),
+    WithdrawProtocolFee(WithdrawProtocolFeeInstruction),
+}
+
+impl DlmmInstruction {
+    /// Unpacks a byte buffer into a [DlmmInstruction](enum.DlmmInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_LB_PAIR => Self::InitializeLbPair(anchor::deserialize(rest)?),
+            INITIALIZE_BIN_ARRAY_BITMAP_EXTENSION => Self::InitializeBinArrayBitmapExtension,
+            INITIALIZE_BIN_ARRAY => Self::InitializeBinArray(anchor::deserialize(rest)?),
+            ADD_LIQUIDITY => Self::AddLiquidity(anchor::deserialize(rest)?),
+            ADD_LIQUIDITY_BY_WEIGHT => Self::AddLiquidityByWeight(anchor::deserialize(rest)?),
+            ADD_LIQUIDITY_BY_STRATEGY => Self::AddLiquidityByStrategy(anchor::deserialize(rest)?),
+            ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE => {
+                Self::AddLiquidityByStrategyOneSide(anchor::deserialize(rest)?)
+            }
+            ADD_LIQUIDITY_ONE_SIDE => Self::AddLiquidityOneSide(anchor::deserialize(rest)?),
+            REMOVE_LIQUIDITY => Self::RemoveLiquidity(anchor::deserialize(rest)?),
+            REMOVE_LIQUIDITY_BY_RANGE => Self::RemoveLiquidityByRange(anchor::deserialize(rest)?),
+            REMOVE_ALL_LIQUIDITY => Self::RemoveAllLiquidity,
+            INITIALIZE_POSITION => Self::InitializePosition(anchor::deserialize(rest)?),
+            INITIALIZE_POSITION_PDA => Self::InitializePositionPda(anchor::deserialize(rest)?),
+            CLOSE_POSITION => Self::ClosePosition,
+            SWAP => Self::Swap(anchor::deserialize(rest)?),
+            SWAP_EXACT_OUT => Self::SwapExactOut(anchor::deserialize(rest)?),
+            SWAP_WITH_PRICE_IMPACT => Self::SwapWithPriceImpact(anchor::deserialize(rest)?),
+            SWAP2 => Self::Swap2(anchor::deserialize(rest)?),
+            SWAP_EXACT_OUT2 => Self::SwapExactOut2(anchor::deserialize(rest)?),
+            SWAP_WITH_PRICE_IMPACT2 => Self::SwapWithPriceImpact2(anchor::deserialize(rest)?),
+            CLAIM_FEE => Self::ClaimFee,
+            CLAIM_REWARD => Self::ClaimReward(anchor::deserialize(rest)?),
+            WITHDRAW_PROTOCOL_FEE => Self::WithdrawProtocolFee(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
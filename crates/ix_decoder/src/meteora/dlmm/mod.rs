@@ -0,0 +1,354 @@
+//! Instruction decoding functions for the meteora dlmm program
+
+use anyhow::Context;
+use ix::DlmmInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+
+const SWAP_ACCOUNTS: [&str; 15] = [
+    "lbPair",
+    "binArrayBitmapExtension",
+    "reserveX",
+    "reserveY",
+    "userTokenIn",
+    "userTokenOut",
+    "tokenXMint",
+    "tokenYMint",
+    "oracle",
+    "hostFeeIn",
+    "user",
+    "tokenXProgram",
+    "tokenYProgram",
+    "eventAuthority",
+    "program",
+];
+
+const SWAP2_ACCOUNTS: [&str; 16] = [
+    "lbPair",
+    "binArrayBitmapExtension",
+    "reserveX",
+    "reserveY",
+    "userTokenIn",
+    "userTokenOut",
+    "tokenXMint",
+    "tokenYMint",
+    "oracle",
+    "hostFeeIn",
+    "user",
+    "tokenXProgram",
+    "tokenYProgram",
+    "memoProgram",
+    "eventAuthority",
+    "program",
+];
+
+/// shared by the two sided add liquidity and all remove liquidity instructions
+const MODIFY_LIQUIDITY_ACCOUNTS: [&str; 16] = [
+    "position",
+    "lbPair",
+    "binArrayBitmapExtension",
+    "userTokenX",
+    "userTokenY",
+    "reserveX",
+    "reserveY",
+    "tokenXMint",
+    "tokenYMint",
+    "binArrayLower",
+    "binArrayUpper",
+    "sender",
+    "tokenXProgram",
+    "tokenYProgram",
+    "eventAuthority",
+    "program",
+];
+
+const ADD_LIQUIDITY_ONE_SIDE_ACCOUNTS: [&str; 12] = [
+    "position",
+    "lbPair",
+    "binArrayBitmapExtension",
+    "userToken",
+    "reserve",
+    "tokenMint",
+    "binArrayLower",
+    "binArrayUpper",
+    "sender",
+    "tokenProgram",
+    "eventAuthority",
+    "program",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DlmmDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DlmmDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DlmmInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DlmmInstruction::InitializeLbPair(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lbPair",
+                    "binArrayBitmapExtension",
+                    "tokenMintX",
+                    "tokenMintY",
+                    "reserveX",
+                    "reserveY",
+                    "oracle",
+                    "presetParameter",
+                    "funder",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializeLbPair".to_string(),
+            }),
+            DlmmInstruction::InitializeBinArrayBitmapExtension => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "lbPair",
+                    "binArrayBitmapExtension",
+                    "funder",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "initializeBinArrayBitmapExtension".to_string(),
+                ..Default::default()
+            }),
+            DlmmInstruction::InitializeBinArray(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lbPair",
+                    "binArray",
+                    "funder",
+                    "systemProgram",
+                ]),
+                name: "initializeBinArray".to_string(),
+            }),
+            DlmmInstruction::AddLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "addLiquidity".to_string(),
+            }),
+            DlmmInstruction::AddLiquidityByWeight(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "addLiquidityByWeight".to_string(),
+            }),
+            DlmmInstruction::AddLiquidityByStrategy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "addLiquidityByStrategy".to_string(),
+            }),
+            DlmmInstruction::AddLiquidityByStrategyOneSide(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ADD_LIQUIDITY_ONE_SIDE_ACCOUNTS),
+                name: "addLiquidityByStrategyOneSide".to_string(),
+            }),
+            DlmmInstruction::AddLiquidityOneSide(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&ADD_LIQUIDITY_ONE_SIDE_ACCOUNTS),
+                name: "addLiquidityOneSide".to_string(),
+            }),
+            DlmmInstruction::RemoveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "removeLiquidity".to_string(),
+            }),
+            DlmmInstruction::RemoveLiquidityByRange(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "removeLiquidityByRange".to_string(),
+            }),
+            DlmmInstruction::RemoveAllLiquidity => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "removeAllLiquidity".to_string(),
+                ..Default::default()
+            }),
+            DlmmInstruction::InitializePosition(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "position",
+                    "lbPair",
+                    "owner",
+                    "systemProgram",
+                    "rent",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializePosition".to_string(),
+            }),
+            DlmmInstruction::InitializePositionPda(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "base",
+                    "position",
+                    "lbPair",
+                    "owner",
+                    "systemProgram",
+                    "rent",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "initializePositionPda".to_string(),
+            }),
+            DlmmInstruction::ClosePosition => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "position",
+                    "lbPair",
+                    "binArrayLower",
+                    "binArrayUpper",
+                    "sender",
+                    "rentReceiver",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "closePosition".to_string(),
+                ..Default::default()
+            }),
+            DlmmInstruction::Swap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swap".to_string(),
+            }),
+            DlmmInstruction::SwapExactOut(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swapExactOut".to_string(),
+            }),
+            DlmmInstruction::SwapWithPriceImpact(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swapWithPriceImpact".to_string(),
+            }),
+            DlmmInstruction::Swap2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP2_ACCOUNTS),
+                name: "swap2".to_string(),
+            }),
+            DlmmInstruction::SwapExactOut2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP2_ACCOUNTS),
+                name: "swapExactOut2".to_string(),
+            }),
+            DlmmInstruction::SwapWithPriceImpact2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP2_ACCOUNTS),
+                name: "swapWithPriceImpact2".to_string(),
+            }),
+            DlmmInstruction::ClaimFee => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "lbPair",
+                    "position",
+                    "binArrayLower",
+                    "binArrayUpper",
+                    "sender",
+                    "reserveX",
+                    "reserveY",
+                    "userTokenX",
+                    "userTokenY",
+                    "tokenXMint",
+                    "tokenYMint",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "claimFee".to_string(),
+                ..Default::default()
+            }),
+            DlmmInstruction::ClaimReward(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lbPair",
+                    "position",
+                    "binArrayLower",
+                    "binArrayUpper",
+                    "sender",
+                    "rewardVault",
+                    "rewardMint",
+                    "userTokenAccount",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "claimReward".to_string(),
+            }),
+            DlmmInstruction::WithdrawProtocolFee(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lbPair",
+                    "reserveX",
+                    "reserveY",
+                    "tokenXMint",
+                    "tokenYMint",
+                    "receiverTokenX",
+                    "receiverTokenY",
+                    "tokenXProgram",
+                    "tokenYProgram",
+                ]),
+                name: "withdrawProtocolFee".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_add_liquidity_by_strategy() {
+        let mut data = ix::ADD_LIQUIDITY_BY_STRATEGY.to_vec();
+        // amount x, amount y, active id, max active bin slippage
+        data.extend(to_vec(&(1_000u64, 2_000u64, -42i32, 3i32)).unwrap());
+        // min bin id, max bin id, strategy type (spot balanced), parameters
+        data.extend(to_vec(&(-50i32, -35i32, 3u8, [0u8; 64])).unwrap());
+        let decoded = DlmmDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: (0..MODIFY_LIQUIDITY_ACCOUNTS.len())
+                .map(|_| Pubkey::new_unique())
+                .collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "addLiquidityByStrategy");
+        assert_eq!(decoded.data["amountX"], 1_000);
+        assert_eq!(decoded.data["activeId"], -42);
+        assert_eq!(
+            decoded.data["strategyParameters"]["strategyType"],
+            "spotBalanced"
+        );
+        assert_eq!(decoded.data["strategyParameters"]["minBinId"], -50);
+        assert_eq!(decoded.accounts.len(), MODIFY_LIQUIDITY_ACCOUNTS.len());
+    }
+
+    #[test]
+    fn test_account_layout_sizes() {
+        // sizes of the accounts, excluding the discriminator, as allocated by the program
+        let mut data = state::LB_PAIR_DISCRIMINATOR.to_vec();
+        data.extend([0u8; 896]);
+        let lb_pair = state::LbPair::unpack(&data).unwrap();
+        assert_eq!(lb_pair.active_id, 0);
+        let mut data = state::BIN_ARRAY_DISCRIMINATOR.to_vec();
+        data.extend([0u8; 10128]);
+        let bin_array = state::BinArray::unpack(&data).unwrap();
+        assert_eq!(bin_array.bin_ids(), 0..=69);
+    }
+}
@@ -0,0 +1,257 @@
+//! Account types
+//!
+//! The dlmm accounts are zero copy, so padding fields are kept to preserve the layout.
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_array, serialize_pubkey, serialize_u128, serialize_u128_array},
+};
+
+pub const LB_PAIR_DISCRIMINATOR: [u8; 8] = [33, 11, 49, 98, 181, 101, 177, 13];
+pub const BIN_ARRAY_DISCRIMINATOR: [u8; 8] = [92, 142, 92, 220, 5, 148, 70, 181];
+pub const POSITION_DISCRIMINATOR: [u8; 8] = [170, 188, 143, 228, 122, 64, 247, 208];
+pub const POSITION_V2_DISCRIMINATOR: [u8; 8] = [117, 176, 212, 199, 245, 180, 133, 182];
+
+pub const NUM_REWARDS: usize = 2;
+pub const MAX_BIN_PER_ARRAY: usize = 70;
+pub const MAX_BIN_PER_POSITION: usize = 70;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticParameters {
+    pub base_factor: u16,
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub variable_fee_control: u32,
+    pub max_volatility_accumulator: u32,
+    pub min_bin_id: i32,
+    pub max_bin_id: i32,
+    pub protocol_share: u16,
+    pub base_fee_power_factor: u8,
+    #[serde(skip)]
+    pub padding: [u8; 5],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableParameters {
+    pub volatility_accumulator: u32,
+    pub volatility_reference: u32,
+    pub index_reference: i32,
+    #[serde(skip)]
+    pub padding: [u8; 4],
+    pub last_update_timestamp: i64,
+    #[serde(skip)]
+    pub padding1: [u8; 8],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolFee {
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardInfo {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub vault: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub funder: Pubkey,
+    pub reward_duration: u64,
+    pub reward_duration_end: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub reward_rate: u128,
+    pub last_update_time: u64,
+    pub cumulative_seconds_with_empty_liquidity_reward: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LbPair {
+    pub parameters: StaticParameters,
+    pub v_parameters: VariableParameters,
+    pub bump_seed: [u8; 1],
+    pub bin_step_seed: [u8; 2],
+    pub pair_type: u8,
+    pub active_id: i32,
+    pub bin_step: u16,
+    pub status: u8,
+    pub require_base_factor_seed: u8,
+    pub base_factor_seed: [u8; 2],
+    pub activation_type: u8,
+    pub creator_pool_on_off_control: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_x_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_y_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub reserve_x: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub reserve_y: Pubkey,
+    pub protocol_fee: ProtocolFee,
+    #[serde(skip)]
+    pub padding1: [u8; 32],
+    pub reward_infos: [RewardInfo; NUM_REWARDS],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub oracle: Pubkey,
+    pub bin_array_bitmap: [u64; 16],
+    pub last_updated_at: i64,
+    #[serde(skip)]
+    pub padding2: [u8; 32],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pre_activation_swap_address: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub base_key: Pubkey,
+    pub activation_point: u64,
+    pub pre_activation_duration: u64,
+    #[serde(skip)]
+    pub padding3: [u8; 8],
+    #[serde(skip)]
+    pub padding4: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub creator: Pubkey,
+}
+
+impl LbPair {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, LB_PAIR_DISCRIMINATOR)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bin {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    /// price of the bin as a Q64.64 fixed point number
+    #[serde(serialize_with = "serialize_u128")]
+    pub price: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity_supply: u128,
+    #[serde(serialize_with = "serialize_u128_array")]
+    pub reward_per_token_stored: [u128; NUM_REWARDS],
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_amount_x_per_token_stored: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_amount_y_per_token_stored: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub amount_x_in: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub amount_y_in: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinArray {
+    pub index: i64,
+    pub version: u8,
+    #[serde(skip)]
+    pub padding: [u8; 7],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lb_pair: Pubkey,
+    #[serde(serialize_with = "serialize_array")]
+    pub bins: [Bin; MAX_BIN_PER_ARRAY],
+}
+
+impl BinArray {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, BIN_ARRAY_DISCRIMINATOR)
+    }
+
+    /// Returns the bin ids covered by the array
+    pub fn bin_ids(&self) -> std::ops::RangeInclusive<i64> {
+        let lower = self.index * MAX_BIN_PER_ARRAY as i64;
+        lower..=lower + MAX_BIN_PER_ARRAY as i64 - 1
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRewardInfo {
+    #[serde(serialize_with = "serialize_u128_array")]
+    pub reward_per_token_completes: [u128; NUM_REWARDS],
+    pub reward_pendings: [u64; NUM_REWARDS],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInfo {
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_x_per_token_complete: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_y_per_token_complete: u128,
+    pub fee_x_pending: u64,
+    pub fee_y_pending: u64,
+}
+
+/// Current position layout, with liquidity shares scaled to u128
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionV2 {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lb_pair: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    #[serde(serialize_with = "serialize_u128_array")]
+    pub liquidity_shares: [u128; MAX_BIN_PER_POSITION],
+    #[serde(serialize_with = "serialize_array")]
+    pub reward_infos: [UserRewardInfo; MAX_BIN_PER_POSITION],
+    #[serde(serialize_with = "serialize_array")]
+    pub fee_infos: [FeeInfo; MAX_BIN_PER_POSITION],
+    pub lower_bin_id: i32,
+    pub upper_bin_id: i32,
+    pub last_updated_at: i64,
+    pub total_claimed_fee_x_amount: u64,
+    pub total_claimed_fee_y_amount: u64,
+    pub total_claimed_rewards: [u64; NUM_REWARDS],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub operator: Pubkey,
+    pub lock_release_point: u64,
+    #[serde(skip)]
+    pub padding0: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_owner: Pubkey,
+}
+
+impl PositionV2 {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, POSITION_V2_DISCRIMINATOR)
+    }
+}
+
+/// Legacy position layout
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lb_pair: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    #[serde(serialize_with = "serialize_array")]
+    pub liquidity_shares: [u64; MAX_BIN_PER_POSITION],
+    #[serde(serialize_with = "serialize_array")]
+    pub reward_infos: [UserRewardInfo; MAX_BIN_PER_POSITION],
+    #[serde(serialize_with = "serialize_array")]
+    pub fee_infos: [FeeInfo; MAX_BIN_PER_POSITION],
+    pub lower_bin_id: i32,
+    pub upper_bin_id: i32,
+    pub last_updated_at: i64,
+    pub total_claimed_fee_x_amount: u64,
+    pub total_claimed_fee_y_amount: u64,
+    pub total_claimed_rewards: [u64; NUM_REWARDS],
+}
+
+impl Position {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, POSITION_DISCRIMINATOR)
+    }
+}
@@ -0,0 +1,212 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const INITIALIZE_PERMISSIONLESS_POOL: [u8; 8] = [118, 173, 41, 157, 173, 72, 97, 103];
+pub const INITIALIZE_PERMISSIONLESS_POOL_WITH_FEE_TIER: [u8; 8] =
+    [6, 135, 68, 147, 229, 82, 169, 113];
+pub const INITIALIZE_PERMISSIONLESS_CONSTANT_PRODUCT_POOL_WITH_CONFIG: [u8; 8] =
+    [7, 166, 138, 171, 206, 171, 236, 244];
+pub const INITIALIZE_PERMISSIONLESS_CONSTANT_PRODUCT_POOL_WITH_CONFIG2: [u8; 8] =
+    [48, 149, 220, 130, 61, 11, 9, 178];
+pub const INITIALIZE_CUSTOMIZABLE_PERMISSIONLESS_CONSTANT_PRODUCT_POOL: [u8; 8] =
+    [145, 24, 172, 194, 219, 125, 3, 190];
+pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const ADD_BALANCE_LIQUIDITY: [u8; 8] = [168, 227, 50, 62, 189, 171, 84, 176];
+pub const ADD_IMBALANCE_LIQUIDITY: [u8; 8] = [79, 35, 122, 84, 173, 15, 93, 191];
+pub const REMOVE_BALANCE_LIQUIDITY: [u8; 8] = [133, 109, 44, 179, 56, 238, 114, 33];
+pub const REMOVE_LIQUIDITY_SINGLE_SIDE: [u8; 8] = [84, 84, 177, 66, 254, 185, 10, 251];
+pub const BOOTSTRAP_LIQUIDITY: [u8; 8] = [4, 228, 215, 71, 225, 253, 119, 206];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DepegType {
+    #[default]
+    None,
+    Marinade,
+    Lido,
+    SplStake,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMultiplier {
+    pub token_a_multiplier: u64,
+    pub token_b_multiplier: u64,
+    pub precision_factor: u8,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Depeg {
+    pub base_virtual_price: u64,
+    pub base_cache_updated: u64,
+    pub depeg_type: DepegType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CurveType {
+    ConstantProduct,
+    #[serde(rename_all = "camelCase")]
+    Stable {
+        amp: u64,
+        token_multiplier: TokenMultiplier,
+        depeg: Depeg,
+        last_amp_updated_timestamp: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePermissionlessPoolInstruction {
+    pub curve_type: CurveType,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePermissionlessPoolWithFeeTierInstruction {
+    pub curve_type: CurveType,
+    pub trade_fee_bps: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+/// Arguments of both the v1 and v2 config based pool initialization, the
+/// activation point is only present in the v2 instruction
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePoolWithConfigInstruction {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation_point: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomizableParams {
+    pub trade_fee_numerator: u32,
+    pub activation_point: Option<u64>,
+    pub has_alpha_vault: bool,
+    pub activation_type: u8,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeCustomizablePoolInstruction {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub params: CustomizableParams,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstruction {
+    pub in_amount: u64,
+    pub minimum_out_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddBalanceLiquidityInstruction {
+    pub pool_token_amount: u64,
+    pub maximum_token_a_amount: u64,
+    pub maximum_token_b_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddImbalanceLiquidityInstruction {
+    pub minimum_pool_token_amount: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveBalanceLiquidityInstruction {
+    pub pool_token_amount: u64,
+    pub minimum_a_token_out: u64,
+    pub minimum_b_token_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveLiquiditySingleSideInstruction {
+    pub pool_token_amount: u64,
+    pub minimum_out_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapLiquidityInstruction {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+/// Instructions supported by the dynamic amm program.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DynamicAmmInstruction {
+    InitializePermissionlessPool(InitializePermissionlessPoolInstruction),
+    InitializePermissionlessPoolWithFeeTier(InitializePermissionlessPoolWithFeeTierInstruction),
+    InitializePermissionlessConstantProductPoolWithConfig(InitializePoolWithConfigInstruction),
+    InitializePermissionlessConstantProductPoolWithConfig2(InitializePoolWithConfigInstruction),
+    InitializeCustomizablePermissionlessConstantProductPool(InitializeCustomizablePoolInstruction),
+    Swap(SwapInstruction),
+    AddBalanceLiquidity(AddBalanceLiquidityInstruction),
+    AddImbalanceLiquidity(AddImbalanceLiquidityInstruction),
+    RemoveBalanceLiquidity(RemoveBalanceLiquidityInstruction),
+    RemoveLiquiditySingleSide(RemoveLiquiditySingleSideInstruction),
+    BootstrapLiquidity(BootstrapLiquidityInstruction),
+}
+
+impl DynamicAmmInstruction {
+    /// Unpacks a byte buffer into a [DynamicAmmInstruction](enum.DynamicAmmInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_PERMISSIONLESS_POOL => {
+                Self::InitializePermissionlessPool(anchor::deserialize(rest)?)
+            }
+            INITIALIZE_PERMISSIONLESS_POOL_WITH_FEE_TIER => {
+                Self::InitializePermissionlessPoolWithFeeTier(anchor::deserialize(rest)?)
+            }
+            INITIALIZE_PERMISSIONLESS_CONSTANT_PRODUCT_POOL_WITH_CONFIG => {
+                let ix: BootstrapLiquidityInstruction = anchor::deserialize(rest)?;
+                Self::InitializePermissionlessConstantProductPoolWithConfig(
+                    InitializePoolWithConfigInstruction {
+                        token_a_amount: ix.token_a_amount,
+                        token_b_amount: ix.token_b_amount,
+                        activation_point: None,
+                    },
+                )
+            }
+            INITIALIZE_PERMISSIONLESS_CONSTANT_PRODUCT_POOL_WITH_CONFIG2 => {
+                Self::InitializePermissionlessConstantProductPoolWithConfig2(anchor::deserialize(
+                    rest,
+                )?)
+            }
+            INITIALIZE_CUSTOMIZABLE_PERMISSIONLESS_CONSTANT_PRODUCT_POOL => {
+                Self::InitializeCustomizablePermissionlessConstantProductPool(anchor::deserialize(
+                    rest,
+                )?)
+            }
+            SWAP => Self::Swap(anchor::deserialize(rest)?),
+            ADD_BALANCE_LIQUIDITY => Self::AddBalanceLiquidity(anchor::deserialize(rest)?),
+            ADD_IMBALANCE_LIQUIDITY => Self::AddImbalanceLiquidity(anchor::deserialize(rest)?),
+            REMOVE_BALANCE_LIQUIDITY => Self::RemoveBalanceLiquidity(anchor::deserialize(rest)?),
+            REMOVE_LIQUIDITY_SINGLE_SIDE => {
+                Self::RemoveLiquiditySingleSide(anchor::deserialize(rest)?)
+            }
+            BOOTSTRAP_LIQUIDITY => Self::BootstrapLiquidity(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
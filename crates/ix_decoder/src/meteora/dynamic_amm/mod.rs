@@ -0,0 +1,294 @@
+//! Instruction decoding functions for the meteora dynamic amm program
+
+use anyhow::Context;
+use ix::DynamicAmmInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+
+/// shared by the balanced liquidity instructions
+const LIQUIDITY_ACCOUNTS: [&str; 16] = [
+    "pool",
+    "lpMint",
+    "userPoolLp",
+    "aVaultLp",
+    "bVaultLp",
+    "aVault",
+    "bVault",
+    "aVaultLpMint",
+    "bVaultLpMint",
+    "aTokenVault",
+    "bTokenVault",
+    "userAToken",
+    "userBToken",
+    "user",
+    "vaultProgram",
+    "tokenProgram",
+];
+
+const INITIALIZE_POOL_ACCOUNTS: [&str; 26] = [
+    "pool",
+    "lpMint",
+    "tokenAMint",
+    "tokenBMint",
+    "aVault",
+    "bVault",
+    "aTokenVault",
+    "bTokenVault",
+    "aVaultLpMint",
+    "bVaultLpMint",
+    "aVaultLp",
+    "bVaultLp",
+    "payerTokenA",
+    "payerTokenB",
+    "payerPoolLp",
+    "protocolTokenAFee",
+    "protocolTokenBFee",
+    "payer",
+    "feeOwner",
+    "rent",
+    "mintMetadata",
+    "metadataProgram",
+    "vaultProgram",
+    "tokenProgram",
+    "associatedTokenProgram",
+    "systemProgram",
+];
+
+const INITIALIZE_POOL_WITH_CONFIG_ACCOUNTS: [&str; 26] = [
+    "pool",
+    "config",
+    "lpMint",
+    "tokenAMint",
+    "tokenBMint",
+    "aVault",
+    "bVault",
+    "aTokenVault",
+    "bTokenVault",
+    "aVaultLpMint",
+    "bVaultLpMint",
+    "aVaultLp",
+    "bVaultLp",
+    "payerTokenA",
+    "payerTokenB",
+    "payerPoolLp",
+    "protocolTokenAFee",
+    "protocolTokenBFee",
+    "payer",
+    "rent",
+    "mintMetadata",
+    "metadataProgram",
+    "vaultProgram",
+    "tokenProgram",
+    "associatedTokenProgram",
+    "systemProgram",
+];
+
+const INITIALIZE_CUSTOMIZABLE_POOL_ACCOUNTS: [&str; 25] = [
+    "pool",
+    "lpMint",
+    "tokenAMint",
+    "tokenBMint",
+    "aVault",
+    "bVault",
+    "aTokenVault",
+    "bTokenVault",
+    "aVaultLpMint",
+    "bVaultLpMint",
+    "aVaultLp",
+    "bVaultLp",
+    "payerTokenA",
+    "payerTokenB",
+    "payerPoolLp",
+    "protocolTokenAFee",
+    "protocolTokenBFee",
+    "payer",
+    "rent",
+    "mintMetadata",
+    "metadataProgram",
+    "vaultProgram",
+    "tokenProgram",
+    "associatedTokenProgram",
+    "systemProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DynamicAmmDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DynamicAmmDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = DynamicAmmInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            DynamicAmmInstruction::InitializePermissionlessPool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&INITIALIZE_POOL_ACCOUNTS),
+                name: "initializePermissionlessPool".to_string(),
+            }),
+            DynamicAmmInstruction::InitializePermissionlessPoolWithFeeTier(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&INITIALIZE_POOL_ACCOUNTS),
+                    name: "initializePermissionlessPoolWithFeeTier".to_string(),
+                })
+            }
+            DynamicAmmInstruction::InitializePermissionlessConstantProductPoolWithConfig(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self
+                        .ix
+                        .named_accounts(&INITIALIZE_POOL_WITH_CONFIG_ACCOUNTS),
+                    name: "initializePermissionlessConstantProductPoolWithConfig".to_string(),
+                })
+            }
+            DynamicAmmInstruction::InitializePermissionlessConstantProductPoolWithConfig2(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self
+                        .ix
+                        .named_accounts(&INITIALIZE_POOL_WITH_CONFIG_ACCOUNTS),
+                    name: "initializePermissionlessConstantProductPoolWithConfig2".to_string(),
+                })
+            }
+            DynamicAmmInstruction::InitializeCustomizablePermissionlessConstantProductPool(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self
+                        .ix
+                        .named_accounts(&INITIALIZE_CUSTOMIZABLE_POOL_ACCOUNTS),
+                    name: "initializeCustomizablePermissionlessConstantProductPool".to_string(),
+                })
+            }
+            DynamicAmmInstruction::Swap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "pool",
+                    "userSourceToken",
+                    "userDestinationToken",
+                    "aVault",
+                    "bVault",
+                    "aTokenVault",
+                    "bTokenVault",
+                    "aVaultLpMint",
+                    "bVaultLpMint",
+                    "aVaultLp",
+                    "bVaultLp",
+                    "protocolTokenFee",
+                    "user",
+                    "vaultProgram",
+                    "tokenProgram",
+                ]),
+                name: "swap".to_string(),
+            }),
+            DynamicAmmInstruction::AddBalanceLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDITY_ACCOUNTS),
+                name: "addBalanceLiquidity".to_string(),
+            }),
+            DynamicAmmInstruction::AddImbalanceLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDITY_ACCOUNTS),
+                name: "addImbalanceLiquidity".to_string(),
+            }),
+            DynamicAmmInstruction::RemoveBalanceLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDITY_ACCOUNTS),
+                name: "removeBalanceLiquidity".to_string(),
+            }),
+            DynamicAmmInstruction::RemoveLiquiditySingleSide(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "pool",
+                    "lpMint",
+                    "userPoolLp",
+                    "aVaultLp",
+                    "bVaultLp",
+                    "aVault",
+                    "bVault",
+                    "aVaultLpMint",
+                    "bVaultLpMint",
+                    "aTokenVault",
+                    "bTokenVault",
+                    "userDestinationToken",
+                    "user",
+                    "vaultProgram",
+                    "tokenProgram",
+                ]),
+                name: "removeLiquiditySingleSide".to_string(),
+            }),
+            DynamicAmmInstruction::BootstrapLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDITY_ACCOUNTS),
+                name: "bootstrapLiquidity".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_swap_and_balance_liquidity() {
+        let decode = |data: Vec<u8>, accounts: &[Pubkey]| {
+            DynamicAmmDecoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: accounts.to_vec(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        let accounts: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+        assert_eq!(ix::SWAP, anchor::instruction_discriminator("swap"));
+        let mut data = ix::SWAP.to_vec();
+        data.extend(to_vec(&(1_000_000u64, 990_000u64)).unwrap());
+        let decoded = decode(data, &accounts[..15]);
+        assert_eq!(decoded.name, "swap");
+        assert_eq!(decoded.data["inAmount"], 1_000_000);
+        assert_eq!(decoded.data["minimumOutAmount"], 990_000);
+        assert_eq!(decoded.accounts["userSourceToken"], accounts[1].to_string());
+        assert_eq!(
+            decoded.accounts["protocolTokenFee"],
+            accounts[11].to_string()
+        );
+        assert_eq!(decoded.accounts["tokenProgram"], accounts[14].to_string());
+
+        assert_eq!(
+            ix::ADD_BALANCE_LIQUIDITY,
+            anchor::instruction_discriminator("add_balance_liquidity")
+        );
+        let mut data = ix::ADD_BALANCE_LIQUIDITY.to_vec();
+        data.extend(to_vec(&(500u64, 1_000u64, 2_000u64)).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "addBalanceLiquidity");
+        assert_eq!(decoded.data["poolTokenAmount"], 500);
+        assert_eq!(decoded.data["maximumTokenBAmount"], 2_000);
+        assert_eq!(decoded.accounts["userPoolLp"], accounts[2].to_string());
+        assert_eq!(decoded.accounts["user"], accounts[13].to_string());
+
+        assert_eq!(
+            ix::REMOVE_BALANCE_LIQUIDITY,
+            anchor::instruction_discriminator("remove_balance_liquidity")
+        );
+        let mut data = ix::REMOVE_BALANCE_LIQUIDITY.to_vec();
+        data.extend(to_vec(&(500u64, 900u64, 1_900u64)).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "removeBalanceLiquidity");
+        assert_eq!(decoded.data["minimumATokenOut"], 900);
+        assert_eq!(decoded.accounts["userBToken"], accounts[12].to_string());
+    }
+}
@@ -0,0 +1,71 @@
+//! Instructions decoding functions for meteora
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use damm_v2::DammV2Decoder;
+use dbc::DbcDecoder;
+use dlmm::DlmmDecoder;
+use dynamic_amm::DynamicAmmDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod damm_v2;
+pub mod dbc;
+pub mod dlmm;
+pub mod dynamic_amm;
+
+pub static METEORA_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(MeteoraProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Dlmm(DlmmDecoder),
+    DynamicAmm(DynamicAmmDecoder),
+    DammV2(DammV2Decoder),
+    Dbc(DbcDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct MeteoraProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Dlmm(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Meteora::Dlmm"),
+            Self::DynamicAmm(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Meteora::DynamicAmm"),
+            Self::DammV2(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Meteora::DammV2"),
+            Self::Dbc(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Meteora::Dbc"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Dlmm(decoder) => format!("{:#?}", decoder),
+            Self::DynamicAmm(decoder) => format!("{:#?}", decoder),
+            Self::DammV2(decoder) => format!("{:#?}", decoder),
+            Self::Dbc(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for MeteoraProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            dlmm::PROGRAM_ID => Some(Box::new(Programs::Dlmm(DlmmDecoder::new(ix.clone())))),
+            dynamic_amm::PROGRAM_ID => Some(Box::new(Programs::DynamicAmm(
+                DynamicAmmDecoder::new(ix.clone()),
+            ))),
+            damm_v2::PROGRAM_ID => Some(Box::new(Programs::DammV2(DammV2Decoder::new(ix.clone())))),
+            dbc::PROGRAM_ID => Some(Box::new(Programs::Dbc(DbcDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
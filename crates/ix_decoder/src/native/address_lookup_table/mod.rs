@@ -0,0 +1,138 @@
+//! Instruction decoding functions for the native address lookup table program
+
+use anyhow::{anyhow, Context};
+use solana_sdk::{
+    address_lookup_table::instruction::ProgramInstruction, program_utils::limited_deserialize,
+    pubkey::Pubkey,
+};
+
+use crate::types::{DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::address_lookup_table::program::ID;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AddressLookupTableDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl AddressLookupTableDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix: ProgramInstruction = limited_deserialize(&self.ix.data)
+            .map_err(|err| anyhow!("{err:#?}"))
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            ProgramInstruction::CreateLookupTable {
+                recent_slot,
+                bump_seed,
+            } => Ok(DecodedInstruction {
+                data: [
+                    ("recentSlot".to_string(), serde_json::to_value(recent_slot)?),
+                    ("bumpSeed".to_string(), serde_json::to_value(bump_seed)?),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "lookupTable",
+                    "authority",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "createLookupTable".to_string(),
+            }),
+            ProgramInstruction::FreezeLookupTable => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["lookupTable", "authority"]),
+                name: "freezeLookupTable".to_string(),
+                ..Default::default()
+            }),
+            ProgramInstruction::ExtendLookupTable { new_addresses } => Ok(DecodedInstruction {
+                data: [(
+                    "newAddresses".to_string(),
+                    serde_json::to_value(
+                        new_addresses
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>(),
+                    )?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "lookupTable",
+                    "authority",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "extendLookupTable".to_string(),
+            }),
+            ProgramInstruction::DeactivateLookupTable => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["lookupTable", "authority"]),
+                name: "deactivateLookupTable".to_string(),
+                ..Default::default()
+            }),
+            ProgramInstruction::CloseLookupTable => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["lookupTable", "authority", "recipient"]),
+                name: "closeLookupTable".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::address_lookup_table::instruction;
+
+    #[test]
+    fn test_decode_lookup_table_lifecycle() {
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let (ix, lookup_table) = instruction::create_lookup_table(authority, payer, 250_000_000);
+        let decoded = AddressLookupTableDecoder::new(ix.into()).decode().unwrap();
+        assert_eq!(decoded.name, "createLookupTable");
+        assert_eq!(decoded.data["recentSlot"], 250_000_000u64);
+        assert_eq!(
+            decoded.data["bumpSeed"],
+            Pubkey::find_program_address(
+                &[authority.as_ref(), &250_000_000u64.to_le_bytes()],
+                &PROGRAM_ID
+            )
+            .1
+        );
+        assert_eq!(decoded.accounts["lookupTable"], lookup_table.to_string());
+        assert_eq!(decoded.accounts["payer"], payer.to_string());
+
+        let new_addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = instruction::extend_lookup_table(
+            lookup_table,
+            authority,
+            Some(payer),
+            new_addresses.clone(),
+        );
+        let decoded = AddressLookupTableDecoder::new(ix.into()).decode().unwrap();
+        assert_eq!(decoded.name, "extendLookupTable");
+        assert_eq!(
+            decoded.data["newAddresses"],
+            serde_json::json!([new_addresses[0].to_string(), new_addresses[1].to_string()])
+        );
+        assert_eq!(decoded.accounts["authority"], authority.to_string());
+
+        let ix = instruction::deactivate_lookup_table(lookup_table, authority);
+        let decoded = AddressLookupTableDecoder::new(ix.into()).decode().unwrap();
+        assert_eq!(decoded.name, "deactivateLookupTable");
+        assert_eq!(decoded.accounts["lookupTable"], lookup_table.to_string());
+
+        let ix = instruction::close_lookup_table(lookup_table, authority, recipient);
+        let decoded = AddressLookupTableDecoder::new(ix.into()).decode().unwrap();
+        assert_eq!(decoded.name, "closeLookupTable");
+        assert_eq!(decoded.accounts["recipient"], recipient.to_string());
+    }
+}
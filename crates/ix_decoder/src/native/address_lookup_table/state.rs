@@ -0,0 +1,96 @@
+//! Account state of address lookup tables
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use solana_sdk::{
+    address_lookup_table::state::AddressLookupTable, clock::Slot,
+    message::v0::MessageAddressTableLookup, pubkey::Pubkey,
+};
+
+/// Owned representation of an address lookup table account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LookupTable {
+    /// None once the table has been frozen
+    pub authority: Option<Pubkey>,
+    /// `Slot::MAX` while the table has not been deactivated
+    pub deactivation_slot: Slot,
+    pub last_extended_slot: Slot,
+    pub last_extended_slot_start_index: u8,
+    pub addresses: Vec<Pubkey>,
+}
+
+impl LookupTable {
+    /// Unpacks the data of a lookup table account
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let table = AddressLookupTable::deserialize(data)
+            .map_err(|err| anyhow!("{err:#?}"))
+            .with_context(|| "failed to decode lookup table")?;
+        Ok(Self {
+            authority: table.meta.authority,
+            deactivation_slot: table.meta.deactivation_slot,
+            last_extended_slot: table.meta.last_extended_slot,
+            last_extended_slot_start_index: table.meta.last_extended_slot_start_index,
+            addresses: table.addresses.into_owned(),
+        })
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.authority.is_none()
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivation_slot != Slot::MAX
+    }
+
+    /// Returns the addresses stored at the given indexes
+    pub fn lookup(&self, indexes: &[u8]) -> anyhow::Result<Vec<Pubkey>> {
+        indexes
+            .iter()
+            .map(|idx| {
+                self.addresses
+                    .get(*idx as usize)
+                    .copied()
+                    .ok_or_else(|| anyhow!("lookup table index {idx} out of bounds"))
+            })
+            .collect()
+    }
+}
+
+/// Addresses loaded by a versioned transaction, one entry per loaded account slot.
+/// Slots which couldn't be resolved hold the reason, so the positions of the other
+/// loaded addresses are kept
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResolvedLoadedAddresses {
+    pub writable: Vec<Result<Pubkey, String>>,
+    pub readonly: Vec<Result<Pubkey, String>>,
+}
+
+/// Resolves the addresses a versioned transaction loads from the given lookup tables.
+///
+/// As with the runtime, all writable addresses are ordered before all readonly addresses.
+/// A missing table only leaves the slots of its own lookup unresolved
+pub fn resolve_loaded_addresses(
+    lookups: &[MessageAddressTableLookup],
+    tables: &HashMap<Pubkey, LookupTable>,
+) -> ResolvedLoadedAddresses {
+    let mut loaded = ResolvedLoadedAddresses::default();
+    for lookup in lookups {
+        let table = tables.get(&lookup.account_key);
+        let resolve = |idx: &u8| match table {
+            Some(table) => table
+                .addresses
+                .get(*idx as usize)
+                .copied()
+                .ok_or_else(|| format!("lookup table index {idx} out of bounds")),
+            None => Err(format!("missing lookup table {}", lookup.account_key)),
+        };
+        loaded
+            .writable
+            .extend(lookup.writable_indexes.iter().map(resolve));
+        loaded
+            .readonly
+            .extend(lookup.readonly_indexes.iter().map(resolve));
+    }
+    loaded
+}
@@ -0,0 +1,168 @@
+//! Instruction decoding functions for the native upgradeable bpf loader
+
+use anyhow::{anyhow, Context};
+use solana_sdk::{
+    hash::hash, loader_upgradeable_instruction::UpgradeableLoaderInstruction,
+    program_utils::limited_deserialize, pubkey::Pubkey,
+};
+
+use crate::types::{DecodedInstruction, PartiallyDecodedInstruction};
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::ID;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BpfUpgradeableLoaderDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl BpfUpgradeableLoaderDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix: UpgradeableLoaderInstruction = limited_deserialize(&self.ix.data)
+            .map_err(|err| anyhow!("{err:#?}"))
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            UpgradeableLoaderInstruction::InitializeBuffer => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["buffer", "authority"]),
+                name: "initializeBuffer".to_string(),
+                ..Default::default()
+            }),
+            // program bytes are summarized by their length and hash, as a deployment
+            // will consist of hundreds of write instructions
+            UpgradeableLoaderInstruction::Write { offset, bytes } => Ok(DecodedInstruction {
+                data: [
+                    ("offset".to_string(), serde_json::to_value(offset)?),
+                    ("length".to_string(), serde_json::to_value(bytes.len())?),
+                    (
+                        "sha256".to_string(),
+                        serde_json::to_value(hash(&bytes).to_string())?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&["buffer", "authority"]),
+                name: "write".to_string(),
+            }),
+            UpgradeableLoaderInstruction::DeployWithMaxDataLen { max_data_len } => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "maxDataLen".to_string(),
+                        serde_json::to_value(max_data_len)?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&[
+                        "payer",
+                        "programData",
+                        "program",
+                        "buffer",
+                        "rentSysvar",
+                        "clockSysvar",
+                        "systemProgram",
+                        "authority",
+                    ]),
+                    name: "deployWithMaxDataLen".to_string(),
+                })
+            }
+            UpgradeableLoaderInstruction::Upgrade => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "programData",
+                    "program",
+                    "buffer",
+                    "spill",
+                    "rentSysvar",
+                    "clockSysvar",
+                    "authority",
+                ]),
+                name: "upgrade".to_string(),
+                ..Default::default()
+            }),
+            // the account may be either a buffer or a program data account, which
+            // can't be determined from the instruction alone
+            UpgradeableLoaderInstruction::SetAuthority => Ok(DecodedInstruction {
+                data: [(
+                    "immutable".to_string(),
+                    serde_json::to_value(self.ix.accounts.len() < 3)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["account", "authority", "newAuthority"]),
+                name: "setAuthority".to_string(),
+            }),
+            UpgradeableLoaderInstruction::Close => Ok(DecodedInstruction {
+                accounts: if self.ix.accounts.len() > 3 {
+                    // the program account is only supplied when closing program data
+                    self.ix
+                        .named_accounts(&["programData", "recipient", "authority", "program"])
+                } else {
+                    self.ix
+                        .named_accounts(&["account", "recipient", "authority"])
+                },
+                name: "close".to_string(),
+                ..Default::default()
+            }),
+            UpgradeableLoaderInstruction::ExtendProgram { additional_bytes } => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "additionalBytes".to_string(),
+                        serde_json::to_value(additional_bytes)?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&[
+                        "programData",
+                        "program",
+                        "systemProgram",
+                        "payer",
+                    ]),
+                    name: "extendProgram".to_string(),
+                })
+            }
+            UpgradeableLoaderInstruction::SetAuthorityChecked => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["account", "authority", "newAuthority"]),
+                name: "setAuthorityChecked".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::bpf_loader_upgradeable;
+
+    #[test]
+    fn test_decode_upgrade_and_revoke_authority() {
+        let program = Pubkey::new_unique();
+        let buffer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let ix = bpf_loader_upgradeable::upgrade(&program, &buffer, &authority, &authority);
+        let decoded = BpfUpgradeableLoaderDecoder::new(ix.into())
+            .decode()
+            .unwrap();
+        assert_eq!(decoded.name, "upgrade");
+        assert_eq!(decoded.accounts["program"], program.to_string());
+        assert_eq!(decoded.accounts["buffer"], buffer.to_string());
+        assert_eq!(
+            decoded.accounts["programData"],
+            Pubkey::find_program_address(&[program.as_ref()], &PROGRAM_ID)
+                .0
+                .to_string()
+        );
+
+        let ix = bpf_loader_upgradeable::set_upgrade_authority(&program, &authority, None);
+        let decoded = BpfUpgradeableLoaderDecoder::new(ix.into())
+            .decode()
+            .unwrap();
+        assert_eq!(decoded.name, "setAuthority");
+        assert_eq!(decoded.data["immutable"], true);
+        assert!(!decoded.accounts.contains_key("newAuthority"));
+    }
+}
@@ -0,0 +1,73 @@
+//! Instruction decoding functions for the native solana programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use address_lookup_table::AddressLookupTableDecoder;
+use anyhow::Context;
+use bpf_loader_upgradeable::BpfUpgradeableLoaderDecoder;
+use once_cell::sync::Lazy;
+use stake::StakeDecoder;
+use std::sync::Arc;
+use vote::VoteDecoder;
+
+pub mod address_lookup_table;
+pub mod bpf_loader_upgradeable;
+pub mod stake;
+pub mod vote;
+
+pub static NATIVE_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(NativeProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Stake(StakeDecoder),
+    Vote(VoteDecoder),
+    BpfUpgradeableLoader(BpfUpgradeableLoaderDecoder),
+    AddressLookupTable(AddressLookupTableDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct NativeProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Stake(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Native::Stake"),
+            Self::Vote(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Native::Vote"),
+            Self::BpfUpgradeableLoader(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Native::BpfUpgradeableLoader"),
+            Self::AddressLookupTable(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Native::AddressLookupTable"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Stake(decoder) => format!("{:#?}", decoder),
+            Self::Vote(decoder) => format!("{:#?}", decoder),
+            Self::BpfUpgradeableLoader(decoder) => format!("{:#?}", decoder),
+            Self::AddressLookupTable(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for NativeProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            stake::PROGRAM_ID => Some(Box::new(Programs::Stake(StakeDecoder::new(ix.clone())))),
+            vote::PROGRAM_ID => Some(Box::new(Programs::Vote(VoteDecoder::new(ix.clone())))),
+            bpf_loader_upgradeable::PROGRAM_ID => Some(Box::new(Programs::BpfUpgradeableLoader(
+                BpfUpgradeableLoaderDecoder::new(ix.clone()),
+            ))),
+            address_lookup_table::PROGRAM_ID => Some(Box::new(Programs::AddressLookupTable(
+                AddressLookupTableDecoder::new(ix.clone()),
+            ))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Instruction decoding functions for the native stake program
+
+use anyhow::{anyhow, Context};
+use solana_sdk::{
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    stake::{
+        instruction::{LockupArgs, StakeInstruction},
+        state::StakeAuthorize,
+    },
+};
+
+use crate::types::{DecodedInstruction, PartiallyDecodedInstruction};
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::stake::program::ID;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StakeDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl StakeDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix: StakeInstruction = limited_deserialize(&self.ix.data)
+            .map_err(|err| anyhow!("{err:#?}"))
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            StakeInstruction::Initialize(authorized, lockup) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "staker".to_string(),
+                        serde_json::to_value(authorized.staker.to_string())?,
+                    ),
+                    (
+                        "withdrawer".to_string(),
+                        serde_json::to_value(authorized.withdrawer.to_string())?,
+                    ),
+                    (
+                        "unixTimestamp".to_string(),
+                        serde_json::to_value(lockup.unix_timestamp)?,
+                    ),
+                    ("epoch".to_string(), serde_json::to_value(lockup.epoch)?),
+                    (
+                        "custodian".to_string(),
+                        serde_json::to_value(lockup.custodian.to_string())?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&["stakeAccount", "rentSysvar"]),
+                name: "initialize".to_string(),
+            }),
+            StakeInstruction::Authorize(new_authority, stake_authorize) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "newAuthority".to_string(),
+                        serde_json::to_value(new_authority.to_string())?,
+                    ),
+                    (
+                        "authorityType".to_string(),
+                        serde_json::to_value(authority_type(stake_authorize))?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "clockSysvar",
+                    "authority",
+                    "custodian",
+                ]),
+                name: "authorize".to_string(),
+            }),
+            StakeInstruction::DelegateStake => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "voteAccount",
+                    "clockSysvar",
+                    "stakeHistorySysvar",
+                    "stakeConfig",
+                    "stakeAuthority",
+                ]),
+                name: "delegateStake".to_string(),
+                ..Default::default()
+            }),
+            StakeInstruction::Split(lamports) => Ok(DecodedInstruction {
+                data: [("lamports".to_string(), serde_json::to_value(lamports)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "newSplitAccount",
+                    "stakeAuthority",
+                ]),
+                name: "split".to_string(),
+            }),
+            StakeInstruction::Withdraw(lamports) => Ok(DecodedInstruction {
+                data: [("lamports".to_string(), serde_json::to_value(lamports)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "destination",
+                    "clockSysvar",
+                    "stakeHistorySysvar",
+                    "withdrawAuthority",
+                    "custodian",
+                ]),
+                name: "withdraw".to_string(),
+            }),
+            StakeInstruction::Deactivate => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "clockSysvar",
+                    "stakeAuthority",
+                ]),
+                name: "deactivate".to_string(),
+                ..Default::default()
+            }),
+            StakeInstruction::SetLockup(lockup) => Ok(DecodedInstruction {
+                data: lockup_args_data(&lockup)?,
+                accounts: self.ix.named_accounts(&["stakeAccount", "custodian"]),
+                name: "setLockup".to_string(),
+            }),
+            StakeInstruction::Merge => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "destination",
+                    "source",
+                    "clockSysvar",
+                    "stakeHistorySysvar",
+                    "stakeAuthority",
+                ]),
+                name: "merge".to_string(),
+                ..Default::default()
+            }),
+            StakeInstruction::AuthorizeWithSeed(args) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "newAuthority".to_string(),
+                        serde_json::to_value(args.new_authorized_pubkey.to_string())?,
+                    ),
+                    (
+                        "authorityType".to_string(),
+                        serde_json::to_value(authority_type(args.stake_authorize))?,
+                    ),
+                    (
+                        "authoritySeed".to_string(),
+                        serde_json::to_value(args.authority_seed)?,
+                    ),
+                    (
+                        "authorityOwner".to_string(),
+                        serde_json::to_value(args.authority_owner.to_string())?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "authorityBase",
+                    "clockSysvar",
+                    "custodian",
+                ]),
+                name: "authorizeWithSeed".to_string(),
+            }),
+            StakeInstruction::InitializeChecked => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "rentSysvar",
+                    "staker",
+                    "withdrawer",
+                ]),
+                name: "initializeChecked".to_string(),
+                ..Default::default()
+            }),
+            StakeInstruction::AuthorizeChecked(stake_authorize) => Ok(DecodedInstruction {
+                data: [(
+                    "authorityType".to_string(),
+                    serde_json::to_value(authority_type(stake_authorize))?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "clockSysvar",
+                    "authority",
+                    "newAuthority",
+                    "custodian",
+                ]),
+                name: "authorizeChecked".to_string(),
+            }),
+            StakeInstruction::AuthorizeCheckedWithSeed(args) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "authorityType".to_string(),
+                        serde_json::to_value(authority_type(args.stake_authorize))?,
+                    ),
+                    (
+                        "authoritySeed".to_string(),
+                        serde_json::to_value(args.authority_seed)?,
+                    ),
+                    (
+                        "authorityOwner".to_string(),
+                        serde_json::to_value(args.authority_owner.to_string())?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "authorityBase",
+                    "clockSysvar",
+                    "newAuthority",
+                    "custodian",
+                ]),
+                name: "authorizeCheckedWithSeed".to_string(),
+            }),
+            StakeInstruction::SetLockupChecked(lockup) => Ok(DecodedInstruction {
+                data: lockup_args_data(&LockupArgs {
+                    unix_timestamp: lockup.unix_timestamp,
+                    epoch: lockup.epoch,
+                    custodian: None,
+                })?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["stakeAccount", "custodian", "newCustodian"]),
+                name: "setLockupChecked".to_string(),
+            }),
+            StakeInstruction::GetMinimumDelegation => Ok(DecodedInstruction {
+                name: "getMinimumDelegation".to_string(),
+                ..Default::default()
+            }),
+            StakeInstruction::DeactivateDelinquent => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "delinquentVoteAccount",
+                    "referenceVoteAccount",
+                ]),
+                name: "deactivateDelinquent".to_string(),
+                ..Default::default()
+            }),
+            #[allow(deprecated)]
+            StakeInstruction::Redelegate => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakeAccount",
+                    "newStakeAccount",
+                    "voteAccount",
+                    "stakeConfig",
+                    "stakeAuthority",
+                ]),
+                name: "redelegate".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+fn authority_type(stake_authorize: StakeAuthorize) -> &'static str {
+    match stake_authorize {
+        StakeAuthorize::Staker => "staker",
+        StakeAuthorize::Withdrawer => "withdrawer",
+    }
+}
+
+/// lockup fields which are not being changed are omitted
+fn lockup_args_data(
+    lockup: &LockupArgs,
+) -> anyhow::Result<std::collections::HashMap<String, serde_json::Value>> {
+    let mut data = std::collections::HashMap::new();
+    if let Some(unix_timestamp) = lockup.unix_timestamp {
+        data.insert(
+            "unixTimestamp".to_string(),
+            serde_json::to_value(unix_timestamp)?,
+        );
+    }
+    if let Some(epoch) = lockup.epoch {
+        data.insert("epoch".to_string(), serde_json::to_value(epoch)?);
+    }
+    if let Some(custodian) = lockup.custodian {
+        data.insert(
+            "custodian".to_string(),
+            serde_json::to_value(custodian.to_string())?,
+        );
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::stake::instruction as stake_instruction;
+
+    #[test]
+    fn test_decode_split() {
+        let stake = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let split_stake = Pubkey::new_unique();
+        let ix = stake_instruction::split(&stake, &authority, 42, &split_stake)
+            .pop()
+            .unwrap();
+        let decoded = StakeDecoder::new(ix.into()).decode().unwrap();
+        assert_eq!(decoded.name, "split");
+        assert_eq!(decoded.data["lamports"], 42);
+        assert_eq!(decoded.accounts["stakeAccount"], stake.to_string());
+        assert_eq!(decoded.accounts["newSplitAccount"], split_stake.to_string());
+        assert_eq!(decoded.accounts["stakeAuthority"], authority.to_string());
+    }
+}
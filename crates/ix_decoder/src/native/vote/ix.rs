@@ -0,0 +1,55 @@
+//! Instruction types which are not yet available in the solana sdk version we depend on
+
+use serde::Deserialize;
+use solana_sdk::{
+    clock::{Slot, UnixTimestamp},
+    hash::Hash,
+    serde_varint, short_vec,
+    vote::state::Lockout,
+};
+
+/// Index of the `TowerSync` variant within the vote program's instruction enum
+pub const TOWER_SYNC_TAG: u32 = 14;
+/// Index of the `TowerSyncSwitch` variant within the vote program's instruction enum
+pub const TOWER_SYNC_SWITCH_TAG: u32 = 15;
+
+/// Compact serialization of a `TowerSync` vote, mirroring `CompactVoteStateUpdate`
+/// with the addition of the voted on block id
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CompactTowerSync {
+    /// `Slot::MAX` indicates that the tower has no root
+    pub root: Slot,
+    #[serde(with = "short_vec")]
+    pub lockout_offsets: Vec<LockoutOffset>,
+    pub hash: Hash,
+    pub timestamp: Option<UnixTimestamp>,
+    pub block_id: Hash,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct LockoutOffset {
+    #[serde(with = "serde_varint")]
+    pub offset: Slot,
+    pub confirmation_count: u8,
+}
+
+impl CompactTowerSync {
+    pub fn root(&self) -> Option<Slot> {
+        (self.root != Slot::MAX).then_some(self.root)
+    }
+
+    /// Expands the slot offsets into absolute lockouts
+    pub fn lockouts(&self) -> Option<Vec<Lockout>> {
+        let mut slot = self.root().unwrap_or_default();
+        self.lockout_offsets
+            .iter()
+            .map(|lockout_offset| {
+                slot = slot.checked_add(lockout_offset.offset)?;
+                Some(Lockout::new_with_confirmation_count(
+                    slot,
+                    lockout_offset.confirmation_count.into(),
+                ))
+            })
+            .collect()
+    }
+}
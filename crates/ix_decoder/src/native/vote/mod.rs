@@ -0,0 +1,381 @@
+//! Instruction decoding functions for the native vote program
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use ix::{CompactTowerSync, TOWER_SYNC_SWITCH_TAG, TOWER_SYNC_TAG};
+use solana_sdk::{
+    clock::{Slot, UnixTimestamp},
+    hash::Hash,
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    vote::{
+        instruction::VoteInstruction,
+        state::{Lockout, Vote, VoteAuthorize, VoteStateUpdate},
+    },
+};
+
+use crate::types::{DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::vote::program::ID;
+
+/// accounts used by all of the instructions which submit a tower update
+const VOTE_STATE_UPDATE_ACCOUNTS: [&str; 2] = ["voteAccount", "voteAuthority"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct VoteDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl VoteDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        if let Some(decoded) = self.try_decode_tower_sync()? {
+            return Ok(decoded);
+        }
+        let ix: VoteInstruction = limited_deserialize(&self.ix.data)
+            .map_err(|err| anyhow!("{err:#?}"))
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            VoteInstruction::InitializeAccount(vote_init) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "node".to_string(),
+                        serde_json::to_value(vote_init.node_pubkey.to_string())?,
+                    ),
+                    (
+                        "authorizedVoter".to_string(),
+                        serde_json::to_value(vote_init.authorized_voter.to_string())?,
+                    ),
+                    (
+                        "authorizedWithdrawer".to_string(),
+                        serde_json::to_value(vote_init.authorized_withdrawer.to_string())?,
+                    ),
+                    (
+                        "commission".to_string(),
+                        serde_json::to_value(vote_init.commission)?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "rentSysvar",
+                    "clockSysvar",
+                    "node",
+                ]),
+                name: "initializeAccount".to_string(),
+            }),
+            VoteInstruction::Authorize(new_authority, vote_authorize) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "newAuthority".to_string(),
+                        serde_json::to_value(new_authority.to_string())?,
+                    ),
+                    (
+                        "authorityType".to_string(),
+                        serde_json::to_value(authority_type(vote_authorize))?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["voteAccount", "clockSysvar", "authority"]),
+                name: "authorize".to_string(),
+            }),
+            VoteInstruction::Vote(vote) => Ok(DecodedInstruction {
+                data: vote_data(&vote)?,
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "slotHashesSysvar",
+                    "clockSysvar",
+                    "voteAuthority",
+                ]),
+                name: "vote".to_string(),
+            }),
+            VoteInstruction::Withdraw(lamports) => Ok(DecodedInstruction {
+                data: [("lamports".to_string(), serde_json::to_value(lamports)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "destination",
+                    "withdrawAuthority",
+                ]),
+                name: "withdraw".to_string(),
+            }),
+            VoteInstruction::UpdateValidatorIdentity => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "newValidatorIdentity",
+                    "withdrawAuthority",
+                ]),
+                name: "updateValidatorIdentity".to_string(),
+                ..Default::default()
+            }),
+            VoteInstruction::UpdateCommission(commission) => Ok(DecodedInstruction {
+                data: [("commission".to_string(), serde_json::to_value(commission)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["voteAccount", "withdrawAuthority"]),
+                name: "updateCommission".to_string(),
+            }),
+            VoteInstruction::VoteSwitch(vote, proof_hash) => Ok(DecodedInstruction {
+                data: with_proof_hash(vote_data(&vote)?, proof_hash)?,
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "slotHashesSysvar",
+                    "clockSysvar",
+                    "voteAuthority",
+                ]),
+                name: "voteSwitch".to_string(),
+            }),
+            VoteInstruction::AuthorizeChecked(vote_authorize) => Ok(DecodedInstruction {
+                data: [(
+                    "authorityType".to_string(),
+                    serde_json::to_value(authority_type(vote_authorize))?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "clockSysvar",
+                    "authority",
+                    "newAuthority",
+                ]),
+                name: "authorizeChecked".to_string(),
+            }),
+            VoteInstruction::UpdateVoteState(update) => Ok(DecodedInstruction {
+                data: vote_state_update_data(&update)?,
+                accounts: self.ix.named_accounts(&VOTE_STATE_UPDATE_ACCOUNTS),
+                name: "updateVoteState".to_string(),
+            }),
+            VoteInstruction::UpdateVoteStateSwitch(update, proof_hash) => Ok(DecodedInstruction {
+                data: with_proof_hash(vote_state_update_data(&update)?, proof_hash)?,
+                accounts: self.ix.named_accounts(&VOTE_STATE_UPDATE_ACCOUNTS),
+                name: "updateVoteStateSwitch".to_string(),
+            }),
+            VoteInstruction::AuthorizeWithSeed(args) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "newAuthority".to_string(),
+                        serde_json::to_value(args.new_authority.to_string())?,
+                    ),
+                    (
+                        "authorityType".to_string(),
+                        serde_json::to_value(authority_type(args.authorization_type))?,
+                    ),
+                    (
+                        "authoritySeed".to_string(),
+                        serde_json::to_value(args.current_authority_derived_key_seed)?,
+                    ),
+                    (
+                        "authorityOwner".to_string(),
+                        serde_json::to_value(args.current_authority_derived_key_owner.to_string())?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["voteAccount", "clockSysvar", "authorityBase"]),
+                name: "authorizeWithSeed".to_string(),
+            }),
+            VoteInstruction::AuthorizeCheckedWithSeed(args) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "authorityType".to_string(),
+                        serde_json::to_value(authority_type(args.authorization_type))?,
+                    ),
+                    (
+                        "authoritySeed".to_string(),
+                        serde_json::to_value(args.current_authority_derived_key_seed)?,
+                    ),
+                    (
+                        "authorityOwner".to_string(),
+                        serde_json::to_value(args.current_authority_derived_key_owner.to_string())?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "voteAccount",
+                    "clockSysvar",
+                    "authorityBase",
+                    "newAuthority",
+                ]),
+                name: "authorizeCheckedWithSeed".to_string(),
+            }),
+            VoteInstruction::CompactUpdateVoteState(update) => Ok(DecodedInstruction {
+                data: vote_state_update_data(&update)?,
+                accounts: self.ix.named_accounts(&VOTE_STATE_UPDATE_ACCOUNTS),
+                name: "compactUpdateVoteState".to_string(),
+            }),
+            VoteInstruction::CompactUpdateVoteStateSwitch(update, proof_hash) => {
+                Ok(DecodedInstruction {
+                    data: with_proof_hash(vote_state_update_data(&update)?, proof_hash)?,
+                    accounts: self.ix.named_accounts(&VOTE_STATE_UPDATE_ACCOUNTS),
+                    name: "compactUpdateVoteStateSwitch".to_string(),
+                })
+            }
+        }
+    }
+
+    /// tower sync votes are newer than the sdk's VoteInstruction enum, so they are decoded separately
+    fn try_decode_tower_sync(&self) -> anyhow::Result<Option<DecodedInstruction>> {
+        let Some(tag) = self.ix.data.get(..4) else {
+            return Err(anyhow!("instruction data too short"));
+        };
+        let (tower_sync, proof_hash, name) = match u32::from_le_bytes(tag.try_into()?) {
+            TOWER_SYNC_TAG => {
+                let tower_sync: CompactTowerSync = limited_deserialize(&self.ix.data[4..])
+                    .map_err(|err| anyhow!("{err:#?}"))
+                    .with_context(|| "failed to decode tower sync")?;
+                (tower_sync, None, "towerSync")
+            }
+            TOWER_SYNC_SWITCH_TAG => {
+                let (tower_sync, proof_hash): (CompactTowerSync, Hash) =
+                    limited_deserialize(&self.ix.data[4..])
+                        .map_err(|err| anyhow!("{err:#?}"))
+                        .with_context(|| "failed to decode tower sync switch")?;
+                (tower_sync, Some(proof_hash), "towerSyncSwitch")
+            }
+            _ => return Ok(None),
+        };
+        let lockouts = tower_sync
+            .lockouts()
+            .ok_or_else(|| anyhow!("tower sync lockout slot overflow"))?;
+        let mut data = tower_data(
+            &lockouts,
+            tower_sync.root(),
+            &tower_sync.hash,
+            tower_sync.timestamp,
+        )?;
+        data.insert(
+            "blockId".to_string(),
+            serde_json::to_value(tower_sync.block_id.to_string())?,
+        );
+        if let Some(proof_hash) = proof_hash {
+            data = with_proof_hash(data, proof_hash)?;
+        }
+        Ok(Some(DecodedInstruction {
+            data,
+            accounts: self.ix.named_accounts(&VOTE_STATE_UPDATE_ACCOUNTS),
+            name: name.to_string(),
+        }))
+    }
+}
+
+fn authority_type(vote_authorize: VoteAuthorize) -> &'static str {
+    match vote_authorize {
+        VoteAuthorize::Voter => "voter",
+        VoteAuthorize::Withdrawer => "withdrawer",
+    }
+}
+
+fn vote_data(vote: &Vote) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+    Ok([
+        ("slots".to_string(), serde_json::to_value(&vote.slots)?),
+        (
+            "hash".to_string(),
+            serde_json::to_value(vote.hash.to_string())?,
+        ),
+        (
+            "timestamp".to_string(),
+            serde_json::to_value(vote.timestamp)?,
+        ),
+    ]
+    .into_iter()
+    .collect())
+}
+
+fn vote_state_update_data(
+    update: &VoteStateUpdate,
+) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+    let lockouts = update.lockouts.iter().copied().collect::<Vec<_>>();
+    tower_data(&lockouts, update.root, &update.hash, update.timestamp)
+}
+
+fn tower_data(
+    lockouts: &[Lockout],
+    root: Option<Slot>,
+    hash: &Hash,
+    timestamp: Option<UnixTimestamp>,
+) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+    Ok([
+        (
+            "lockouts".to_string(),
+            lockouts
+                .iter()
+                .map(|lockout| {
+                    serde_json::json!({
+                        "slot": lockout.slot(),
+                        "confirmationCount": lockout.confirmation_count(),
+                    })
+                })
+                .collect(),
+        ),
+        ("root".to_string(), serde_json::to_value(root)?),
+        ("hash".to_string(), serde_json::to_value(hash.to_string())?),
+        ("timestamp".to_string(), serde_json::to_value(timestamp)?),
+    ]
+    .into_iter()
+    .collect())
+}
+
+fn with_proof_hash(
+    mut data: HashMap<String, serde_json::Value>,
+    proof_hash: Hash,
+) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+    data.insert(
+        "proofHash".to_string(),
+        serde_json::to_value(proof_hash.to_string())?,
+    );
+    Ok(data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_tower_sync() {
+        let mut data = TOWER_SYNC_TAG.to_le_bytes().to_vec();
+        data.extend_from_slice(&100u64.to_le_bytes());
+        // two lockouts at root + 1 and root + 3
+        data.extend_from_slice(&[2, 1, 2, 2, 1]);
+        data.extend_from_slice(&[1; 32]);
+        data.push(0);
+        data.extend_from_slice(&[2; 32]);
+        let vote_account = Pubkey::new_unique();
+        let decoded = VoteDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![vote_account, Pubkey::new_unique()],
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "towerSync");
+        assert_eq!(decoded.data["root"], 100);
+        assert_eq!(
+            decoded.data["lockouts"],
+            serde_json::json!([
+                {"slot": 101, "confirmationCount": 2},
+                {"slot": 103, "confirmationCount": 1},
+            ])
+        );
+        assert_eq!(
+            decoded.data["blockId"],
+            Hash::new_from_array([2; 32]).to_string()
+        );
+        assert_eq!(decoded.accounts["voteAccount"], vote_account.to_string());
+    }
+}
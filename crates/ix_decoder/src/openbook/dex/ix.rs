@@ -0,0 +1,304 @@
+//! Instruction types
+//!
+//! Instruction data starts with a version byte, which must be 0, followed by the
+//! little endian u32 tag of the instruction and its bincode encoded arguments.
+
+use serde::Serialize;
+use solana_sdk::program_error::ProgramError;
+
+use crate::types::serialize_u128;
+
+/// Length of a new order v3 including the `max_ts` added by later program versions
+const NEW_ORDER_V3_LEN: usize = 54;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+impl TryFrom<u32> for Side {
+    type Error = ProgramError;
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bid),
+            1 => Ok(Self::Ask),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderType {
+    Limit,
+    ImmediateOrCancel,
+    PostOnly,
+}
+
+impl TryFrom<u32> for OrderType {
+    type Error = ProgramError;
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Limit),
+            1 => Ok(Self::ImmediateOrCancel),
+            2 => Ok(Self::PostOnly),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SelfTradeBehavior {
+    DecrementTake,
+    CancelProvide,
+    AbortTransaction,
+}
+
+impl TryFrom<u32> for SelfTradeBehavior {
+    type Error = ProgramError;
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::DecrementTake),
+            1 => Ok(Self::CancelProvide),
+            2 => Ok(Self::AbortTransaction),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeMarketInstruction {
+    pub coin_lot_size: u64,
+    pub pc_lot_size: u64,
+    pub fee_rate_bps: u16,
+    pub vault_signer_nonce: u64,
+    pub pc_dust_threshold: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderInstructionV3 {
+    pub side: Side,
+    /// price in pc lots per coin lot
+    pub limit_price: u64,
+    pub max_coin_qty: u64,
+    pub max_native_pc_qty_including_fees: u64,
+    pub self_trade_behavior: SelfTradeBehavior,
+    pub order_type: OrderType,
+    pub client_order_id: u64,
+    /// maximum number of orders to match against
+    pub limit: u16,
+    /// unix timestamp after which the order is rejected, i64::MAX when not given
+    pub max_ts: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderInstructionV2 {
+    pub side: Side,
+    #[serde(serialize_with = "serialize_u128")]
+    pub order_id: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendTakeInstruction {
+    pub side: Side,
+    pub limit_price: u64,
+    pub max_coin_qty: u64,
+    pub max_native_pc_qty_including_fees: u64,
+    pub min_coin_qty: u64,
+    pub min_native_pc_qty: u64,
+    pub limit: u16,
+}
+
+/// Instructions supported by the serum dex v3 program and its openbook fork.
+///
+/// The v1 and v2 order instructions were deprecated in favour of their v3 equivalents
+/// and are only recognised, not decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MarketInstruction {
+    InitializeMarket(InitializeMarketInstruction),
+    NewOrder,
+    MatchOrders(u16),
+    ConsumeEvents(u16),
+    CancelOrder,
+    SettleFunds,
+    CancelOrderByClientId,
+    DisableMarket,
+    SweepFees,
+    NewOrderV2,
+    NewOrderV3(NewOrderInstructionV3),
+    CancelOrderV2(CancelOrderInstructionV2),
+    CancelOrderByClientIdV2(u64),
+    SendTake(SendTakeInstruction),
+    CloseOpenOrders,
+    InitOpenOrders,
+    Prune(u16),
+    ConsumeEventsPermissioned(u16),
+    CancelOrdersByClientIds([u64; 8]),
+    ReplaceOrderByClientId(NewOrderInstructionV3),
+    ReplaceOrdersByClientIds(Vec<NewOrderInstructionV3>),
+}
+
+impl MarketInstruction {
+    /// Unpacks a byte buffer into a [MarketInstruction](enum.MarketInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&version, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if version != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (tag, rest) = Self::unpack_u32(rest)?;
+        Ok(match tag {
+            0 => {
+                let (coin_lot_size, rest) = Self::unpack_u64(rest)?;
+                let (pc_lot_size, rest) = Self::unpack_u64(rest)?;
+                let (fee_rate_bps, rest) = Self::unpack_u16(rest)?;
+                let (vault_signer_nonce, rest) = Self::unpack_u64(rest)?;
+                let (pc_dust_threshold, _rest) = Self::unpack_u64(rest)?;
+                Self::InitializeMarket(InitializeMarketInstruction {
+                    coin_lot_size,
+                    pc_lot_size,
+                    fee_rate_bps,
+                    vault_signer_nonce,
+                    pc_dust_threshold,
+                })
+            }
+            1 => Self::NewOrder,
+            2 => Self::MatchOrders(Self::unpack_u16(rest)?.0),
+            3 => Self::ConsumeEvents(Self::unpack_u16(rest)?.0),
+            4 => Self::CancelOrder,
+            5 => Self::SettleFunds,
+            6 => Self::CancelOrderByClientId,
+            7 => Self::DisableMarket,
+            8 => Self::SweepFees,
+            9 => Self::NewOrderV2,
+            10 => Self::NewOrderV3(Self::unpack_new_order_v3(rest)?.0),
+            11 => {
+                let (side, rest) = Self::unpack_u32(rest)?;
+                let (order_id, _rest) = Self::unpack_u128(rest)?;
+                Self::CancelOrderV2(CancelOrderInstructionV2 {
+                    side: side.try_into()?,
+                    order_id,
+                })
+            }
+            12 => Self::CancelOrderByClientIdV2(Self::unpack_u64(rest)?.0),
+            13 => {
+                let (side, rest) = Self::unpack_u32(rest)?;
+                let (limit_price, rest) = Self::unpack_u64(rest)?;
+                let (max_coin_qty, rest) = Self::unpack_u64(rest)?;
+                let (max_native_pc_qty_including_fees, rest) = Self::unpack_u64(rest)?;
+                let (min_coin_qty, rest) = Self::unpack_u64(rest)?;
+                let (min_native_pc_qty, rest) = Self::unpack_u64(rest)?;
+                let (limit, _rest) = Self::unpack_u16(rest)?;
+                Self::SendTake(SendTakeInstruction {
+                    side: side.try_into()?,
+                    limit_price,
+                    max_coin_qty,
+                    max_native_pc_qty_including_fees,
+                    min_coin_qty,
+                    min_native_pc_qty,
+                    limit,
+                })
+            }
+            14 => Self::CloseOpenOrders,
+            15 => Self::InitOpenOrders,
+            16 => Self::Prune(Self::unpack_u16(rest)?.0),
+            17 => Self::ConsumeEventsPermissioned(Self::unpack_u16(rest)?.0),
+            18 => {
+                let mut client_order_ids = [0u64; 8];
+                let mut rest = rest;
+                for client_order_id in client_order_ids.iter_mut() {
+                    (*client_order_id, rest) = Self::unpack_u64(rest)?;
+                }
+                Self::CancelOrdersByClientIds(client_order_ids)
+            }
+            19 => Self::ReplaceOrderByClientId(Self::unpack_new_order_v3(rest)?.0),
+            20 => {
+                let (count, mut rest) = Self::unpack_u64(rest)?;
+                let mut orders = Vec::new();
+                for _ in 0..count {
+                    // every order carries its max_ts, unlike a single new order
+                    if rest.len() < NEW_ORDER_V3_LEN {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                    let (order, _) = Self::unpack_new_order_v3(&rest[..NEW_ORDER_V3_LEN])?;
+                    orders.push(order);
+                    rest = &rest[NEW_ORDER_V3_LEN..];
+                }
+                Self::ReplaceOrdersByClientIds(orders)
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_new_order_v3(input: &[u8]) -> Result<(NewOrderInstructionV3, &[u8]), ProgramError> {
+        let (side, rest) = Self::unpack_u32(input)?;
+        let (limit_price, rest) = Self::unpack_u64(rest)?;
+        let (max_coin_qty, rest) = Self::unpack_u64(rest)?;
+        let (max_native_pc_qty_including_fees, rest) = Self::unpack_u64(rest)?;
+        let (self_trade_behavior, rest) = Self::unpack_u32(rest)?;
+        let (order_type, rest) = Self::unpack_u32(rest)?;
+        let (client_order_id, rest) = Self::unpack_u64(rest)?;
+        let (limit, rest) = Self::unpack_u16(rest)?;
+        // max_ts was added by a later program version
+        let (max_ts, rest) = if rest.len() >= 8 {
+            let (max_ts, rest) = Self::unpack_u64(rest)?;
+            (max_ts as i64, rest)
+        } else {
+            (i64::MAX, rest)
+        };
+        Ok((
+            NewOrderInstructionV3 {
+                side: side.try_into()?,
+                limit_price,
+                max_coin_qty,
+                max_native_pc_qty_including_fees,
+                self_trade_behavior: self_trade_behavior.try_into()?,
+                order_type: order_type.try_into()?,
+                client_order_id,
+                limit,
+                max_ts,
+            },
+            rest,
+        ))
+    }
+
+    fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+        if input.len() < 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (value, rest) = input.split_at(2);
+        Ok((u16::from_le_bytes(value.try_into().unwrap()), rest))
+    }
+
+    fn unpack_u32(input: &[u8]) -> Result<(u32, &[u8]), ProgramError> {
+        if input.len() < 4 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (value, rest) = input.split_at(4);
+        Ok((u32::from_le_bytes(value.try_into().unwrap()), rest))
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (value, rest) = input.split_at(8);
+        Ok((u64::from_le_bytes(value.try_into().unwrap()), rest))
+    }
+
+    fn unpack_u128(input: &[u8]) -> Result<(u128, &[u8]), ProgramError> {
+        if input.len() < 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (value, rest) = input.split_at(16);
+        Ok((u128::from_le_bytes(value.try_into().unwrap()), rest))
+    }
+}
@@ -0,0 +1,360 @@
+//! Instruction decoding functions for the serum dex v3 program and its openbook v1 fork
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use ix::MarketInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const SERUM_V3_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+pub const OPENBOOK_V1_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX");
+
+/// Deployments sharing the serum dex v3 instruction set and account layouts
+pub const PROGRAM_IDS: [Pubkey; 2] = [SERUM_V3_PROGRAM_ID, OPENBOOK_V1_PROGRAM_ID];
+
+const NEW_ORDER_ACCOUNTS: [&str; 13] = [
+    "market",
+    "openOrders",
+    "requestQueue",
+    "eventQueue",
+    "bids",
+    "asks",
+    "orderPayer",
+    "openOrdersOwner",
+    "coinVault",
+    "pcVault",
+    "tokenProgram",
+    "rent",
+    "srmFeeDiscount",
+];
+
+const CANCEL_ORDER_ACCOUNTS: [&str; 6] = [
+    "market",
+    "bids",
+    "asks",
+    "openOrders",
+    "openOrdersOwner",
+    "eventQueue",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DexDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl DexDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = MarketInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            MarketInstruction::InitializeMarket(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "requestQueue",
+                    "eventQueue",
+                    "bids",
+                    "asks",
+                    "coinVault",
+                    "pcVault",
+                    "coinMint",
+                    "pcMint",
+                    "rent",
+                    "openOrdersMarketAuthority",
+                    "pruneAuthority",
+                    "crankAuthority",
+                ]),
+                name: "initializeMarket".to_string(),
+            }),
+            MarketInstruction::NewOrder
+            | MarketInstruction::CancelOrder
+            | MarketInstruction::CancelOrderByClientId
+            | MarketInstruction::NewOrderV2 => Err(anyhow!("unimplemented")),
+            MarketInstruction::MatchOrders(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "requestQueue",
+                    "eventQueue",
+                    "bids",
+                    "asks",
+                    "coinFeeReceivable",
+                    "pcFeeReceivable",
+                ]),
+                name: "matchOrders".to_string(),
+            }),
+            MarketInstruction::ConsumeEvents(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.crank_accounts(&[
+                    "market",
+                    "eventQueue",
+                    "coinFeeReceivable",
+                    "pcFeeReceivable",
+                ]),
+                name: "consumeEvents".to_string(),
+            }),
+            MarketInstruction::ConsumeEventsPermissioned(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.crank_accounts(&["market", "eventQueue", "crankAuthority"]),
+                name: "consumeEventsPermissioned".to_string(),
+            }),
+            MarketInstruction::SettleFunds => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "openOrders",
+                    "openOrdersOwner",
+                    "coinVault",
+                    "pcVault",
+                    "coinWallet",
+                    "pcWallet",
+                    "vaultSigner",
+                    "tokenProgram",
+                    "referrerPcWallet",
+                ]),
+                name: "settleFunds".to_string(),
+                ..Default::default()
+            }),
+            MarketInstruction::DisableMarket => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["market", "disableAuthority"]),
+                name: "disableMarket".to_string(),
+                ..Default::default()
+            }),
+            MarketInstruction::SweepFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "pcVault",
+                    "feeSweepingAuthority",
+                    "pcWallet",
+                    "vaultSigner",
+                    "tokenProgram",
+                ]),
+                name: "sweepFees".to_string(),
+                ..Default::default()
+            }),
+            MarketInstruction::NewOrderV3(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&NEW_ORDER_ACCOUNTS),
+                name: "newOrderV3".to_string(),
+            }),
+            MarketInstruction::CancelOrderV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CANCEL_ORDER_ACCOUNTS),
+                name: "cancelOrderV2".to_string(),
+            }),
+            MarketInstruction::CancelOrderByClientIdV2(client_order_id) => Ok(DecodedInstruction {
+                data: [(
+                    "clientOrderId".to_string(),
+                    serde_json::to_value(client_order_id)?,
+                )]
+                .into_iter()
+                .collect(),
+                accounts: self.ix.named_accounts(&CANCEL_ORDER_ACCOUNTS),
+                name: "cancelOrderByClientIdV2".to_string(),
+            }),
+            MarketInstruction::SendTake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "requestQueue",
+                    "eventQueue",
+                    "bids",
+                    "asks",
+                    "coinWallet",
+                    "pcWallet",
+                    "signer",
+                    "coinVault",
+                    "pcVault",
+                    "tokenProgram",
+                    "vaultSigner",
+                    "srmFeeDiscount",
+                ]),
+                name: "sendTake".to_string(),
+            }),
+            MarketInstruction::CloseOpenOrders => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "openOrders",
+                    "openOrdersOwner",
+                    "destination",
+                    "market",
+                ]),
+                name: "closeOpenOrders".to_string(),
+                ..Default::default()
+            }),
+            MarketInstruction::InitOpenOrders => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "openOrders",
+                    "openOrdersOwner",
+                    "market",
+                    "rent",
+                    "openOrdersMarketAuthority",
+                ]),
+                name: "initOpenOrders".to_string(),
+                ..Default::default()
+            }),
+            MarketInstruction::Prune(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "bids",
+                    "asks",
+                    "pruneAuthority",
+                    "openOrders",
+                    "openOrdersOwner",
+                    "eventQueue",
+                ]),
+                name: "prune".to_string(),
+            }),
+            MarketInstruction::CancelOrdersByClientIds(client_order_ids) => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "clientOrderIds".to_string(),
+                        serde_json::to_value(client_order_ids)?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&CANCEL_ORDER_ACCOUNTS),
+                    name: "cancelOrdersByClientIds".to_string(),
+                })
+            }
+            MarketInstruction::ReplaceOrderByClientId(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&NEW_ORDER_ACCOUNTS),
+                name: "replaceOrderByClientId".to_string(),
+            }),
+            MarketInstruction::ReplaceOrdersByClientIds(orders) => Ok(DecodedInstruction {
+                data: [("orders".to_string(), serde_json::to_value(orders)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&NEW_ORDER_ACCOUNTS),
+                name: "replaceOrdersByClientIds".to_string(),
+            }),
+        }
+    }
+
+    /// Crank instructions take a variable number of open orders accounts followed by
+    /// the fixed `names` accounts
+    fn crank_accounts(&self, names: &[&str]) -> HashMap<String, serde_json::Value> {
+        let split = self.ix.accounts.len().saturating_sub(names.len());
+        let (open_orders, rest) = self.ix.accounts.split_at(split);
+        let mut accounts: HashMap<_, _> = rest
+            .iter()
+            .zip(names)
+            .map(|(account, name)| {
+                (
+                    name.to_string(),
+                    serde_json::Value::String(account.to_string()),
+                )
+            })
+            .collect();
+        accounts.insert(
+            "openOrders".to_string(),
+            open_orders.iter().map(ToString::to_string).collect(),
+        );
+        accounts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_new_order_v3_and_consume_events() {
+        let mut data = vec![0u8];
+        data.extend(10u32.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.extend(2_500u64.to_le_bytes());
+        data.extend(10u64.to_le_bytes());
+        data.extend(u64::MAX.to_le_bytes());
+        data.extend(2u32.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.extend(42u64.to_le_bytes());
+        data.extend(65_535u16.to_le_bytes());
+        let decode = |data: Vec<u8>, accounts: usize| {
+            DexDecoder::new(PartiallyDecodedInstruction {
+                program_id: OPENBOOK_V1_PROGRAM_ID,
+                accounts: (0..accounts).map(|_| Pubkey::new_unique()).collect(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        let decoded = decode(data, 12);
+        assert_eq!(decoded.name, "newOrderV3");
+        assert_eq!(decoded.data["side"], "ask");
+        assert_eq!(decoded.data["orderType"], "immediateOrCancel");
+        assert_eq!(decoded.data["selfTradeBehavior"], "abortTransaction");
+        assert_eq!(decoded.data["clientOrderId"], 42);
+        assert_eq!(decoded.data["maxTs"], i64::MAX);
+        assert!(!decoded.accounts.contains_key("srmFeeDiscount"));
+
+        let mut data = vec![0u8];
+        data.extend(3u32.to_le_bytes());
+        data.extend(5u16.to_le_bytes());
+        let decoded = decode(data, 7);
+        assert_eq!(decoded.name, "consumeEvents");
+        assert_eq!(decoded.accounts["openOrders"].as_array().unwrap().len(), 3);
+        assert!(decoded.accounts.contains_key("pcFeeReceivable"));
+    }
+
+    #[test]
+    fn test_unpack_market_and_open_orders() {
+        let wrap = |inner: Vec<u8>| {
+            let mut data = state::ACCOUNT_HEAD_PADDING.to_vec();
+            data.extend(inner);
+            data.extend(state::ACCOUNT_TAIL_PADDING);
+            data
+        };
+        let market_address = Pubkey::new_unique();
+        let coin_mint = Pubkey::new_unique();
+        let pc_mint = Pubkey::new_unique();
+        let mut market = vec![0u8; state::MARKET_LEN];
+        market[..8].copy_from_slice(
+            &(state::account_flag::INITIALIZED | state::account_flag::MARKET).to_le_bytes(),
+        );
+        market[8..40].copy_from_slice(market_address.as_ref());
+        market[48..80].copy_from_slice(coin_mint.as_ref());
+        market[80..112].copy_from_slice(pc_mint.as_ref());
+        let market = state::Market::unpack(&wrap(market)).unwrap();
+        assert_eq!(market.own_address, market_address);
+        assert_eq!(market.coin_mint, coin_mint);
+        assert_eq!(market.pc_mint, pc_mint);
+
+        let mut open_orders = vec![0u8; state::OPEN_ORDERS_LEN];
+        open_orders[..8].copy_from_slice(
+            &(state::account_flag::INITIALIZED | state::account_flag::OPEN_ORDERS).to_le_bytes(),
+        );
+        open_orders[8..40].copy_from_slice(market_address.as_ref());
+        // every slot free except slot 1, which holds a bid
+        open_orders[104..120].copy_from_slice(&(u128::MAX ^ 0b10).to_le_bytes());
+        open_orders[120..136].copy_from_slice(&0b10u128.to_le_bytes());
+        open_orders[152..168].copy_from_slice(&((25u128 << 64) | 7).to_le_bytes());
+        let open_orders = state::OpenOrders::unpack(&wrap(open_orders)).unwrap();
+        assert_eq!(open_orders.market, market_address);
+        assert_eq!(open_orders.orders.len(), 1);
+        assert_eq!(open_orders.orders[0].slot, 1);
+        assert_eq!(open_orders.orders[0].side, ix::Side::Bid);
+        assert_eq!(open_orders.orders[0].price, 25);
+
+        assert!(state::Market::unpack(&wrap(vec![0u8; state::MARKET_LEN])).is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! Account types
+//!
+//! Every dex account is wrapped in a 5 byte `serum` head and a 7 byte `padding` tail,
+//! with the account type identified by the flags stored at the start of the account.
+
+use std::cmp::Reverse;
+
+use anyhow::anyhow;
+use arrayref::{array_ref, array_refs};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::ix::Side;
+use crate::types::{serialize_pubkey, serialize_u128};
+
+pub const ACCOUNT_HEAD_PADDING: &[u8; 5] = b"serum";
+pub const ACCOUNT_TAIL_PADDING: &[u8; 7] = b"padding";
+
+pub const MARKET_LEN: usize = 376;
+pub const OPEN_ORDERS_LEN: usize = 3216;
+const QUEUE_HEADER_LEN: usize = 32;
+const EVENT_LEN: usize = 88;
+const SLAB_HEADER_LEN: usize = 40;
+const SLAB_NODE_LEN: usize = 72;
+
+/// Bit flags stored at the start of every dex account
+pub mod account_flag {
+    pub const INITIALIZED: u64 = 1 << 0;
+    pub const MARKET: u64 = 1 << 1;
+    pub const OPEN_ORDERS: u64 = 1 << 2;
+    pub const REQUEST_QUEUE: u64 = 1 << 3;
+    pub const EVENT_QUEUE: u64 = 1 << 4;
+    pub const BIDS: u64 = 1 << 5;
+    pub const ASKS: u64 = 1 << 6;
+    pub const DISABLED: u64 = 1 << 7;
+    pub const CLOSED: u64 = 1 << 8;
+    pub const PERMISSIONED: u64 = 1 << 9;
+    pub const CRANK_AUTHORITY_REQUIRED: u64 = 1 << 10;
+}
+
+/// Strips the account padding, checking the account carries the expected flag
+fn strip_padding(data: &[u8], expected_flag: u64) -> anyhow::Result<&[u8]> {
+    if data.len() < ACCOUNT_HEAD_PADDING.len() + ACCOUNT_TAIL_PADDING.len() + 8
+        || !data.starts_with(ACCOUNT_HEAD_PADDING)
+        || !data.ends_with(ACCOUNT_TAIL_PADDING)
+    {
+        return Err(anyhow!("invalid account padding"));
+    }
+    let inner = &data[ACCOUNT_HEAD_PADDING.len()..data.len() - ACCOUNT_TAIL_PADDING.len()];
+    let flags = u64::from_le_bytes(*array_ref![inner, 0, 8]);
+    if flags & (account_flag::INITIALIZED | expected_flag)
+        != account_flag::INITIALIZED | expected_flag
+    {
+        return Err(anyhow!("unexpected account flags {flags:#x}"));
+    }
+    Ok(inner)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    pub account_flags: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub own_address: Pubkey,
+    pub vault_signer_nonce: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub coin_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pc_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub coin_vault: Pubkey,
+    pub coin_deposits_total: u64,
+    pub coin_fees_accrued: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pc_vault: Pubkey,
+    pub pc_deposits_total: u64,
+    pub pc_fees_accrued: u64,
+    pub pc_dust_threshold: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub request_queue: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub event_queue: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub bids: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub asks: Pubkey,
+    pub coin_lot_size: u64,
+    pub pc_lot_size: u64,
+    pub fee_rate_bps: u64,
+    pub referrer_rebates_accrued: u64,
+}
+
+impl Market {
+    /// Unpacks a market, the authorities appended by permissioned markets are not decoded
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let inner = strip_padding(data, account_flag::MARKET)?;
+        if inner.len() < MARKET_LEN {
+            return Err(anyhow!("invalid market length"));
+        }
+        let input = array_ref![inner, 0, MARKET_LEN];
+        let (
+            account_flags,
+            own_address,
+            vault_signer_nonce,
+            coin_mint,
+            pc_mint,
+            coin_vault,
+            coin_deposits_total,
+            coin_fees_accrued,
+            pc_vault,
+            pc_deposits_total,
+            pc_fees_accrued,
+            pc_dust_threshold,
+            request_queue,
+            event_queue,
+            bids,
+            asks,
+            coin_lot_size,
+            pc_lot_size,
+            fee_rate_bps,
+            referrer_rebates_accrued,
+        ) = array_refs![input, 8, 32, 8, 32, 32, 32, 8, 8, 32, 8, 8, 8, 32, 32, 32, 32, 8, 8, 8, 8];
+        Ok(Self {
+            account_flags: u64::from_le_bytes(*account_flags),
+            own_address: Pubkey::new_from_array(*own_address),
+            vault_signer_nonce: u64::from_le_bytes(*vault_signer_nonce),
+            coin_mint: Pubkey::new_from_array(*coin_mint),
+            pc_mint: Pubkey::new_from_array(*pc_mint),
+            coin_vault: Pubkey::new_from_array(*coin_vault),
+            coin_deposits_total: u64::from_le_bytes(*coin_deposits_total),
+            coin_fees_accrued: u64::from_le_bytes(*coin_fees_accrued),
+            pc_vault: Pubkey::new_from_array(*pc_vault),
+            pc_deposits_total: u64::from_le_bytes(*pc_deposits_total),
+            pc_fees_accrued: u64::from_le_bytes(*pc_fees_accrued),
+            pc_dust_threshold: u64::from_le_bytes(*pc_dust_threshold),
+            request_queue: Pubkey::new_from_array(*request_queue),
+            event_queue: Pubkey::new_from_array(*event_queue),
+            bids: Pubkey::new_from_array(*bids),
+            asks: Pubkey::new_from_array(*asks),
+            coin_lot_size: u64::from_le_bytes(*coin_lot_size),
+            pc_lot_size: u64::from_le_bytes(*pc_lot_size),
+            fee_rate_bps: u64::from_le_bytes(*fee_rate_bps),
+            referrer_rebates_accrued: u64::from_le_bytes(*referrer_rebates_accrued),
+        })
+    }
+}
+
+/// An order held in one of the 128 slots of an open orders account
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrdersSlot {
+    pub slot: u8,
+    pub side: Side,
+    #[serde(serialize_with = "serialize_u128")]
+    pub order_id: u128,
+    /// price in pc lots per coin lot, stored in the upper bits of the order id
+    pub price: u64,
+    pub client_order_id: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrders {
+    pub account_flags: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    pub native_coin_free: u64,
+    pub native_coin_total: u64,
+    pub native_pc_free: u64,
+    pub native_pc_total: u64,
+    /// the occupied order slots
+    pub orders: Vec<OpenOrdersSlot>,
+    pub referrer_rebates_accrued: u64,
+}
+
+impl OpenOrders {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let inner = strip_padding(data, account_flag::OPEN_ORDERS)?;
+        if inner.len() < OPEN_ORDERS_LEN {
+            return Err(anyhow!("invalid open orders length"));
+        }
+        let input = array_ref![inner, 0, OPEN_ORDERS_LEN];
+        let (
+            account_flags,
+            market,
+            owner,
+            native_coin_free,
+            native_coin_total,
+            native_pc_free,
+            native_pc_total,
+            free_slot_bits,
+            is_bid_bits,
+            orders,
+            client_order_ids,
+            referrer_rebates_accrued,
+        ) = array_refs![input, 8, 32, 32, 8, 8, 8, 8, 16, 16, 2048, 1024, 8];
+        let free_slot_bits = u128::from_le_bytes(*free_slot_bits);
+        let is_bid_bits = u128::from_le_bytes(*is_bid_bits);
+        let orders = (0..128u8)
+            .filter(|slot| free_slot_bits & (1 << slot) == 0)
+            .map(|slot| {
+                let idx = slot as usize;
+                let order_id = u128::from_le_bytes(*array_ref![orders, idx * 16, 16]);
+                OpenOrdersSlot {
+                    slot,
+                    side: if is_bid_bits & (1 << slot) != 0 {
+                        Side::Bid
+                    } else {
+                        Side::Ask
+                    },
+                    order_id,
+                    price: (order_id >> 64) as u64,
+                    client_order_id: u64::from_le_bytes(*array_ref![client_order_ids, idx * 8, 8]),
+                }
+            })
+            .collect();
+        Ok(Self {
+            account_flags: u64::from_le_bytes(*account_flags),
+            market: Pubkey::new_from_array(*market),
+            owner: Pubkey::new_from_array(*owner),
+            native_coin_free: u64::from_le_bytes(*native_coin_free),
+            native_coin_total: u64::from_le_bytes(*native_coin_total),
+            native_pc_free: u64::from_le_bytes(*native_pc_free),
+            native_pc_total: u64::from_le_bytes(*native_pc_total),
+            orders,
+            referrer_rebates_accrued: u64::from_le_bytes(*referrer_rebates_accrued),
+        })
+    }
+}
+
+/// A fill or an order leaving the book, consumed by the crank to settle open orders
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    /// true for fills, false when the order left the book
+    pub fill: bool,
+    pub side: Side,
+    pub maker: bool,
+    pub release_funds: bool,
+    pub owner_slot: u8,
+    pub fee_tier: u8,
+    pub native_qty_released: u64,
+    pub native_qty_paid: u64,
+    pub native_fee_or_rebate: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub order_id: u128,
+    /// the open orders account of the order
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    pub client_order_id: u64,
+}
+
+impl Event {
+    fn unpack(input: &[u8; EVENT_LEN]) -> Self {
+        let (
+            flags,
+            owner_slot,
+            fee_tier,
+            _padding,
+            native_qty_released,
+            native_qty_paid,
+            native_fee_or_rebate,
+            order_id,
+            owner,
+            client_order_id,
+        ) = array_refs![input, 1, 1, 1, 5, 8, 8, 8, 16, 32, 8];
+        let flags = flags[0];
+        Self {
+            fill: flags & 0x1 != 0,
+            side: if flags & 0x4 != 0 {
+                Side::Bid
+            } else {
+                Side::Ask
+            },
+            maker: flags & 0x8 != 0,
+            release_funds: flags & 0x10 != 0,
+            owner_slot: owner_slot[0],
+            fee_tier: fee_tier[0],
+            native_qty_released: u64::from_le_bytes(*native_qty_released),
+            native_qty_paid: u64::from_le_bytes(*native_qty_paid),
+            native_fee_or_rebate: u64::from_le_bytes(*native_fee_or_rebate),
+            order_id: u128::from_le_bytes(*order_id),
+            owner: Pubkey::new_from_array(*owner),
+            client_order_id: u64::from_le_bytes(*client_order_id),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventQueue {
+    pub account_flags: u64,
+    /// sequence number of the next event pushed to the queue
+    pub seq_num: u64,
+    /// the unconsumed events, oldest first
+    pub events: Vec<Event>,
+}
+
+impl EventQueue {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let inner = strip_padding(data, account_flag::EVENT_QUEUE)?;
+        if inner.len() < QUEUE_HEADER_LEN {
+            return Err(anyhow!("invalid event queue length"));
+        }
+        let (account_flags, head, count, seq_num) =
+            array_refs![array_ref![inner, 0, QUEUE_HEADER_LEN], 8, 8, 8, 8];
+        let capacity = (inner.len() - QUEUE_HEADER_LEN) / EVENT_LEN;
+        let head = u64::from_le_bytes(*head) as usize;
+        let count = u64::from_le_bytes(*count) as usize;
+        if capacity == 0 || count > capacity {
+            return Err(anyhow!("invalid event queue count"));
+        }
+        let events = (0..count)
+            .map(|idx| {
+                let offset = QUEUE_HEADER_LEN + ((head + idx) % capacity) * EVENT_LEN;
+                Event::unpack(array_ref![inner, offset, EVENT_LEN])
+            })
+            .collect();
+        Ok(Self {
+            account_flags: u64::from_le_bytes(*account_flags),
+            seq_num: u64::from_le_bytes(*seq_num),
+            events,
+        })
+    }
+}
+
+/// An order resting on the book
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlabOrder {
+    #[serde(serialize_with = "serialize_u128")]
+    pub order_id: u128,
+    pub price: u64,
+    /// the open orders account of the order
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    pub owner_slot: u8,
+    pub fee_tier: u8,
+    /// remaining quantity in coin lots
+    pub quantity: u64,
+    pub client_order_id: u64,
+}
+
+/// One side of the order book, stored as a crit-bit tree keyed by order id
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slab {
+    pub account_flags: u64,
+    pub side: Side,
+    /// the resting orders, best price first
+    pub orders: Vec<SlabOrder>,
+}
+
+impl Slab {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let inner = strip_padding(data, account_flag::INITIALIZED)?;
+        let account_flags = u64::from_le_bytes(*array_ref![inner, 0, 8]);
+        let side = match (
+            account_flags & account_flag::BIDS != 0,
+            account_flags & account_flag::ASKS != 0,
+        ) {
+            (true, false) => Side::Bid,
+            (false, true) => Side::Ask,
+            _ => return Err(anyhow!("unexpected account flags {account_flags:#x}")),
+        };
+        if inner.len() < SLAB_HEADER_LEN {
+            return Err(anyhow!("invalid slab length"));
+        }
+        // the bump index bounds the nodes which have ever been allocated
+        let bump_index = u64::from_le_bytes(*array_ref![inner, 8, 8]) as usize;
+        let nodes = &inner[SLAB_HEADER_LEN..];
+        let mut orders = nodes
+            .chunks_exact(SLAB_NODE_LEN)
+            .take(bump_index)
+            .filter(|node| u32::from_le_bytes(*array_ref![node, 0, 4]) == 2)
+            .map(|node| {
+                let (_tag, owner_slot, fee_tier, _padding, key, owner, quantity, client_order_id) =
+                    array_refs![array_ref![node, 0, SLAB_NODE_LEN], 4, 1, 1, 2, 16, 32, 8, 8];
+                let order_id = u128::from_le_bytes(*key);
+                SlabOrder {
+                    order_id,
+                    price: (order_id >> 64) as u64,
+                    owner: Pubkey::new_from_array(*owner),
+                    owner_slot: owner_slot[0],
+                    fee_tier: fee_tier[0],
+                    quantity: u64::from_le_bytes(*quantity),
+                    client_order_id: u64::from_le_bytes(*client_order_id),
+                }
+            })
+            .collect::<Vec<_>>();
+        // order ids hold the price in the upper 64 bits and the sequence number in the lower,
+        // negated for bids, so sorting by id orders the book by price and then time
+        match side {
+            Side::Bid => orders.sort_by_key(|order| Reverse(order.order_id)),
+            Side::Ask => orders.sort_by_key(|order| order.order_id),
+        }
+        Ok(Self {
+            account_flags,
+            side,
+            orders,
+        })
+    }
+}
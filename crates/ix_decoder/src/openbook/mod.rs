@@ -0,0 +1,55 @@
+//! Instruction decoding functions for the openbook and serum order book programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use dex::DexDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use v2::OpenbookV2Decoder;
+
+pub mod dex;
+pub mod v2;
+
+pub static OPENBOOK_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(OpenbookProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Dex(DexDecoder),
+    V2(OpenbookV2Decoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct OpenbookProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Dex(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Openbook::Dex"),
+            Self::V2(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Openbook::V2"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Dex(decoder) => format!("{:#?}", decoder),
+            Self::V2(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for OpenbookProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            program_id if dex::PROGRAM_IDS.contains(&program_id) => {
+                Some(Box::new(Programs::Dex(DexDecoder::new(ix.clone()))))
+            }
+            v2::PROGRAM_ID => Some(Box::new(Programs::V2(OpenbookV2Decoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Event types, emitted by the program through logs or self cpi
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::ix::Side;
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey},
+};
+
+pub const FILL_LOG: [u8; 8] = [150, 23, 41, 148, 152, 162, 215, 64];
+pub const TOTAL_ORDER_FILL_EVENT: [u8; 8] = [8, 235, 48, 58, 174, 76, 156, 105];
+pub const MARKET_META_DATA_LOG: [u8; 8] = [209, 87, 212, 236, 164, 58, 60, 117];
+pub const SETTLE_FUNDS_LOG: [u8; 8] = [10, 50, 240, 117, 237, 67, 230, 233];
+pub const DEPOSIT_LOG: [u8; 8] = [141, 186, 168, 252, 108, 141, 72, 94];
+
+/// A match between a taker and a resting maker order, quantities are in lots
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FillLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market: Pubkey,
+    /// 0 when the taker bought, 1 when it sold
+    pub taker_side: u8,
+    pub maker_slot: u8,
+    /// true when the maker order was completely filled
+    pub maker_out: bool,
+    pub timestamp: u64,
+    pub seq_num: u64,
+    /// open orders account of the maker
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    pub maker_client_order_id: u64,
+    /// in native quote
+    pub maker_fee: u64,
+    pub maker_timestamp: u64,
+    /// owner of the taker order
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub taker: Pubkey,
+    pub taker_client_order_id: u64,
+    /// in native quote
+    pub taker_fee_ceil: u64,
+    /// in quote lots per base lot
+    pub price: i64,
+    /// in base lots
+    pub quantity: i64,
+}
+
+/// A fill with its price and size converted to native units
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market: Pubkey,
+    pub seq_num: u64,
+    pub timestamp: u64,
+    pub taker_side: Side,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    pub maker_client_order_id: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub taker: Pubkey,
+    pub taker_client_order_id: u64,
+    /// native quote per native base
+    pub price: f64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub maker_fee: u64,
+    pub taker_fee: u64,
+}
+
+impl FillLog {
+    /// Converts the fill to native units using the market's lot sizes, which are found in
+    /// the market account or the `MarketMetaDataLog` emitted when it was created
+    pub fn to_fill(&self, base_lot_size: i64, quote_lot_size: i64) -> Fill {
+        let base_amount = self.quantity as i128 * base_lot_size as i128;
+        let quote_amount = self.quantity as i128 * self.price as i128 * quote_lot_size as i128;
+        Fill {
+            market: self.market,
+            seq_num: self.seq_num,
+            timestamp: self.timestamp,
+            taker_side: if self.taker_side == 0 {
+                Side::Bid
+            } else {
+                Side::Ask
+            },
+            maker: self.maker,
+            maker_client_order_id: self.maker_client_order_id,
+            taker: self.taker,
+            taker_client_order_id: self.taker_client_order_id,
+            price: self.price as f64 * quote_lot_size as f64 / base_lot_size as f64,
+            base_amount: base_amount as u64,
+            quote_amount: quote_amount as u64,
+            maker_fee: self.maker_fee,
+            taker_fee: self.taker_fee_ceil,
+        }
+    }
+}
+
+/// Summary of the fills of a taker order, quantities are native
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotalOrderFillEvent {
+    pub side: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub taker: Pubkey,
+    pub total_quantity_paid: u64,
+    pub total_quantity_received: u64,
+    pub fees: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketMetaDataLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market: Pubkey,
+    pub name: String,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub base_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub quote_mint: Pubkey,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub base_lot_size: i64,
+    pub quote_lot_size: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettleFundsLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub open_orders_account: Pubkey,
+    pub base_native: u64,
+    pub quote_native: u64,
+    pub referrer_rebate: u64,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub referrer: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositLog {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub open_orders_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub signer: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpenbookEvent {
+    Fill(FillLog),
+    TotalOrderFill(TotalOrderFillEvent),
+    MarketMetaData(MarketMetaDataLog),
+    SettleFunds(SettleFundsLog),
+    Deposit(DepositLog),
+}
+
+impl OpenbookEvent {
+    /// Unpacks the event data, as logged or following the self cpi event tag
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            FILL_LOG => Self::Fill(anchor::deserialize(rest)?),
+            TOTAL_ORDER_FILL_EVENT => Self::TotalOrderFill(anchor::deserialize(rest)?),
+            MARKET_META_DATA_LOG => Self::MarketMetaData(anchor::deserialize(rest)?),
+            SETTLE_FUNDS_LOG => Self::SettleFunds(anchor::deserialize(rest)?),
+            DEPOSIT_LOG => Self::Deposit(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+}
+
+/// Decodes the events the program logged during a transaction, skipping events
+/// which aren't supported
+pub fn log_events(logs: &[String]) -> Vec<OpenbookEvent> {
+    anchor::log_events(logs, &super::PROGRAM_ID)
+        .iter()
+        .filter_map(|data| OpenbookEvent::unpack(data).ok())
+        .collect()
+}
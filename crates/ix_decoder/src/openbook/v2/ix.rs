@@ -0,0 +1,228 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use super::events::OpenbookEvent;
+use crate::{anchor, types::serialize_u128};
+
+pub const CREATE_MARKET: [u8; 8] = [103, 226, 97, 235, 200, 188, 251, 254];
+pub const CREATE_OPEN_ORDERS_ACCOUNT: [u8; 8] = [204, 181, 175, 222, 40, 125, 188, 71];
+pub const CLOSE_OPEN_ORDERS_ACCOUNT: [u8; 8] = [176, 74, 115, 210, 54, 179, 91, 103];
+pub const PLACE_ORDER: [u8; 8] = [51, 194, 155, 175, 109, 130, 96, 106];
+pub const EDIT_ORDER: [u8; 8] = [254, 208, 118, 29, 173, 248, 200, 70];
+pub const PLACE_ORDERS: [u8; 8] = [60, 63, 50, 123, 12, 197, 60, 190];
+pub const CANCEL_ALL_AND_PLACE_ORDERS: [u8; 8] = [128, 155, 222, 60, 186, 40, 225, 50];
+pub const PLACE_TAKE_ORDER: [u8; 8] = [3, 44, 71, 3, 26, 199, 203, 85];
+pub const CONSUME_EVENTS: [u8; 8] = [221, 145, 177, 52, 31, 47, 63, 201];
+pub const CONSUME_GIVEN_EVENTS: [u8; 8] = [209, 227, 54, 4, 109, 172, 41, 71];
+pub const CANCEL_ORDER: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+pub const CANCEL_ORDER_BY_CLIENT_ORDER_ID: [u8; 8] = [115, 178, 201, 8, 175, 183, 123, 119];
+pub const CANCEL_ALL_ORDERS: [u8; 8] = [196, 83, 243, 171, 17, 100, 160, 143];
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const REFILL: [u8; 8] = [128, 207, 142, 11, 54, 232, 38, 201];
+pub const SETTLE_FUNDS: [u8; 8] = [238, 64, 163, 96, 75, 171, 16, 33];
+pub const SETTLE_FUNDS_EXPIRED: [u8; 8] = [107, 18, 56, 69, 228, 56, 55, 164];
+pub const SWEEP_FEES: [u8; 8] = [175, 225, 98, 71, 118, 66, 34, 148];
+pub const CLOSE_MARKET: [u8; 8] = [88, 154, 248, 186, 48, 14, 123, 244];
+pub const PRUNE_ORDERS: [u8; 8] = [27, 213, 159, 191, 12, 116, 112, 121];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlaceOrderType {
+    Limit,
+    ImmediateOrCancel,
+    PostOnly,
+    Market,
+    PostOnlySlide,
+    FillOrKill,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SelfTradeBehavior {
+    DecrementTake,
+    CancelProvide,
+    AbortTransaction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleConfigParams {
+    pub conf_filter: f32,
+    pub max_staleness_slots: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMarketInstruction {
+    pub name: String,
+    pub oracle_config: OracleConfigParams,
+    pub quote_lot_size: i64,
+    pub base_lot_size: i64,
+    pub maker_fee: i64,
+    pub taker_fee: i64,
+    /// unix timestamp after which the market can be closed, 0 if it never expires
+    pub time_expiry: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOpenOrdersAccountInstruction {
+    pub name: String,
+}
+
+/// Prices are in quote lots per base lot
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceOrderArgs {
+    pub side: Side,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots_including_fees: i64,
+    pub client_order_id: u64,
+    pub order_type: PlaceOrderType,
+    /// unix timestamp after which the order expires, 0 if it never expires
+    pub expiry_timestamp: u64,
+    pub self_trade_behavior: SelfTradeBehavior,
+    /// maximum number of orders to match against
+    pub limit: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditOrderInstruction {
+    /// client order id of the order to cancel
+    pub client_order_id: u64,
+    pub expected_cancel_size: i64,
+    pub place_order: PlaceOrderArgs,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceMultipleOrdersArgs {
+    pub price_lots: i64,
+    pub max_quote_lots_including_fees: i64,
+    pub expiry_timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceOrdersInstruction {
+    pub orders_type: PlaceOrderType,
+    pub bids: Vec<PlaceMultipleOrdersArgs>,
+    pub asks: Vec<PlaceMultipleOrdersArgs>,
+    pub limit: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceTakeOrderArgs {
+    pub side: Side,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots_including_fees: i64,
+    pub order_type: PlaceOrderType,
+    pub limit: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderInstruction {
+    #[serde(serialize_with = "serialize_u128")]
+    pub order_id: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelAllOrdersInstruction {
+    /// only orders of this side are cancelled when set
+    pub side_option: Option<Side>,
+    pub limit: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumeGivenEventsInstruction {
+    /// event heap slots of the events to consume
+    pub slots: Vec<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInstruction {
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+/// Instructions supported by the openbook v2 program, along with the events it
+/// emits through self cpi.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpenbookInstruction {
+    CreateMarket(CreateMarketInstruction),
+    CreateOpenOrdersAccount(CreateOpenOrdersAccountInstruction),
+    CloseOpenOrdersAccount,
+    PlaceOrder(PlaceOrderArgs),
+    EditOrder(EditOrderInstruction),
+    PlaceOrders(PlaceOrdersInstruction),
+    CancelAllAndPlaceOrders(PlaceOrdersInstruction),
+    PlaceTakeOrder(PlaceTakeOrderArgs),
+    ConsumeEvents(u64),
+    ConsumeGivenEvents(ConsumeGivenEventsInstruction),
+    CancelOrder(CancelOrderInstruction),
+    CancelOrderByClientOrderId(u64),
+    CancelAllOrders(CancelAllOrdersInstruction),
+    Deposit(DepositInstruction),
+    Refill(DepositInstruction),
+    SettleFunds,
+    SettleFundsExpired,
+    SweepFees,
+    CloseMarket,
+    PruneOrders(u8),
+    Event(OpenbookEvent),
+}
+
+impl OpenbookInstruction {
+    /// Unpacks a byte buffer into a [OpenbookInstruction](enum.OpenbookInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        if let Some(event) = anchor::strip_event_tag(input) {
+            return Ok(Self::Event(OpenbookEvent::unpack(event)?));
+        }
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE_MARKET => Self::CreateMarket(anchor::deserialize(rest)?),
+            CREATE_OPEN_ORDERS_ACCOUNT => Self::CreateOpenOrdersAccount(anchor::deserialize(rest)?),
+            CLOSE_OPEN_ORDERS_ACCOUNT => Self::CloseOpenOrdersAccount,
+            PLACE_ORDER => Self::PlaceOrder(anchor::deserialize(rest)?),
+            EDIT_ORDER => Self::EditOrder(anchor::deserialize(rest)?),
+            PLACE_ORDERS => Self::PlaceOrders(anchor::deserialize(rest)?),
+            CANCEL_ALL_AND_PLACE_ORDERS => {
+                Self::CancelAllAndPlaceOrders(anchor::deserialize(rest)?)
+            }
+            PLACE_TAKE_ORDER => Self::PlaceTakeOrder(anchor::deserialize(rest)?),
+            CONSUME_EVENTS => Self::ConsumeEvents(anchor::deserialize(rest)?),
+            CONSUME_GIVEN_EVENTS => Self::ConsumeGivenEvents(anchor::deserialize(rest)?),
+            CANCEL_ORDER => Self::CancelOrder(anchor::deserialize(rest)?),
+            CANCEL_ORDER_BY_CLIENT_ORDER_ID => {
+                Self::CancelOrderByClientOrderId(anchor::deserialize(rest)?)
+            }
+            CANCEL_ALL_ORDERS => Self::CancelAllOrders(anchor::deserialize(rest)?),
+            DEPOSIT => Self::Deposit(anchor::deserialize(rest)?),
+            REFILL => Self::Refill(anchor::deserialize(rest)?),
+            SETTLE_FUNDS => Self::SettleFunds,
+            SETTLE_FUNDS_EXPIRED => Self::SettleFundsExpired,
+            SWEEP_FEES => Self::SweepFees,
+            CLOSE_MARKET => Self::CloseMarket,
+            PRUNE_ORDERS => Self::PruneOrders(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,378 @@
+//! Instruction decoding functions for the openbook v2 program
+//!
+//! Fills are logged rather than emitted through self cpi, [events::log_events] decodes
+//! them from the logs of a transaction.
+
+use anyhow::Context;
+use events::OpenbookEvent;
+use ix::OpenbookInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb");
+
+const PLACE_ORDER_ACCOUNTS: [&str; 12] = [
+    "signer",
+    "openOrdersAccount",
+    "openOrdersAdmin",
+    "userTokenAccount",
+    "market",
+    "bids",
+    "asks",
+    "eventHeap",
+    "marketVault",
+    "oracleA",
+    "oracleB",
+    "tokenProgram",
+];
+
+const PLACE_ORDERS_ACCOUNTS: [&str; 14] = [
+    "signer",
+    "openOrdersAccount",
+    "openOrdersAdmin",
+    "userQuoteAccount",
+    "userBaseAccount",
+    "market",
+    "bids",
+    "asks",
+    "eventHeap",
+    "marketQuoteVault",
+    "marketBaseVault",
+    "oracleA",
+    "oracleB",
+    "tokenProgram",
+];
+
+const CANCEL_ORDER_ACCOUNTS: [&str; 5] = ["signer", "openOrdersAccount", "market", "bids", "asks"];
+
+const CONSUME_EVENTS_ACCOUNTS: [&str; 3] = ["consumeEventsAdmin", "market", "eventHeap"];
+
+const DEPOSIT_ACCOUNTS: [&str; 8] = [
+    "owner",
+    "userBaseAccount",
+    "userQuoteAccount",
+    "openOrdersAccount",
+    "market",
+    "marketBaseVault",
+    "marketQuoteVault",
+    "tokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OpenbookV2Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl OpenbookV2Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = OpenbookInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            OpenbookInstruction::CreateMarket(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "market",
+                    "marketAuthority",
+                    "bids",
+                    "asks",
+                    "eventHeap",
+                    "payer",
+                    "marketBaseVault",
+                    "marketQuoteVault",
+                    "baseMint",
+                    "quoteMint",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "oracleA",
+                    "oracleB",
+                    "collectFeeAdmin",
+                    "openOrdersAdmin",
+                    "consumeEventsAdmin",
+                    "closeMarketAdmin",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "createMarket".to_string(),
+            }),
+            OpenbookInstruction::CreateOpenOrdersAccount(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "owner",
+                    "delegateAccount",
+                    "openOrdersIndexer",
+                    "openOrdersAccount",
+                    "market",
+                    "systemProgram",
+                ]),
+                name: "createOpenOrdersAccount".to_string(),
+            }),
+            OpenbookInstruction::CloseOpenOrdersAccount => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "openOrdersIndexer",
+                    "openOrdersAccount",
+                    "solDestination",
+                    "systemProgram",
+                ]),
+                name: "closeOpenOrdersAccount".to_string(),
+                ..Default::default()
+            }),
+            OpenbookInstruction::PlaceOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDER_ACCOUNTS),
+                name: "placeOrder".to_string(),
+            }),
+            OpenbookInstruction::EditOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDER_ACCOUNTS),
+                name: "editOrder".to_string(),
+            }),
+            OpenbookInstruction::PlaceOrders(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDERS_ACCOUNTS),
+                name: "placeOrders".to_string(),
+            }),
+            OpenbookInstruction::CancelAllAndPlaceOrders(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDERS_ACCOUNTS),
+                name: "cancelAllAndPlaceOrders".to_string(),
+            }),
+            OpenbookInstruction::PlaceTakeOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "signer",
+                    "penaltyPayer",
+                    "market",
+                    "marketAuthority",
+                    "bids",
+                    "asks",
+                    "marketBaseVault",
+                    "marketQuoteVault",
+                    "eventHeap",
+                    "userBaseAccount",
+                    "userQuoteAccount",
+                    "oracleA",
+                    "oracleB",
+                    "tokenProgram",
+                    "systemProgram",
+                    "openOrdersAdmin",
+                ]),
+                name: "placeTakeOrder".to_string(),
+            }),
+            OpenbookInstruction::ConsumeEvents(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&CONSUME_EVENTS_ACCOUNTS),
+                name: "consumeEvents".to_string(),
+            }),
+            OpenbookInstruction::ConsumeGivenEvents(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONSUME_EVENTS_ACCOUNTS),
+                name: "consumeGivenEvents".to_string(),
+            }),
+            OpenbookInstruction::CancelOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CANCEL_ORDER_ACCOUNTS),
+                name: "cancelOrder".to_string(),
+            }),
+            OpenbookInstruction::CancelOrderByClientOrderId(client_order_id) => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "clientOrderId".to_string(),
+                        serde_json::to_value(client_order_id)?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&CANCEL_ORDER_ACCOUNTS),
+                    name: "cancelOrderByClientOrderId".to_string(),
+                })
+            }
+            OpenbookInstruction::CancelAllOrders(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CANCEL_ORDER_ACCOUNTS),
+                name: "cancelAllOrders".to_string(),
+            }),
+            OpenbookInstruction::Deposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_ACCOUNTS),
+                name: "deposit".to_string(),
+            }),
+            OpenbookInstruction::Refill(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_ACCOUNTS),
+                name: "refill".to_string(),
+            }),
+            OpenbookInstruction::SettleFunds => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "penaltyPayer",
+                    "openOrdersAccount",
+                    "market",
+                    "marketAuthority",
+                    "marketBaseVault",
+                    "marketQuoteVault",
+                    "userBaseAccount",
+                    "userQuoteAccount",
+                    "referrerAccount",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "settleFunds".to_string(),
+                ..Default::default()
+            }),
+            OpenbookInstruction::SettleFundsExpired => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "closeMarketAdmin",
+                    "owner",
+                    "penaltyPayer",
+                    "openOrdersAccount",
+                    "market",
+                    "marketAuthority",
+                    "marketBaseVault",
+                    "marketQuoteVault",
+                    "userBaseAccount",
+                    "userQuoteAccount",
+                    "referrerAccount",
+                    "tokenProgram",
+                    "systemProgram",
+                ]),
+                name: "settleFundsExpired".to_string(),
+                ..Default::default()
+            }),
+            OpenbookInstruction::SweepFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "collectFeeAdmin",
+                    "market",
+                    "marketAuthority",
+                    "marketQuoteVault",
+                    "tokenReceiverAccount",
+                    "tokenProgram",
+                ]),
+                name: "sweepFees".to_string(),
+                ..Default::default()
+            }),
+            OpenbookInstruction::CloseMarket => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "closeMarketAdmin",
+                    "market",
+                    "bids",
+                    "asks",
+                    "eventHeap",
+                    "solDestination",
+                    "tokenProgram",
+                ]),
+                name: "closeMarket".to_string(),
+                ..Default::default()
+            }),
+            OpenbookInstruction::PruneOrders(limit) => Ok(DecodedInstruction {
+                data: [("limit".to_string(), serde_json::to_value(limit)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "closeMarketAdmin",
+                    "openOrdersAccount",
+                    "market",
+                    "bids",
+                    "asks",
+                ]),
+                name: "pruneOrders".to_string(),
+            }),
+            OpenbookInstruction::Event(event) => {
+                let (name, data) = match event {
+                    OpenbookEvent::Fill(event) => ("fillLog", to_data_map(&event)?),
+                    OpenbookEvent::TotalOrderFill(event) => {
+                        ("totalOrderFillEvent", to_data_map(&event)?)
+                    }
+                    OpenbookEvent::MarketMetaData(event) => {
+                        ("marketMetaDataLog", to_data_map(&event)?)
+                    }
+                    OpenbookEvent::SettleFunds(event) => ("settleFundsLog", to_data_map(&event)?),
+                    OpenbookEvent::Deposit(event) => ("depositLog", to_data_map(&event)?),
+                };
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&["eventAuthority"]),
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use borsh::to_vec;
+
+    #[test]
+    fn test_fills_from_logs() {
+        let market = Pubkey::new_unique();
+        let mut fill = events::FILL_LOG.to_vec();
+        fill.extend(to_vec(&(market, 1u8, 3u8, true, 1_700_000_000u64, 9u64)).unwrap());
+        fill.extend(to_vec(&(Pubkey::new_unique(), 11u64, 20u64, 1_699_999_000u64)).unwrap());
+        fill.extend(to_vec(&(Pubkey::new_unique(), 12u64, 40u64, 1_500i64, 3i64)).unwrap());
+        let other_program = Pubkey::new_unique();
+        let logs = [
+            format!("Program {PROGRAM_ID} invoke [1]"),
+            "Program log: Instruction: PlaceTakeOrder".to_string(),
+            format!("Program {other_program} invoke [2]"),
+            // data logged by another program is ignored
+            format!("Program data: {}", STANDARD.encode(&fill)),
+            format!("Program {other_program} success"),
+            format!("Program data: {}", STANDARD.encode(&fill)),
+            format!("Program {PROGRAM_ID} consumed 31000 of 200000 compute units"),
+            format!("Program {PROGRAM_ID} success"),
+        ];
+        let events = events::log_events(&logs);
+        assert_eq!(events.len(), 1);
+        let events::OpenbookEvent::Fill(fill) = &events[0] else {
+            panic!("expected a fill");
+        };
+        assert_eq!(fill.market, market);
+        assert_eq!(fill.price, 1_500);
+
+        // base lots of 1_000_000 native units and quote lots of a single native unit
+        let fill = fill.to_fill(1_000_000, 1);
+        assert_eq!(fill.taker_side, ix::Side::Ask);
+        assert_eq!(fill.base_amount, 3_000_000);
+        assert_eq!(fill.quote_amount, 4_500);
+        assert_eq!(fill.price, 0.0015);
+        assert_eq!(fill.taker_fee, 40);
+    }
+
+    #[test]
+    fn test_unpack_market() {
+        let (base_mint, quote_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (base_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = state::MARKET_DISCRIMINATOR.to_vec();
+        // bump and decimals, followed by the admins, order book, oracle, lot size and fee fields
+        data.extend([255u8, 9, 6]);
+        data.extend([0u8; 565]);
+        data.extend(to_vec(&(base_mint, quote_mint)).unwrap());
+        // each vault is stored next to its deposit total
+        data.extend(to_vec(&(base_vault, 1_250_000_000u64, quote_vault, 98_000_000u64)).unwrap());
+        data.extend([0u8; 128]);
+        assert_eq!(data.len(), 8 + 840);
+        let market = state::Market::unpack(&data).unwrap();
+        assert_eq!(market.base_decimals, 9);
+        assert_eq!(market.quote_decimals, 6);
+        assert_eq!(market.base_mint, base_mint);
+        assert_eq!(market.quote_mint, quote_mint);
+        assert_eq!(market.market_base_vault, base_vault);
+        assert_eq!(market.base_deposit_total, 1_250_000_000);
+        assert_eq!(market.market_quote_vault, quote_vault);
+        assert_eq!(market.quote_deposit_total, 98_000_000);
+    }
+}
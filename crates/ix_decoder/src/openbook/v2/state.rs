@@ -0,0 +1,89 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_pubkey, serialize_u128},
+};
+
+pub const MARKET_DISCRIMINATOR: [u8; 8] = [219, 190, 213, 55, 0, 227, 198, 154];
+
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleConfig {
+    pub conf_filter: f64,
+    pub max_staleness_slots: i64,
+    #[serde(skip)]
+    pub reserved: [u8; 72],
+}
+
+/// Optional admins and oracles are stored as the default pubkey when unset.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    pub bump: u8,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    #[serde(skip)]
+    pub padding: [u8; 5],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market_authority: Pubkey,
+    pub time_expiry: i64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub collect_fee_admin: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub open_orders_admin: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub consume_events_admin: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub close_market_admin: Pubkey,
+    /// utf8 name padded with zeros
+    pub name: [u8; 16],
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub bids: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub asks: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub event_heap: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub oracle_a: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub oracle_b: Pubkey,
+    pub oracle_config: OracleConfig,
+    pub quote_lot_size: i64,
+    pub base_lot_size: i64,
+    pub seq_num: u64,
+    pub registration_time: i64,
+    /// fees in units of 1e-6
+    pub maker_fee: i64,
+    pub taker_fee: i64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fees_accrued: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fees_to_referrers: u128,
+    pub referrer_rebates_accrued: u64,
+    pub fees_available: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub maker_volume: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub taker_volume_wo_oo: u128,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub base_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub quote_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market_base_vault: Pubkey,
+    pub base_deposit_total: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market_quote_vault: Pubkey,
+    pub quote_deposit_total: u64,
+}
+
+impl Market {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, MARKET_DISCRIMINATOR)
+    }
+}
@@ -0,0 +1,47 @@
+//! Instructions decoding functions for orca
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use whirlpool::WhirlpoolDecoder;
+
+pub mod whirlpool;
+
+pub static ORCA_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(OrcaProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Whirlpool(WhirlpoolDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct OrcaProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Whirlpool(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Orca::Whirlpool"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Whirlpool(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for OrcaProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            whirlpool::PROGRAM_ID => Some(Box::new(Programs::Whirlpool(WhirlpoolDecoder::new(
+                ix.clone(),
+            )))),
+            _ => None,
+        }
+    }
+}
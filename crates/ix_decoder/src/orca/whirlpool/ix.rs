@@ -0,0 +1,200 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{anchor, types::serialize_u128};
+
+pub const INITIALIZE_POOL: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+pub const INITIALIZE_POOL_V2: [u8; 8] = [207, 45, 87, 242, 27, 63, 204, 67];
+pub const INITIALIZE_TICK_ARRAY: [u8; 8] = [11, 188, 193, 214, 141, 91, 149, 184];
+pub const OPEN_POSITION: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+pub const OPEN_POSITION_WITH_METADATA: [u8; 8] = [242, 29, 134, 48, 58, 110, 14, 60];
+pub const OPEN_POSITION_WITH_TOKEN_EXTENSIONS: [u8; 8] = [212, 47, 95, 92, 114, 102, 131, 250];
+pub const INCREASE_LIQUIDITY: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+pub const INCREASE_LIQUIDITY_V2: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+pub const DECREASE_LIQUIDITY: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+pub const DECREASE_LIQUIDITY_V2: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+pub const UPDATE_FEES_AND_REWARDS: [u8; 8] = [154, 230, 250, 13, 236, 209, 75, 223];
+pub const COLLECT_FEES: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+pub const COLLECT_FEES_V2: [u8; 8] = [207, 117, 95, 191, 229, 180, 226, 15];
+pub const COLLECT_REWARD: [u8; 8] = [70, 5, 132, 87, 86, 235, 177, 34];
+pub const COLLECT_REWARD_V2: [u8; 8] = [177, 107, 37, 180, 160, 19, 49, 209];
+pub const COLLECT_PROTOCOL_FEES: [u8; 8] = [22, 67, 23, 98, 150, 178, 70, 220];
+pub const COLLECT_PROTOCOL_FEES_V2: [u8; 8] = [103, 128, 222, 134, 114, 200, 22, 200];
+pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+pub const SWAP_V2: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+pub const TWO_HOP_SWAP: [u8; 8] = [195, 96, 237, 108, 68, 162, 219, 230];
+pub const TWO_HOP_SWAP_V2: [u8; 8] = [186, 143, 209, 29, 254, 2, 194, 117];
+pub const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+pub const CLOSE_POSITION_WITH_TOKEN_EXTENSIONS: [u8; 8] = [1, 182, 135, 59, 155, 25, 99, 223];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePoolInstruction {
+    pub whirlpool_bump: u8,
+    pub tick_spacing: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub initial_sqrt_price: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePoolV2Instruction {
+    pub tick_spacing: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub initial_sqrt_price: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeTickArrayInstruction {
+    pub start_tick_index: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenPositionInstruction {
+    pub position_bump: u8,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenPositionWithMetadataInstruction {
+    pub position_bump: u8,
+    pub metadata_bump: u8,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenPositionWithTokenExtensionsInstruction {
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub with_token_metadata_extension: bool,
+}
+
+/// Arguments of both the v1 and v2 increase liquidity instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncreaseLiquidityInstruction {
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity_amount: u128,
+    pub token_max_a: u64,
+    pub token_max_b: u64,
+}
+
+/// Arguments of both the v1 and v2 decrease liquidity instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecreaseLiquidityInstruction {
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity_amount: u128,
+    pub token_min_a: u64,
+    pub token_min_b: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectRewardInstruction {
+    pub reward_index: u8,
+}
+
+/// Arguments of both the v1 and v2 swap instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInstruction {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+}
+
+/// Arguments of both the v1 and v2 two hop swap instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoHopSwapInstruction {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub amount_specified_is_input: bool,
+    pub a_to_b_one: bool,
+    pub a_to_b_two: bool,
+    #[serde(serialize_with = "serialize_u128")]
+    pub sqrt_price_limit_one: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub sqrt_price_limit_two: u128,
+}
+
+/// Instructions supported by the whirlpool program.
+///
+/// The v2 instructions support token-2022 mints, their trailing
+/// `remaining_accounts_info` argument is not decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WhirlpoolInstruction {
+    InitializePool(InitializePoolInstruction),
+    InitializePoolV2(InitializePoolV2Instruction),
+    InitializeTickArray(InitializeTickArrayInstruction),
+    OpenPosition(OpenPositionInstruction),
+    OpenPositionWithMetadata(OpenPositionWithMetadataInstruction),
+    OpenPositionWithTokenExtensions(OpenPositionWithTokenExtensionsInstruction),
+    IncreaseLiquidity(IncreaseLiquidityInstruction),
+    IncreaseLiquidityV2(IncreaseLiquidityInstruction),
+    DecreaseLiquidity(DecreaseLiquidityInstruction),
+    DecreaseLiquidityV2(DecreaseLiquidityInstruction),
+    UpdateFeesAndRewards,
+    CollectFees,
+    CollectFeesV2,
+    CollectReward(CollectRewardInstruction),
+    CollectRewardV2(CollectRewardInstruction),
+    CollectProtocolFees,
+    CollectProtocolFeesV2,
+    Swap(SwapInstruction),
+    SwapV2(SwapInstruction),
+    TwoHopSwap(TwoHopSwapInstruction),
+    TwoHopSwapV2(TwoHopSwapInstruction),
+    ClosePosition,
+    ClosePositionWithTokenExtensions,
+}
+
+impl WhirlpoolInstruction {
+    /// Unpacks a byte buffer into a [WhirlpoolInstruction](enum.WhirlpoolInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE_POOL => Self::InitializePool(anchor::deserialize(rest)?),
+            INITIALIZE_POOL_V2 => Self::InitializePoolV2(anchor::deserialize(rest)?),
+            INITIALIZE_TICK_ARRAY => Self::InitializeTickArray(anchor::deserialize(rest)?),
+            OPEN_POSITION => Self::OpenPosition(anchor::deserialize(rest)?),
+            OPEN_POSITION_WITH_METADATA => {
+                Self::OpenPositionWithMetadata(anchor::deserialize(rest)?)
+            }
+            OPEN_POSITION_WITH_TOKEN_EXTENSIONS => {
+                Self::OpenPositionWithTokenExtensions(anchor::deserialize(rest)?)
+            }
+            INCREASE_LIQUIDITY => Self::IncreaseLiquidity(anchor::deserialize(rest)?),
+            INCREASE_LIQUIDITY_V2 => Self::IncreaseLiquidityV2(anchor::deserialize(rest)?),
+            DECREASE_LIQUIDITY => Self::DecreaseLiquidity(anchor::deserialize(rest)?),
+            DECREASE_LIQUIDITY_V2 => Self::DecreaseLiquidityV2(anchor::deserialize(rest)?),
+            UPDATE_FEES_AND_REWARDS => Self::UpdateFeesAndRewards,
+            COLLECT_FEES => Self::CollectFees,
+            COLLECT_FEES_V2 => Self::CollectFeesV2,
+            COLLECT_REWARD => Self::CollectReward(anchor::deserialize(rest)?),
+            COLLECT_REWARD_V2 => Self::CollectRewardV2(anchor::deserialize(rest)?),
+            COLLECT_PROTOCOL_FEES => Self::CollectProtocolFees,
+            COLLECT_PROTOCOL_FEES_V2 => Self::CollectProtocolFeesV2,
+            SWAP => Self::Swap(anchor::deserialize(rest)?),
+            SWAP_V2 => Self::SwapV2(anchor::deserialize(rest)?),
+            TWO_HOP_SWAP => Self::TwoHopSwap(anchor::deserialize(rest)?),
+            TWO_HOP_SWAP_V2 => Self::TwoHopSwapV2(anchor::deserialize(rest)?),
+            CLOSE_POSITION => Self::ClosePosition,
+            CLOSE_POSITION_WITH_TOKEN_EXTENSIONS => Self::ClosePositionWithTokenExtensions,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
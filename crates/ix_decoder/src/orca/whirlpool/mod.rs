@@ -0,0 +1,458 @@
+//! Instruction decoding functions for the orca whirlpool program
+
+use anyhow::Context;
+use ix::WhirlpoolInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+const SWAP_ACCOUNTS: [&str; 11] = [
+    "tokenProgram",
+    "tokenAuthority",
+    "whirlpool",
+    "tokenOwnerAccountA",
+    "tokenVaultA",
+    "tokenOwnerAccountB",
+    "tokenVaultB",
+    "tickArray0",
+    "tickArray1",
+    "tickArray2",
+    "oracle",
+];
+
+const SWAP_V2_ACCOUNTS: [&str; 15] = [
+    "tokenProgramA",
+    "tokenProgramB",
+    "memoProgram",
+    "tokenAuthority",
+    "whirlpool",
+    "tokenMintA",
+    "tokenMintB",
+    "tokenOwnerAccountA",
+    "tokenVaultA",
+    "tokenOwnerAccountB",
+    "tokenVaultB",
+    "tickArray0",
+    "tickArray1",
+    "tickArray2",
+    "oracle",
+];
+
+const TWO_HOP_SWAP_ACCOUNTS: [&str; 20] = [
+    "tokenProgram",
+    "tokenAuthority",
+    "whirlpoolOne",
+    "whirlpoolTwo",
+    "tokenOwnerAccountOneA",
+    "tokenVaultOneA",
+    "tokenOwnerAccountOneB",
+    "tokenVaultOneB",
+    "tokenOwnerAccountTwoA",
+    "tokenVaultTwoA",
+    "tokenOwnerAccountTwoB",
+    "tokenVaultTwoB",
+    "tickArrayOne0",
+    "tickArrayOne1",
+    "tickArrayOne2",
+    "tickArrayTwo0",
+    "tickArrayTwo1",
+    "tickArrayTwo2",
+    "oracleOne",
+    "oracleTwo",
+];
+
+const TWO_HOP_SWAP_V2_ACCOUNTS: [&str; 24] = [
+    "whirlpoolOne",
+    "whirlpoolTwo",
+    "tokenMintInput",
+    "tokenMintIntermediate",
+    "tokenMintOutput",
+    "tokenProgramInput",
+    "tokenProgramIntermediate",
+    "tokenProgramOutput",
+    "tokenOwnerAccountInput",
+    "tokenVaultOneInput",
+    "tokenVaultOneIntermediate",
+    "tokenVaultTwoIntermediate",
+    "tokenVaultTwoOutput",
+    "tokenOwnerAccountOutput",
+    "tokenAuthority",
+    "tickArrayOne0",
+    "tickArrayOne1",
+    "tickArrayOne2",
+    "tickArrayTwo0",
+    "tickArrayTwo1",
+    "tickArrayTwo2",
+    "oracleOne",
+    "oracleTwo",
+    "memoProgram",
+];
+
+const MODIFY_LIQUIDITY_ACCOUNTS: [&str; 11] = [
+    "whirlpool",
+    "tokenProgram",
+    "positionAuthority",
+    "position",
+    "positionTokenAccount",
+    "tokenOwnerAccountA",
+    "tokenOwnerAccountB",
+    "tokenVaultA",
+    "tokenVaultB",
+    "tickArrayLower",
+    "tickArrayUpper",
+];
+
+const MODIFY_LIQUIDITY_V2_ACCOUNTS: [&str; 15] = [
+    "whirlpool",
+    "tokenProgramA",
+    "tokenProgramB",
+    "memoProgram",
+    "positionAuthority",
+    "position",
+    "positionTokenAccount",
+    "tokenMintA",
+    "tokenMintB",
+    "tokenOwnerAccountA",
+    "tokenOwnerAccountB",
+    "tokenVaultA",
+    "tokenVaultB",
+    "tickArrayLower",
+    "tickArrayUpper",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WhirlpoolDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl WhirlpoolDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = WhirlpoolInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            WhirlpoolInstruction::InitializePool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "whirlpoolsConfig",
+                    "tokenMintA",
+                    "tokenMintB",
+                    "funder",
+                    "whirlpool",
+                    "tokenVaultA",
+                    "tokenVaultB",
+                    "feeTier",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "initializePool".to_string(),
+            }),
+            WhirlpoolInstruction::InitializePoolV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "whirlpoolsConfig",
+                    "tokenMintA",
+                    "tokenMintB",
+                    "tokenBadgeA",
+                    "tokenBadgeB",
+                    "funder",
+                    "whirlpool",
+                    "tokenVaultA",
+                    "tokenVaultB",
+                    "feeTier",
+                    "tokenProgramA",
+                    "tokenProgramB",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "initializePoolV2".to_string(),
+            }),
+            WhirlpoolInstruction::InitializeTickArray(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "whirlpool",
+                    "funder",
+                    "tickArray",
+                    "systemProgram",
+                ]),
+                name: "initializeTickArray".to_string(),
+            }),
+            WhirlpoolInstruction::OpenPosition(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "funder",
+                    "owner",
+                    "position",
+                    "positionMint",
+                    "positionTokenAccount",
+                    "whirlpool",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                    "associatedTokenProgram",
+                ]),
+                name: "openPosition".to_string(),
+            }),
+            WhirlpoolInstruction::OpenPositionWithMetadata(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "funder",
+                    "owner",
+                    "position",
+                    "positionMint",
+                    "positionMetadataAccount",
+                    "positionTokenAccount",
+                    "whirlpool",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                    "associatedTokenProgram",
+                    "metadataProgram",
+                    "metadataUpdateAuth",
+                ]),
+                name: "openPositionWithMetadata".to_string(),
+            }),
+            WhirlpoolInstruction::OpenPositionWithTokenExtensions(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "funder",
+                    "owner",
+                    "position",
+                    "positionMint",
+                    "positionTokenAccount",
+                    "whirlpool",
+                    "token2022Program",
+                    "systemProgram",
+                    "associatedTokenProgram",
+                    "metadataUpdateAuth",
+                ]),
+                name: "openPositionWithTokenExtensions".to_string(),
+            }),
+            WhirlpoolInstruction::IncreaseLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "increaseLiquidity".to_string(),
+            }),
+            WhirlpoolInstruction::IncreaseLiquidityV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_V2_ACCOUNTS),
+                name: "increaseLiquidityV2".to_string(),
+            }),
+            WhirlpoolInstruction::DecreaseLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_ACCOUNTS),
+                name: "decreaseLiquidity".to_string(),
+            }),
+            WhirlpoolInstruction::DecreaseLiquidityV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_LIQUIDITY_V2_ACCOUNTS),
+                name: "decreaseLiquidityV2".to_string(),
+            }),
+            WhirlpoolInstruction::UpdateFeesAndRewards => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "whirlpool",
+                    "position",
+                    "tickArrayLower",
+                    "tickArrayUpper",
+                ]),
+                name: "updateFeesAndRewards".to_string(),
+                ..Default::default()
+            }),
+            WhirlpoolInstruction::CollectFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "whirlpool",
+                    "positionAuthority",
+                    "position",
+                    "positionTokenAccount",
+                    "tokenOwnerAccountA",
+                    "tokenVaultA",
+                    "tokenOwnerAccountB",
+                    "tokenVaultB",
+                    "tokenProgram",
+                ]),
+                name: "collectFees".to_string(),
+                ..Default::default()
+            }),
+            WhirlpoolInstruction::CollectFeesV2 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "whirlpool",
+                    "positionAuthority",
+                    "position",
+                    "positionTokenAccount",
+                    "tokenMintA",
+                    "tokenMintB",
+                    "tokenOwnerAccountA",
+                    "tokenVaultA",
+                    "tokenOwnerAccountB",
+                    "tokenVaultB",
+                    "tokenProgramA",
+                    "tokenProgramB",
+                    "memoProgram",
+                ]),
+                name: "collectFeesV2".to_string(),
+                ..Default::default()
+            }),
+            WhirlpoolInstruction::CollectReward(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "whirlpool",
+                    "positionAuthority",
+                    "position",
+                    "positionTokenAccount",
+                    "rewardOwnerAccount",
+                    "rewardVault",
+                    "tokenProgram",
+                ]),
+                name: "collectReward".to_string(),
+            }),
+            WhirlpoolInstruction::CollectRewardV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "whirlpool",
+                    "positionAuthority",
+                    "position",
+                    "positionTokenAccount",
+                    "rewardOwnerAccount",
+                    "rewardMint",
+                    "rewardVault",
+                    "rewardTokenProgram",
+                    "memoProgram",
+                ]),
+                name: "collectRewardV2".to_string(),
+            }),
+            WhirlpoolInstruction::CollectProtocolFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "whirlpoolsConfig",
+                    "whirlpool",
+                    "collectProtocolFeesAuthority",
+                    "tokenVaultA",
+                    "tokenVaultB",
+                    "tokenDestinationA",
+                    "tokenDestinationB",
+                    "tokenProgram",
+                ]),
+                name: "collectProtocolFees".to_string(),
+                ..Default::default()
+            }),
+            WhirlpoolInstruction::CollectProtocolFeesV2 => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "whirlpoolsConfig",
+                    "whirlpool",
+                    "collectProtocolFeesAuthority",
+                    "tokenMintA",
+                    "tokenMintB",
+                    "tokenVaultA",
+                    "tokenVaultB",
+                    "tokenDestinationA",
+                    "tokenDestinationB",
+                    "tokenProgramA",
+                    "tokenProgramB",
+                    "memoProgram",
+                ]),
+                name: "collectProtocolFeesV2".to_string(),
+                ..Default::default()
+            }),
+            WhirlpoolInstruction::Swap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swap".to_string(),
+            }),
+            WhirlpoolInstruction::SwapV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_V2_ACCOUNTS),
+                name: "swapV2".to_string(),
+            }),
+            WhirlpoolInstruction::TwoHopSwap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TWO_HOP_SWAP_ACCOUNTS),
+                name: "twoHopSwap".to_string(),
+            }),
+            WhirlpoolInstruction::TwoHopSwapV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TWO_HOP_SWAP_V2_ACCOUNTS),
+                name: "twoHopSwapV2".to_string(),
+            }),
+            WhirlpoolInstruction::ClosePosition => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "positionAuthority",
+                    "receiver",
+                    "position",
+                    "positionMint",
+                    "positionTokenAccount",
+                    "tokenProgram",
+                ]),
+                name: "closePosition".to_string(),
+                ..Default::default()
+            }),
+            WhirlpoolInstruction::ClosePositionWithTokenExtensions => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "positionAuthority",
+                    "receiver",
+                    "position",
+                    "positionMint",
+                    "positionTokenAccount",
+                    "token2022Program",
+                ]),
+                name: "closePositionWithTokenExtensions".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_discriminators() {
+        assert_eq!(anchor::instruction_discriminator("swap"), ix::SWAP);
+        assert_eq!(
+            anchor::instruction_discriminator("two_hop_swap_v2"),
+            ix::TWO_HOP_SWAP_V2
+        );
+        assert_eq!(
+            anchor::account_discriminator("Whirlpool"),
+            state::WHIRLPOOL_DISCRIMINATOR
+        );
+        assert_eq!(
+            anchor::account_discriminator("TickArray"),
+            state::TICK_ARRAY_DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_decode_swap() {
+        let mut data = ix::SWAP.to_vec();
+        data.extend(to_vec(&(1_000_000u64, 990u64, 4295048016u128, true, false)).unwrap());
+        let accounts = (0..SWAP_ACCOUNTS.len())
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+        let decoded = WhirlpoolDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "swap");
+        assert_eq!(decoded.data["amount"], 1_000_000);
+        assert_eq!(decoded.data["otherAmountThreshold"], 990);
+        assert_eq!(decoded.data["sqrtPriceLimit"], "4295048016");
+        assert_eq!(decoded.data["amountSpecifiedIsInput"], true);
+        assert_eq!(decoded.data["aToB"], false);
+        assert_eq!(decoded.accounts["whirlpool"], accounts[2].to_string());
+        assert_eq!(decoded.accounts["oracle"], accounts[10].to_string());
+    }
+}
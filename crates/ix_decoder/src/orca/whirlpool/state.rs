@@ -0,0 +1,166 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_i128, serialize_pubkey, serialize_u128, serialize_u128_array},
+};
+
+pub const WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [63, 149, 209, 12, 225, 128, 99, 9];
+pub const POSITION_DISCRIMINATOR: [u8; 8] = [170, 188, 143, 228, 122, 64, 247, 208];
+pub const TICK_ARRAY_DISCRIMINATOR: [u8; 8] = [69, 97, 189, 190, 110, 7, 66, 187];
+
+pub const NUM_REWARDS: usize = 3;
+pub const TICK_ARRAY_SIZE: usize = 88;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WhirlpoolRewardInfo {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub vault: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub authority: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub emissions_per_second_x64: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub growth_global_x64: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Whirlpool {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub whirlpools_config: Pubkey,
+    pub whirlpool_bump: [u8; 1],
+    pub tick_spacing: u16,
+    pub tick_spacing_seed: [u8; 2],
+    pub fee_rate: u16,
+    pub protocol_fee_rate: u16,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub protocol_fee_owed_a: u64,
+    pub protocol_fee_owed_b: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_mint_a: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_vault_a: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_growth_global_a: u128,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_mint_b: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_vault_b: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_growth_global_b: u128,
+    pub reward_last_updated_timestamp: u64,
+    pub reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS],
+}
+
+impl Whirlpool {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, WHIRLPOOL_DISCRIMINATOR)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRewardInfo {
+    #[serde(serialize_with = "serialize_u128")]
+    pub growth_inside_checkpoint: u128,
+    pub amount_owed: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub whirlpool: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub position_mint: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity: u128,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_growth_checkpoint_a: u128,
+    pub fee_owed_a: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_growth_checkpoint_b: u128,
+    pub fee_owed_b: u64,
+    pub reward_infos: [PositionRewardInfo; NUM_REWARDS],
+}
+
+impl Position {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, POSITION_DISCRIMINATOR)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tick {
+    pub initialized: bool,
+    #[serde(serialize_with = "serialize_i128")]
+    pub liquidity_net: i128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub liquidity_gross: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_growth_outside_a: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee_growth_outside_b: u128,
+    #[serde(serialize_with = "serialize_u128_array")]
+    pub reward_growths_outside: [u128; NUM_REWARDS],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickArray {
+    pub start_tick_index: i32,
+    pub ticks: Vec<Tick>,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub whirlpool: Pubkey,
+}
+
+impl TickArray {
+    /// Tick arrays are zero copy accounts, so the ticks are a fixed size array
+    /// rather than a length prefixed vector
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, mut rest) = anchor::split_discriminator(data)?;
+        if discriminator != TICK_ARRAY_DISCRIMINATOR {
+            return Err(anyhow::anyhow!("invalid account discriminator"));
+        }
+        let start_tick_index = i32::deserialize(&mut rest)?;
+        let ticks = (0..TICK_ARRAY_SIZE)
+            .map(|_| Tick::deserialize(&mut rest))
+            .collect::<Result<Vec<_>, _>>()?;
+        let whirlpool = Pubkey::deserialize(&mut rest)?;
+        Ok(Self {
+            start_tick_index,
+            ticks,
+            whirlpool,
+        })
+    }
+
+    /// Returns the initialized ticks along with their tick index
+    pub fn initialized_ticks(&self, tick_spacing: u16) -> Vec<(i32, &Tick)> {
+        self.ticks
+            .iter()
+            .enumerate()
+            .filter(|(_, tick)| tick.initialized)
+            .map(|(offset, tick)| {
+                (
+                    self.start_tick_index + offset as i32 * i32::from(tick_spacing),
+                    tick,
+                )
+            })
+            .collect()
+    }
+}
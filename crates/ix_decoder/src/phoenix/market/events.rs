@@ -0,0 +1,169 @@
+//! Event types, recorded by the program through a self cpi to its log instruction
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{ix::Side, state::MarketHeader};
+use crate::{
+    anchor,
+    types::{serialize_pubkey, serialize_u128},
+};
+
+/// Identifies the instruction and market the following events belong to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogHeader {
+    /// tag of the instruction which recorded the events
+    pub instruction: u8,
+    pub sequence_number: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub signer: Pubkey,
+    pub total_events: u16,
+}
+
+/// Prices are in ticks and sizes in base lots
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum MarketEvent {
+    Uninitialized,
+    Header {
+        header: AuditLogHeader,
+    },
+    Fill {
+        index: u16,
+        #[serde(serialize_with = "serialize_pubkey")]
+        maker_id: Pubkey,
+        order_sequence_number: u64,
+        price_in_ticks: u64,
+        base_lots_filled: u64,
+        base_lots_remaining: u64,
+    },
+    Place {
+        index: u16,
+        order_sequence_number: u64,
+        #[serde(serialize_with = "serialize_u128")]
+        client_order_id: u128,
+        price_in_ticks: u64,
+        base_lots_placed: u64,
+    },
+    Reduce {
+        index: u16,
+        order_sequence_number: u64,
+        price_in_ticks: u64,
+        base_lots_removed: u64,
+        base_lots_remaining: u64,
+    },
+    Evict {
+        index: u16,
+        #[serde(serialize_with = "serialize_pubkey")]
+        maker_id: Pubkey,
+        order_sequence_number: u64,
+        price_in_ticks: u64,
+        base_lots_evicted: u64,
+    },
+    FillSummary {
+        index: u16,
+        #[serde(serialize_with = "serialize_u128")]
+        client_order_id: u128,
+        total_base_lots_filled: u64,
+        total_quote_lots_filled: u64,
+        total_fee_in_quote_lots: u64,
+    },
+    Fee {
+        index: u16,
+        fees_collected_in_quote_lots: u64,
+    },
+    TimeInForce {
+        index: u16,
+        order_sequence_number: u64,
+        last_valid_slot: u64,
+        last_valid_unix_timestamp_in_seconds: u64,
+    },
+    ExpiredOrder {
+        index: u16,
+        #[serde(serialize_with = "serialize_pubkey")]
+        maker_id: Pubkey,
+        order_sequence_number: u64,
+        price_in_ticks: u64,
+        base_lots_removed: u64,
+    },
+}
+
+/// A batch of events recorded by a single instruction
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogInstruction {
+    pub header: AuditLogHeader,
+    pub events: Vec<MarketEvent>,
+}
+
+impl LogInstruction {
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (header, events) = anchor::deserialize::<(MarketEvent, Vec<MarketEvent>)>(input)?;
+        let MarketEvent::Header { header } = header else {
+            return Err(anyhow::anyhow!("log does not start with a header"));
+        };
+        Ok(Self { header, events })
+    }
+
+    /// Converts the fill events of the batch to native units using the market's lot sizes
+    pub fn fills(&self, market: &MarketHeader) -> Vec<Fill> {
+        self.events
+            .iter()
+            .filter_map(|event| match *event {
+                MarketEvent::Fill {
+                    maker_id,
+                    order_sequence_number,
+                    price_in_ticks,
+                    base_lots_filled,
+                    ..
+                } => Some(Fill {
+                    market: self.header.market,
+                    sequence_number: self.header.sequence_number,
+                    timestamp: self.header.timestamp,
+                    // bid order sequence numbers are stored inverted, setting the top bit
+                    taker_side: if order_sequence_number >> 63 == 1 {
+                        Side::Ask
+                    } else {
+                        Side::Bid
+                    },
+                    maker: maker_id,
+                    taker: self.header.signer,
+                    order_sequence_number,
+                    price_in_ticks,
+                    price: market.native_price(price_in_ticks),
+                    base_amount: base_lots_filled.saturating_mul(market.base_lot_size),
+                    quote_amount: market.native_quote_amount(price_in_ticks, base_lots_filled),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A fill with its price and size converted to native units
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub market: Pubkey,
+    pub sequence_number: u64,
+    pub timestamp: i64,
+    pub taker_side: Side,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub taker: Pubkey,
+    /// sequence number of the maker order
+    pub order_sequence_number: u64,
+    pub price_in_ticks: u64,
+    /// native quote per native base
+    pub price: f64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
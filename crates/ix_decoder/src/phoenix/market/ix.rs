@@ -0,0 +1,234 @@
+//! Instruction types
+//!
+//! Instruction data is a single byte tag followed by the borsh encoded arguments.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::events::LogInstruction;
+use crate::{
+    anchor,
+    types::{serialize_option_u128, serialize_pubkey, serialize_u128},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SelfTradeBehavior {
+    Abort,
+    CancelProvide,
+    DecrementTake,
+}
+
+/// Prices are in ticks and sizes in base lots
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum OrderPacket {
+    PostOnly {
+        side: Side,
+        price_in_ticks: u64,
+        num_base_lots: u64,
+        #[serde(serialize_with = "serialize_u128")]
+        client_order_id: u128,
+        reject_post_only: bool,
+        use_only_deposited_funds: bool,
+        last_valid_slot: Option<u64>,
+        last_valid_unix_timestamp_in_seconds: Option<u64>,
+        fail_silently_on_insufficient_funds: bool,
+    },
+    Limit {
+        side: Side,
+        price_in_ticks: u64,
+        num_base_lots: u64,
+        self_trade_behavior: SelfTradeBehavior,
+        match_limit: Option<u64>,
+        #[serde(serialize_with = "serialize_u128")]
+        client_order_id: u128,
+        use_only_deposited_funds: bool,
+        last_valid_slot: Option<u64>,
+        last_valid_unix_timestamp_in_seconds: Option<u64>,
+        fail_silently_on_insufficient_funds: bool,
+    },
+    ImmediateOrCancel {
+        side: Side,
+        price_in_ticks: Option<u64>,
+        num_base_lots: u64,
+        num_quote_lots: u64,
+        min_base_lots_to_fill: u64,
+        min_quote_lots_to_fill: u64,
+        self_trade_behavior: SelfTradeBehavior,
+        match_limit: Option<u64>,
+        #[serde(serialize_with = "serialize_u128")]
+        client_order_id: u128,
+        use_only_deposited_funds: bool,
+        last_valid_slot: Option<u64>,
+        last_valid_unix_timestamp_in_seconds: Option<u64>,
+    },
+}
+
+impl OrderPacket {
+    /// Older clients omit the trailing `fail_silently_on_insufficient_funds` flag, which
+    /// the program then treats as false
+    fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        anchor::deserialize(input).or_else(|_| anchor::deserialize(&[input, &[0]].concat()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderParams {
+    pub side: Side,
+    pub price_in_ticks: u64,
+    pub order_sequence_number: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReduceOrderParams {
+    pub base_params: CancelOrderParams,
+    /// base lots to remove from the order
+    pub size: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelUpToParams {
+    pub side: Side,
+    pub tick_limit: Option<u64>,
+    pub num_orders_to_search: Option<u32>,
+    pub num_orders_to_cancel: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelMultipleOrdersByIdParams {
+    pub orders: Vec<CancelOrderParams>,
+}
+
+/// Amounts are withdrawn in full when not given
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawParams {
+    pub quote_lots_to_withdraw: Option<u64>,
+    pub base_lots_to_withdraw: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositParams {
+    pub quote_lots_to_deposit: u64,
+    pub base_lots_to_deposit: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CondensedOrder {
+    pub price_in_ticks: u64,
+    pub size_in_base_lots: u64,
+    pub last_valid_slot: Option<u64>,
+    pub last_valid_unix_timestamp_in_seconds: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FailedMultipleLimitOrderBehavior {
+    FailOnInsufficientFundsAndAmendOnCross,
+    FailOnInsufficientFundsAndFailOnCross,
+    SkipOnInsufficientFundsAndAmendOnCross,
+    SkipOnInsufficientFundsAndFailOnCross,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleOrderPacket {
+    pub bids: Vec<CondensedOrder>,
+    pub asks: Vec<CondensedOrder>,
+    #[serde(serialize_with = "serialize_option_u128")]
+    pub client_order_id: Option<u128>,
+    pub failed_multiple_limit_order_behavior: FailedMultipleLimitOrderBehavior,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketSizeParams {
+    pub bids_size: u64,
+    pub asks_size: u64,
+    pub num_seats: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeParams {
+    pub market_size_params: MarketSizeParams,
+    pub num_quote_lots_per_quote_unit: u64,
+    pub tick_size_in_quote_lots_per_base_unit: u64,
+    pub num_base_lots_per_base_unit: u64,
+    pub taker_fee_bps: u16,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_collector: Pubkey,
+    pub raw_base_units_per_base_unit: Option<u32>,
+}
+
+/// Instructions supported by the phoenix program, the market administration
+/// instructions are not decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PhoenixInstruction {
+    Swap(OrderPacket),
+    SwapWithFreeFunds(OrderPacket),
+    PlaceLimitOrder(OrderPacket),
+    PlaceLimitOrderWithFreeFunds(OrderPacket),
+    ReduceOrder(ReduceOrderParams),
+    ReduceOrderWithFreeFunds(ReduceOrderParams),
+    CancelAllOrders,
+    CancelAllOrdersWithFreeFunds,
+    CancelUpTo(CancelUpToParams),
+    CancelUpToWithFreeFunds(CancelUpToParams),
+    CancelMultipleOrdersById(CancelMultipleOrdersByIdParams),
+    CancelMultipleOrdersByIdWithFreeFunds(CancelMultipleOrdersByIdParams),
+    WithdrawFunds(WithdrawParams),
+    DepositFunds(DepositParams),
+    RequestSeat,
+    Log(LogInstruction),
+    PlaceMultiplePostOnlyOrders(MultipleOrderPacket),
+    PlaceMultiplePostOnlyOrdersWithFreeFunds(MultipleOrderPacket),
+    InitializeMarket(InitializeParams),
+}
+
+impl PhoenixInstruction {
+    /// Unpacks a byte buffer into a [PhoenixInstruction](enum.PhoenixInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::Swap(OrderPacket::unpack(rest)?),
+            1 => Self::SwapWithFreeFunds(OrderPacket::unpack(rest)?),
+            2 => Self::PlaceLimitOrder(OrderPacket::unpack(rest)?),
+            3 => Self::PlaceLimitOrderWithFreeFunds(OrderPacket::unpack(rest)?),
+            4 => Self::ReduceOrder(anchor::deserialize(rest)?),
+            5 => Self::ReduceOrderWithFreeFunds(anchor::deserialize(rest)?),
+            6 => Self::CancelAllOrders,
+            7 => Self::CancelAllOrdersWithFreeFunds,
+            8 => Self::CancelUpTo(anchor::deserialize(rest)?),
+            9 => Self::CancelUpToWithFreeFunds(anchor::deserialize(rest)?),
+            10 => Self::CancelMultipleOrdersById(anchor::deserialize(rest)?),
+            11 => Self::CancelMultipleOrdersByIdWithFreeFunds(anchor::deserialize(rest)?),
+            12 => Self::WithdrawFunds(anchor::deserialize(rest)?),
+            13 => Self::DepositFunds(anchor::deserialize(rest)?),
+            14 => Self::RequestSeat,
+            15 => Self::Log(LogInstruction::unpack(rest)?),
+            16 => Self::PlaceMultiplePostOnlyOrders(anchor::deserialize(rest)?),
+            17 => Self::PlaceMultiplePostOnlyOrdersWithFreeFunds(anchor::deserialize(rest)?),
+            100 => Self::InitializeMarket(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
@@ -0,0 +1,277 @@
+//! Instruction decoding functions for the phoenix order book program
+//!
+//! Market events are recorded through a self cpi to the log instruction, its decoded data
+//! carries the audit log header and the events, [events::LogInstruction::fills] converts
+//! the fills to native units.
+
+use anyhow::Context;
+use ix::PhoenixInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY");
+
+const SWAP_ACCOUNTS: [&str; 9] = [
+    "phoenixProgram",
+    "logAuthority",
+    "market",
+    "trader",
+    "baseAccount",
+    "quoteAccount",
+    "baseVault",
+    "quoteVault",
+    "tokenProgram",
+];
+
+const FREE_FUNDS_ACCOUNTS: [&str; 5] =
+    ["phoenixProgram", "logAuthority", "market", "trader", "seat"];
+
+const PLACE_ORDER_ACCOUNTS: [&str; 10] = [
+    "phoenixProgram",
+    "logAuthority",
+    "market",
+    "trader",
+    "seat",
+    "baseAccount",
+    "quoteAccount",
+    "baseVault",
+    "quoteVault",
+    "tokenProgram",
+];
+
+const CANCEL_WITH_FREE_FUNDS_ACCOUNTS: [&str; 4] =
+    ["phoenixProgram", "logAuthority", "market", "trader"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PhoenixDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl PhoenixDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = PhoenixInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            PhoenixInstruction::Swap(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swap".to_string(),
+            }),
+            PhoenixInstruction::SwapWithFreeFunds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FREE_FUNDS_ACCOUNTS),
+                name: "swapWithFreeFunds".to_string(),
+            }),
+            PhoenixInstruction::PlaceLimitOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDER_ACCOUNTS),
+                name: "placeLimitOrder".to_string(),
+            }),
+            PhoenixInstruction::PlaceLimitOrderWithFreeFunds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&FREE_FUNDS_ACCOUNTS),
+                name: "placeLimitOrderWithFreeFunds".to_string(),
+            }),
+            PhoenixInstruction::ReduceOrder(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "reduceOrder".to_string(),
+            }),
+            PhoenixInstruction::ReduceOrderWithFreeFunds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CANCEL_WITH_FREE_FUNDS_ACCOUNTS),
+                name: "reduceOrderWithFreeFunds".to_string(),
+            }),
+            PhoenixInstruction::CancelAllOrders => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "cancelAllOrders".to_string(),
+                ..Default::default()
+            }),
+            PhoenixInstruction::CancelAllOrdersWithFreeFunds => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&CANCEL_WITH_FREE_FUNDS_ACCOUNTS),
+                name: "cancelAllOrdersWithFreeFunds".to_string(),
+                ..Default::default()
+            }),
+            PhoenixInstruction::CancelUpTo(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "cancelUpTo".to_string(),
+            }),
+            PhoenixInstruction::CancelUpToWithFreeFunds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CANCEL_WITH_FREE_FUNDS_ACCOUNTS),
+                name: "cancelUpToWithFreeFunds".to_string(),
+            }),
+            PhoenixInstruction::CancelMultipleOrdersById(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "cancelMultipleOrdersById".to_string(),
+            }),
+            PhoenixInstruction::CancelMultipleOrdersByIdWithFreeFunds(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&CANCEL_WITH_FREE_FUNDS_ACCOUNTS),
+                    name: "cancelMultipleOrdersByIdWithFreeFunds".to_string(),
+                })
+            }
+            PhoenixInstruction::WithdrawFunds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "withdrawFunds".to_string(),
+            }),
+            PhoenixInstruction::DepositFunds(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDER_ACCOUNTS),
+                name: "depositFunds".to_string(),
+            }),
+            PhoenixInstruction::RequestSeat => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "phoenixProgram",
+                    "logAuthority",
+                    "market",
+                    "payer",
+                    "seat",
+                    "systemProgram",
+                ]),
+                name: "requestSeat".to_string(),
+                ..Default::default()
+            }),
+            PhoenixInstruction::Log(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&["logAuthority"]),
+                name: "log".to_string(),
+            }),
+            PhoenixInstruction::PlaceMultiplePostOnlyOrders(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PLACE_ORDER_ACCOUNTS),
+                name: "placeMultiplePostOnlyOrders".to_string(),
+            }),
+            PhoenixInstruction::PlaceMultiplePostOnlyOrdersWithFreeFunds(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&FREE_FUNDS_ACCOUNTS),
+                    name: "placeMultiplePostOnlyOrdersWithFreeFunds".to_string(),
+                })
+            }
+            PhoenixInstruction::InitializeMarket(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "phoenixProgram",
+                    "logAuthority",
+                    "market",
+                    "marketCreator",
+                    "baseMint",
+                    "quoteMint",
+                    "baseVault",
+                    "quoteVault",
+                    "systemProgram",
+                    "tokenProgram",
+                ]),
+                name: "initializeMarket".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_log_and_fills() {
+        let market = Pubkey::new_unique();
+        let trader = Pubkey::new_unique();
+        let maker = Pubkey::new_unique();
+        let mut data = vec![15u8, 1u8];
+        data.extend(to_vec(&(0u8, 77u64, 1_700_000_000i64, 250_000_000u64)).unwrap());
+        data.extend(to_vec(&(market, trader, 3u16)).unwrap());
+        data.extend(to_vec(&2u32).unwrap());
+        // a fill against a resting bid, bid sequence numbers have their top bit set
+        data.push(2);
+        data.extend(to_vec(&(0u16, maker, !5u64, 150_000u64, 2_000u64, 0u64)).unwrap());
+        data.push(7);
+        data.extend(to_vec(&(1u16, 30u64)).unwrap());
+        let decoded = PhoenixDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique()],
+            data: data.clone(),
+            stack_height: Some(2),
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "log");
+        assert_eq!(decoded.data["header"]["signer"], trader.to_string());
+        assert_eq!(decoded.data["events"][0]["fill"]["baseLotsFilled"], 2_000);
+        assert_eq!(
+            decoded.data["events"][1]["fee"]["feesCollectedInQuoteLots"],
+            30
+        );
+
+        let PhoenixInstruction::Log(log) = PhoenixInstruction::unpack(&data).unwrap() else {
+            panic!("expected a log instruction");
+        };
+        // 0.001 base unit lots of a 9 decimal base mint, priced in 0.001 units of a 6 decimal quote
+        let header = state::MarketHeader {
+            base_params: state::TokenParams {
+                decimals: 9,
+                ..Default::default()
+            },
+            base_lot_size: 1_000_000,
+            quote_params: state::TokenParams {
+                decimals: 6,
+                ..Default::default()
+            },
+            quote_lot_size: 1,
+            tick_size_in_quote_atoms_per_base_unit: 1_000,
+            raw_base_units_per_base_unit: 1,
+            ..Default::default()
+        };
+        let fills = log.fills(&header);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].taker_side, ix::Side::Ask);
+        assert_eq!(fills[0].maker, maker);
+        assert_eq!(fills[0].taker, trader);
+        assert_eq!(fills[0].base_amount, 2_000_000_000);
+        assert_eq!(fills[0].quote_amount, 300_000_000);
+        assert_eq!(fills[0].price, 0.15);
+    }
+
+    #[test]
+    fn test_unpack_market_header() {
+        assert_eq!(
+            state::discriminant("phoenix::program::accounts::MarketHeader"),
+            state::MARKET_HEADER_DISCRIMINANT
+        );
+        let sol = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+        let usdc = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+        let (base_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = state::MARKET_HEADER_DISCRIMINANT.to_le_bytes().to_vec();
+        data.extend(to_vec(&(1u64, 4096u64, 4096u64, 8193u64)).unwrap());
+        data.extend(to_vec(&(9u32, 255u32, sol, base_vault, 1_000_000u64)).unwrap());
+        data.extend(to_vec(&(6u32, 254u32, usdc, quote_vault, 1u64, 1_000u64)).unwrap());
+        data.extend(to_vec(&(Pubkey::new_unique(), Pubkey::new_unique(), 42u64)).unwrap());
+        data.extend(to_vec(&(Pubkey::default(), 1u32, 0u32)).unwrap());
+        // the order book follows the header padding
+        data.extend([0u8; 8 * 32 + 64]);
+        let header = state::MarketHeader::unpack(&data).unwrap();
+        assert_eq!(header.status, 1);
+        assert_eq!(header.market_size_params.num_seats, 8193);
+        assert_eq!(header.base_params.mint_key, sol);
+        assert_eq!(header.quote_params.vault_key, quote_vault);
+        assert_eq!(header.market_sequence_number, 42);
+        assert_eq!(header.native_price(150_000), 0.15);
+
+        // anchor style account data is rejected
+        data[..8].copy_from_slice(&crate::anchor::account_discriminator("MarketHeader"));
+        assert!(state::MarketHeader::unpack(&data).is_err());
+    }
+}
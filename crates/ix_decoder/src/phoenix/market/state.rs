@@ -0,0 +1,94 @@
+//! Account types
+
+use anyhow::{anyhow, Context};
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::{hash::hashv, pubkey::Pubkey};
+
+use super::PROGRAM_ID;
+use crate::types::serialize_pubkey;
+
+/// Phoenix isn't an anchor program, accounts start with a little endian u64 discriminant
+/// taken from the first 8 bytes of `sha256(program_id || type_name)`
+pub const MARKET_HEADER_DISCRIMINANT: u64 = 5885176012900175465;
+
+/// Returns the discriminant of the account with the given fully qualified type name
+pub fn discriminant(type_name: &str) -> u64 {
+    let hash = hashv(&[PROGRAM_ID.as_ref(), type_name.as_bytes()]);
+    u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketSizeParams {
+    pub bids_size: u64,
+    pub asks_size: u64,
+    pub num_seats: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenParams {
+    pub decimals: u32,
+    pub vault_bump: u32,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint_key: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub vault_key: Pubkey,
+}
+
+/// The header at the start of a market account, the order book which follows it is not decoded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketHeader {
+    pub status: u64,
+    pub market_size_params: MarketSizeParams,
+    pub base_params: TokenParams,
+    /// native base per base lot
+    pub base_lot_size: u64,
+    pub quote_params: TokenParams,
+    /// native quote per quote lot
+    pub quote_lot_size: u64,
+    pub tick_size_in_quote_atoms_per_base_unit: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub authority: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_recipient: Pubkey,
+    pub market_sequence_number: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub successor: Pubkey,
+    pub raw_base_units_per_base_unit: u32,
+}
+
+impl MarketHeader {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let (discriminant, mut rest) = data
+            .split_first_chunk::<8>()
+            .ok_or_else(|| anyhow!("data too short to contain a discriminant"))?;
+        if u64::from_le_bytes(*discriminant) != MARKET_HEADER_DISCRIMINANT {
+            return Err(anyhow!("invalid account discriminant"));
+        }
+        Self::deserialize(&mut rest).with_context(|| "failed to deserialize")
+    }
+
+    /// Native base in one base unit, the unit prices are quoted in
+    fn base_atoms_per_base_unit(&self) -> u128 {
+        10u128.pow(self.base_params.decimals) * self.raw_base_units_per_base_unit.max(1) as u128
+    }
+
+    /// Converts a price in ticks to native quote per native base
+    pub fn native_price(&self, price_in_ticks: u64) -> f64 {
+        price_in_ticks as f64 * self.tick_size_in_quote_atoms_per_base_unit as f64
+            / self.base_atoms_per_base_unit() as f64
+    }
+
+    /// Native quote exchanged for `base_lots` at `price_in_ticks`
+    pub fn native_quote_amount(&self, price_in_ticks: u64, base_lots: u64) -> u64 {
+        let quote = price_in_ticks as u128
+            * self.tick_size_in_quote_atoms_per_base_unit as u128
+            * base_lots as u128
+            * self.base_lot_size as u128
+            / self.base_atoms_per_base_unit();
+        quote.try_into().unwrap_or(u64::MAX)
+    }
+}
@@ -0,0 +1,45 @@
+//! Instruction decoding functions for the phoenix order book program
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use market::PhoenixDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod market;
+
+pub static PHOENIX_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(PhoenixProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Market(PhoenixDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct PhoenixProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Market(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Phoenix::Market"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Market(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for PhoenixProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            market::PROGRAM_ID => Some(Box::new(Programs::Market(PhoenixDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Event types, emitted by the program through self cpi
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_pubkey};
+
+pub const BUY_EVENT: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119];
+pub const SELL_EVENT: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42];
+
+/// Emitted by buys with the realized amounts and fees of the trade.
+///
+/// Fields added by later program versions are not decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyEvent {
+    pub timestamp: i64,
+    pub base_amount_out: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount_in: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_in_with_lp_fee: u64,
+    pub user_quote_amount_in: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_base_token_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_quote_token_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub protocol_fee_recipient: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub protocol_fee_recipient_token_account: Pubkey,
+}
+
+/// Emitted by sells with the realized amounts and fees of the trade.
+///
+/// Fields added by later program versions are not decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellEvent {
+    pub timestamp: i64,
+    pub base_amount_in: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount_out: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_out_without_lp_fee: u64,
+    pub user_quote_amount_out: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_base_token_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user_quote_token_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub protocol_fee_recipient: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub protocol_fee_recipient_token_account: Pubkey,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PumpAmmEvent {
+    Buy(BuyEvent),
+    Sell(SellEvent),
+}
+
+impl PumpAmmEvent {
+    /// Unpacks the event data following the self cpi event tag
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            BUY_EVENT => Self::Buy(anchor::deserialize(rest)?),
+            SELL_EVENT => Self::Sell(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+}
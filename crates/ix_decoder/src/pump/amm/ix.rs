@@ -0,0 +1,147 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::events::PumpAmmEvent;
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkeys},
+};
+
+pub const CREATE_POOL: [u8; 8] = [233, 146, 209, 142, 207, 104, 64, 188];
+pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const CREATE_CONFIG: [u8; 8] = [201, 207, 243, 114, 75, 111, 47, 189];
+pub const UPDATE_FEE_CONFIG: [u8; 8] = [104, 184, 103, 242, 88, 151, 107, 20];
+pub const COLLECT_COIN_CREATOR_FEE: [u8; 8] = [160, 57, 89, 42, 181, 139, 43, 66];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePoolInstruction {
+    pub index: u16,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    /// only present since creator fees were introduced
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub coin_creator: Option<Pubkey>,
+}
+
+impl CreatePoolInstruction {
+    fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let mut input = input;
+        let (index, base_amount_in, quote_amount_in) = <(u16, u64, u64)>::deserialize(&mut input)?;
+        let coin_creator = if input.len() >= 32 {
+            Some(Pubkey::deserialize(&mut input)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            index,
+            base_amount_in,
+            quote_amount_in,
+            coin_creator,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyInstruction {
+    pub base_amount_out: u64,
+    pub max_quote_amount_in: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellInstruction {
+    pub base_amount_in: u64,
+    pub min_quote_amount_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInstruction {
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawInstruction {
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
+}
+
+/// Arguments of both the create config and update fee config instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeConfigInstruction {
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    #[serde(serialize_with = "serialize_pubkeys")]
+    pub protocol_fee_recipients: [Pubkey; 8],
+    /// only present since creator fees were introduced
+    pub coin_creator_fee_basis_points: Option<u64>,
+}
+
+impl FeeConfigInstruction {
+    fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let mut input = input;
+        let (lp_fee_basis_points, protocol_fee_basis_points, protocol_fee_recipients) =
+            <(u64, u64, [Pubkey; 8])>::deserialize(&mut input)?;
+        let coin_creator_fee_basis_points = if input.len() >= 8 {
+            Some(u64::deserialize(&mut input)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            lp_fee_basis_points,
+            protocol_fee_basis_points,
+            protocol_fee_recipients,
+            coin_creator_fee_basis_points,
+        })
+    }
+}
+
+/// Instructions supported by the pump swap amm program, along with the events
+/// it emits through self cpi.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PumpAmmInstruction {
+    CreatePool(CreatePoolInstruction),
+    Buy(BuyInstruction),
+    Sell(SellInstruction),
+    Deposit(DepositInstruction),
+    Withdraw(WithdrawInstruction),
+    CreateConfig(FeeConfigInstruction),
+    UpdateFeeConfig(FeeConfigInstruction),
+    CollectCoinCreatorFee,
+    Event(PumpAmmEvent),
+}
+
+impl PumpAmmInstruction {
+    /// Unpacks a byte buffer into a [PumpAmmInstruction](enum.PumpAmmInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        if let Some(event) = anchor::strip_event_tag(input) {
+            return Ok(Self::Event(PumpAmmEvent::unpack(event)?));
+        }
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE_POOL => Self::CreatePool(CreatePoolInstruction::unpack(rest)?),
+            BUY => Self::Buy(anchor::deserialize(rest)?),
+            SELL => Self::Sell(anchor::deserialize(rest)?),
+            DEPOSIT => Self::Deposit(anchor::deserialize(rest)?),
+            WITHDRAW => Self::Withdraw(anchor::deserialize(rest)?),
+            CREATE_CONFIG => Self::CreateConfig(FeeConfigInstruction::unpack(rest)?),
+            UPDATE_FEE_CONFIG => Self::UpdateFeeConfig(FeeConfigInstruction::unpack(rest)?),
+            COLLECT_COIN_CREATOR_FEE => Self::CollectCoinCreatorFee,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,259 @@
+//! Instruction decoding functions for the pump swap amm program
+
+use anyhow::Context;
+use events::PumpAmmEvent;
+use ix::PumpAmmInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+
+const SWAP_ACCOUNTS: [&str; 19] = [
+    "pool",
+    "user",
+    "globalConfig",
+    "baseMint",
+    "quoteMint",
+    "userBaseTokenAccount",
+    "userQuoteTokenAccount",
+    "poolBaseTokenAccount",
+    "poolQuoteTokenAccount",
+    "protocolFeeRecipient",
+    "protocolFeeRecipientTokenAccount",
+    "baseTokenProgram",
+    "quoteTokenProgram",
+    "systemProgram",
+    "associatedTokenProgram",
+    "eventAuthority",
+    "program",
+    "coinCreatorVaultAta",
+    "coinCreatorVaultAuthority",
+];
+
+const LIQUIDITY_ACCOUNTS: [&str; 15] = [
+    "pool",
+    "globalConfig",
+    "user",
+    "baseMint",
+    "quoteMint",
+    "lpMint",
+    "userBaseTokenAccount",
+    "userQuoteTokenAccount",
+    "userPoolTokenAccount",
+    "poolBaseTokenAccount",
+    "poolQuoteTokenAccount",
+    "tokenProgram",
+    "token2022Program",
+    "eventAuthority",
+    "program",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PumpAmmDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl PumpAmmDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = PumpAmmInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            PumpAmmInstruction::CreatePool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "pool",
+                    "globalConfig",
+                    "creator",
+                    "baseMint",
+                    "quoteMint",
+                    "lpMint",
+                    "userBaseTokenAccount",
+                    "userQuoteTokenAccount",
+                    "userPoolTokenAccount",
+                    "poolBaseTokenAccount",
+                    "poolQuoteTokenAccount",
+                    "systemProgram",
+                    "token2022Program",
+                    "baseTokenProgram",
+                    "quoteTokenProgram",
+                    "associatedTokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "createPool".to_string(),
+            }),
+            PumpAmmInstruction::Buy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "buy".to_string(),
+            }),
+            PumpAmmInstruction::Sell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "sell".to_string(),
+            }),
+            PumpAmmInstruction::Deposit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDITY_ACCOUNTS),
+                name: "deposit".to_string(),
+            }),
+            PumpAmmInstruction::Withdraw(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&LIQUIDITY_ACCOUNTS),
+                name: "withdraw".to_string(),
+            }),
+            PumpAmmInstruction::CreateConfig(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "admin",
+                    "globalConfig",
+                    "systemProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "createConfig".to_string(),
+            }),
+            PumpAmmInstruction::UpdateFeeConfig(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "admin",
+                    "globalConfig",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "updateFeeConfig".to_string(),
+            }),
+            PumpAmmInstruction::CollectCoinCreatorFee => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "quoteMint",
+                    "quoteTokenProgram",
+                    "coinCreator",
+                    "coinCreatorVaultAuthority",
+                    "coinCreatorVaultAta",
+                    "coinCreatorTokenAccount",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "collectCoinCreatorFee".to_string(),
+                ..Default::default()
+            }),
+            PumpAmmInstruction::Event(event) => {
+                let (name, data) = match event {
+                    PumpAmmEvent::Buy(event) => ("buyEvent", to_data_map(&event)?),
+                    PumpAmmEvent::Sell(event) => ("sellEvent", to_data_map(&event)?),
+                };
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&["eventAuthority"]),
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_unpack_accounts() {
+        let base_mint = Pubkey::new_unique();
+        let mut data = state::POOL_DISCRIMINATOR.to_vec();
+        data.push(255);
+        data.extend(7u16.to_le_bytes());
+        data.extend(Pubkey::new_unique().to_bytes());
+        data.extend(base_mint.to_bytes());
+        data.extend([0u8; 32 * 4]);
+        data.extend(1_000u64.to_le_bytes());
+        let pool = state::Pool::unpack(&data).unwrap();
+        assert_eq!(pool.index, 7);
+        assert_eq!(pool.base_mint, base_mint);
+        assert_eq!(pool.lp_supply, 1_000);
+        assert_eq!(pool.coin_creator, None);
+
+        assert_eq!(
+            anchor::account_discriminator("GlobalConfig"),
+            state::GLOBAL_CONFIG_DISCRIMINATOR
+        );
+        assert_eq!(anchor::event_discriminator("SellEvent"), events::SELL_EVENT);
+    }
+
+    #[test]
+    fn test_decode_swaps_and_trade_events() {
+        let accounts: Vec<Pubkey> = (0..SWAP_ACCOUNTS.len())
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let decode = |data: Vec<u8>, accounts: &[Pubkey]| {
+            PumpAmmDecoder::new(PartiallyDecodedInstruction {
+                program_id: PROGRAM_ID,
+                accounts: accounts.to_vec(),
+                data,
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+        assert_eq!(anchor::instruction_discriminator("buy"), ix::BUY);
+        let mut data = ix::BUY.to_vec();
+        data.extend(to_vec(&(5_000_000u64, 120_000_000u64)).unwrap());
+        let decoded = decode(data, &accounts);
+        assert_eq!(decoded.name, "buy");
+        assert_eq!(decoded.data["baseAmountOut"], 5_000_000);
+        assert_eq!(decoded.data["maxQuoteAmountIn"], 120_000_000);
+        assert_eq!(decoded.accounts["user"], accounts[1].to_string());
+        assert_eq!(
+            decoded.accounts["coinCreatorVaultAuthority"],
+            accounts[18].to_string()
+        );
+
+        let (pool, user) = (accounts[0], accounts[1]);
+        let amounts = |first: u64| {
+            // timestamp, amount, limit, user and pool reserves, realized amount and fees
+            let mut data = to_vec(&(1_700_000_000i64, first, 1u64, 2u64, 3u64)).unwrap();
+            data.extend(to_vec(&(4u64, 5u64, 110_000_000u64, 20u64, 220_000u64)).unwrap());
+            data.extend(to_vec(&(5u64, 55_000u64, 110_220_000u64, 110_275_000u64)).unwrap());
+            data.extend(to_vec(&(pool, user, accounts[5], accounts[6])).unwrap());
+            data.extend(to_vec(&(accounts[9], accounts[10])).unwrap());
+            // fields appended by newer program versions are ignored
+            data.extend([0u8; 48]);
+            data
+        };
+        assert_eq!(anchor::event_discriminator("BuyEvent"), events::BUY_EVENT);
+        let mut data = anchor::EVENT_IX_TAG.to_vec();
+        data.extend(events::BUY_EVENT);
+        data.extend(amounts(5_000_000));
+        let decoded = decode(data, &accounts[..1]);
+        assert_eq!(decoded.name, "buyEvent");
+        assert_eq!(decoded.data["baseAmountOut"], 5_000_000);
+        assert_eq!(decoded.data["quoteAmountIn"], 110_000_000);
+        assert_eq!(decoded.data["protocolFee"], 55_000);
+        assert_eq!(decoded.data["userQuoteAmountIn"], 110_275_000);
+        assert_eq!(decoded.data["pool"], pool.to_string());
+        assert_eq!(
+            decoded.data["protocolFeeRecipientTokenAccount"],
+            accounts[10].to_string()
+        );
+        assert_eq!(decoded.accounts["eventAuthority"], accounts[0].to_string());
+
+        assert_eq!(anchor::event_discriminator("SellEvent"), events::SELL_EVENT);
+        let mut data = anchor::EVENT_IX_TAG.to_vec();
+        data.extend(events::SELL_EVENT);
+        data.extend(amounts(7_000_000));
+        let decoded = decode(data, &accounts[..1]);
+        assert_eq!(decoded.name, "sellEvent");
+        assert_eq!(decoded.data["baseAmountIn"], 7_000_000);
+        assert_eq!(decoded.data["quoteAmountOut"], 110_000_000);
+        assert_eq!(decoded.data["user"], user.to_string());
+    }
+}
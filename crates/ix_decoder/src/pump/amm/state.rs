@@ -0,0 +1,86 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey, serialize_pubkeys},
+};
+
+pub const POOL_DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pool {
+    pub pool_bump: u8,
+    pub index: u16,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub creator: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub base_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub quote_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lp_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool_base_token_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool_quote_token_account: Pubkey,
+    pub lp_supply: u64,
+    /// only present in pools created or resized since creator fees were introduced
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub coin_creator: Option<Pubkey>,
+}
+
+impl Pool {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, mut rest) = anchor::split_discriminator(data)?;
+        if discriminator != POOL_DISCRIMINATOR {
+            return Err(anyhow::anyhow!("invalid account discriminator"));
+        }
+        let (pool_bump, index, creator, base_mint, quote_mint, lp_mint) =
+            <(u8, u16, Pubkey, Pubkey, Pubkey, Pubkey)>::deserialize(&mut rest)?;
+        let (pool_base_token_account, pool_quote_token_account, lp_supply) =
+            <(Pubkey, Pubkey, u64)>::deserialize(&mut rest)?;
+        let coin_creator = if rest.len() >= 32 {
+            Some(Pubkey::deserialize(&mut rest)?).filter(|creator| *creator != Pubkey::default())
+        } else {
+            None
+        };
+        Ok(Self {
+            pool_bump,
+            index,
+            creator,
+            base_mint,
+            quote_mint,
+            lp_mint,
+            pool_base_token_account,
+            pool_quote_token_account,
+            lp_supply,
+            coin_creator,
+        })
+    }
+}
+
+/// Fields added by later program versions are not decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalConfig {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub admin: Pubkey,
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    /// bit flags disabling create pool, deposit, withdraw, buy and sell
+    pub disable_flags: u8,
+    #[serde(serialize_with = "serialize_pubkeys")]
+    pub protocol_fee_recipients: [Pubkey; 8],
+}
+
+impl GlobalConfig {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::unpack_account(data, GLOBAL_CONFIG_DISCRIMINATOR)
+    }
+}
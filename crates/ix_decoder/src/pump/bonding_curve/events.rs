@@ -0,0 +1,105 @@
+//! Event types, emitted by the program through self cpi
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey},
+};
+
+pub const CREATE_EVENT: [u8; 8] = [27, 114, 169, 77, 222, 235, 99, 118];
+pub const TRADE_EVENT: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+pub const COMPLETE_EVENT: [u8; 8] = [95, 114, 97, 156, 212, 46, 152, 8];
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub bonding_curve: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    /// only present since creator fees were introduced
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub creator: Option<Pubkey>,
+}
+
+impl CreateEvent {
+    fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let mut input = input;
+        let (name, symbol, uri, mint, bonding_curve, user) =
+            <(String, String, String, Pubkey, Pubkey, Pubkey)>::deserialize(&mut input)?;
+        let creator = if input.len() >= 32 {
+            Some(Pubkey::deserialize(&mut input)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            name,
+            symbol,
+            uri,
+            mint,
+            bonding_curve,
+            user,
+            creator,
+        })
+    }
+}
+
+/// Emitted by buys and sells with the realized amounts of the trade.
+///
+/// Fields added by later program versions are not decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeEvent {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub is_buy: bool,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+}
+
+/// Emitted when the bonding curve completes and the token can be migrated
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteEvent {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub user: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub bonding_curve: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PumpEvent {
+    Create(CreateEvent),
+    Trade(TradeEvent),
+    Complete(CompleteEvent),
+}
+
+impl PumpEvent {
+    /// Unpacks the event data following the self cpi event tag
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE_EVENT => Self::Create(CreateEvent::unpack(rest)?),
+            TRADE_EVENT => Self::Trade(anchor::deserialize(rest)?),
+            COMPLETE_EVENT => Self::Complete(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+}
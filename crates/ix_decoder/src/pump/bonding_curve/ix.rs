@@ -0,0 +1,114 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::events::PumpEvent;
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey},
+};
+
+pub const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+pub const SET_PARAMS: [u8; 8] = [27, 234, 178, 52, 147, 2, 187, 141];
+pub const CREATE: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const MIGRATE: [u8; 8] = [155, 234, 231, 146, 236, 158, 162, 30];
+
+/// Parameters set on the global account.
+///
+/// Parameters added by later program versions are not decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetParamsInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub fee_recipient: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInstruction {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// only present since creator fees were introduced
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub creator: Option<Pubkey>,
+}
+
+impl CreateInstruction {
+    fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let mut input = input;
+        let (name, symbol, uri) = <(String, String, String)>::deserialize(&mut input)?;
+        let creator = if input.len() >= 32 {
+            Some(Pubkey::deserialize(&mut input)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            name,
+            symbol,
+            uri,
+            creator,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyInstruction {
+    /// amount of tokens to buy
+    pub amount: u64,
+    pub max_sol_cost: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellInstruction {
+    /// amount of tokens to sell
+    pub amount: u64,
+    pub min_sol_output: u64,
+}
+
+/// Instructions supported by the pump.fun program, along with the events it
+/// emits through self cpi.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PumpInstruction {
+    Initialize,
+    SetParams(SetParamsInstruction),
+    Create(CreateInstruction),
+    Buy(BuyInstruction),
+    Sell(SellInstruction),
+    Withdraw,
+    Migrate,
+    Event(PumpEvent),
+}
+
+impl PumpInstruction {
+    /// Unpacks a byte buffer into a [PumpInstruction](enum.PumpInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        if let Some(event) = anchor::strip_event_tag(input) {
+            return Ok(Self::Event(PumpEvent::unpack(event)?));
+        }
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INITIALIZE => Self::Initialize,
+            SET_PARAMS => Self::SetParams(anchor::deserialize(rest)?),
+            CREATE => Self::Create(CreateInstruction::unpack(rest)?),
+            BUY => Self::Buy(anchor::deserialize(rest)?),
+            SELL => Self::Sell(anchor::deserialize(rest)?),
+            WITHDRAW => Self::Withdraw,
+            MIGRATE => Self::Migrate,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
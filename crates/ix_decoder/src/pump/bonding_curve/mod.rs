@@ -0,0 +1,202 @@
+//! Instruction decoding functions for the pump.fun bonding curve program
+
+use anyhow::Context;
+use events::PumpEvent;
+use ix::PumpInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PumpDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl PumpDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = PumpInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            PumpInstruction::Initialize => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["global", "user", "systemProgram"]),
+                name: "initialize".to_string(),
+                ..Default::default()
+            }),
+            PumpInstruction::SetParams(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["global", "user", "eventAuthority", "program"]),
+                name: "setParams".to_string(),
+            }),
+            PumpInstruction::Create(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "mint",
+                    "mintAuthority",
+                    "bondingCurve",
+                    "associatedBondingCurve",
+                    "global",
+                    "mplTokenMetadata",
+                    "metadata",
+                    "user",
+                    "systemProgram",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "rent",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "create".to_string(),
+            }),
+            PumpInstruction::Buy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "global",
+                    "feeRecipient",
+                    "mint",
+                    "bondingCurve",
+                    "associatedBondingCurve",
+                    "associatedUser",
+                    "user",
+                    "systemProgram",
+                    "tokenProgram",
+                    "creatorVault",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "buy".to_string(),
+            }),
+            PumpInstruction::Sell(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "global",
+                    "feeRecipient",
+                    "mint",
+                    "bondingCurve",
+                    "associatedBondingCurve",
+                    "associatedUser",
+                    "user",
+                    "systemProgram",
+                    "creatorVault",
+                    "tokenProgram",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "sell".to_string(),
+            }),
+            PumpInstruction::Withdraw => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "global",
+                    "lastWithdraw",
+                    "mint",
+                    "bondingCurve",
+                    "associatedBondingCurve",
+                    "associatedUser",
+                    "user",
+                    "systemProgram",
+                    "tokenProgram",
+                    "rent",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "withdraw".to_string(),
+                ..Default::default()
+            }),
+            PumpInstruction::Migrate => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "global",
+                    "withdrawAuthority",
+                    "mint",
+                    "bondingCurve",
+                    "associatedBondingCurve",
+                    "user",
+                    "systemProgram",
+                    "tokenProgram",
+                    "pumpAmm",
+                    "pool",
+                    "poolAuthority",
+                    "poolAuthorityMintAccount",
+                    "poolAuthorityWsolAccount",
+                    "ammGlobalConfig",
+                    "wsolMint",
+                    "lpMint",
+                    "userPoolTokenAccount",
+                    "poolBaseTokenAccount",
+                    "poolQuoteTokenAccount",
+                    "token2022Program",
+                    "associatedTokenProgram",
+                    "pumpAmmEventAuthority",
+                    "eventAuthority",
+                    "program",
+                ]),
+                name: "migrate".to_string(),
+                ..Default::default()
+            }),
+            PumpInstruction::Event(event) => {
+                let (name, data) = match event {
+                    PumpEvent::Create(event) => ("createEvent", to_data_map(&event)?),
+                    PumpEvent::Trade(event) => ("tradeEvent", to_data_map(&event)?),
+                    PumpEvent::Complete(event) => ("completeEvent", to_data_map(&event)?),
+                };
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&["eventAuthority"]),
+                    name: name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::anchor;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_trade_event() {
+        let mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let mut data = anchor::EVENT_IX_TAG.to_vec();
+        data.extend(events::TRADE_EVENT);
+        data.extend(
+            to_vec(&(
+                mint,
+                1_500_000_000u64,
+                42_000_000u64,
+                true,
+                user,
+                1_700_000_000i64,
+                31_500_000_000u64,
+                1_030_000_000_000_000u64,
+            ))
+            .unwrap(),
+        );
+        // fields appended by newer program versions are ignored
+        data.extend([0u8; 64]);
+        let decoded = PumpDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique()],
+            data,
+            stack_height: Some(2),
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "tradeEvent");
+        assert_eq!(decoded.data["mint"], mint.to_string());
+        assert_eq!(decoded.data["solAmount"], 1_500_000_000u64);
+        assert_eq!(decoded.data["tokenAmount"], 42_000_000u64);
+        assert_eq!(decoded.data["isBuy"], true);
+        assert_eq!(decoded.data["user"], user.to_string());
+    }
+}
@@ -0,0 +1,55 @@
+//! Instructions decoding functions for pump.fun
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use amm::PumpAmmDecoder;
+use anyhow::Context;
+use bonding_curve::PumpDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod amm;
+pub mod bonding_curve;
+
+pub static PUMP_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(PumpProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    BondingCurve(PumpDecoder),
+    Amm(PumpAmmDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct PumpProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::BondingCurve(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Pump::BondingCurve"),
+            Self::Amm(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Pump::Amm"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::BondingCurve(decoder) => format!("{:#?}", decoder),
+            Self::Amm(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for PumpProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            bonding_curve::PROGRAM_ID => Some(Box::new(Programs::BondingCurve(PumpDecoder::new(
+                ix.clone(),
+            )))),
+            amm::PROGRAM_ID => Some(Box::new(Programs::Amm(PumpAmmDecoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
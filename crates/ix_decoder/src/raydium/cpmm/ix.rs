@@ -425,7 +425,7 @@ impl AmmInstruction {
                                 last_order_distance: None,
                             })
                         } else {
-                            return Err(ProgramError::InvalidInstructionData.into());
+                            return Err(ProgramError::InvalidInstructionData);
                         }
                     }
                     AmmParams::Fees => {
@@ -440,7 +440,7 @@ impl AmmInstruction {
                                 last_order_distance: None,
                             })
                         } else {
-                            return Err(ProgramError::InvalidInstructionData.into());
+                            return Err(ProgramError::InvalidInstructionData);
                         }
                     }
                     AmmParams::LastOrderDistance => {
@@ -458,7 +458,7 @@ impl AmmInstruction {
                                 }),
                             })
                         } else {
-                            return Err(ProgramError::InvalidInstructionData.into());
+                            return Err(ProgramError::InvalidInstructionData);
                         }
                     }
                     _ => {
@@ -472,7 +472,7 @@ impl AmmInstruction {
                                 last_order_distance: None,
                             })
                         } else {
-                            return Err(ProgramError::InvalidInstructionData.into());
+                            return Err(ProgramError::InvalidInstructionData);
                         }
                     }
                 }
@@ -565,16 +565,16 @@ impl AmmInstruction {
                         })
                     }
                     _ => {
-                        return Err(ProgramError::InvalidInstructionData.into());
+                        return Err(ProgramError::InvalidInstructionData);
                     }
                 }
             }
-            _ => return Err(ProgramError::InvalidInstructionData.into()),
+            _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 
     fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
-        if input.len() >= 1 {
+        if !input.is_empty() {
             let (amount, rest) = input.split_at(1);
             let amount = amount
                 .get(..1)
@@ -583,7 +583,7 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
@@ -597,7 +597,7 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
@@ -611,14 +611,14 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
     /// Packs a [AmmInstruction](enum.AmmInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
-        match &*self {
+        match self {
             Self::Initialize(InitializeInstruction { nonce, open_time }) => {
                 buf.push(0);
                 buf.push(*nonce);
@@ -676,14 +676,14 @@ impl AmmInstruction {
                     AmmParams::AmmOwner => {
                         let new_pubkey = match new_pubkey {
                             Some(a) => a,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&new_pubkey.to_bytes());
                     }
                     AmmParams::Fees => {
                         let fees = match fees {
                             Some(a) => a,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         let mut fees_slice = [0u8; Fees::LEN];
                         Pack::pack_into_slice(fees, &mut fees_slice[..]);
@@ -692,7 +692,7 @@ impl AmmInstruction {
                     AmmParams::LastOrderDistance => {
                         let distance = match last_order_distance {
                             Some(a) => a,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&distance.last_order_numerator.to_le_bytes());
                         buf.extend_from_slice(&distance.last_order_denominator.to_le_bytes());
@@ -700,7 +700,7 @@ impl AmmInstruction {
                     _ => {
                         let value = match value {
                             Some(a) => a,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&value.to_le_bytes());
                     }
@@ -745,7 +745,7 @@ impl AmmInstruction {
                     SimulateParams::SwapBaseInInfo => {
                         let swap_base_in = match swap_base_in_value {
                             Some(a) => a,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&swap_base_in.amount_in.to_le_bytes());
                         buf.extend_from_slice(&swap_base_in.minimum_amount_out.to_le_bytes());
@@ -753,7 +753,7 @@ impl AmmInstruction {
                     SimulateParams::SwapBaseOutInfo => {
                         let swap_base_out = match swap_base_out_value {
                             Some(a) => a,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&swap_base_out.max_amount_in.to_le_bytes());
                         buf.extend_from_slice(&swap_base_out.amount_out.to_le_bytes());
@@ -779,23 +779,23 @@ impl AmmInstruction {
                         let owner = match owner {
                             Some(owner) => {
                                 if *owner == Pubkey::default() {
-                                    return Err(ProgramError::InvalidInstructionData.into());
+                                    return Err(ProgramError::InvalidInstructionData);
                                 } else {
                                     owner
                                 }
                             }
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&owner.to_bytes());
                     }
                     2 => {
                         let create_pool_fee = match create_pool_fee {
                             Some(create_pool_fee) => create_pool_fee,
-                            None => return Err(ProgramError::InvalidInstructionData.into()),
+                            None => return Err(ProgramError::InvalidInstructionData),
                         };
                         buf.extend_from_slice(&create_pool_fee.to_le_bytes());
                     }
-                    _ => return Err(ProgramError::InvalidInstructionData.into()),
+                    _ => return Err(ProgramError::InvalidInstructionData),
                 }
             }
         }
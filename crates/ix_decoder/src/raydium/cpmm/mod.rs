@@ -1,10 +1,15 @@
 //! Instruction decoding functions for the v1 CPMM program
 
+use std::collections::HashMap;
+
 use anyhow::{anyhow, Context};
 use ix::AmmInstruction;
 use solana_sdk::pubkey::Pubkey;
 
-use crate::types::{DecodedInstruction, IxDataInput, PartiallyDecodedInstruction};
+use crate::{
+    openbook::dex::state::{Market, OpenOrders},
+    types::{DecodedInstruction, PartiallyDecodedInstruction},
+};
 
 pub mod ix;
 pub mod state;
@@ -20,6 +25,7 @@ impl CpmmDecoder {
     pub fn new(ix: PartiallyDecodedInstruction) -> Self {
         Self { ix }
     }
+    #[allow(deprecated)]
     pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
         let ix = AmmInstruction::unpack(&self.ix.data)
             .with_context(|| "failed to decode instruction")?;
@@ -158,13 +164,13 @@ impl CpmmDecoder {
                 name: "withdraw".to_string(),
             }),
 
-            AmmInstruction::MigrateToOpenBook {} => Ok(DecodedInstruction {
+            AmmInstruction::MigrateToOpenBook => Ok(DecodedInstruction {
                 name: "migrateToOpenBook".to_string(),
                 ..Default::default()
             }),
-            AmmInstruction::SetParams(ix) => Err(anyhow!("unimplemented")),
+            AmmInstruction::SetParams(_) => Err(anyhow!("unimplemented")),
             AmmInstruction::WithdrawPnl => Err(anyhow!("unimplemented")),
-            AmmInstruction::WithdrawSrm(WithdrawSrmInstruction) => Err(anyhow!("unimplemented")),
+            AmmInstruction::WithdrawSrm(_) => Err(anyhow!("unimplemented")),
             AmmInstruction::SwapBaseIn(ix) => Ok(DecodedInstruction {
                 data: [
                     ("amountIn".to_string(), serde_json::to_value(ix.amount_in)?),
@@ -245,7 +251,7 @@ impl CpmmDecoder {
                 },
                 name: "swapBaseIn".to_string(),
             }),
-            AmmInstruction::PreInitialize(PreInitializeInstruction) => {
+            AmmInstruction::PreInitialize(_) => {
                 Err(anyhow!("unimplemented"))
             }
             AmmInstruction::SwapBaseOut(ix) => Ok(DecodedInstruction {
@@ -332,13 +338,90 @@ impl CpmmDecoder {
                 name: "swapBaseOut".to_string(),
             }),
 
-            AmmInstruction::SimulateInfo(SimulateInstruction) => Err(anyhow!("unimplemented")),
+            AmmInstruction::SimulateInfo(_) => Err(anyhow!("unimplemented")),
 
-            AmmInstruction::AdminCancelOrders(AdminCancelOrdersInstruction) => {
+            AmmInstruction::AdminCancelOrders(_) => {
                 Err(anyhow!("unimplemented"))
             }
             AmmInstruction::CreateConfigAccount => Err(anyhow!("unimplemented")),
-            AmmInstruction::UpdateConfigAccount(ConfigArgs) => Err(anyhow!("unimplemented")),
+            AmmInstruction::UpdateConfigAccount(_) => Err(anyhow!("unimplemented")),
         }
     }
 }
+
+/// Adds the `coinMint` and `pcMint` of the amm's serum market to the accounts of a decoded
+/// instruction, resolved through its market account or the market of its `ammOpenOrders`.
+///
+/// Returns false when neither account is present in the instruction or in the given state.
+pub fn resolve_market_mints(
+    decoded: &mut DecodedInstruction,
+    markets: &HashMap<Pubkey, Market>,
+    open_orders: &HashMap<Pubkey, OpenOrders>,
+) -> bool {
+    let account = |name: &str| {
+        decoded
+            .accounts
+            .get(name)
+            .and_then(|account| account.as_str()?.parse::<Pubkey>().ok())
+    };
+    let market = ["dexMarket", "dex"]
+        .into_iter()
+        .filter_map(account)
+        .chain(
+            account("ammOpenOrders")
+                .and_then(|key| open_orders.get(&key))
+                .map(|open_orders| open_orders.market),
+        )
+        .find_map(|key| markets.get(&key));
+    let Some(market) = market else {
+        return false;
+    };
+    for (name, mint) in [("coinMint", market.coin_mint), ("pcMint", market.pc_mint)] {
+        decoded
+            .accounts
+            .insert(name.to_string(), serde_json::Value::String(mint.to_string()));
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_resolve_market_mints() {
+        let amm_open_orders = Pubkey::new_unique();
+        let market_address = Pubkey::new_unique();
+        let market = Market {
+            own_address: market_address,
+            coin_mint: Pubkey::new_unique(),
+            pc_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let markets = [(market_address, market)].into_iter().collect();
+        let open_orders = [(
+            amm_open_orders,
+            OpenOrders {
+                market: market_address,
+                ..Default::default()
+            },
+        )]
+        .into_iter()
+        .collect();
+        let mut decoded = DecodedInstruction {
+            accounts: [(
+                "ammOpenOrders".to_string(),
+                serde_json::Value::String(amm_open_orders.to_string()),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        assert!(resolve_market_mints(&mut decoded, &markets, &open_orders));
+        assert_eq!(decoded.accounts["coinMint"], market.coin_mint.to_string());
+        assert_eq!(decoded.accounts["pcMint"], market.pc_mint.to_string());
+
+        let mut decoded = DecodedInstruction::default();
+        assert!(!resolve_market_mints(&mut decoded, &markets, &open_orders));
+    }
+}
@@ -1,10 +1,9 @@
 //! Instructions decoding functions for raydium
 
 use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
-use anyhow::{anyhow, Context};
+use anyhow::Context;
 use cpmm::CpmmDecoder as CpmmV1Decoder;
 use once_cell::sync::Lazy;
-use serde::de;
 use std::sync::Arc;
 
 pub mod cpmm;
@@ -25,9 +24,9 @@ impl ProgramDecoder for Programs {
     fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
         match self {
             Self::CpmmV1(decoder) => {
-                return decoder
+                decoder
                     .decode()
-                    .with_context(|| "failed to decde Raydium::CpmmV1");
+                    .with_context(|| "failed to decde Raydium::CpmmV1")
             }
         }
     }
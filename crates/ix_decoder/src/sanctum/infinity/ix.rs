@@ -0,0 +1,86 @@
+//! Instruction types
+//!
+//! Instruction data is a single byte tag followed by the borsh encoded arguments. The
+//! `*_value_calc_accs` arguments count the sol value calculator accounts of each liquid staking
+//! token, which follow the named accounts ahead of the pricing program accounts.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSolValueInstruction {
+    pub lst_index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapExactInInstruction {
+    pub src_lst_value_calc_accs: u8,
+    pub dst_lst_value_calc_accs: u8,
+    pub src_lst_index: u32,
+    pub dst_lst_index: u32,
+    pub min_amount_out: u64,
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapExactOutInstruction {
+    pub src_lst_value_calc_accs: u8,
+    pub dst_lst_value_calc_accs: u8,
+    pub src_lst_index: u32,
+    pub dst_lst_index: u32,
+    pub max_amount_in: u64,
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddLiquidityInstruction {
+    pub lst_value_calc_accs: u8,
+    pub lst_index: u32,
+    pub lst_amount: u64,
+    pub min_lp_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveLiquidityInstruction {
+    pub lst_value_calc_accs: u8,
+    pub lst_index: u32,
+    pub lp_token_amount: u64,
+    pub min_lst_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InfinityInstruction {
+    SyncSolValue(SyncSolValueInstruction),
+    SwapExactIn(SwapExactInInstruction),
+    SwapExactOut(SwapExactOutInstruction),
+    AddLiquidity(AddLiquidityInstruction),
+    RemoveLiquidity(RemoveLiquidityInstruction),
+    /// pool administration, which isn't decoded
+    Admin(u8),
+}
+
+impl InfinityInstruction {
+    /// Unpacks a byte buffer into a [InfinityInstruction](enum.InfinityInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::SyncSolValue(anchor::deserialize(rest)?),
+            1 => Self::SwapExactIn(anchor::deserialize(rest)?),
+            2 => Self::SwapExactOut(anchor::deserialize(rest)?),
+            3 => Self::AddLiquidity(anchor::deserialize(rest)?),
+            4 => Self::RemoveLiquidity(anchor::deserialize(rest)?),
+            5..=22 => Self::Admin(tag),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
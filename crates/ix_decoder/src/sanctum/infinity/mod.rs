@@ -0,0 +1,127 @@
+//! Instruction decoding functions for the sanctum infinity pool program
+
+use anyhow::{anyhow, Context};
+use ix::InfinityInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx");
+
+const SWAP_ACCOUNTS: [&str; 12] = [
+    "signer",
+    "srcLstMint",
+    "dstLstMint",
+    "srcLstAcc",
+    "dstLstAcc",
+    "protocolFeeAccumulator",
+    "srcLstTokenProgram",
+    "dstLstTokenProgram",
+    "poolState",
+    "lstStateList",
+    "srcPoolReserves",
+    "dstPoolReserves",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InfinityDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl InfinityDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = InfinityInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            InfinityInstruction::SyncSolValue(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lstMint",
+                    "poolState",
+                    "lstStateList",
+                    "poolReserves",
+                ]),
+                name: "syncSolValue".to_string(),
+            }),
+            InfinityInstruction::SwapExactIn(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swapExactIn".to_string(),
+            }),
+            InfinityInstruction::SwapExactOut(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SWAP_ACCOUNTS),
+                name: "swapExactOut".to_string(),
+            }),
+            InfinityInstruction::AddLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "signer",
+                    "lstMint",
+                    "srcLstAcc",
+                    "dstLpAcc",
+                    "lpTokenMint",
+                    "protocolFeeAccumulator",
+                    "lstTokenProgram",
+                    "lpTokenProgram",
+                    "poolState",
+                    "lstStateList",
+                    "poolReserves",
+                ]),
+                name: "addLiquidity".to_string(),
+            }),
+            InfinityInstruction::RemoveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "signer",
+                    "lstMint",
+                    "dstLstAcc",
+                    "srcLpAcc",
+                    "lpTokenMint",
+                    "protocolFeeAccumulator",
+                    "lstTokenProgram",
+                    "lpTokenProgram",
+                    "poolState",
+                    "lstStateList",
+                    "poolReserves",
+                ]),
+                name: "removeLiquidity".to_string(),
+            }),
+            InfinityInstruction::Admin(_) => Err(anyhow!("unimplemented")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_swap_exact_in() {
+        let mut data = vec![1u8, 2, 1];
+        data.extend(3u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(990_000u64.to_le_bytes());
+        data.extend(1_000_000u64.to_le_bytes());
+        let accounts: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+        let decoded = InfinityDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "swapExactIn");
+        assert_eq!(decoded.data["srcLstValueCalcAccs"], 2);
+        assert_eq!(decoded.data["srcLstIndex"], 3);
+        assert_eq!(decoded.data["amount"], 1_000_000);
+        assert_eq!(decoded.accounts["dstLstMint"], accounts[2].to_string());
+        assert_eq!(decoded.accounts.len(), SWAP_ACCOUNTS.len());
+    }
+}
@@ -0,0 +1,48 @@
+//! Instruction decoding functions for the sanctum programs, the spl stake pool forks sanctum
+//! deploys are decoded by [crate::spl::stake_pool]
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use infinity::InfinityDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod infinity;
+
+pub static SANCTUM_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(SanctumProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Infinity(InfinityDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct SanctumProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Infinity(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Sanctum::Infinity"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Infinity(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for SanctumProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            infinity::PROGRAM_ID => Some(Box::new(Programs::Infinity(InfinityDecoder::new(
+                ix.clone(),
+            )))),
+            _ => None,
+        }
+    }
+}
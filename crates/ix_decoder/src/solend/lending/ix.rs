@@ -0,0 +1,124 @@
+//! Instruction types
+//!
+//! Instruction data is a single byte tag followed by the little endian encoded arguments.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_pubkey},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitLendingMarketInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    /// name of the currency prices are quoted in, padded with zeroes
+    #[serde(serialize_with = "serialize_hex")]
+    pub quote_currency: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityAmountInstruction {
+    pub liquidity_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollateralAmountInstruction {
+    pub collateral_amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlashLoanInstruction {
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlashRepayInstruction {
+    pub liquidity_amount: u64,
+    /// index of the matching flash borrow within the transaction
+    pub borrow_instruction_index: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetObligationCloseabilityStatusInstruction {
+    pub closeable: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LendingInstruction {
+    InitLendingMarket(InitLendingMarketInstruction),
+    SetLendingMarketOwnerAndConfig,
+    /// the reserve config following the amount is not decoded
+    InitReserve(LiquidityAmountInstruction),
+    RefreshReserve,
+    DepositReserveLiquidity(LiquidityAmountInstruction),
+    RedeemReserveCollateral(CollateralAmountInstruction),
+    InitObligation,
+    RefreshObligation,
+    DepositObligationCollateral(CollateralAmountInstruction),
+    WithdrawObligationCollateral(CollateralAmountInstruction),
+    BorrowObligationLiquidity(LiquidityAmountInstruction),
+    RepayObligationLiquidity(LiquidityAmountInstruction),
+    LiquidateObligation(LiquidityAmountInstruction),
+    FlashLoan(FlashLoanInstruction),
+    DepositReserveLiquidityAndObligationCollateral(LiquidityAmountInstruction),
+    WithdrawObligationCollateralAndRedeemReserveCollateral(CollateralAmountInstruction),
+    UpdateReserveConfig,
+    LiquidateObligationAndRedeemReserveCollateral(LiquidityAmountInstruction),
+    RedeemFees,
+    FlashBorrowReserveLiquidity(LiquidityAmountInstruction),
+    FlashRepayReserveLiquidity(FlashRepayInstruction),
+    ForgiveDebt(LiquidityAmountInstruction),
+    UpdateMarketMetadata,
+    SetObligationCloseabilityStatus(SetObligationCloseabilityStatusInstruction),
+    DonateToReserve(LiquidityAmountInstruction),
+}
+
+impl LendingInstruction {
+    /// Unpacks a byte buffer into a [LendingInstruction](enum.LendingInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::InitLendingMarket(anchor::deserialize(rest)?),
+            1 => Self::SetLendingMarketOwnerAndConfig,
+            2 => Self::InitReserve(anchor::deserialize(rest)?),
+            3 => Self::RefreshReserve,
+            4 => Self::DepositReserveLiquidity(anchor::deserialize(rest)?),
+            5 => Self::RedeemReserveCollateral(anchor::deserialize(rest)?),
+            6 => Self::InitObligation,
+            7 => Self::RefreshObligation,
+            8 => Self::DepositObligationCollateral(anchor::deserialize(rest)?),
+            9 => Self::WithdrawObligationCollateral(anchor::deserialize(rest)?),
+            10 => Self::BorrowObligationLiquidity(anchor::deserialize(rest)?),
+            11 => Self::RepayObligationLiquidity(anchor::deserialize(rest)?),
+            12 => Self::LiquidateObligation(anchor::deserialize(rest)?),
+            13 => Self::FlashLoan(anchor::deserialize(rest)?),
+            14 => Self::DepositReserveLiquidityAndObligationCollateral(anchor::deserialize(rest)?),
+            15 => Self::WithdrawObligationCollateralAndRedeemReserveCollateral(
+                anchor::deserialize(rest)?,
+            ),
+            16 => Self::UpdateReserveConfig,
+            17 => Self::LiquidateObligationAndRedeemReserveCollateral(anchor::deserialize(rest)?),
+            18 => Self::RedeemFees,
+            19 => Self::FlashBorrowReserveLiquidity(anchor::deserialize(rest)?),
+            20 => Self::FlashRepayReserveLiquidity(anchor::deserialize(rest)?),
+            21 => Self::ForgiveDebt(anchor::deserialize(rest)?),
+            22 => Self::UpdateMarketMetadata,
+            23 => Self::SetObligationCloseabilityStatus(anchor::deserialize(rest)?),
+            24 => Self::DonateToReserve(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
@@ -0,0 +1,392 @@
+//! Instruction decoding functions for the solend (save) lending program
+
+use anyhow::{anyhow, Context};
+use ix::LendingInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SolendDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl SolendDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = LendingInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            LendingInstruction::InitLendingMarket(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "lendingMarket",
+                    "rent",
+                    "tokenProgram",
+                    "oracleProgram",
+                    "switchboardOracleProgram",
+                ]),
+                name: "initLendingMarket".to_string(),
+            }),
+            LendingInstruction::SetLendingMarketOwnerAndConfig => Err(anyhow!("unimplemented")),
+            LendingInstruction::InitReserve(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationCollateral",
+                    "reserve",
+                    "reserveLiquidityMint",
+                    "reserveLiquiditySupply",
+                    "reserveLiquidityFeeReceiver",
+                    "reserveCollateralMint",
+                    "reserveCollateralSupply",
+                    "pythProduct",
+                    "pythPrice",
+                    "switchboardFeed",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "lendingMarketOwner",
+                    "userTransferAuthority",
+                    "rent",
+                    "tokenProgram",
+                ]),
+                name: "initReserve".to_string(),
+            }),
+            LendingInstruction::RefreshReserve => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["reserve", "pythPrice", "switchboardFeed"]),
+                name: "refreshReserve".to_string(),
+                ..Default::default()
+            }),
+            LendingInstruction::DepositReserveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationCollateral",
+                    "reserve",
+                    "reserveLiquiditySupply",
+                    "reserveCollateralMint",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "userTransferAuthority",
+                    "tokenProgram",
+                ]),
+                name: "depositReserveLiquidity".to_string(),
+            }),
+            LendingInstruction::RedeemReserveCollateral(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceCollateral",
+                    "destinationLiquidity",
+                    "reserve",
+                    "reserveCollateralMint",
+                    "reserveLiquiditySupply",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "userTransferAuthority",
+                    "tokenProgram",
+                ]),
+                name: "redeemReserveCollateral".to_string(),
+            }),
+            LendingInstruction::InitObligation => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "obligation",
+                    "lendingMarket",
+                    "obligationOwner",
+                    "rent",
+                    "tokenProgram",
+                ]),
+                name: "initObligation".to_string(),
+                ..Default::default()
+            }),
+            // the reserves of the obligation's deposits and borrows follow the obligation
+            LendingInstruction::RefreshObligation => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["obligation"]),
+                name: "refreshObligation".to_string(),
+                ..Default::default()
+            }),
+            LendingInstruction::DepositObligationCollateral(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceCollateral",
+                    "destinationCollateral",
+                    "depositReserve",
+                    "obligation",
+                    "lendingMarket",
+                    "obligationOwner",
+                    "userTransferAuthority",
+                    "tokenProgram",
+                ]),
+                name: "depositObligationCollateral".to_string(),
+            }),
+            LendingInstruction::WithdrawObligationCollateral(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceCollateral",
+                    "destinationCollateral",
+                    "withdrawReserve",
+                    "obligation",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "obligationOwner",
+                    "tokenProgram",
+                ]),
+                name: "withdrawObligationCollateral".to_string(),
+            }),
+            LendingInstruction::BorrowObligationLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationLiquidity",
+                    "borrowReserve",
+                    "borrowReserveLiquidityFeeReceiver",
+                    "obligation",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "obligationOwner",
+                    "tokenProgram",
+                    "hostFeeReceiver",
+                ]),
+                name: "borrowObligationLiquidity".to_string(),
+            }),
+            LendingInstruction::RepayObligationLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationLiquidity",
+                    "repayReserve",
+                    "obligation",
+                    "lendingMarket",
+                    "userTransferAuthority",
+                    "tokenProgram",
+                ]),
+                name: "repayObligationLiquidity".to_string(),
+            }),
+            LendingInstruction::LiquidateObligation(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationCollateral",
+                    "repayReserve",
+                    "repayReserveLiquiditySupply",
+                    "withdrawReserve",
+                    "withdrawReserveCollateralSupply",
+                    "obligation",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "userTransferAuthority",
+                    "tokenProgram",
+                ]),
+                name: "liquidateObligation".to_string(),
+            }),
+            LendingInstruction::FlashLoan(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationLiquidity",
+                    "reserve",
+                    "flashLoanFeeReceiver",
+                    "hostFeeReceiver",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "tokenProgram",
+                    "flashLoanReceiverProgram",
+                ]),
+                name: "flashLoan".to_string(),
+            }),
+            LendingInstruction::DepositReserveLiquidityAndObligationCollateral(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "sourceLiquidity",
+                        "userCollateral",
+                        "reserve",
+                        "reserveLiquiditySupply",
+                        "reserveCollateralMint",
+                        "lendingMarket",
+                        "lendingMarketAuthority",
+                        "destinationCollateral",
+                        "obligation",
+                        "obligationOwner",
+                        "pythPrice",
+                        "switchboardFeed",
+                        "userTransferAuthority",
+                        "tokenProgram",
+                    ]),
+                    name: "depositReserveLiquidityAndObligationCollateral".to_string(),
+                })
+            }
+            LendingInstruction::WithdrawObligationCollateralAndRedeemReserveCollateral(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "sourceCollateral",
+                        "destinationCollateral",
+                        "withdrawReserve",
+                        "obligation",
+                        "lendingMarket",
+                        "lendingMarketAuthority",
+                        "destinationLiquidity",
+                        "reserveCollateralMint",
+                        "reserveLiquiditySupply",
+                        "obligationOwner",
+                        "userTransferAuthority",
+                        "tokenProgram",
+                    ]),
+                    name: "withdrawObligationCollateralAndRedeemReserveCollateral".to_string(),
+                })
+            }
+            LendingInstruction::UpdateReserveConfig => Err(anyhow!("unimplemented")),
+            LendingInstruction::LiquidateObligationAndRedeemReserveCollateral(ix) => {
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&[
+                        "sourceLiquidity",
+                        "destinationCollateral",
+                        "destinationLiquidity",
+                        "repayReserve",
+                        "repayReserveLiquiditySupply",
+                        "withdrawReserve",
+                        "withdrawReserveCollateralMint",
+                        "withdrawReserveCollateralSupply",
+                        "withdrawReserveLiquiditySupply",
+                        "withdrawReserveLiquidityFeeReceiver",
+                        "obligation",
+                        "lendingMarket",
+                        "lendingMarketAuthority",
+                        "userTransferAuthority",
+                        "tokenProgram",
+                    ]),
+                    name: "liquidateObligationAndRedeemReserveCollateral".to_string(),
+                })
+            }
+            LendingInstruction::RedeemFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "reserve",
+                    "reserveLiquidityFeeReceiver",
+                    "reserveSupplyLiquidity",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "tokenProgram",
+                ]),
+                name: "redeemFees".to_string(),
+                ..Default::default()
+            }),
+            LendingInstruction::FlashBorrowReserveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationLiquidity",
+                    "reserve",
+                    "lendingMarket",
+                    "lendingMarketAuthority",
+                    "instructionsSysvar",
+                    "tokenProgram",
+                ]),
+                name: "flashBorrowReserveLiquidity".to_string(),
+            }),
+            LendingInstruction::FlashRepayReserveLiquidity(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "sourceLiquidity",
+                    "destinationLiquidity",
+                    "reserveLiquidityFeeReceiver",
+                    "hostFeeReceiver",
+                    "reserve",
+                    "lendingMarket",
+                    "userTransferAuthority",
+                    "instructionsSysvar",
+                    "tokenProgram",
+                ]),
+                name: "flashRepayReserveLiquidity".to_string(),
+            }),
+            LendingInstruction::ForgiveDebt(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "obligation",
+                    "reserve",
+                    "lendingMarket",
+                    "lendingMarketOwner",
+                ]),
+                name: "forgiveDebt".to_string(),
+            }),
+            LendingInstruction::UpdateMarketMetadata => Err(anyhow!("unimplemented")),
+            LendingInstruction::SetObligationCloseabilityStatus(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "obligation",
+                    "reserve",
+                    "lendingMarket",
+                    "riskAuthority",
+                    "instructionsSysvar",
+                ]),
+                name: "setObligationCloseabilityStatus".to_string(),
+            }),
+            LendingInstruction::DonateToReserve(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "reserve",
+                    "lendingMarket",
+                    "sourceLiquidity",
+                    "destinationLiquidity",
+                    "userTransferAuthority",
+                    "tokenProgram",
+                ]),
+                name: "donateToReserve".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_borrow_and_obligation() {
+        let mut data = vec![10u8];
+        data.extend(7_500u64.to_le_bytes());
+        let accounts: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+        let decoded = SolendDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "borrowObligationLiquidity");
+        assert_eq!(decoded.data["liquidityAmount"], 7_500);
+        assert_eq!(decoded.accounts["obligation"], accounts[4].to_string());
+        // the host fee receiver is optional
+        assert!(!decoded.accounts.contains_key("hostFeeReceiver"));
+
+        let deposit_reserve = Pubkey::new_unique();
+        let borrow_reserve = Pubkey::new_unique();
+        let mut obligation = vec![state::PROGRAM_VERSION];
+        obligation.extend([0u8; 9 + 64 + 64 + 64]);
+        obligation.extend([1, 1]);
+        obligation.extend(deposit_reserve.to_bytes());
+        obligation.extend(1_000u64.to_le_bytes());
+        obligation.extend(0u128.to_le_bytes());
+        obligation.extend(borrow_reserve.to_bytes());
+        obligation.extend(0u128.to_le_bytes());
+        obligation.extend(12_500_000_000_000_000_000u128.to_le_bytes());
+        obligation.resize(state::OBLIGATION_LEN, 0);
+        let obligation = state::Obligation::unpack(&obligation).unwrap();
+        assert_eq!(obligation.deposits.len(), 1);
+        assert_eq!(obligation.deposits[0].deposit_reserve, deposit_reserve);
+        assert_eq!(obligation.deposits[0].deposited_amount, 1_000);
+        assert_eq!(obligation.borrows.len(), 1);
+        assert_eq!(obligation.borrows[0].borrow_reserve, borrow_reserve);
+        assert_eq!(obligation.borrows[0].borrowed_amount(), 12.5);
+    }
+}
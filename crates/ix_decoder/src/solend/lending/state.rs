@@ -0,0 +1,187 @@
+//! Account types
+//!
+//! Values suffixed with `_wads` are fixed point numbers scaled by 10^18, see [wads_to_f64].
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{serialize_pubkey, serialize_u128};
+
+pub const PROGRAM_VERSION: u8 = 1;
+pub const RESERVE_LEN: usize = 619;
+pub const OBLIGATION_LEN: usize = 1300;
+
+const WAD: f64 = 1_000_000_000_000_000_000.0;
+
+/// Converts a `_wads` value to a float
+pub fn wads_to_f64(value: u128) -> f64 {
+    value as f64 / WAD
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastUpdate {
+    pub slot: u64,
+    pub stale: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReserveLiquidity {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint_pubkey: Pubkey,
+    pub mint_decimals: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub supply_pubkey: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pyth_oracle_pubkey: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub switchboard_oracle_pubkey: Pubkey,
+    pub available_amount: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrowed_amount_wads: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub cumulative_borrow_rate_wads: u128,
+    /// price of a whole token in the quote currency, in wads
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_price: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReserveCollateral {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint_pubkey: Pubkey,
+    pub mint_total_supply: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub supply_pubkey: Pubkey,
+}
+
+/// A reserve of a lending market, the reserve config which follows the collateral is not decoded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reserve {
+    pub version: u8,
+    pub last_update: LastUpdate,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lending_market: Pubkey,
+    pub liquidity: ReserveLiquidity,
+    pub collateral: ReserveCollateral,
+}
+
+impl Reserve {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() != RESERVE_LEN {
+            return Err(anyhow!("invalid reserve length"));
+        }
+        let reserve = Self::deserialize(&mut &data[..])?;
+        if reserve.version != PROGRAM_VERSION {
+            return Err(anyhow!("unsupported reserve version"));
+        }
+        Ok(reserve)
+    }
+
+    /// Liquidity owed to the reserve, available or lent out, in native units
+    pub fn total_liquidity(&self) -> f64 {
+        self.liquidity.available_amount as f64 + wads_to_f64(self.liquidity.borrowed_amount_wads)
+    }
+
+    /// Converts an amount of collateral tokens to the liquidity they can be redeemed for
+    pub fn collateral_to_liquidity(&self, collateral_amount: u64) -> f64 {
+        if self.collateral.mint_total_supply == 0 {
+            return collateral_amount as f64;
+        }
+        collateral_amount as f64 * self.total_liquidity() / self.collateral.mint_total_supply as f64
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObligationCollateral {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub deposit_reserve: Pubkey,
+    /// collateral tokens of the deposit reserve
+    pub deposited_amount: u64,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_value: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObligationLiquidity {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub borrow_reserve: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub cumulative_borrow_rate_wads: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrowed_amount_wads: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub market_value: u128,
+}
+
+impl ObligationLiquidity {
+    /// Debt in native units of the borrowed liquidity, as of the last refresh
+    pub fn borrowed_amount(&self) -> f64 {
+        wads_to_f64(self.borrowed_amount_wads)
+    }
+}
+
+/// A user's deposits and borrows within a lending market, values are in the quote currency
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Obligation {
+    pub version: u8,
+    pub last_update: LastUpdate,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub lending_market: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub owner: Pubkey,
+    #[serde(serialize_with = "serialize_u128")]
+    pub deposited_value: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub borrowed_value: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub allowed_borrow_value: u128,
+    #[serde(serialize_with = "serialize_u128")]
+    pub unhealthy_borrow_value: u128,
+    pub deposits: Vec<ObligationCollateral>,
+    pub borrows: Vec<ObligationLiquidity>,
+}
+
+impl Obligation {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() != OBLIGATION_LEN {
+            return Err(anyhow!("invalid obligation length"));
+        }
+        let mut input = data;
+        let (version, last_update, lending_market, owner) =
+            <(u8, LastUpdate, Pubkey, Pubkey)>::deserialize(&mut input)?;
+        if version != PROGRAM_VERSION {
+            return Err(anyhow!("unsupported obligation version"));
+        }
+        let (deposited_value, borrowed_value, allowed_borrow_value, unhealthy_borrow_value) =
+            <(u128, u128, u128, u128)>::deserialize(&mut input)?;
+        // fields added by later program versions live in the padding
+        let (_padding, deposits_len, borrows_len) = <([u8; 64], u8, u8)>::deserialize(&mut input)?;
+        let deposits = (0..deposits_len)
+            .map(|_| ObligationCollateral::deserialize(&mut input))
+            .collect::<Result<_, _>>()?;
+        let borrows = (0..borrows_len)
+            .map(|_| ObligationLiquidity::deserialize(&mut input))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            version,
+            last_update,
+            lending_market,
+            owner,
+            deposited_value,
+            borrowed_value,
+            allowed_borrow_value,
+            unhealthy_borrow_value,
+            deposits,
+            borrows,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+//! Instruction decoding functions for the solend programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use lending::SolendDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub mod lending;
+
+pub static SOLEND_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(SolendProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Lending(SolendDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct SolendProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Lending(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Solend::Lending"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Lending(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for SolendProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            lending::PROGRAM_ID => {
+                Some(Box::new(Programs::Lending(SolendDecoder::new(ix.clone()))))
+            }
+            _ => None,
+        }
+    }
+}
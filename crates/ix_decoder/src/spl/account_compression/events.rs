@@ -0,0 +1,84 @@
+//! Events logged through the noop program
+//!
+//! The compression program logs the change log of every tree modification, programs
+//! building on it log their own application data the same way.
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_pubkey, to_data_map, DecodedInstruction},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathNode {
+    #[serde(serialize_with = "serialize_hex")]
+    pub node: [u8; 32],
+    /// index of the node in the tree, the root being 1
+    pub index: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogEventV1 {
+    /// the merkle tree which was modified
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub id: Pubkey,
+    /// nodes from the modified leaf up to the root
+    pub path: Vec<PathNode>,
+    /// number of modifications made to the tree
+    pub seq: u64,
+    /// index of the modified leaf
+    pub index: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize)]
+pub enum ChangeLogEvent {
+    V1(ChangeLogEventV1),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize)]
+pub struct ApplicationDataEventV1 {
+    pub application_data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize)]
+pub enum ApplicationDataEvent {
+    V1(ApplicationDataEventV1),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize)]
+pub enum AccountCompressionEvent {
+    ChangeLog(ChangeLogEvent),
+    ApplicationData(ApplicationDataEvent),
+}
+
+impl AccountCompressionEvent {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        anchor::deserialize(data)
+    }
+
+    /// Returns the event as a [DecodedInstruction] named after the event
+    pub fn to_decoded_instruction(&self) -> anyhow::Result<DecodedInstruction> {
+        match self {
+            Self::ChangeLog(ChangeLogEvent::V1(event)) => Ok(DecodedInstruction {
+                data: to_data_map(event)?,
+                name: "changeLog".to_string(),
+                ..Default::default()
+            }),
+            Self::ApplicationData(ApplicationDataEvent::V1(event)) => Ok(DecodedInstruction {
+                data: [(
+                    "applicationData".to_string(),
+                    serde_json::to_value(hex::encode(&event.application_data))?,
+                )]
+                .into_iter()
+                .collect(),
+                name: "applicationData".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
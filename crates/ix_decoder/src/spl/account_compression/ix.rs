@@ -0,0 +1,74 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{anchor, types::serialize_hex};
+
+pub const INIT_EMPTY_MERKLE_TREE: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+pub const REPLACE_LEAF: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+pub const TRANSFER_AUTHORITY: [u8; 8] = [48, 169, 76, 72, 229, 180, 55, 161];
+pub const VERIFY_LEAF: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+pub const APPEND: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+pub const INSERT_OR_APPEND: [u8; 8] = [6, 42, 50, 190, 51, 109, 178, 168];
+pub const CLOSE_EMPTY_TREE: [u8; 8] = [50, 14, 219, 107, 78, 103, 16, 103];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitEmptyMerkleTreeInstruction {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceLeafInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub previous_leaf: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub new_leaf: [u8; 32],
+    pub index: u32,
+}
+
+/// Arguments of `verify_leaf` and `insert_or_append`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeafInstruction {
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub leaf: [u8; 32],
+    pub index: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountCompressionInstruction {
+    InitEmptyMerkleTree(InitEmptyMerkleTreeInstruction),
+    ReplaceLeaf(ReplaceLeafInstruction),
+    TransferAuthority(Pubkey),
+    VerifyLeaf(LeafInstruction),
+    Append([u8; 32]),
+    InsertOrAppend(LeafInstruction),
+    CloseEmptyTree,
+}
+
+impl AccountCompressionInstruction {
+    /// Unpacks a byte buffer into a [AccountCompressionInstruction](enum.AccountCompressionInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            INIT_EMPTY_MERKLE_TREE => Self::InitEmptyMerkleTree(anchor::deserialize(rest)?),
+            REPLACE_LEAF => Self::ReplaceLeaf(anchor::deserialize(rest)?),
+            TRANSFER_AUTHORITY => Self::TransferAuthority(anchor::deserialize(rest)?),
+            VERIFY_LEAF => Self::VerifyLeaf(anchor::deserialize(rest)?),
+            APPEND => Self::Append(anchor::deserialize(rest)?),
+            INSERT_OR_APPEND => Self::InsertOrAppend(anchor::deserialize(rest)?),
+            CLOSE_EMPTY_TREE => Self::CloseEmptyTree,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,108 @@
+//! Instruction decoding functions for the spl account compression program
+//!
+//! Every instruction modifying a tree logs its change log through the noop program,
+//! see [events] and [crate::spl::noop].
+
+use anyhow::Context;
+use ix::AccountCompressionInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+const MODIFY_ACCOUNTS: [&str; 3] = ["merkleTree", "authority", "noop"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AccountCompressionDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl AccountCompressionDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = AccountCompressionInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            AccountCompressionInstruction::InitEmptyMerkleTree(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_ACCOUNTS),
+                name: "initEmptyMerkleTree".to_string(),
+            }),
+            AccountCompressionInstruction::ReplaceLeaf(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_ACCOUNTS),
+                name: "replaceLeaf".to_string(),
+            }),
+            AccountCompressionInstruction::TransferAuthority(new_authority) => {
+                Ok(DecodedInstruction {
+                    data: [(
+                        "newAuthority".to_string(),
+                        serde_json::to_value(new_authority.to_string())?,
+                    )]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.ix.named_accounts(&["merkleTree", "authority"]),
+                    name: "transferAuthority".to_string(),
+                })
+            }
+            AccountCompressionInstruction::VerifyLeaf(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&["merkleTree"]),
+                name: "verifyLeaf".to_string(),
+            }),
+            AccountCompressionInstruction::Append(leaf) => Ok(DecodedInstruction {
+                data: [("leaf".to_string(), serde_json::to_value(hex::encode(leaf))?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&MODIFY_ACCOUNTS),
+                name: "append".to_string(),
+            }),
+            AccountCompressionInstruction::InsertOrAppend(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MODIFY_ACCOUNTS),
+                name: "insertOrAppend".to_string(),
+            }),
+            AccountCompressionInstruction::CloseEmptyTree => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["merkleTree", "authority", "recipient"]),
+                name: "closeEmptyTree".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_replace_leaf() {
+        let tree = Pubkey::new_unique();
+        let mut data = ix::REPLACE_LEAF.to_vec();
+        data.extend([1u8; 32]);
+        data.extend([2u8; 32]);
+        data.extend([3u8; 32]);
+        data.extend(to_vec(&17u32).unwrap());
+        let decoded = AccountCompressionDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![tree, Pubkey::new_unique(), Pubkey::new_unique()],
+            data,
+            stack_height: Some(2),
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "replaceLeaf");
+        assert_eq!(decoded.accounts["merkleTree"], tree.to_string());
+        assert_eq!(decoded.data["newLeaf"], hex::encode([3u8; 32]));
+        assert_eq!(decoded.data["index"], 17);
+    }
+}
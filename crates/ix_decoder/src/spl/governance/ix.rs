@@ -0,0 +1,270 @@
+//! Instruction types
+//!
+//! Instructions are borsh encoded, the variant index being the leading byte.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::state::{GovernanceConfig, RealmConfigArgs};
+use crate::{
+    anchor,
+    types::{
+        serialize_hex, serialize_option_pubkey, serialize_pubkey, EmbeddedInstruction,
+        PartiallyDecodedInstruction,
+    },
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRealmInstruction {
+    pub name: String,
+    pub config_args: RealmConfigArgs,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmountInstruction {
+    pub amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetGovernanceDelegateInstruction {
+    /// removes the delegate when empty
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub new_governance_delegate: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GovernanceConfigInstruction {
+    pub config: GovernanceConfig,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProgramGovernanceInstruction {
+    pub config: GovernanceConfig,
+    pub transfer_upgrade_authority: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMintGovernanceInstruction {
+    pub config: GovernanceConfig,
+    pub transfer_mint_authorities: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTokenGovernanceInstruction {
+    pub config: GovernanceConfig,
+    pub transfer_account_authorities: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MultiChoiceType {
+    /// each selected option receives the full voter weight
+    FullWeight,
+    Weighted,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum VoteType {
+    SingleChoice,
+    MultiChoice {
+        choice_type: MultiChoiceType,
+        min_voter_options: u8,
+        max_voter_options: u8,
+        max_winning_options: u8,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProposalInstruction {
+    pub name: String,
+    pub description_link: String,
+    pub vote_type: VoteType,
+    pub options: Vec<String>,
+    pub use_deny_option: bool,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub proposal_seed: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatoryInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub signatory: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMetaData {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// An instruction stored in a proposal transaction, executed once the proposal succeeds
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionData {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMetaData>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub data: Vec<u8>,
+}
+
+impl InstructionData {
+    pub fn decode(&self) -> EmbeddedInstruction {
+        EmbeddedInstruction::decode(
+            self.program_id.to_string(),
+            Ok(PartiallyDecodedInstruction {
+                program_id: self.program_id,
+                accounts: self.accounts.iter().map(|meta| meta.pubkey).collect(),
+                data: self.data.clone(),
+                stack_height: None,
+            }),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertTransactionInstruction {
+    pub option_index: u8,
+    pub index: u16,
+    /// unused since v3, the governance's minimum hold up time applying instead
+    pub hold_up_time: u32,
+    #[serde(rename = "instructionData")]
+    pub instructions: Vec<InstructionData>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteChoice {
+    pub rank: u8,
+    pub weight_percentage: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Vote {
+    /// one choice per proposal option
+    Approve(Vec<VoteChoice>),
+    Deny,
+    Abstain,
+    Veto,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CastVoteInstruction {
+    pub vote: Vote,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SetRealmAuthorityAction {
+    /// sets the authority without checking it is a governance of the realm
+    SetUnchecked,
+    SetChecked,
+    Remove,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRealmAuthorityInstruction {
+    pub action: SetRealmAuthorityAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRealmConfigInstruction {
+    pub config_args: RealmConfigArgs,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GovernanceInstruction {
+    CreateRealm(CreateRealmInstruction),
+    DepositGoverningTokens(AmountInstruction),
+    WithdrawGoverningTokens,
+    SetGovernanceDelegate(SetGovernanceDelegateInstruction),
+    CreateGovernance(GovernanceConfigInstruction),
+    CreateProgramGovernance(CreateProgramGovernanceInstruction),
+    CreateProposal(CreateProposalInstruction),
+    AddSignatory(SignatoryInstruction),
+    InsertTransaction(InsertTransactionInstruction),
+    RemoveTransaction,
+    CancelProposal,
+    SignOffProposal,
+    CastVote(CastVoteInstruction),
+    FinalizeVote,
+    RelinquishVote,
+    ExecuteTransaction,
+    CreateMintGovernance(CreateMintGovernanceInstruction),
+    CreateTokenGovernance(CreateTokenGovernanceInstruction),
+    SetGovernanceConfig(GovernanceConfigInstruction),
+    FlagTransactionError,
+    SetRealmAuthority(SetRealmAuthorityInstruction),
+    SetRealmConfig(SetRealmConfigInstruction),
+    CreateTokenOwnerRecord,
+    UpdateProgramMetadata,
+    CreateNativeTreasury,
+    RevokeGoverningTokens(AmountInstruction),
+    RefundProposalDeposit,
+    CompleteProposal,
+    AddRequiredSignatory(SignatoryInstruction),
+    RemoveRequiredSignatory,
+}
+
+impl GovernanceInstruction {
+    /// Unpacks a byte buffer into a [GovernanceInstruction](enum.GovernanceInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::CreateRealm(anchor::deserialize(rest)?),
+            1 => Self::DepositGoverningTokens(anchor::deserialize(rest)?),
+            2 => Self::WithdrawGoverningTokens,
+            3 => Self::SetGovernanceDelegate(anchor::deserialize(rest)?),
+            4 => Self::CreateGovernance(anchor::deserialize(rest)?),
+            5 => Self::CreateProgramGovernance(anchor::deserialize(rest)?),
+            6 => Self::CreateProposal(anchor::deserialize(rest)?),
+            7 => Self::AddSignatory(anchor::deserialize(rest)?),
+            // 8 was remove signatory, removed in v3
+            9 => Self::InsertTransaction(anchor::deserialize(rest)?),
+            10 => Self::RemoveTransaction,
+            11 => Self::CancelProposal,
+            12 => Self::SignOffProposal,
+            13 => Self::CastVote(anchor::deserialize(rest)?),
+            14 => Self::FinalizeVote,
+            15 => Self::RelinquishVote,
+            16 => Self::ExecuteTransaction,
+            17 => Self::CreateMintGovernance(anchor::deserialize(rest)?),
+            18 => Self::CreateTokenGovernance(anchor::deserialize(rest)?),
+            19 => Self::SetGovernanceConfig(anchor::deserialize(rest)?),
+            20 => Self::FlagTransactionError,
+            21 => Self::SetRealmAuthority(anchor::deserialize(rest)?),
+            22 => Self::SetRealmConfig(anchor::deserialize(rest)?),
+            23 => Self::CreateTokenOwnerRecord,
+            24 => Self::UpdateProgramMetadata,
+            25 => Self::CreateNativeTreasury,
+            26 => Self::RevokeGoverningTokens(anchor::deserialize(rest)?),
+            27 => Self::RefundProposalDeposit,
+            28 => Self::CompleteProposal,
+            29 => Self::AddRequiredSignatory(anchor::deserialize(rest)?),
+            30 => Self::RemoveRequiredSignatory,
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
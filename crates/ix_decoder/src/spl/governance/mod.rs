@@ -0,0 +1,459 @@
+//! Instruction decoding functions for the spl governance program behind realms, along with
+//! the forks of it deployed by daos running their own instance
+//!
+//! Instructions inserted into proposal transactions are decoded as they are inserted, as
+//! the execution only references the proposal transaction account.
+
+use anyhow::Context;
+use ix::GovernanceInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+/// the realms deployment
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+pub const MANGO_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("GqTPL6qRf5aUuqscLh8Rg2HTxPUXfhhAXDptTLhp1t2J");
+pub const HELIUM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("hgovkRU6Ghe1Qoyb54HdSLdqN7VtxaifBzRmh9jtd3S");
+
+/// programs sharing the governance instruction layout
+pub const PROGRAM_IDS: [Pubkey; 3] = [PROGRAM_ID, MANGO_PROGRAM_ID, HELIUM_PROGRAM_ID];
+
+const TRANSACTION_ACCOUNTS: [&str; 4] = [
+    "proposal",
+    "tokenOwnerRecord",
+    "governanceAuthority",
+    "proposalTransaction",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GovernanceDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl GovernanceDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = GovernanceInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            GovernanceInstruction::CreateRealm(ix) => {
+                let mut accounts = vec![
+                    "realm",
+                    "realmAuthority",
+                    "communityTokenMint",
+                    "communityTokenHolding",
+                    "payer",
+                    "systemProgram",
+                    "tokenProgram",
+                    "rent",
+                ];
+                // the council accounts are only supplied when the realm has a council
+                if ix.config_args.use_council_mint {
+                    accounts.extend(["councilTokenMint", "councilTokenHolding"]);
+                }
+                accounts.push("realmConfig");
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&accounts),
+                    name: "createRealm".to_string(),
+                })
+            }
+            GovernanceInstruction::DepositGoverningTokens(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governingTokenHolding",
+                    "governingTokenSource",
+                    "governingTokenOwner",
+                    "governingTokenSourceAuthority",
+                    "tokenOwnerRecord",
+                    "payer",
+                    "systemProgram",
+                    "tokenProgram",
+                    "realmConfig",
+                ]),
+                name: "depositGoverningTokens".to_string(),
+            }),
+            GovernanceInstruction::WithdrawGoverningTokens => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governingTokenHolding",
+                    "governingTokenDestination",
+                    "governingTokenOwner",
+                    "tokenOwnerRecord",
+                    "tokenProgram",
+                    "realmConfig",
+                ]),
+                name: "withdrawGoverningTokens".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::SetGovernanceDelegate(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["governanceAuthority", "tokenOwnerRecord"]),
+                name: "setGovernanceDelegate".to_string(),
+            }),
+            GovernanceInstruction::CreateGovernance(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governance",
+                    "governedAccount",
+                    "tokenOwnerRecord",
+                    "payer",
+                    "systemProgram",
+                    "governanceAuthority",
+                    "realmConfig",
+                    "voterWeightRecord",
+                ]),
+                name: "createGovernance".to_string(),
+            }),
+            GovernanceInstruction::CreateProgramGovernance(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "programGovernance",
+                    "governedProgram",
+                    "governedProgramData",
+                    "governedProgramUpgradeAuthority",
+                    "tokenOwnerRecord",
+                    "payer",
+                    "bpfUpgradeableLoader",
+                    "systemProgram",
+                    "governanceAuthority",
+                    "realmConfig",
+                ]),
+                name: "createProgramGovernance".to_string(),
+            }),
+            // the voter weight record and proposal deposit accounts follow depending on
+            // the realm's addins, so are left unnamed
+            GovernanceInstruction::CreateProposal(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "proposal",
+                    "governance",
+                    "tokenOwnerRecord",
+                    "governingTokenMint",
+                    "governanceAuthority",
+                    "payer",
+                    "systemProgram",
+                    "realmConfig",
+                ]),
+                name: "createProposal".to_string(),
+            }),
+            GovernanceInstruction::AddSignatory(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "proposal",
+                    "signatoryRecord",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "addSignatory".to_string(),
+            }),
+            GovernanceInstruction::InsertTransaction(ix) => {
+                let instructions: Vec<_> = ix.instructions.iter().map(|ix| ix.decode()).collect();
+                let mut data = to_data_map(&ix)?;
+                data.insert(
+                    "instructions".to_string(),
+                    serde_json::to_value(instructions)?,
+                );
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&[
+                        "governance",
+                        "proposal",
+                        "tokenOwnerRecord",
+                        "governanceAuthority",
+                        "proposalTransaction",
+                        "payer",
+                        "systemProgram",
+                        "rent",
+                    ]),
+                    name: "insertTransaction".to_string(),
+                })
+            }
+            GovernanceInstruction::RemoveTransaction => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "proposal",
+                    "tokenOwnerRecord",
+                    "governanceAuthority",
+                    "proposalTransaction",
+                    "beneficiary",
+                ]),
+                name: "removeTransaction".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::CancelProposal => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governance",
+                    "proposal",
+                    "tokenOwnerRecord",
+                    "governanceAuthority",
+                ]),
+                name: "cancelProposal".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::SignOffProposal => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governance",
+                    "proposal",
+                    "signatory",
+                    "signatoryRecord",
+                ]),
+                name: "signOffProposal".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::CastVote(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governance",
+                    "proposal",
+                    "proposalTokenOwnerRecord",
+                    "voterTokenOwnerRecord",
+                    "governanceAuthority",
+                    "voteRecord",
+                    "voteGoverningTokenMint",
+                    "payer",
+                    "systemProgram",
+                    "realmConfig",
+                ]),
+                name: "castVote".to_string(),
+            }),
+            GovernanceInstruction::FinalizeVote => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governance",
+                    "proposal",
+                    "proposalTokenOwnerRecord",
+                    "governingTokenMint",
+                    "realmConfig",
+                ]),
+                name: "finalizeVote".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::RelinquishVote => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governance",
+                    "proposal",
+                    "tokenOwnerRecord",
+                    "voteRecord",
+                    "voteGoverningTokenMint",
+                    "governanceAuthority",
+                    "beneficiary",
+                ]),
+                name: "relinquishVote".to_string(),
+                ..Default::default()
+            }),
+            // the accounts of the executed instructions follow
+            GovernanceInstruction::ExecuteTransaction => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "governance",
+                    "proposal",
+                    "proposalTransaction",
+                ]),
+                name: "executeTransaction".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::CreateMintGovernance(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "mintGovernance",
+                    "governedMint",
+                    "governedMintAuthority",
+                    "tokenOwnerRecord",
+                    "payer",
+                    "tokenProgram",
+                    "systemProgram",
+                    "governanceAuthority",
+                    "realmConfig",
+                ]),
+                name: "createMintGovernance".to_string(),
+            }),
+            GovernanceInstruction::CreateTokenGovernance(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "tokenGovernance",
+                    "governedToken",
+                    "governedTokenOwner",
+                    "tokenOwnerRecord",
+                    "payer",
+                    "tokenProgram",
+                    "systemProgram",
+                    "governanceAuthority",
+                    "realmConfig",
+                ]),
+                name: "createTokenGovernance".to_string(),
+            }),
+            GovernanceInstruction::SetGovernanceConfig(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&["governance"]),
+                name: "setGovernanceConfig".to_string(),
+            }),
+            GovernanceInstruction::FlagTransactionError => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&TRANSACTION_ACCOUNTS),
+                name: "flagTransactionError".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::SetRealmAuthority(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["realm", "realmAuthority", "newRealmAuthority"]),
+                name: "setRealmAuthority".to_string(),
+            }),
+            GovernanceInstruction::SetRealmConfig(ix) => {
+                let mut accounts = vec!["realm", "realmAuthority"];
+                if ix.config_args.use_council_mint {
+                    accounts.extend(["councilTokenMint", "councilTokenHolding"]);
+                }
+                accounts.extend(["systemProgram", "realmConfig"]);
+                Ok(DecodedInstruction {
+                    data: to_data_map(&ix)?,
+                    accounts: self.ix.named_accounts(&accounts),
+                    name: "setRealmConfig".to_string(),
+                })
+            }
+            GovernanceInstruction::CreateTokenOwnerRecord => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governingTokenOwner",
+                    "tokenOwnerRecord",
+                    "governingTokenMint",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "createTokenOwnerRecord".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::UpdateProgramMetadata => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["programMetadata", "payer", "systemProgram"]),
+                name: "updateProgramMetadata".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::CreateNativeTreasury => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "governance",
+                    "nativeTreasury",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "createNativeTreasury".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::RevokeGoverningTokens(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "realm",
+                    "governingTokenHolding",
+                    "tokenOwnerRecord",
+                    "governingTokenMint",
+                    "revokeAuthority",
+                    "realmConfig",
+                    "tokenProgram",
+                ]),
+                name: "revokeGoverningTokens".to_string(),
+            }),
+            GovernanceInstruction::RefundProposalDeposit => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "proposal",
+                    "proposalDeposit",
+                    "proposalDepositPayer",
+                ]),
+                name: "refundProposalDeposit".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::CompleteProposal => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "proposal",
+                    "tokenOwnerRecord",
+                    "completeProposalAuthority",
+                ]),
+                name: "completeProposal".to_string(),
+                ..Default::default()
+            }),
+            GovernanceInstruction::AddRequiredSignatory(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "governance",
+                    "requiredSignatory",
+                    "payer",
+                    "systemProgram",
+                ]),
+                name: "addRequiredSignatory".to_string(),
+            }),
+            GovernanceInstruction::RemoveRequiredSignatory => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "governance",
+                    "requiredSignatory",
+                    "beneficiary",
+                ]),
+                name: "removeRequiredSignatory".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+    use solana_sdk::bpf_loader_upgradeable;
+
+    #[test]
+    fn test_decode_insert_upgrade_transaction() {
+        let program = Pubkey::new_unique();
+        let buffer = Pubkey::new_unique();
+        let governance = Pubkey::new_unique();
+        let upgrade = bpf_loader_upgradeable::upgrade(&program, &buffer, &governance, &governance);
+        let mut data = vec![9u8];
+        data.extend(to_vec(&(0u8, 0u16, 0u32)).unwrap());
+        data.extend(
+            to_vec(&vec![(
+                upgrade.program_id,
+                upgrade
+                    .accounts
+                    .iter()
+                    .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+                    .collect::<Vec<_>>(),
+                upgrade.data,
+            )])
+            .unwrap(),
+        );
+        let decoded = GovernanceDecoder::new(PartiallyDecodedInstruction {
+            program_id: MANGO_PROGRAM_ID,
+            accounts: (0..8).map(|_| Pubkey::new_unique()).collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "insertTransaction");
+        assert_eq!(
+            decoded.data["instructionData"][0]["programId"],
+            bpf_loader_upgradeable::ID.to_string()
+        );
+        let upgrade = &decoded.data["instructions"][0]["decoded"];
+        assert_eq!(upgrade["name"], "upgrade");
+        assert_eq!(upgrade["accounts"]["program"], program.to_string());
+        assert_eq!(upgrade["accounts"]["authority"], governance.to_string());
+    }
+}
@@ -0,0 +1,83 @@
+//! Account types
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteThreshold {
+    /// percentage of yes votes out of the max voter weight required for success
+    YesVotePercentage(u8),
+    /// not supported by the program
+    QuorumPercentage(u8),
+    /// voting is disabled for the population
+    Disabled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteTipping {
+    /// the vote ends as soon as the outcome can't change
+    Strict,
+    /// the vote ends as soon as the threshold is reached
+    Early,
+    /// the vote always runs for the full voting time
+    Disabled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GovernanceConfig {
+    pub community_vote_threshold: VoteThreshold,
+    pub min_community_weight_to_create_proposal: u64,
+    /// seconds before an approved transaction can be executed
+    pub min_transaction_hold_up_time: u32,
+    /// seconds during which any vote can be cast
+    pub voting_base_time: u32,
+    pub community_vote_tipping: VoteTipping,
+    pub council_vote_threshold: VoteThreshold,
+    pub council_veto_vote_threshold: VoteThreshold,
+    pub min_council_weight_to_create_proposal: u64,
+    pub council_vote_tipping: VoteTipping,
+    pub community_veto_vote_threshold: VoteThreshold,
+    /// seconds after the base voting time during which only vetoes and denials can be cast
+    pub voting_cool_off_time: u32,
+    /// proposals a member can have open without paying a deposit
+    pub deposit_exempt_proposal_count: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MintMaxVoterWeightSource {
+    /// fraction of the mint supply, scaled by 10^10
+    SupplyFraction(u64),
+    Absolute(u64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GoverningTokenType {
+    Liquid,
+    /// tokens can't be withdrawn, only revoked by the mint authority
+    Membership,
+    /// tokens can't be used to vote or create proposals
+    Dormant,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoverningTokenConfigArgs {
+    pub use_voter_weight_addin: bool,
+    pub use_max_voter_weight_addin: bool,
+    pub token_type: GoverningTokenType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealmConfigArgs {
+    pub use_council_mint: bool,
+    pub min_community_weight_to_create_governance: u64,
+    pub community_mint_max_voter_weight_source: MintMaxVoterWeightSource,
+    pub community_token_config_args: GoverningTokenConfigArgs,
+    pub council_token_config_args: GoverningTokenConfigArgs,
+}
@@ -0,0 +1,136 @@
+//! Instruction decoding functions for the v1 and v2 memo programs
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{DecodedInstruction, PartiallyDecodedInstruction};
+
+pub const PROGRAM_ID_V1: Pubkey =
+    solana_sdk::pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+pub const PROGRAM_ID_V2: Pubkey =
+    solana_sdk::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MemoEncoding {
+    Utf8,
+    /// used when the memo is not valid utf-8
+    Hex,
+}
+
+/// A memo attached to a transaction
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Memo {
+    pub program_id: String,
+    /// the memo text, or its hex encoding if the memo is not valid utf-8
+    pub memo: String,
+    pub encoding: MemoEncoding,
+    /// accounts passed to the memo program. The v2 program requires all of them to sign
+    /// the transaction, while the v1 program doesn't check them
+    pub accounts: Vec<String>,
+}
+
+impl Memo {
+    /// Returns the memo carried by the instruction, or None if it isn't a memo instruction
+    pub fn from_instruction(ix: &PartiallyDecodedInstruction) -> Option<Self> {
+        if ix.program_id != PROGRAM_ID_V1 && ix.program_id != PROGRAM_ID_V2 {
+            return None;
+        }
+        let (memo, encoding) = match std::str::from_utf8(&ix.data) {
+            Ok(memo) => (memo.to_string(), MemoEncoding::Utf8),
+            Err(_) => (hex::encode(&ix.data), MemoEncoding::Hex),
+        };
+        Some(Self {
+            program_id: ix.program_id.to_string(),
+            memo,
+            encoding,
+            accounts: ix.accounts.iter().map(ToString::to_string).collect(),
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MemoDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl MemoDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let memo = Memo::from_instruction(&self.ix)
+            .ok_or_else(|| anyhow::anyhow!("not a memo instruction"))?;
+        // only the v2 program checks that its accounts signed
+        let accounts_name = if self.ix.program_id == PROGRAM_ID_V2 {
+            "signers"
+        } else {
+            "accounts"
+        };
+        Ok(DecodedInstruction {
+            data: [
+                ("memo".to_string(), serde_json::to_value(memo.memo)?),
+                ("encoding".to_string(), serde_json::to_value(memo.encoding)?),
+            ]
+            .into_iter()
+            .collect(),
+            accounts: [(
+                accounts_name.to_string(),
+                serde_json::to_value(memo.accounts)?,
+            )]
+            .into_iter()
+            .collect(),
+            name: "memo".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_memo() {
+        let decode = |program_id: Pubkey, data: &[u8], accounts: Vec<Pubkey>| {
+            MemoDecoder::new(PartiallyDecodedInstruction {
+                program_id,
+                accounts,
+                data: data.to_vec(),
+                stack_height: None,
+            })
+            .decode()
+            .unwrap()
+        };
+
+        // v1 memos have no accounts
+        let decoded = decode(PROGRAM_ID_V1, "hello ☀".as_bytes(), vec![]);
+        assert_eq!(decoded.name, "memo");
+        assert_eq!(decoded.data["memo"], "hello ☀");
+        assert_eq!(decoded.data["encoding"], "utf8");
+        assert_eq!(decoded.accounts["accounts"], serde_json::json!([]));
+
+        let signers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let decoded = decode(PROGRAM_ID_V2, &[0xff, 0x00, 0xab], signers.clone());
+        assert_eq!(decoded.data["memo"], "ff00ab");
+        assert_eq!(decoded.data["encoding"], "hex");
+        assert_eq!(
+            decoded.accounts["signers"],
+            serde_json::json!([signers[0].to_string(), signers[1].to_string()])
+        );
+
+        let decoded = decode(PROGRAM_ID_V2, &[], vec![]);
+        assert_eq!(decoded.data["memo"], "");
+        assert_eq!(decoded.data["encoding"], "utf8");
+        assert_eq!(decoded.accounts["signers"], serde_json::json!([]));
+
+        assert!(MemoDecoder::new(PartiallyDecodedInstruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: b"memo".to_vec(),
+            stack_height: None,
+        })
+        .decode()
+        .is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! Instruction decoding functions for the solana program library programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use account_compression::AccountCompressionDecoder;
+use anyhow::Context;
+use governance::GovernanceDecoder;
+use memo::MemoDecoder;
+use noop::NoopDecoder;
+use once_cell::sync::Lazy;
+use stake_pool::StakePoolDecoder;
+use std::sync::Arc;
+use token_swap::TokenSwapDecoder;
+
+pub mod account_compression;
+pub mod governance;
+pub mod memo;
+pub mod noop;
+pub mod stake_pool;
+pub mod token_swap;
+
+pub static SPL_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(SplProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    Memo(MemoDecoder),
+    StakePool(StakePoolDecoder),
+    TokenSwap(TokenSwapDecoder),
+    AccountCompression(AccountCompressionDecoder),
+    Noop(NoopDecoder),
+    Governance(GovernanceDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct SplProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::Memo(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Spl::Memo"),
+            Self::StakePool(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Spl::StakePool"),
+            Self::TokenSwap(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Spl::TokenSwap"),
+            Self::AccountCompression(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Spl::AccountCompression"),
+            Self::Noop(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Spl::Noop"),
+            Self::Governance(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Spl::Governance"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::Memo(decoder) => format!("{:#?}", decoder),
+            Self::StakePool(decoder) => format!("{:#?}", decoder),
+            Self::TokenSwap(decoder) => format!("{:#?}", decoder),
+            Self::AccountCompression(decoder) => format!("{:#?}", decoder),
+            Self::Noop(decoder) => format!("{:#?}", decoder),
+            Self::Governance(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for SplProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            memo::PROGRAM_ID_V1 | memo::PROGRAM_ID_V2 => {
+                Some(Box::new(Programs::Memo(MemoDecoder::new(ix.clone()))))
+            }
+            program_id if stake_pool::PROGRAM_IDS.contains(&program_id) => Some(Box::new(
+                Programs::StakePool(StakePoolDecoder::new(ix.clone())),
+            )),
+            program_id if token_swap::PROGRAM_IDS.contains(&program_id) => Some(Box::new(
+                Programs::TokenSwap(TokenSwapDecoder::new(ix.clone())),
+            )),
+            account_compression::PROGRAM_ID => Some(Box::new(Programs::AccountCompression(
+                AccountCompressionDecoder::new(ix.clone()),
+            ))),
+            noop::PROGRAM_ID => Some(Box::new(Programs::Noop(NoopDecoder::new(ix.clone())))),
+            program_id if governance::PROGRAM_IDS.contains(&program_id) => Some(Box::new(
+                Programs::Governance(GovernanceDecoder::new(ix.clone())),
+            )),
+            _ => None,
+        }
+    }
+}
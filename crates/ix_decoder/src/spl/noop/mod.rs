@@ -0,0 +1,72 @@
+//! Instruction decoding functions for the spl noop program
+//!
+//! The noop program is used to log data through instruction data, which unlike program
+//! logs isn't truncated. Only the account compression events are decoded, when logged on
+//! behalf of bubblegum the decoded leaf is attached to the bubblegum instruction by
+//! [crate::transaction::decode_transaction].
+
+use anyhow::Context;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    spl::account_compression::events::AccountCompressionEvent,
+    types::{DecodedInstruction, PartiallyDecodedInstruction},
+};
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NoopDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl NoopDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        AccountCompressionEvent::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?
+            .to_decoded_instruction()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_change_log() {
+        let tree = Pubkey::new_unique();
+        // change log, v1
+        let mut data = vec![0, 0];
+        data.extend(tree.to_bytes());
+        data.extend(to_vec(&vec![([4u8; 32], 5u32), ([6u8; 32], 2u32)]).unwrap());
+        data.extend(to_vec(&(9u64, 1u32)).unwrap());
+        let decoded = NoopDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![],
+            data,
+            stack_height: Some(3),
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "changeLog");
+        assert_eq!(decoded.data["id"], tree.to_string());
+        assert_eq!(decoded.data["seq"], 9);
+        assert_eq!(decoded.data["index"], 1);
+        assert_eq!(decoded.data["path"][1]["node"], hex::encode([6u8; 32]));
+
+        let decoded = NoopDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![],
+            data: to_vec(&(1u8, 0u8, vec![0xabu8, 0xcd])).unwrap(),
+            stack_height: Some(2),
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "applicationData");
+        assert_eq!(decoded.data["applicationData"], "abcd");
+    }
+}
@@ -0,0 +1,211 @@
+//! Instruction types
+//!
+//! Instructions are borsh encoded, the variant index being the leading byte.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::state::Fee;
+use crate::{anchor, types::serialize_option_pubkey};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeInstruction {
+    pub fee: Fee,
+    pub withdrawal_fee: Fee,
+    pub deposit_fee: Fee,
+    /// percentage of the deposit fee paid to the referrer
+    pub referral_fee: u8,
+    pub max_validators: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddValidatorToPoolInstruction {
+    /// seed of the validator stake account, zero for the default address
+    pub seed: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStakeInstruction {
+    pub lamports: u64,
+    pub transient_stake_seed: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdditionalValidatorStakeInstruction {
+    pub lamports: u64,
+    pub transient_stake_seed: u64,
+    pub ephemeral_stake_seed: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreferredValidatorType {
+    Deposit,
+    Withdraw,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPreferredValidatorInstruction {
+    pub validator_type: PreferredValidatorType,
+    /// unsets the preferred validator when empty
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub validator_vote_address: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateValidatorListBalanceInstruction {
+    pub start_index: u32,
+    pub no_merge: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolTokensInstruction {
+    pub pool_tokens: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LamportsInstruction {
+    pub lamports: u64,
+}
+
+/// Percentages are out of 100, fees are fractions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeeType {
+    SolReferral(u8),
+    StakeReferral(u8),
+    Epoch(Fee),
+    StakeWithdrawal(Fee),
+    SolDeposit(Fee),
+    StakeDeposit(Fee),
+    SolWithdrawal(Fee),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeeInstruction {
+    pub fee: FeeType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FundingType {
+    StakeDeposit,
+    SolDeposit,
+    SolWithdraw,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFundingAuthorityInstruction {
+    pub funding_type: FundingType,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadataInstruction {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositWithSlippageInstruction {
+    pub minimum_pool_tokens_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositSolWithSlippageInstruction {
+    pub lamports_in: u64,
+    pub minimum_pool_tokens_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawWithSlippageInstruction {
+    pub pool_tokens_in: u64,
+    pub minimum_lamports_out: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StakePoolInstruction {
+    Initialize(InitializeInstruction),
+    AddValidatorToPool(AddValidatorToPoolInstruction),
+    RemoveValidatorFromPool,
+    DecreaseValidatorStake(ValidatorStakeInstruction),
+    IncreaseValidatorStake(ValidatorStakeInstruction),
+    SetPreferredValidator(SetPreferredValidatorInstruction),
+    UpdateValidatorListBalance(UpdateValidatorListBalanceInstruction),
+    UpdateStakePoolBalance,
+    CleanupRemovedValidatorEntries,
+    DepositStake,
+    WithdrawStake(PoolTokensInstruction),
+    SetManager,
+    SetFee(SetFeeInstruction),
+    SetStaker,
+    DepositSol(LamportsInstruction),
+    SetFundingAuthority(SetFundingAuthorityInstruction),
+    WithdrawSol(PoolTokensInstruction),
+    CreateTokenMetadata(TokenMetadataInstruction),
+    UpdateTokenMetadata(TokenMetadataInstruction),
+    IncreaseAdditionalValidatorStake(AdditionalValidatorStakeInstruction),
+    DecreaseAdditionalValidatorStake(AdditionalValidatorStakeInstruction),
+    DecreaseValidatorStakeWithReserve(ValidatorStakeInstruction),
+    /// deprecated
+    Redelegate,
+    DepositStakeWithSlippage(DepositWithSlippageInstruction),
+    WithdrawStakeWithSlippage(WithdrawWithSlippageInstruction),
+    DepositSolWithSlippage(DepositSolWithSlippageInstruction),
+    WithdrawSolWithSlippage(WithdrawWithSlippageInstruction),
+}
+
+impl StakePoolInstruction {
+    /// Unpacks a byte buffer into a [StakePoolInstruction](enum.StakePoolInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::Initialize(anchor::deserialize(rest)?),
+            1 => Self::AddValidatorToPool(anchor::deserialize(rest)?),
+            2 => Self::RemoveValidatorFromPool,
+            3 => Self::DecreaseValidatorStake(anchor::deserialize(rest)?),
+            4 => Self::IncreaseValidatorStake(anchor::deserialize(rest)?),
+            5 => Self::SetPreferredValidator(anchor::deserialize(rest)?),
+            6 => Self::UpdateValidatorListBalance(anchor::deserialize(rest)?),
+            7 => Self::UpdateStakePoolBalance,
+            8 => Self::CleanupRemovedValidatorEntries,
+            9 => Self::DepositStake,
+            10 => Self::WithdrawStake(anchor::deserialize(rest)?),
+            11 => Self::SetManager,
+            12 => Self::SetFee(anchor::deserialize(rest)?),
+            13 => Self::SetStaker,
+            14 => Self::DepositSol(anchor::deserialize(rest)?),
+            15 => Self::SetFundingAuthority(anchor::deserialize(rest)?),
+            16 => Self::WithdrawSol(anchor::deserialize(rest)?),
+            17 => Self::CreateTokenMetadata(anchor::deserialize(rest)?),
+            18 => Self::UpdateTokenMetadata(anchor::deserialize(rest)?),
+            19 => Self::IncreaseAdditionalValidatorStake(anchor::deserialize(rest)?),
+            20 => Self::DecreaseAdditionalValidatorStake(anchor::deserialize(rest)?),
+            21 => Self::DecreaseValidatorStakeWithReserve(anchor::deserialize(rest)?),
+            22 => Self::Redelegate,
+            23 => Self::DepositStakeWithSlippage(anchor::deserialize(rest)?),
+            24 => Self::WithdrawStakeWithSlippage(anchor::deserialize(rest)?),
+            25 => Self::DepositSolWithSlippage(anchor::deserialize(rest)?),
+            26 => Self::WithdrawSolWithSlippage(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
@@ -0,0 +1,422 @@
+//! Instruction decoding functions for the spl stake pool program, which runs most stake
+//! pools including jito's, and the forks of it deployed by sanctum
+
+use anyhow::{anyhow, Context};
+use ix::StakePoolInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+/// the spl reference deployment
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+pub const SANCTUM_SINGLE_VALIDATOR_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY");
+pub const SANCTUM_MULTI_VALIDATOR_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn");
+
+/// programs sharing the stake pool instruction layout
+pub const PROGRAM_IDS: [Pubkey; 3] = [
+    PROGRAM_ID,
+    SANCTUM_SINGLE_VALIDATOR_PROGRAM_ID,
+    SANCTUM_MULTI_VALIDATOR_PROGRAM_ID,
+];
+
+const DEPOSIT_STAKE_ACCOUNTS: [&str; 15] = [
+    "stakePool",
+    "validatorList",
+    "stakeDepositAuthority",
+    "withdrawAuthority",
+    "depositStake",
+    "validatorStake",
+    "reserveStake",
+    "destinationPoolAccount",
+    "managerFeeAccount",
+    "referrerPoolTokensAccount",
+    "poolMint",
+    "clock",
+    "stakeHistory",
+    "tokenProgram",
+    "stakeProgram",
+];
+
+const WITHDRAW_STAKE_ACCOUNTS: [&str; 13] = [
+    "stakePool",
+    "validatorList",
+    "withdrawAuthority",
+    "stakeToSplit",
+    "stakeToReceive",
+    "userStakeAuthority",
+    "userTransferAuthority",
+    "sourcePoolAccount",
+    "managerFeeAccount",
+    "poolMint",
+    "clock",
+    "tokenProgram",
+    "stakeProgram",
+];
+
+const DEPOSIT_SOL_ACCOUNTS: [&str; 11] = [
+    "stakePool",
+    "withdrawAuthority",
+    "reserveStake",
+    "fundingAccount",
+    "destinationPoolAccount",
+    "managerFeeAccount",
+    "referrerPoolTokensAccount",
+    "poolMint",
+    "systemProgram",
+    "tokenProgram",
+    "solDepositAuthority",
+];
+
+const WITHDRAW_SOL_ACCOUNTS: [&str; 13] = [
+    "stakePool",
+    "withdrawAuthority",
+    "userTransferAuthority",
+    "sourcePoolAccount",
+    "reserveStake",
+    "destinationSystemAccount",
+    "managerFeeAccount",
+    "poolMint",
+    "clock",
+    "stakeHistory",
+    "stakeProgram",
+    "tokenProgram",
+    "solWithdrawAuthority",
+];
+
+const DECREASE_VALIDATOR_STAKE_WITH_RESERVE_ACCOUNTS: [&str; 11] = [
+    "stakePool",
+    "staker",
+    "withdrawAuthority",
+    "validatorList",
+    "reserveStake",
+    "validatorStake",
+    "transientStake",
+    "clock",
+    "stakeHistory",
+    "systemProgram",
+    "stakeProgram",
+];
+
+const TOKEN_METADATA_ACCOUNTS: [&str; 8] = [
+    "stakePool",
+    "manager",
+    "withdrawAuthority",
+    "poolMint",
+    "payer",
+    "tokenMetadata",
+    "mplTokenMetadata",
+    "systemProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StakePoolDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl StakePoolDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = StakePoolInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            StakePoolInstruction::Initialize(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "manager",
+                    "staker",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "reserveStake",
+                    "poolMint",
+                    "managerFeeAccount",
+                    "tokenProgram",
+                    "depositAuthority",
+                ]),
+                name: "initialize".to_string(),
+            }),
+            StakePoolInstruction::AddValidatorToPool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "staker",
+                    "reserveStake",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "validatorStake",
+                    "validatorVote",
+                    "rent",
+                    "clock",
+                    "stakeHistory",
+                    "stakeConfig",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "addValidatorToPool".to_string(),
+            }),
+            StakePoolInstruction::RemoveValidatorFromPool => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "staker",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "validatorStake",
+                    "transientStake",
+                    "clock",
+                    "stakeProgram",
+                ]),
+                name: "removeValidatorFromPool".to_string(),
+                ..Default::default()
+            }),
+            StakePoolInstruction::DecreaseValidatorStake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "staker",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "validatorStake",
+                    "transientStake",
+                    "clock",
+                    "rent",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "decreaseValidatorStake".to_string(),
+            }),
+            StakePoolInstruction::IncreaseValidatorStake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "staker",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "reserveStake",
+                    "transientStake",
+                    "validatorStake",
+                    "validatorVote",
+                    "clock",
+                    "rent",
+                    "stakeHistory",
+                    "stakeConfig",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "increaseValidatorStake".to_string(),
+            }),
+            StakePoolInstruction::SetPreferredValidator(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["stakePool", "staker", "validatorList"]),
+                name: "setPreferredValidator".to_string(),
+            }),
+            // validator and transient stake account pairs follow the fixed accounts
+            StakePoolInstruction::UpdateValidatorListBalance(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "reserveStake",
+                    "clock",
+                    "stakeHistory",
+                    "stakeProgram",
+                ]),
+                name: "updateValidatorListBalance".to_string(),
+            }),
+            StakePoolInstruction::UpdateStakePoolBalance => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "reserveStake",
+                    "managerFeeAccount",
+                    "poolMint",
+                    "tokenProgram",
+                ]),
+                name: "updateStakePoolBalance".to_string(),
+                ..Default::default()
+            }),
+            StakePoolInstruction::CleanupRemovedValidatorEntries => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["stakePool", "validatorList"]),
+                name: "cleanupRemovedValidatorEntries".to_string(),
+                ..Default::default()
+            }),
+            StakePoolInstruction::DepositStake => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&DEPOSIT_STAKE_ACCOUNTS),
+                name: "depositStake".to_string(),
+                ..Default::default()
+            }),
+            StakePoolInstruction::WithdrawStake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_STAKE_ACCOUNTS),
+                name: "withdrawStake".to_string(),
+            }),
+            StakePoolInstruction::SetManager => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "manager",
+                    "newManager",
+                    "newManagerFeeAccount",
+                ]),
+                name: "setManager".to_string(),
+                ..Default::default()
+            }),
+            StakePoolInstruction::SetFee(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&["stakePool", "manager"]),
+                name: "setFee".to_string(),
+            }),
+            StakePoolInstruction::SetStaker => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["stakePool", "setStakerAuthority", "newStaker"]),
+                name: "setStaker".to_string(),
+                ..Default::default()
+            }),
+            StakePoolInstruction::DepositSol(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_SOL_ACCOUNTS),
+                name: "depositSol".to_string(),
+            }),
+            StakePoolInstruction::SetFundingAuthority(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["stakePool", "manager", "newAuthority"]),
+                name: "setFundingAuthority".to_string(),
+            }),
+            StakePoolInstruction::WithdrawSol(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_SOL_ACCOUNTS),
+                name: "withdrawSol".to_string(),
+            }),
+            StakePoolInstruction::CreateTokenMetadata(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TOKEN_METADATA_ACCOUNTS),
+                name: "createTokenMetadata".to_string(),
+            }),
+            StakePoolInstruction::UpdateTokenMetadata(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "manager",
+                    "withdrawAuthority",
+                    "tokenMetadata",
+                    "mplTokenMetadata",
+                ]),
+                name: "updateTokenMetadata".to_string(),
+            }),
+            StakePoolInstruction::IncreaseAdditionalValidatorStake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "staker",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "reserveStake",
+                    "ephemeralStake",
+                    "transientStake",
+                    "validatorStake",
+                    "validatorVote",
+                    "clock",
+                    "stakeHistory",
+                    "stakeConfig",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "increaseAdditionalValidatorStake".to_string(),
+            }),
+            StakePoolInstruction::DecreaseAdditionalValidatorStake(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "stakePool",
+                    "staker",
+                    "withdrawAuthority",
+                    "validatorList",
+                    "reserveStake",
+                    "validatorStake",
+                    "ephemeralStake",
+                    "transientStake",
+                    "clock",
+                    "stakeHistory",
+                    "systemProgram",
+                    "stakeProgram",
+                ]),
+                name: "decreaseAdditionalValidatorStake".to_string(),
+            }),
+            StakePoolInstruction::DecreaseValidatorStakeWithReserve(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&DECREASE_VALIDATOR_STAKE_WITH_RESERVE_ACCOUNTS),
+                name: "decreaseValidatorStakeWithReserve".to_string(),
+            }),
+            StakePoolInstruction::Redelegate => Err(anyhow!("unimplemented")),
+            StakePoolInstruction::DepositStakeWithSlippage(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_STAKE_ACCOUNTS),
+                name: "depositStakeWithSlippage".to_string(),
+            }),
+            StakePoolInstruction::WithdrawStakeWithSlippage(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_STAKE_ACCOUNTS),
+                name: "withdrawStakeWithSlippage".to_string(),
+            }),
+            StakePoolInstruction::DepositSolWithSlippage(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&DEPOSIT_SOL_ACCOUNTS),
+                name: "depositSolWithSlippage".to_string(),
+            }),
+            StakePoolInstruction::WithdrawSolWithSlippage(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&WITHDRAW_SOL_ACCOUNTS),
+                name: "withdrawSolWithSlippage".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_deposit_sol_and_stake_pool() {
+        let mut data = vec![14u8];
+        data.extend(5_000_000_000u64.to_le_bytes());
+        // pools without a deposit authority omit the last account
+        let accounts: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let decoded = StakePoolDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.clone(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "depositSol");
+        assert_eq!(decoded.data["lamports"], 5_000_000_000u64);
+        assert_eq!(decoded.accounts["poolMint"], accounts[7].to_string());
+        assert!(!decoded.accounts.contains_key("solDepositAuthority"));
+
+        let mut pool = vec![1u8];
+        pool.extend([0u8; 32 * 3 + 1 + 32 * 5]);
+        pool.extend(to_vec(&(1_150_000_000_000u64, 1_000_000_000_000u64, 600u64)).unwrap());
+        pool.extend([0u8; 8 + 8 + 32 + 16 + 1 + 1 + 1 + 16 + 16 + 1 + 1 + 1 + 16 + 1 + 1 + 16]);
+        pool.extend([0u8; 1 + 8 + 8]);
+        let pool = state::StakePool::unpack(&pool).unwrap();
+        assert_eq!(pool.exchange_rate(), 1.15);
+        assert_eq!(pool.pool_tokens_to_lamports(2_000_000_000), 2_300_000_000);
+        assert_eq!(pool.lamports_to_pool_tokens(1_150_000_000), 1_000_000_000);
+    }
+}
@@ -0,0 +1,132 @@
+//! Account types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fee {
+    pub denominator: u64,
+    pub numerator: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountType {
+    #[default]
+    Uninitialized,
+    StakePool,
+    ValidatorList,
+}
+
+/// A fee change which takes effect once one or two epochs have passed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FutureEpoch {
+    #[default]
+    None,
+    One(Fee),
+    Two(Fee),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub custodian: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePool {
+    pub account_type: AccountType,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub manager: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub staker: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub stake_deposit_authority: Pubkey,
+    pub stake_withdraw_bump_seed: u8,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub validator_list: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub reserve_stake: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pool_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub manager_fee_account: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_program_id: Pubkey,
+    /// lamports held by the pool as of the last update
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub last_update_epoch: u64,
+    pub lockup: Lockup,
+    pub epoch_fee: Fee,
+    pub next_epoch_fee: FutureEpoch,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub preferred_deposit_validator_vote_address: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub preferred_withdraw_validator_vote_address: Option<Pubkey>,
+    pub stake_deposit_fee: Fee,
+    pub stake_withdrawal_fee: Fee,
+    pub next_stake_withdrawal_fee: FutureEpoch,
+    pub stake_referral_fee: u8,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub sol_deposit_authority: Option<Pubkey>,
+    pub sol_deposit_fee: Fee,
+    pub sol_referral_fee: u8,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub sol_withdraw_authority: Option<Pubkey>,
+    pub sol_withdrawal_fee: Fee,
+    pub next_sol_withdrawal_fee: FutureEpoch,
+    pub last_epoch_pool_token_supply: u64,
+    pub last_epoch_total_lamports: u64,
+}
+
+impl StakePool {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        let pool: Self = anchor::deserialize(data)?;
+        if pool.account_type != AccountType::StakePool {
+            return Err(anyhow!("account is not a stake pool"));
+        }
+        Ok(pool)
+    }
+
+    /// Sol per pool token as of the last update
+    pub fn exchange_rate(&self) -> f64 {
+        if self.pool_token_supply == 0 {
+            return 1.0;
+        }
+        self.total_lamports as f64 / self.pool_token_supply as f64
+    }
+
+    /// Lamports pool tokens are worth before withdrawal fees
+    pub fn pool_tokens_to_lamports(&self, pool_tokens: u64) -> u64 {
+        if self.pool_token_supply == 0 {
+            return pool_tokens;
+        }
+        (pool_tokens as u128 * self.total_lamports as u128 / self.pool_token_supply as u128)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+
+    /// Pool tokens minted for lamports before deposit fees
+    pub fn lamports_to_pool_tokens(&self, lamports: u64) -> u64 {
+        if self.total_lamports == 0 || self.pool_token_supply == 0 {
+            return lamports;
+        }
+        (lamports as u128 * self.pool_token_supply as u128 / self.total_lamports as u128)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+}
@@ -0,0 +1,315 @@
+//! Instruction types
+
+use serde::Serialize;
+use solana_sdk::program_error::ProgramError;
+use std::convert::TryInto;
+use std::mem::size_of;
+
+/// Length of the curve parameters following the curve type
+pub const CURVE_PARAMETERS_LEN: usize = 32;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fees {
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+    pub owner_withdraw_fee_numerator: u64,
+    pub owner_withdraw_fee_denominator: u64,
+    pub host_fee_numerator: u64,
+    pub host_fee_denominator: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CurveType {
+    ConstantProduct,
+    ConstantPrice,
+    Stable,
+    Offset,
+}
+
+impl TryFrom<u8> for CurveType {
+    type Error = ProgramError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ConstantProduct),
+            1 => Ok(Self::ConstantPrice),
+            2 => Ok(Self::Stable),
+            3 => Ok(Self::Offset),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitializeInstruction {
+    /// nonce used to create the swap authority, only present in the older
+    /// program versions deployed by orca and most forks
+    pub nonce: Option<u8>,
+    pub fees: Fees,
+    pub curve_type: CurveType,
+    /// curve specific parameters, eg. the token b price of a constant price curve
+    pub curve_parameters: [u8; CURVE_PARAMETERS_LEN],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapInstruction {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DepositAllTokenTypesInstruction {
+    pub pool_token_amount: u64,
+    pub maximum_token_a_amount: u64,
+    pub maximum_token_b_amount: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawAllTokenTypesInstruction {
+    pub pool_token_amount: u64,
+    pub minimum_token_a_amount: u64,
+    pub minimum_token_b_amount: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DepositSingleTokenTypeExactAmountInInstruction {
+    pub source_token_amount: u64,
+    pub minimum_pool_token_amount: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawSingleTokenTypeExactAmountOutInstruction {
+    pub destination_token_amount: u64,
+    pub maximum_pool_token_amount: u64,
+}
+
+/// Instructions supported by the token swap program and its forks.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SwapProgramInstruction {
+    Initialize(InitializeInstruction),
+    Swap(SwapInstruction),
+    DepositAllTokenTypes(DepositAllTokenTypesInstruction),
+    WithdrawAllTokenTypes(WithdrawAllTokenTypesInstruction),
+    DepositSingleTokenTypeExactAmountIn(DepositSingleTokenTypeExactAmountInInstruction),
+    WithdrawSingleTokenTypeExactAmountOut(WithdrawSingleTokenTypeExactAmountOutInstruction),
+}
+
+impl SwapProgramInstruction {
+    /// Unpacks a byte buffer into a [SwapProgramInstruction](enum.SwapProgramInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
+            0 => {
+                // the nonce was removed from later versions of the reference program,
+                // so its presence is inferred from the instruction length
+                let (nonce, rest) = if rest.len() > size_of::<Fees>() + 1 + CURVE_PARAMETERS_LEN {
+                    let (nonce, rest) = Self::unpack_u8(rest)?;
+                    (Some(nonce), rest)
+                } else {
+                    (None, rest)
+                };
+                let (fees, rest) = Self::unpack_fees(rest)?;
+                let (curve_type, rest) = Self::unpack_u8(rest)?;
+                if rest.len() < CURVE_PARAMETERS_LEN {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                Self::Initialize(InitializeInstruction {
+                    nonce,
+                    fees,
+                    curve_type: curve_type.try_into()?,
+                    curve_parameters: rest[..CURVE_PARAMETERS_LEN]
+                        .try_into()
+                        .map_err(|_| ProgramError::InvalidInstructionData)?,
+                })
+            }
+            1 => {
+                let (amount_in, rest) = Self::unpack_u64(rest)?;
+                let (minimum_amount_out, _rest) = Self::unpack_u64(rest)?;
+                Self::Swap(SwapInstruction {
+                    amount_in,
+                    minimum_amount_out,
+                })
+            }
+            2 => {
+                let (pool_token_amount, rest) = Self::unpack_u64(rest)?;
+                let (maximum_token_a_amount, rest) = Self::unpack_u64(rest)?;
+                let (maximum_token_b_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositAllTokenTypes(DepositAllTokenTypesInstruction {
+                    pool_token_amount,
+                    maximum_token_a_amount,
+                    maximum_token_b_amount,
+                })
+            }
+            3 => {
+                let (pool_token_amount, rest) = Self::unpack_u64(rest)?;
+                let (minimum_token_a_amount, rest) = Self::unpack_u64(rest)?;
+                let (minimum_token_b_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::WithdrawAllTokenTypes(WithdrawAllTokenTypesInstruction {
+                    pool_token_amount,
+                    minimum_token_a_amount,
+                    minimum_token_b_amount,
+                })
+            }
+            4 => {
+                let (source_token_amount, rest) = Self::unpack_u64(rest)?;
+                let (minimum_pool_token_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositSingleTokenTypeExactAmountIn(
+                    DepositSingleTokenTypeExactAmountInInstruction {
+                        source_token_amount,
+                        minimum_pool_token_amount,
+                    },
+                )
+            }
+            5 => {
+                let (destination_token_amount, rest) = Self::unpack_u64(rest)?;
+                let (maximum_pool_token_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::WithdrawSingleTokenTypeExactAmountOut(
+                    WithdrawSingleTokenTypeExactAmountOutInstruction {
+                        destination_token_amount,
+                        maximum_pool_token_amount,
+                    },
+                )
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_fees(input: &[u8]) -> Result<(Fees, &[u8]), ProgramError> {
+        let (trade_fee_numerator, rest) = Self::unpack_u64(input)?;
+        let (trade_fee_denominator, rest) = Self::unpack_u64(rest)?;
+        let (owner_trade_fee_numerator, rest) = Self::unpack_u64(rest)?;
+        let (owner_trade_fee_denominator, rest) = Self::unpack_u64(rest)?;
+        let (owner_withdraw_fee_numerator, rest) = Self::unpack_u64(rest)?;
+        let (owner_withdraw_fee_denominator, rest) = Self::unpack_u64(rest)?;
+        let (host_fee_numerator, rest) = Self::unpack_u64(rest)?;
+        let (host_fee_denominator, rest) = Self::unpack_u64(rest)?;
+        Ok((
+            Fees {
+                trade_fee_numerator,
+                trade_fee_denominator,
+                owner_trade_fee_numerator,
+                owner_trade_fee_denominator,
+                owner_withdraw_fee_numerator,
+                owner_withdraw_fee_denominator,
+                host_fee_numerator,
+                host_fee_denominator,
+            },
+            rest,
+        ))
+    }
+
+    fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
+        input
+            .split_first()
+            .map(|(&value, rest)| (value, rest))
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() >= 8 {
+            let (amount, rest) = input.split_at(8);
+            let amount = amount
+                .get(..8)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    /// Packs a [SwapProgramInstruction](enum.SwapProgramInstruction.html) into a byte buffer.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(size_of::<Self>());
+        match self {
+            Self::Initialize(InitializeInstruction {
+                nonce,
+                fees,
+                curve_type,
+                curve_parameters,
+            }) => {
+                buf.push(0);
+                if let Some(nonce) = nonce {
+                    buf.push(*nonce);
+                }
+                for value in [
+                    fees.trade_fee_numerator,
+                    fees.trade_fee_denominator,
+                    fees.owner_trade_fee_numerator,
+                    fees.owner_trade_fee_denominator,
+                    fees.owner_withdraw_fee_numerator,
+                    fees.owner_withdraw_fee_denominator,
+                    fees.host_fee_numerator,
+                    fees.host_fee_denominator,
+                ] {
+                    buf.extend_from_slice(&value.to_le_bytes());
+                }
+                buf.push(*curve_type as u8);
+                buf.extend_from_slice(curve_parameters);
+            }
+            Self::Swap(SwapInstruction {
+                amount_in,
+                minimum_amount_out,
+            }) => {
+                buf.push(1);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+                buf.extend_from_slice(&minimum_amount_out.to_le_bytes());
+            }
+            Self::DepositAllTokenTypes(DepositAllTokenTypesInstruction {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            }) => {
+                buf.push(2);
+                buf.extend_from_slice(&pool_token_amount.to_le_bytes());
+                buf.extend_from_slice(&maximum_token_a_amount.to_le_bytes());
+                buf.extend_from_slice(&maximum_token_b_amount.to_le_bytes());
+            }
+            Self::WithdrawAllTokenTypes(WithdrawAllTokenTypesInstruction {
+                pool_token_amount,
+                minimum_token_a_amount,
+                minimum_token_b_amount,
+            }) => {
+                buf.push(3);
+                buf.extend_from_slice(&pool_token_amount.to_le_bytes());
+                buf.extend_from_slice(&minimum_token_a_amount.to_le_bytes());
+                buf.extend_from_slice(&minimum_token_b_amount.to_le_bytes());
+            }
+            Self::DepositSingleTokenTypeExactAmountIn(
+                DepositSingleTokenTypeExactAmountInInstruction {
+                    source_token_amount,
+                    minimum_pool_token_amount,
+                },
+            ) => {
+                buf.push(4);
+                buf.extend_from_slice(&source_token_amount.to_le_bytes());
+                buf.extend_from_slice(&minimum_pool_token_amount.to_le_bytes());
+            }
+            Self::WithdrawSingleTokenTypeExactAmountOut(
+                WithdrawSingleTokenTypeExactAmountOutInstruction {
+                    destination_token_amount,
+                    maximum_pool_token_amount,
+                },
+            ) => {
+                buf.push(5);
+                buf.extend_from_slice(&destination_token_amount.to_le_bytes());
+                buf.extend_from_slice(&maximum_pool_token_amount.to_le_bytes());
+            }
+        }
+        buf
+    }
+}
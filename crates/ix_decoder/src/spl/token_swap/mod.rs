@@ -0,0 +1,474 @@
+//! Instruction decoding functions for the spl token swap program, which is also
+//! used by the legacy orca pools and a number of forks
+
+use anyhow::{anyhow, Context};
+use ix::{CurveType, InitializeInstruction, SwapProgramInstruction};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+use crate::types::{DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+
+/// the spl reference deployment
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8");
+pub const ORCA_V1_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1");
+pub const ORCA_V2_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP");
+pub const STEP_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ");
+pub const PENGUIN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("PSwapMdSai8tjrEXcxFeQth87xC4rRsa4VA5mhGhXkP");
+pub const SAROS_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr");
+
+/// programs sharing the token swap instruction layout
+pub const PROGRAM_IDS: [Pubkey; 6] = [
+    PROGRAM_ID,
+    ORCA_V1_PROGRAM_ID,
+    ORCA_V2_PROGRAM_ID,
+    STEP_PROGRAM_ID,
+    PENGUIN_PROGRAM_ID,
+    SAROS_PROGRAM_ID,
+];
+
+// the current spl deployment supports token 2022, passing the mints and token program of
+// each side in place of the single token program of the legacy layout
+const LEGACY_SWAP_ACCOUNTS: [&str; 11] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "source",
+    "swapSource",
+    "swapDestination",
+    "destination",
+    "poolMint",
+    "poolFeeAccount",
+    "tokenProgram",
+    "hostFeeAccount",
+];
+
+const SWAP_ACCOUNTS: [&str; 15] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "source",
+    "swapSource",
+    "swapDestination",
+    "destination",
+    "poolMint",
+    "poolFeeAccount",
+    "sourceMint",
+    "destinationMint",
+    "sourceTokenProgram",
+    "destinationTokenProgram",
+    "poolTokenProgram",
+    "hostFeeAccount",
+];
+
+const LEGACY_DEPOSIT_ALL_ACCOUNTS: [&str; 10] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "sourceA",
+    "sourceB",
+    "swapTokenA",
+    "swapTokenB",
+    "poolMint",
+    "destination",
+    "tokenProgram",
+];
+
+const DEPOSIT_ALL_ACCOUNTS: [&str; 14] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "sourceA",
+    "sourceB",
+    "swapTokenA",
+    "swapTokenB",
+    "poolMint",
+    "destination",
+    "tokenAMint",
+    "tokenBMint",
+    "tokenAProgram",
+    "tokenBProgram",
+    "poolTokenProgram",
+];
+
+const LEGACY_WITHDRAW_ALL_ACCOUNTS: [&str; 11] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "poolMint",
+    "source",
+    "swapTokenA",
+    "swapTokenB",
+    "destinationA",
+    "destinationB",
+    "poolFeeAccount",
+    "tokenProgram",
+];
+
+const WITHDRAW_ALL_ACCOUNTS: [&str; 15] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "poolMint",
+    "source",
+    "swapTokenA",
+    "swapTokenB",
+    "destinationA",
+    "destinationB",
+    "poolFeeAccount",
+    "tokenAMint",
+    "tokenBMint",
+    "poolTokenProgram",
+    "tokenAProgram",
+    "tokenBProgram",
+];
+
+const LEGACY_DEPOSIT_SINGLE_ACCOUNTS: [&str; 9] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "source",
+    "swapTokenA",
+    "swapTokenB",
+    "poolMint",
+    "destination",
+    "tokenProgram",
+];
+
+const DEPOSIT_SINGLE_ACCOUNTS: [&str; 11] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "source",
+    "swapTokenA",
+    "swapTokenB",
+    "poolMint",
+    "destination",
+    "sourceMint",
+    "sourceTokenProgram",
+    "poolTokenProgram",
+];
+
+const LEGACY_WITHDRAW_SINGLE_ACCOUNTS: [&str; 10] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "poolMint",
+    "source",
+    "swapTokenA",
+    "swapTokenB",
+    "destination",
+    "poolFeeAccount",
+    "tokenProgram",
+];
+
+const WITHDRAW_SINGLE_ACCOUNTS: [&str; 12] = [
+    "swap",
+    "authority",
+    "userTransferAuthority",
+    "poolMint",
+    "source",
+    "swapTokenA",
+    "swapTokenB",
+    "destination",
+    "poolFeeAccount",
+    "destinationMint",
+    "poolTokenProgram",
+    "destinationTokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TokenSwapDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl TokenSwapDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = SwapProgramInstruction::unpack(&self.ix.data)
+            .map_err(|err| anyhow!("{err:#?}"))
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            SwapProgramInstruction::Initialize(ix) => Ok(DecodedInstruction {
+                data: initialize_data(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "swap",
+                    "authority",
+                    "tokenA",
+                    "tokenB",
+                    "poolMint",
+                    "feeAccount",
+                    "destination",
+                    "tokenProgram",
+                ]),
+                name: "initialize".to_string(),
+            }),
+            SwapProgramInstruction::Swap(ix) => Ok(DecodedInstruction {
+                data: [
+                    ("amountIn".to_string(), serde_json::to_value(ix.amount_in)?),
+                    (
+                        "minimumAmountOut".to_string(),
+                        serde_json::to_value(ix.minimum_amount_out)?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.layout_accounts(&LEGACY_SWAP_ACCOUNTS, &SWAP_ACCOUNTS),
+                name: "swap".to_string(),
+            }),
+            SwapProgramInstruction::DepositAllTokenTypes(ix) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "poolTokenAmount".to_string(),
+                        serde_json::to_value(ix.pool_token_amount)?,
+                    ),
+                    (
+                        "maximumTokenAAmount".to_string(),
+                        serde_json::to_value(ix.maximum_token_a_amount)?,
+                    ),
+                    (
+                        "maximumTokenBAmount".to_string(),
+                        serde_json::to_value(ix.maximum_token_b_amount)?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self.layout_accounts(&LEGACY_DEPOSIT_ALL_ACCOUNTS, &DEPOSIT_ALL_ACCOUNTS),
+                name: "depositAllTokenTypes".to_string(),
+            }),
+            SwapProgramInstruction::WithdrawAllTokenTypes(ix) => Ok(DecodedInstruction {
+                data: [
+                    (
+                        "poolTokenAmount".to_string(),
+                        serde_json::to_value(ix.pool_token_amount)?,
+                    ),
+                    (
+                        "minimumTokenAAmount".to_string(),
+                        serde_json::to_value(ix.minimum_token_a_amount)?,
+                    ),
+                    (
+                        "minimumTokenBAmount".to_string(),
+                        serde_json::to_value(ix.minimum_token_b_amount)?,
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                accounts: self
+                    .layout_accounts(&LEGACY_WITHDRAW_ALL_ACCOUNTS, &WITHDRAW_ALL_ACCOUNTS),
+                name: "withdrawAllTokenTypes".to_string(),
+            }),
+            SwapProgramInstruction::DepositSingleTokenTypeExactAmountIn(ix) => {
+                Ok(DecodedInstruction {
+                    data: [
+                        (
+                            "sourceTokenAmount".to_string(),
+                            serde_json::to_value(ix.source_token_amount)?,
+                        ),
+                        (
+                            "minimumPoolTokenAmount".to_string(),
+                            serde_json::to_value(ix.minimum_pool_token_amount)?,
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    accounts: self
+                        .layout_accounts(&LEGACY_DEPOSIT_SINGLE_ACCOUNTS, &DEPOSIT_SINGLE_ACCOUNTS),
+                    name: "depositSingleTokenTypeExactAmountIn".to_string(),
+                })
+            }
+            SwapProgramInstruction::WithdrawSingleTokenTypeExactAmountOut(ix) => {
+                Ok(DecodedInstruction {
+                    data: [
+                        (
+                            "destinationTokenAmount".to_string(),
+                            serde_json::to_value(ix.destination_token_amount)?,
+                        ),
+                        (
+                            "maximumPoolTokenAmount".to_string(),
+                            serde_json::to_value(ix.maximum_pool_token_amount)?,
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    accounts: self.layout_accounts(
+                        &LEGACY_WITHDRAW_SINGLE_ACCOUNTS,
+                        &WITHDRAW_SINGLE_ACCOUNTS,
+                    ),
+                    name: "withdrawSingleTokenTypeExactAmountOut".to_string(),
+                })
+            }
+        }
+    }
+
+    /// Names the accounts with the current layout when there are more accounts than the
+    /// legacy layout has, the legacy swap layout's last account being the optional host fee
+    fn layout_accounts(
+        &self,
+        legacy: &[&str],
+        current: &[&str],
+    ) -> HashMap<String, serde_json::Value> {
+        if self.ix.accounts.len() > legacy.len() {
+            self.ix.named_accounts(current)
+        } else {
+            self.ix.named_accounts(legacy)
+        }
+    }
+}
+
+/// the curve parameters are decoded for the curves that have them, and always
+/// included as hex so parameters of unknown fork curves aren't lost
+fn initialize_data(
+    ix: &InitializeInstruction,
+) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+    let mut data: HashMap<String, serde_json::Value> = [
+        ("fees".to_string(), serde_json::to_value(ix.fees)?),
+        (
+            "curveType".to_string(),
+            serde_json::to_value(ix.curve_type)?,
+        ),
+        (
+            "curveParameters".to_string(),
+            serde_json::to_value(hex::encode(ix.curve_parameters))?,
+        ),
+    ]
+    .into_iter()
+    .collect();
+    if let Some(nonce) = ix.nonce {
+        data.insert("nonce".to_string(), serde_json::to_value(nonce)?);
+    }
+    let parameter = u64::from_le_bytes(ix.curve_parameters[..8].try_into()?);
+    match ix.curve_type {
+        CurveType::ConstantProduct => {}
+        CurveType::ConstantPrice => {
+            data.insert("tokenBPrice".to_string(), serde_json::to_value(parameter)?);
+        }
+        CurveType::Stable => {
+            data.insert("amp".to_string(), serde_json::to_value(parameter)?);
+        }
+        CurveType::Offset => {
+            data.insert("tokenBOffset".to_string(), serde_json::to_value(parameter)?);
+        }
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ix::{
+        DepositAllTokenTypesInstruction, Fees, SwapInstruction, WithdrawAllTokenTypesInstruction,
+    };
+
+    fn decode(
+        program_id: Pubkey,
+        num_accounts: usize,
+        ix: SwapProgramInstruction,
+    ) -> (Vec<Pubkey>, DecodedInstruction) {
+        let accounts = (0..num_accounts)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+        let decoded = TokenSwapDecoder::new(PartiallyDecodedInstruction {
+            program_id,
+            accounts: accounts.clone(),
+            data: ix.pack(),
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        (accounts, decoded)
+    }
+
+    #[test]
+    fn test_decode_swap() {
+        let swap = SwapProgramInstruction::Swap(SwapInstruction {
+            amount_in: 1_000,
+            minimum_amount_out: 990,
+        });
+        // legacy orca pools, with and without the host fee account
+        let (accounts, decoded) = decode(ORCA_V2_PROGRAM_ID, 11, swap.clone());
+        assert_eq!(decoded.name, "swap");
+        assert_eq!(decoded.data["amountIn"], 1_000);
+        assert_eq!(decoded.data["minimumAmountOut"], 990);
+        assert_eq!(decoded.accounts["swap"], accounts[0].to_string());
+        assert_eq!(decoded.accounts["tokenProgram"], accounts[9].to_string());
+        assert_eq!(decoded.accounts["hostFeeAccount"], accounts[10].to_string());
+        let (_, decoded) = decode(ORCA_V2_PROGRAM_ID, 10, swap.clone());
+        assert!(!decoded.accounts.contains_key("hostFeeAccount"));
+
+        // the current spl deployment, passing the mints and token programs
+        let (accounts, decoded) = decode(PROGRAM_ID, 14, swap);
+        assert_eq!(decoded.accounts["sourceMint"], accounts[9].to_string());
+        assert_eq!(
+            decoded.accounts["poolTokenProgram"],
+            accounts[13].to_string()
+        );
+        assert!(!decoded.accounts.contains_key("tokenProgram"));
+        assert!(!decoded.accounts.contains_key("hostFeeAccount"));
+    }
+
+    #[test]
+    fn test_decode_deposit_and_withdraw_all_token_types() {
+        let deposit =
+            SwapProgramInstruction::DepositAllTokenTypes(DepositAllTokenTypesInstruction {
+                pool_token_amount: 500,
+                maximum_token_a_amount: 1_000,
+                maximum_token_b_amount: 2_000,
+            });
+        let (accounts, decoded) = decode(ORCA_V1_PROGRAM_ID, 10, deposit.clone());
+        assert_eq!(decoded.name, "depositAllTokenTypes");
+        assert_eq!(decoded.data["maximumTokenBAmount"], 2_000);
+        assert_eq!(decoded.accounts["tokenProgram"], accounts[9].to_string());
+        let (accounts, decoded) = decode(PROGRAM_ID, 14, deposit);
+        assert_eq!(decoded.accounts["tokenAMint"], accounts[9].to_string());
+        assert_eq!(decoded.accounts["tokenBProgram"], accounts[12].to_string());
+        assert_eq!(
+            decoded.accounts["poolTokenProgram"],
+            accounts[13].to_string()
+        );
+
+        let withdraw =
+            SwapProgramInstruction::WithdrawAllTokenTypes(WithdrawAllTokenTypesInstruction {
+                pool_token_amount: 500,
+                minimum_token_a_amount: 900,
+                minimum_token_b_amount: 1_900,
+            });
+        let (accounts, decoded) = decode(ORCA_V1_PROGRAM_ID, 11, withdraw.clone());
+        assert_eq!(decoded.name, "withdrawAllTokenTypes");
+        assert_eq!(decoded.data["minimumTokenAAmount"], 900);
+        assert_eq!(decoded.accounts["tokenProgram"], accounts[10].to_string());
+        let (accounts, decoded) = decode(PROGRAM_ID, 15, withdraw);
+        assert_eq!(decoded.accounts["tokenBMint"], accounts[11].to_string());
+        assert_eq!(
+            decoded.accounts["poolTokenProgram"],
+            accounts[12].to_string()
+        );
+        assert_eq!(decoded.accounts["tokenBProgram"], accounts[14].to_string());
+    }
+
+    #[test]
+    fn test_unpack_initialize_with_and_without_nonce() {
+        for nonce in [Some(255), None] {
+            let mut curve_parameters = [0u8; ix::CURVE_PARAMETERS_LEN];
+            curve_parameters[..8].copy_from_slice(&100u64.to_le_bytes());
+            let ix = SwapProgramInstruction::Initialize(InitializeInstruction {
+                nonce,
+                fees: Fees {
+                    trade_fee_numerator: 25,
+                    trade_fee_denominator: 10_000,
+                    ..Default::default()
+                },
+                curve_type: CurveType::Stable,
+                curve_parameters,
+            });
+            assert_eq!(SwapProgramInstruction::unpack(&ix.pack()).unwrap(), ix);
+        }
+    }
+}
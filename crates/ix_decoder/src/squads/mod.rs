@@ -0,0 +1,53 @@
+//! Instruction decoding functions for the squads multisig programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use v3::SquadsV3Decoder;
+use v4::SquadsV4Decoder;
+
+pub mod v3;
+pub mod v4;
+
+pub static SQUADS_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(SquadsProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    V3(SquadsV3Decoder),
+    V4(SquadsV4Decoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct SquadsProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::V3(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Squads::V3"),
+            Self::V4(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Squads::V4"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::V3(decoder) => format!("{:#?}", decoder),
+            Self::V4(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for SquadsProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            v3::PROGRAM_ID => Some(Box::new(Programs::V3(SquadsV3Decoder::new(ix.clone())))),
+            v4::PROGRAM_ID => Some(Box::new(Programs::V4(SquadsV4Decoder::new(ix.clone())))),
+            _ => None,
+        }
+    }
+}
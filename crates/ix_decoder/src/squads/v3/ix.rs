@@ -0,0 +1,165 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_pubkey, serialize_pubkeys, PartiallyDecodedInstruction},
+};
+
+pub const CREATE: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+pub const ADD_MEMBER: [u8; 8] = [13, 116, 123, 130, 126, 198, 57, 34];
+pub const REMOVE_MEMBER: [u8; 8] = [171, 57, 231, 150, 167, 128, 18, 55];
+pub const ADD_MEMBER_AND_CHANGE_THRESHOLD: [u8; 8] = [114, 213, 59, 47, 214, 157, 150, 170];
+pub const REMOVE_MEMBER_AND_CHANGE_THRESHOLD: [u8; 8] = [230, 97, 183, 248, 43, 190, 154, 29];
+pub const CHANGE_THRESHOLD: [u8; 8] = [146, 151, 213, 63, 121, 79, 9, 29];
+pub const CREATE_TRANSACTION: [u8; 8] = [227, 193, 53, 239, 55, 126, 112, 105];
+pub const ACTIVATE_TRANSACTION: [u8; 8] = [56, 17, 0, 163, 135, 11, 135, 32];
+pub const ADD_INSTRUCTION: [u8; 8] = [11, 70, 136, 166, 202, 55, 246, 74];
+pub const APPROVE_TRANSACTION: [u8; 8] = [224, 39, 88, 181, 36, 59, 155, 122];
+pub const REJECT_TRANSACTION: [u8; 8] = [47, 141, 218, 192, 80, 97, 209, 116];
+pub const CANCEL_TRANSACTION: [u8; 8] = [65, 191, 19, 127, 230, 26, 214, 142];
+pub const EXECUTE_TRANSACTION: [u8; 8] = [231, 173, 49, 91, 235, 24, 68, 19];
+pub const EXECUTE_INSTRUCTION: [u8; 8] = [48, 18, 40, 40, 75, 74, 147, 110];
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInstruction {
+    pub threshold: u16,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub create_key: Pubkey,
+    #[serde(serialize_with = "serialize_pubkeys")]
+    pub members: Vec<Pubkey>,
+    pub meta: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddMemberInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub new_member: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveMemberInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub old_member: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddMemberAndChangeThresholdInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub new_member: Pubkey,
+    pub new_threshold: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveMemberAndChangeThresholdInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub old_member: Pubkey,
+    pub new_threshold: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeThresholdInstruction {
+    pub new_threshold: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTransactionInstruction {
+    /// index of the authority pda signing the transaction, 0 being the multisig itself
+    /// for config changes and 1 the default vault
+    pub authority_index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMeta {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// An instruction added to a transaction, executed once the transaction is approved
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub program_id: Pubkey,
+    pub keys: Vec<AccountMeta>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub data: Vec<u8>,
+}
+
+impl IncomingInstruction {
+    pub fn to_partially_decoded(&self) -> PartiallyDecodedInstruction {
+        PartiallyDecodedInstruction {
+            program_id: self.program_id,
+            accounts: self.keys.iter().map(|meta| meta.pubkey).collect(),
+            data: self.data.clone(),
+            stack_height: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteTransactionInstruction {
+    /// indexes into the remaining accounts for each instruction's accounts
+    pub account_list: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SquadsV3Instruction {
+    Create(CreateInstruction),
+    AddMember(AddMemberInstruction),
+    RemoveMember(RemoveMemberInstruction),
+    AddMemberAndChangeThreshold(AddMemberAndChangeThresholdInstruction),
+    RemoveMemberAndChangeThreshold(RemoveMemberAndChangeThresholdInstruction),
+    ChangeThreshold(ChangeThresholdInstruction),
+    CreateTransaction(CreateTransactionInstruction),
+    ActivateTransaction,
+    AddInstruction(IncomingInstruction),
+    ApproveTransaction,
+    RejectTransaction,
+    CancelTransaction,
+    ExecuteTransaction(ExecuteTransactionInstruction),
+    ExecuteInstruction,
+}
+
+impl SquadsV3Instruction {
+    /// Unpacks a byte buffer into a [SquadsV3Instruction](enum.SquadsV3Instruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            CREATE => Self::Create(anchor::deserialize(rest)?),
+            ADD_MEMBER => Self::AddMember(anchor::deserialize(rest)?),
+            REMOVE_MEMBER => Self::RemoveMember(anchor::deserialize(rest)?),
+            ADD_MEMBER_AND_CHANGE_THRESHOLD => {
+                Self::AddMemberAndChangeThreshold(anchor::deserialize(rest)?)
+            }
+            REMOVE_MEMBER_AND_CHANGE_THRESHOLD => {
+                Self::RemoveMemberAndChangeThreshold(anchor::deserialize(rest)?)
+            }
+            CHANGE_THRESHOLD => Self::ChangeThreshold(anchor::deserialize(rest)?),
+            CREATE_TRANSACTION => Self::CreateTransaction(anchor::deserialize(rest)?),
+            ACTIVATE_TRANSACTION => Self::ActivateTransaction,
+            ADD_INSTRUCTION => Self::AddInstruction(anchor::deserialize(rest)?),
+            APPROVE_TRANSACTION => Self::ApproveTransaction,
+            REJECT_TRANSACTION => Self::RejectTransaction,
+            CANCEL_TRANSACTION => Self::CancelTransaction,
+            EXECUTE_TRANSACTION => Self::ExecuteTransaction(anchor::deserialize(rest)?),
+            EXECUTE_INSTRUCTION => Self::ExecuteInstruction,
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
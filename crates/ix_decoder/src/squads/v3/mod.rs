@@ -0,0 +1,181 @@
+//! Instruction decoding functions for the squads v3 (squads mpl) multisig program
+//!
+//! Transactions are built one instruction at a time through `add_instruction`, each
+//! instruction being decoded as it is added. Member and threshold changes are themselves
+//! instructions of a transaction signed by the multisig.
+
+use anyhow::Context;
+use ix::SquadsV3Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{
+    to_data_map, DecodedInstruction, EmbeddedInstruction, PartiallyDecodedInstruction,
+};
+
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("SMPLecH534NA9acpos4G6x7uf3LWbCAwZQE9e8ZekMu");
+
+const MULTISIG_ACCOUNTS: [&str; 1] = ["multisig"];
+
+const MULTISIG_REALLOC_ACCOUNTS: [&str; 3] = ["multisig", "rent", "systemProgram"];
+
+const VOTE_ACCOUNTS: [&str; 3] = ["multisig", "transaction", "member"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SquadsV3Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl SquadsV3Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = SquadsV3Instruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            SquadsV3Instruction::Create(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["multisig", "creator", "systemProgram"]),
+                name: "create".to_string(),
+            }),
+            SquadsV3Instruction::AddMember(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MULTISIG_REALLOC_ACCOUNTS),
+                name: "addMember".to_string(),
+            }),
+            SquadsV3Instruction::RemoveMember(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MULTISIG_ACCOUNTS),
+                name: "removeMember".to_string(),
+            }),
+            SquadsV3Instruction::AddMemberAndChangeThreshold(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MULTISIG_REALLOC_ACCOUNTS),
+                name: "addMemberAndChangeThreshold".to_string(),
+            }),
+            SquadsV3Instruction::RemoveMemberAndChangeThreshold(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MULTISIG_ACCOUNTS),
+                name: "removeMemberAndChangeThreshold".to_string(),
+            }),
+            SquadsV3Instruction::ChangeThreshold(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&MULTISIG_ACCOUNTS),
+                name: "changeThreshold".to_string(),
+            }),
+            SquadsV3Instruction::CreateTransaction(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "transaction",
+                    "creator",
+                    "systemProgram",
+                ]),
+                name: "createTransaction".to_string(),
+            }),
+            SquadsV3Instruction::ActivateTransaction => Ok(DecodedInstruction {
+                accounts: self
+                    .ix
+                    .named_accounts(&["multisig", "transaction", "creator"]),
+                name: "activateTransaction".to_string(),
+                ..Default::default()
+            }),
+            SquadsV3Instruction::AddInstruction(ix) => {
+                let embedded = EmbeddedInstruction::decode(
+                    ix.program_id.to_string(),
+                    Ok(ix.to_partially_decoded()),
+                );
+                let mut data = to_data_map(&ix)?;
+                data.insert("instruction".to_string(), serde_json::to_value(embedded)?);
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&[
+                        "multisig",
+                        "transaction",
+                        "instruction",
+                        "creator",
+                        "systemProgram",
+                    ]),
+                    name: "addInstruction".to_string(),
+                })
+            }
+            SquadsV3Instruction::ApproveTransaction => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&VOTE_ACCOUNTS),
+                name: "approveTransaction".to_string(),
+                ..Default::default()
+            }),
+            SquadsV3Instruction::RejectTransaction => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&VOTE_ACCOUNTS),
+                name: "rejectTransaction".to_string(),
+                ..Default::default()
+            }),
+            SquadsV3Instruction::CancelTransaction => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "transaction",
+                    "member",
+                    "systemProgram",
+                ]),
+                name: "cancelTransaction".to_string(),
+                ..Default::default()
+            }),
+            SquadsV3Instruction::ExecuteTransaction(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&VOTE_ACCOUNTS),
+                name: "executeTransaction".to_string(),
+            }),
+            SquadsV3Instruction::ExecuteInstruction => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "transaction",
+                    "instruction",
+                    "member",
+                ]),
+                name: "executeInstruction".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_add_instruction() {
+        let vault = Pubkey::new_unique();
+        let new_member = Pubkey::new_unique();
+        // the multisig adding a member to itself, decoded recursively
+        let mut inner_data = ix::ADD_MEMBER.to_vec();
+        inner_data.extend(new_member.to_bytes());
+        let mut data = ix::ADD_INSTRUCTION.to_vec();
+        data.extend(to_vec(&(PROGRAM_ID, vec![(vault, true, true)], inner_data)).unwrap());
+        let decoded = SquadsV3Decoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: (0..5).map(|_| Pubkey::new_unique()).collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "addInstruction");
+        assert_eq!(decoded.data["keys"][0]["pubkey"], vault.to_string());
+        let instruction = &decoded.data["instruction"];
+        assert_eq!(instruction["programId"], PROGRAM_ID.to_string());
+        assert_eq!(instruction["decoded"]["name"], "addMember");
+        assert_eq!(
+            instruction["decoded"]["data"]["newMember"],
+            new_member.to_string()
+        );
+        assert_eq!(
+            instruction["decoded"]["accounts"]["multisig"],
+            vault.to_string()
+        );
+    }
+}
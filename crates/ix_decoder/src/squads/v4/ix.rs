@@ -0,0 +1,271 @@
+//! Instruction types
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_option_pubkey, serialize_pubkey, serialize_pubkeys},
+};
+
+pub const MULTISIG_CREATE_V2: [u8; 8] = [50, 221, 199, 93, 40, 245, 139, 233];
+pub const MULTISIG_ADD_MEMBER: [u8; 8] = [1, 219, 215, 108, 184, 229, 214, 8];
+pub const MULTISIG_REMOVE_MEMBER: [u8; 8] = [217, 117, 177, 210, 182, 145, 218, 72];
+pub const MULTISIG_SET_TIME_LOCK: [u8; 8] = [148, 154, 121, 77, 212, 254, 155, 72];
+pub const MULTISIG_CHANGE_THRESHOLD: [u8; 8] = [141, 42, 15, 126, 169, 92, 62, 181];
+pub const MULTISIG_SET_CONFIG_AUTHORITY: [u8; 8] = [143, 93, 199, 143, 92, 169, 193, 232];
+pub const MULTISIG_SET_RENT_COLLECTOR: [u8; 8] = [48, 204, 65, 57, 210, 70, 156, 74];
+pub const MULTISIG_ADD_SPENDING_LIMIT: [u8; 8] = [11, 242, 159, 42, 86, 197, 89, 115];
+pub const MULTISIG_REMOVE_SPENDING_LIMIT: [u8; 8] = [228, 198, 136, 111, 123, 4, 178, 113];
+pub const CONFIG_TRANSACTION_CREATE: [u8; 8] = [155, 236, 87, 228, 137, 75, 81, 39];
+pub const CONFIG_TRANSACTION_EXECUTE: [u8; 8] = [114, 146, 244, 189, 252, 140, 36, 40];
+pub const VAULT_TRANSACTION_CREATE: [u8; 8] = [48, 250, 78, 168, 208, 226, 218, 211];
+pub const VAULT_TRANSACTION_EXECUTE: [u8; 8] = [194, 8, 161, 87, 153, 164, 25, 171];
+pub const PROPOSAL_CREATE: [u8; 8] = [220, 60, 73, 224, 30, 108, 79, 159];
+pub const PROPOSAL_ACTIVATE: [u8; 8] = [11, 34, 92, 248, 154, 27, 51, 106];
+pub const PROPOSAL_APPROVE: [u8; 8] = [144, 37, 164, 136, 188, 216, 42, 248];
+pub const PROPOSAL_REJECT: [u8; 8] = [243, 62, 134, 156, 230, 106, 246, 135];
+pub const PROPOSAL_CANCEL: [u8; 8] = [27, 42, 127, 237, 38, 163, 84, 203];
+pub const SPENDING_LIMIT_USE: [u8; 8] = [16, 57, 130, 127, 193, 20, 155, 134];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Member {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub key: Pubkey,
+    /// bitmask of the initiate (1), vote (2) and execute (4) permissions
+    pub permissions: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Period {
+    OneTime,
+    Day,
+    Week,
+    Month,
+}
+
+/// A change to the multisig, applied once its config transaction executes
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ConfigAction {
+    AddMember {
+        new_member: Member,
+    },
+    RemoveMember {
+        #[serde(serialize_with = "serialize_pubkey")]
+        old_member: Pubkey,
+    },
+    ChangeThreshold {
+        new_threshold: u16,
+    },
+    SetTimeLock {
+        new_time_lock: u32,
+    },
+    AddSpendingLimit {
+        #[serde(serialize_with = "serialize_pubkey")]
+        create_key: Pubkey,
+        vault_index: u8,
+        #[serde(serialize_with = "serialize_pubkey")]
+        mint: Pubkey,
+        amount: u64,
+        period: Period,
+        #[serde(serialize_with = "serialize_pubkeys")]
+        members: Vec<Pubkey>,
+        #[serde(serialize_with = "serialize_pubkeys")]
+        destinations: Vec<Pubkey>,
+    },
+    RemoveSpendingLimit {
+        #[serde(serialize_with = "serialize_pubkey")]
+        spending_limit: Pubkey,
+    },
+    SetRentCollector {
+        #[serde(serialize_with = "serialize_option_pubkey")]
+        new_rent_collector: Option<Pubkey>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigCreateArgs {
+    /// when set the multisig is controlled by this key rather than by config transactions
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub config_authority: Option<Pubkey>,
+    pub threshold: u16,
+    pub members: Vec<Member>,
+    /// seconds between a proposal's approval and its execution
+    pub time_lock: u32,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub rent_collector: Option<Pubkey>,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddMemberArgs {
+    pub new_member: Member,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveMemberArgs {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub old_member: Pubkey,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTimeLockArgs {
+    pub time_lock: u32,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeThresholdArgs {
+    pub new_threshold: u16,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetConfigAuthorityArgs {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub config_authority: Pubkey,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRentCollectorArgs {
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub rent_collector: Option<Pubkey>,
+    pub memo: Option<String>,
+}
+
+/// Allows `members` to transfer up to `amount` of the mint from the vault each period,
+/// without a proposal
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddSpendingLimitArgs {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub create_key: Pubkey,
+    pub vault_index: u8,
+    /// the default pubkey for sol
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub period: Period,
+    #[serde(serialize_with = "serialize_pubkeys")]
+    pub members: Vec<Pubkey>,
+    /// any destination is allowed when empty
+    #[serde(serialize_with = "serialize_pubkeys")]
+    pub destinations: Vec<Pubkey>,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoArgs {
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigTransactionCreateArgs {
+    pub actions: Vec<ConfigAction>,
+    pub memo: Option<String>,
+}
+
+/// The transaction message is decoded separately, see
+/// [TransactionMessage](super::message::TransactionMessage)
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultTransactionCreateArgs {
+    pub vault_index: u8,
+    pub ephemeral_signers: u8,
+    #[serde(skip)]
+    pub transaction_message: Vec<u8>,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalCreateArgs {
+    pub transaction_index: u64,
+    /// draft proposals must be activated before they can be voted on
+    pub draft: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendingLimitUseArgs {
+    pub amount: u64,
+    pub decimals: u8,
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SquadsV4Instruction {
+    MultisigCreateV2(MultisigCreateArgs),
+    MultisigAddMember(AddMemberArgs),
+    MultisigRemoveMember(RemoveMemberArgs),
+    MultisigSetTimeLock(SetTimeLockArgs),
+    MultisigChangeThreshold(ChangeThresholdArgs),
+    MultisigSetConfigAuthority(SetConfigAuthorityArgs),
+    MultisigSetRentCollector(SetRentCollectorArgs),
+    MultisigAddSpendingLimit(AddSpendingLimitArgs),
+    MultisigRemoveSpendingLimit(MemoArgs),
+    ConfigTransactionCreate(ConfigTransactionCreateArgs),
+    ConfigTransactionExecute,
+    VaultTransactionCreate(VaultTransactionCreateArgs),
+    VaultTransactionExecute,
+    ProposalCreate(ProposalCreateArgs),
+    ProposalActivate,
+    ProposalApprove(MemoArgs),
+    ProposalReject(MemoArgs),
+    ProposalCancel(MemoArgs),
+    SpendingLimitUse(SpendingLimitUseArgs),
+}
+
+impl SquadsV4Instruction {
+    /// Unpacks a byte buffer into a [SquadsV4Instruction](enum.SquadsV4Instruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            MULTISIG_CREATE_V2 => Self::MultisigCreateV2(anchor::deserialize(rest)?),
+            MULTISIG_ADD_MEMBER => Self::MultisigAddMember(anchor::deserialize(rest)?),
+            MULTISIG_REMOVE_MEMBER => Self::MultisigRemoveMember(anchor::deserialize(rest)?),
+            MULTISIG_SET_TIME_LOCK => Self::MultisigSetTimeLock(anchor::deserialize(rest)?),
+            MULTISIG_CHANGE_THRESHOLD => Self::MultisigChangeThreshold(anchor::deserialize(rest)?),
+            MULTISIG_SET_CONFIG_AUTHORITY => {
+                Self::MultisigSetConfigAuthority(anchor::deserialize(rest)?)
+            }
+            MULTISIG_SET_RENT_COLLECTOR => {
+                Self::MultisigSetRentCollector(anchor::deserialize(rest)?)
+            }
+            MULTISIG_ADD_SPENDING_LIMIT => {
+                Self::MultisigAddSpendingLimit(anchor::deserialize(rest)?)
+            }
+            MULTISIG_REMOVE_SPENDING_LIMIT => {
+                Self::MultisigRemoveSpendingLimit(anchor::deserialize(rest)?)
+            }
+            CONFIG_TRANSACTION_CREATE => Self::ConfigTransactionCreate(anchor::deserialize(rest)?),
+            CONFIG_TRANSACTION_EXECUTE => Self::ConfigTransactionExecute,
+            VAULT_TRANSACTION_CREATE => Self::VaultTransactionCreate(anchor::deserialize(rest)?),
+            VAULT_TRANSACTION_EXECUTE => Self::VaultTransactionExecute,
+            PROPOSAL_CREATE => Self::ProposalCreate(anchor::deserialize(rest)?),
+            PROPOSAL_ACTIVATE => Self::ProposalActivate,
+            PROPOSAL_APPROVE => Self::ProposalApprove(anchor::deserialize(rest)?),
+            PROPOSAL_REJECT => Self::ProposalReject(anchor::deserialize(rest)?),
+            PROPOSAL_CANCEL => Self::ProposalCancel(anchor::deserialize(rest)?),
+            SPENDING_LIMIT_USE => Self::SpendingLimitUse(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
@@ -0,0 +1,135 @@
+//! The transaction message stored by vault transactions, a compact form of a v0 message
+//! whose vectors are prefixed by a u8 length, or u16 for instruction data
+
+use std::io::Read;
+
+use anyhow::{anyhow, Context};
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{
+    serialize_hex, serialize_pubkey, serialize_pubkeys, EmbeddedInstruction,
+    PartiallyDecodedInstruction,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub account_indexes: Vec<u8>,
+    #[serde(serialize_with = "serialize_hex")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageAddressTableLookup {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub account_key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMessage {
+    pub num_signers: u8,
+    pub num_writable_signers: u8,
+    pub num_writable_non_signers: u8,
+    /// the static keys, accounts loaded from lookup tables following them
+    #[serde(serialize_with = "serialize_pubkeys")]
+    pub account_keys: Vec<Pubkey>,
+    pub instructions: Vec<CompiledInstruction>,
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+/// Reads `len` items, the length prefix having been read by the caller
+fn read_items<R: Read, T: BorshDeserialize>(reader: &mut R, len: usize) -> std::io::Result<Vec<T>> {
+    (0..len).map(|_| T::deserialize_reader(reader)).collect()
+}
+
+impl BorshDeserialize for CompiledInstruction {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let program_id_index = u8::deserialize_reader(reader)?;
+        let len = u8::deserialize_reader(reader)?;
+        let account_indexes = read_items(reader, len.into())?;
+        let len = u16::deserialize_reader(reader)?;
+        let data = read_items(reader, len.into())?;
+        Ok(Self {
+            program_id_index,
+            account_indexes,
+            data,
+        })
+    }
+}
+
+impl BorshDeserialize for MessageAddressTableLookup {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let account_key = Pubkey::deserialize_reader(reader)?;
+        let len = u8::deserialize_reader(reader)?;
+        let writable_indexes = read_items(reader, len.into())?;
+        let len = u8::deserialize_reader(reader)?;
+        let readonly_indexes = read_items(reader, len.into())?;
+        Ok(Self {
+            account_key,
+            writable_indexes,
+            readonly_indexes,
+        })
+    }
+}
+
+impl BorshDeserialize for TransactionMessage {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let [num_signers, num_writable_signers, num_writable_non_signers] =
+            <[u8; 3]>::deserialize_reader(reader)?;
+        let len = u8::deserialize_reader(reader)?;
+        let account_keys = read_items(reader, len.into())?;
+        let len = u8::deserialize_reader(reader)?;
+        let instructions = read_items(reader, len.into())?;
+        let len = u8::deserialize_reader(reader)?;
+        let address_table_lookups = read_items(reader, len.into())?;
+        Ok(Self {
+            num_signers,
+            num_writable_signers,
+            num_writable_non_signers,
+            account_keys,
+            instructions,
+            address_table_lookups,
+        })
+    }
+}
+
+impl TransactionMessage {
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        Self::try_from_slice(data).with_context(|| "failed to deserialize transaction message")
+    }
+
+    /// Decodes the instructions of the message through the program decoders. Instructions
+    /// using accounts loaded from lookup tables can't be resolved and record an error.
+    pub fn decode_instructions(&self) -> Vec<EmbeddedInstruction> {
+        self.instructions
+            .iter()
+            .map(|ix| {
+                let program_id = self.account_keys.get(ix.program_id_index as usize);
+                let resolved = ix
+                    .account_indexes
+                    .iter()
+                    .map(|&index| self.account_keys.get(index as usize).copied())
+                    .collect::<Option<Vec<_>>>()
+                    .zip(program_id)
+                    .map(|(accounts, &program_id)| PartiallyDecodedInstruction {
+                        program_id,
+                        accounts,
+                        data: ix.data.clone(),
+                        stack_height: None,
+                    })
+                    .ok_or_else(|| anyhow!("instruction uses accounts loaded from a lookup table"));
+                EmbeddedInstruction::decode(
+                    program_id.map(ToString::to_string).unwrap_or_default(),
+                    resolved,
+                )
+            })
+            .collect()
+    }
+}
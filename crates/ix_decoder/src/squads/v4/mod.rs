@@ -0,0 +1,261 @@
+//! Instruction decoding functions for the squads v4 multisig program
+//!
+//! Vault transactions carry the instructions the multisig's vaults will sign, which are
+//! decoded along with `vault_transaction_create` so a proposal can be inspected before it
+//! executes. Changes to the multisig itself go through config transactions, unless the
+//! multisig has a config authority.
+
+use anyhow::Context;
+use ix::SquadsV4Instruction;
+use message::TransactionMessage;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod message;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+const CONFIG_ACCOUNTS: [&str; 4] = ["multisig", "configAuthority", "rentPayer", "systemProgram"];
+
+const TRANSACTION_CREATE_ACCOUNTS: [&str; 5] = [
+    "multisig",
+    "transaction",
+    "creator",
+    "rentPayer",
+    "systemProgram",
+];
+
+const PROPOSAL_VOTE_ACCOUNTS: [&str; 3] = ["multisig", "member", "proposal"];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SquadsV4Decoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl SquadsV4Decoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = SquadsV4Instruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            SquadsV4Instruction::MultisigCreateV2(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "programConfig",
+                    "treasury",
+                    "multisig",
+                    "createKey",
+                    "creator",
+                    "systemProgram",
+                ]),
+                name: "multisigCreateV2".to_string(),
+            }),
+            SquadsV4Instruction::MultisigAddMember(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONFIG_ACCOUNTS),
+                name: "multisigAddMember".to_string(),
+            }),
+            SquadsV4Instruction::MultisigRemoveMember(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONFIG_ACCOUNTS),
+                name: "multisigRemoveMember".to_string(),
+            }),
+            SquadsV4Instruction::MultisigSetTimeLock(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONFIG_ACCOUNTS),
+                name: "multisigSetTimeLock".to_string(),
+            }),
+            SquadsV4Instruction::MultisigChangeThreshold(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONFIG_ACCOUNTS),
+                name: "multisigChangeThreshold".to_string(),
+            }),
+            SquadsV4Instruction::MultisigSetConfigAuthority(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONFIG_ACCOUNTS),
+                name: "multisigSetConfigAuthority".to_string(),
+            }),
+            SquadsV4Instruction::MultisigSetRentCollector(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&CONFIG_ACCOUNTS),
+                name: "multisigSetRentCollector".to_string(),
+            }),
+            SquadsV4Instruction::MultisigAddSpendingLimit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "configAuthority",
+                    "spendingLimit",
+                    "rentPayer",
+                    "systemProgram",
+                ]),
+                name: "multisigAddSpendingLimit".to_string(),
+            }),
+            SquadsV4Instruction::MultisigRemoveSpendingLimit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "configAuthority",
+                    "spendingLimit",
+                    "rentCollector",
+                ]),
+                name: "multisigRemoveSpendingLimit".to_string(),
+            }),
+            SquadsV4Instruction::ConfigTransactionCreate(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TRANSACTION_CREATE_ACCOUNTS),
+                name: "configTransactionCreate".to_string(),
+            }),
+            SquadsV4Instruction::ConfigTransactionExecute => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "member",
+                    "proposal",
+                    "transaction",
+                    "rentPayer",
+                    "systemProgram",
+                ]),
+                name: "configTransactionExecute".to_string(),
+                ..Default::default()
+            }),
+            SquadsV4Instruction::VaultTransactionCreate(ix) => {
+                let message = TransactionMessage::unpack(&ix.transaction_message)?;
+                let mut data = to_data_map(&ix)?;
+                data.insert(
+                    "instructions".to_string(),
+                    serde_json::to_value(message.decode_instructions())?,
+                );
+                data.insert(
+                    "transactionMessage".to_string(),
+                    serde_json::to_value(message)?,
+                );
+                Ok(DecodedInstruction {
+                    data,
+                    accounts: self.ix.named_accounts(&TRANSACTION_CREATE_ACCOUNTS),
+                    name: "vaultTransactionCreate".to_string(),
+                })
+            }
+            SquadsV4Instruction::VaultTransactionExecute => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "proposal",
+                    "transaction",
+                    "member",
+                ]),
+                name: "vaultTransactionExecute".to_string(),
+                ..Default::default()
+            }),
+            SquadsV4Instruction::ProposalCreate(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "proposal",
+                    "creator",
+                    "rentPayer",
+                    "systemProgram",
+                ]),
+                name: "proposalCreate".to_string(),
+            }),
+            SquadsV4Instruction::ProposalActivate => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&PROPOSAL_VOTE_ACCOUNTS),
+                name: "proposalActivate".to_string(),
+                ..Default::default()
+            }),
+            SquadsV4Instruction::ProposalApprove(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PROPOSAL_VOTE_ACCOUNTS),
+                name: "proposalApprove".to_string(),
+            }),
+            SquadsV4Instruction::ProposalReject(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PROPOSAL_VOTE_ACCOUNTS),
+                name: "proposalReject".to_string(),
+            }),
+            SquadsV4Instruction::ProposalCancel(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&PROPOSAL_VOTE_ACCOUNTS),
+                name: "proposalCancel".to_string(),
+            }),
+            SquadsV4Instruction::SpendingLimitUse(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "multisig",
+                    "member",
+                    "spendingLimit",
+                    "vault",
+                    "destination",
+                    "systemProgram",
+                    "mint",
+                    "vaultTokenAccount",
+                    "destinationTokenAccount",
+                    "tokenProgram",
+                ]),
+                name: "spendingLimitUse".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::native::bpf_loader_upgradeable;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_vault_transaction_create() {
+        let vault = Pubkey::new_unique();
+        let program_data = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let buffer = Pubkey::new_unique();
+        // a message upgrading `program`, the upgrade instruction being bincode encoded
+        let mut message = vec![1, 1, 3, 5];
+        for key in [
+            vault,
+            program_data,
+            program,
+            buffer,
+            bpf_loader_upgradeable::PROGRAM_ID,
+        ] {
+            message.extend(key.to_bytes());
+        }
+        message.extend([1, 4, 7, 1, 2, 3, 0, 3, 3, 0]);
+        message.extend(4u16.to_le_bytes());
+        message.extend(3u32.to_le_bytes());
+        message.push(0);
+        let mut data = ix::VAULT_TRANSACTION_CREATE.to_vec();
+        data.extend(to_vec(&(0u8, 0u8, message, Some("upgrade".to_string()))).unwrap());
+        let decoded = SquadsV4Decoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: (0..5).map(|_| Pubkey::new_unique()).collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "vaultTransactionCreate");
+        assert_eq!(decoded.data["memo"], "upgrade");
+        assert_eq!(
+            decoded.data["transactionMessage"]["accountKeys"][0],
+            vault.to_string()
+        );
+        let instructions = &decoded.data["instructions"];
+        assert_eq!(
+            instructions[0]["programId"],
+            bpf_loader_upgradeable::PROGRAM_ID.to_string()
+        );
+        assert_eq!(instructions[0]["decoded"]["name"], "upgrade");
+        assert_eq!(
+            instructions[0]["decoded"]["accounts"]["program"],
+            program.to_string()
+        );
+        assert_eq!(
+            instructions[0]["decoded"]["accounts"]["authority"],
+            vault.to_string()
+        );
+    }
+}
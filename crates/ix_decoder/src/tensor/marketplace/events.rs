@@ -0,0 +1,95 @@
+//! Event types, logged by the program through a cpi to its own `tcomp_noop` instruction
+
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{serialize_option_pubkey, serialize_pubkey, to_data_map, DecodedInstruction};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Target {
+    AssetId,
+    Whitelist,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Field {
+    Name,
+}
+
+/// Emitted when a listing or bid is created or edited
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MakeEvent {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub maker: Pubkey,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub bid_id: Option<Pubkey>,
+    pub target: Target,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub target_id: Pubkey,
+    pub field: Option<Field>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub field_id: Option<Pubkey>,
+    pub amount: u64,
+    pub quantity: u32,
+    /// the spl token the price is denominated in, sol when not set
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub currency: Option<Pubkey>,
+    pub expiry: i64,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub private_taker: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub asset_id: Option<Pubkey>,
+}
+
+/// Emitted when a listing is bought or a bid is taken, `taker` being the buyer or
+/// seller respectively
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakeEvent {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub taker: Pubkey,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub bid_id: Option<Pubkey>,
+    pub target: Target,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub target_id: Pubkey,
+    pub field: Option<Field>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub field_id: Option<Pubkey>,
+    pub amount: u64,
+    pub quantity: u32,
+    pub tcomp_fee: u64,
+    pub taker_broker_fee: u64,
+    pub maker_broker_fee: u64,
+    /// royalties paid to the creators
+    pub creator_fee: u64,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub currency: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub asset_id: Option<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize)]
+pub enum TcompEvent {
+    Maker(MakeEvent),
+    Taker(TakeEvent),
+}
+
+impl TcompEvent {
+    /// Converts the event to a decoded instruction named after the event, without accounts
+    pub fn to_decoded_instruction(&self) -> anyhow::Result<DecodedInstruction> {
+        let (name, data) = match self {
+            Self::Maker(event) => ("makeEvent", to_data_map(event)?),
+            Self::Taker(event) => ("takeEvent", to_data_map(event)?),
+        };
+        Ok(DecodedInstruction {
+            data,
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+}
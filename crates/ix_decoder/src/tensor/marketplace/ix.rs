@@ -0,0 +1,168 @@
+//! Instruction types
+//!
+//! Compressed nft instructions identify the leaf as bubblegum does. The authorization
+//! data which trails the arguments of legacy instructions is not decoded.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use super::events::{Field, Target, TcompEvent};
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_option_pubkey, serialize_pubkey},
+};
+
+pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+pub const LIST: [u8; 8] = [54, 174, 193, 67, 17, 41, 132, 38];
+pub const DELIST: [u8; 8] = [55, 136, 205, 107, 107, 173, 4, 31];
+pub const EDIT: [u8; 8] = [15, 183, 33, 86, 87, 28, 151, 145];
+pub const BID: [u8; 8] = [199, 56, 85, 38, 146, 243, 37, 158];
+pub const CANCEL_BID: [u8; 8] = [40, 243, 190, 217, 208, 253, 86, 206];
+pub const TAKE_BID_META_HASH: [u8; 8] = [85, 227, 202, 70, 45, 215, 10, 193];
+pub const BUY_LEGACY: [u8; 8] = [68, 127, 43, 8, 212, 31, 249, 114];
+pub const LIST_LEGACY: [u8; 8] = [6, 110, 255, 18, 16, 36, 8, 30];
+pub const DELIST_LEGACY: [u8; 8] = [88, 35, 231, 184, 110, 218, 149, 23];
+pub const TAKE_BID_LEGACY: [u8; 8] = [188, 35, 116, 108, 0, 233, 237, 201];
+pub const TCOMP_NOOP: [u8; 8] = [106, 162, 10, 226, 132, 68, 223, 21];
+
+/// Arguments of `buy` and `take_bid_meta_hash`, `amount` being the buyer's maximum or
+/// the seller's minimum price
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressedTradeInstruction {
+    pub nonce: u64,
+    pub index: u32,
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub meta_hash: [u8; 32],
+    pub creator_shares: Vec<u8>,
+    pub creator_verified: Vec<bool>,
+    pub seller_fee_basis_points: u16,
+    pub amount: u64,
+    pub optional_royalty_pct: Option<u16>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListInstruction {
+    pub nonce: u64,
+    pub index: u32,
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub data_hash: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub creator_hash: [u8; 32],
+    pub amount: u64,
+    pub expire_in_sec: Option<u64>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub currency: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub private_taker: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub maker_broker: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelistInstruction {
+    pub nonce: u64,
+    pub index: u32,
+    #[serde(serialize_with = "serialize_hex")]
+    pub root: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub data_hash: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub creator_hash: [u8; 32],
+}
+
+/// Terms of a listing, the arguments of `edit` and `list_legacy`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingTerms {
+    pub amount: u64,
+    pub expire_in_sec: Option<u64>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub currency: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub private_taker: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub maker_broker: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BidInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub bid_id: Pubkey,
+    pub target: Target,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub target_id: Pubkey,
+    pub field: Option<Field>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub field_id: Option<Pubkey>,
+    pub amount: u64,
+    pub quantity: u32,
+    pub expire_in_sec: Option<u64>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub currency: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub private_taker: Option<Pubkey>,
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub maker_broker: Option<Pubkey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyLegacyInstruction {
+    pub max_amount: u64,
+    pub optional_royalty_pct: Option<u16>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakeBidLegacyInstruction {
+    pub min_amount: u64,
+    pub optional_royalty_pct: Option<u16>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MarketplaceInstruction {
+    Buy(CompressedTradeInstruction),
+    List(ListInstruction),
+    Delist(DelistInstruction),
+    Edit(ListingTerms),
+    Bid(BidInstruction),
+    CancelBid,
+    TakeBidMetaHash(CompressedTradeInstruction),
+    BuyLegacy(BuyLegacyInstruction),
+    ListLegacy(ListingTerms),
+    DelistLegacy,
+    TakeBidLegacy(TakeBidLegacyInstruction),
+    TcompNoop(TcompEvent),
+}
+
+impl MarketplaceInstruction {
+    /// Unpacks a byte buffer into a [MarketplaceInstruction](enum.MarketplaceInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            BUY => Self::Buy(anchor::deserialize(rest)?),
+            LIST => Self::List(anchor::deserialize(rest)?),
+            DELIST => Self::Delist(anchor::deserialize(rest)?),
+            EDIT => Self::Edit(anchor::deserialize(rest)?),
+            BID => Self::Bid(anchor::deserialize(rest)?),
+            CANCEL_BID => Self::CancelBid,
+            TAKE_BID_META_HASH => Self::TakeBidMetaHash(anchor::deserialize(rest)?),
+            BUY_LEGACY => Self::BuyLegacy(anchor::deserialize(rest)?),
+            LIST_LEGACY => Self::ListLegacy(anchor::deserialize(rest)?),
+            DELIST_LEGACY => Self::DelistLegacy,
+            TAKE_BID_LEGACY => Self::TakeBidLegacy(anchor::deserialize(rest)?),
+            TCOMP_NOOP => Self::TcompNoop(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
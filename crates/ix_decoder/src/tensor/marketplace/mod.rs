@@ -0,0 +1,252 @@
+//! Instruction decoding functions for the tensor marketplace program, formerly tcomp
+//!
+//! Sale prices and royalties are emitted through a cpi to the program's own
+//! `tcomp_noop` instruction, which [crate::transaction::decode_transaction] attaches to
+//! the instruction which emitted it.
+
+use anyhow::Context;
+use ix::MarketplaceInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TCMPhJdwDryooaGtiocG1u3xcYbRpiJzb283XfCZsDp");
+
+/// Returns true if the instruction is an event emitted through `tcomp_noop`
+pub fn is_event_instruction(ix: &PartiallyDecodedInstruction) -> bool {
+    ix.program_id == PROGRAM_ID && ix.data.starts_with(&ix::TCOMP_NOOP)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MarketplaceDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl MarketplaceDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = MarketplaceInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            MarketplaceInstruction::Buy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "tcomp",
+                    "treeAuthority",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                    "bubblegumProgram",
+                    "tcompProgram",
+                    "listState",
+                    "buyer",
+                    "payer",
+                    "owner",
+                    "takerBroker",
+                    "makerBroker",
+                    "rentDest",
+                ]),
+                name: "buy".to_string(),
+            }),
+            MarketplaceInstruction::List(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "tcomp",
+                    "treeAuthority",
+                    "owner",
+                    "delegate",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                    "bubblegumProgram",
+                    "tcompProgram",
+                    "listState",
+                    "rentPayer",
+                ]),
+                name: "list".to_string(),
+            }),
+            MarketplaceInstruction::Delist(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "tcomp",
+                    "treeAuthority",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                    "bubblegumProgram",
+                    "tcompProgram",
+                    "listState",
+                    "owner",
+                    "rentDest",
+                ]),
+                name: "delist".to_string(),
+            }),
+            MarketplaceInstruction::Edit(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["listState", "owner", "tcompProgram"]),
+                name: "edit".to_string(),
+            }),
+            MarketplaceInstruction::Bid(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "systemProgram",
+                    "tcompProgram",
+                    "bidState",
+                    "owner",
+                    "sharedEscrow",
+                ]),
+                name: "bid".to_string(),
+            }),
+            MarketplaceInstruction::CancelBid => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "bidState",
+                    "owner",
+                    "systemProgram",
+                    "tcompProgram",
+                    "rentDest",
+                ]),
+                name: "cancelBid".to_string(),
+                ..Default::default()
+            }),
+            MarketplaceInstruction::TakeBidMetaHash(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "tcomp",
+                    "treeAuthority",
+                    "seller",
+                    "delegate",
+                    "merkleTree",
+                    "logWrapper",
+                    "compressionProgram",
+                    "systemProgram",
+                    "bubblegumProgram",
+                    "tcompProgram",
+                    "tensorswapProgram",
+                    "bidState",
+                    "owner",
+                    "takerBroker",
+                    "makerBroker",
+                    "marginAccount",
+                    "whitelist",
+                    "cosigner",
+                    "rentDest",
+                ]),
+                name: "takeBidMetaHash".to_string(),
+            }),
+            MarketplaceInstruction::BuyLegacy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "feeVault",
+                    "buyer",
+                    "buyerTa",
+                    "listState",
+                    "listTa",
+                    "mint",
+                    "payer",
+                    "takerBroker",
+                    "makerBroker",
+                    "owner",
+                    "rentDestination",
+                ]),
+                name: "buyLegacy".to_string(),
+            }),
+            MarketplaceInstruction::ListLegacy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "ownerTa",
+                    "listState",
+                    "listTa",
+                    "mint",
+                    "payer",
+                ]),
+                name: "listLegacy".to_string(),
+            }),
+            MarketplaceInstruction::DelistLegacy => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "owner",
+                    "ownerTa",
+                    "listState",
+                    "listTa",
+                    "mint",
+                    "rentDestination",
+                ]),
+                name: "delistLegacy".to_string(),
+                ..Default::default()
+            }),
+            MarketplaceInstruction::TakeBidLegacy(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "feeVault",
+                    "seller",
+                    "bidState",
+                    "owner",
+                    "takerBroker",
+                    "makerBroker",
+                    "marginAccount",
+                    "whitelist",
+                    "sellerTa",
+                    "mint",
+                    "metadata",
+                ]),
+                name: "takeBidLegacy".to_string(),
+            }),
+            MarketplaceInstruction::TcompNoop(event) => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&["tcompSigner"]),
+                ..event.to_decoded_instruction()?
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_take_event() {
+        let buyer = Pubkey::new_unique();
+        let asset_id = Pubkey::new_unique();
+        let mut data = ix::TCOMP_NOOP.to_vec();
+        data.push(1);
+        data.extend(
+            to_vec(&(
+                buyer,
+                None::<Pubkey>,
+                0u8,
+                asset_id,
+                None::<u8>,
+                None::<Pubkey>,
+            ))
+            .unwrap(),
+        );
+        data.extend(to_vec(&(4_000_000_000u64, 1u32, 60_000_000u64, 0u64, 0u64)).unwrap());
+        data.extend(to_vec(&(200_000_000u64, None::<Pubkey>, Some(asset_id))).unwrap());
+        let ix = PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![Pubkey::new_unique()],
+            data,
+            stack_height: Some(2),
+        };
+        assert!(is_event_instruction(&ix));
+        let decoded = MarketplaceDecoder::new(ix).decode().unwrap();
+        assert_eq!(decoded.name, "takeEvent");
+        assert_eq!(decoded.data["taker"], buyer.to_string());
+        assert_eq!(decoded.data["target"], "assetId");
+        assert_eq!(decoded.data["amount"], 4_000_000_000u64);
+        assert_eq!(decoded.data["creatorFee"], 200_000_000u64);
+        assert_eq!(decoded.data["assetId"], asset_id.to_string());
+        assert!(decoded.accounts.contains_key("tcompSigner"));
+    }
+}
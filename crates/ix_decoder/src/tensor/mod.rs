@@ -0,0 +1,55 @@
+//! Instruction decoding functions for the tensor programs
+
+use crate::types::{PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher};
+use anyhow::Context;
+use marketplace::MarketplaceDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use tswap::TSwapDecoder;
+
+pub mod marketplace;
+pub mod tswap;
+
+pub static TENSOR_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(TensorProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    TSwap(TSwapDecoder),
+    Marketplace(MarketplaceDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct TensorProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::TSwap(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Tensor::TSwap"),
+            Self::Marketplace(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Tensor::Marketplace"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::TSwap(decoder) => format!("{:#?}", decoder),
+            Self::Marketplace(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for TensorProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            tswap::PROGRAM_ID => Some(Box::new(Programs::TSwap(TSwapDecoder::new(ix.clone())))),
+            marketplace::PROGRAM_ID => Some(Box::new(Programs::Marketplace(
+                MarketplaceDecoder::new(ix.clone()),
+            ))),
+            _ => None,
+        }
+    }
+}
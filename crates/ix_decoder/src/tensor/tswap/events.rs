@@ -0,0 +1,60 @@
+//! Event types, logged by the program through `emit!`
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{
+    anchor,
+    types::{to_data_map, DecodedInstruction},
+};
+
+pub const BUY_SELL_EVENT: [u8; 8] = [98, 208, 120, 60, 93, 32, 19, 180];
+
+/// Emitted by pool buys and sells with the price paid and the fees taken from it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuySellEvent {
+    pub current_price: u64,
+    pub tswap_fee: u64,
+    pub mm_fee: u64,
+    /// royalties paid to the creators
+    pub creators_fee: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TSwapEvent {
+    BuySell(BuySellEvent),
+}
+
+impl TSwapEvent {
+    /// Unpacks the logged event data
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            BUY_SELL_EVENT => Self::BuySell(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported event discriminator")),
+        })
+    }
+
+    /// Converts the event to a decoded instruction named after the event, without accounts
+    pub fn to_decoded_instruction(&self) -> anyhow::Result<DecodedInstruction> {
+        let (name, data) = match self {
+            Self::BuySell(event) => ("buySellEvent", to_data_map(event)?),
+        };
+        Ok(DecodedInstruction {
+            data,
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Decodes the events the program logged during a transaction, skipping events
+/// which aren't supported
+pub fn log_events(logs: &[String]) -> Vec<TSwapEvent> {
+    anchor::log_events(logs, &super::PROGRAM_ID)
+        .iter()
+        .filter_map(|data| TSwapEvent::unpack(data).ok())
+        .collect()
+}
@@ -0,0 +1,103 @@
+//! Instruction types
+//!
+//! The royalty and authorization arguments which trail the prices of the buy and sell
+//! instructions are not decoded.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::anchor;
+
+pub const LIST: [u8; 8] = [54, 174, 193, 67, 17, 41, 132, 38];
+pub const DELIST: [u8; 8] = [55, 136, 205, 107, 107, 173, 4, 31];
+pub const EDIT_SINGLE_LISTING: [u8; 8] = [88, 38, 236, 212, 31, 185, 18, 166];
+pub const BUY_SINGLE_LISTING: [u8; 8] = [245, 220, 105, 73, 117, 98, 78, 141];
+pub const BUY_NFT: [u8; 8] = [96, 0, 28, 190, 49, 107, 83, 222];
+pub const SELL_NFT_TOKEN_POOL: [u8; 8] = [57, 44, 192, 48, 83, 8, 107, 48];
+pub const SELL_NFT_TRADE_POOL: [u8; 8] = [131, 82, 125, 77, 13, 157, 36, 90];
+pub const DEPOSIT_SOL: [u8; 8] = [108, 81, 78, 117, 125, 155, 56, 200];
+pub const WITHDRAW_SOL: [u8; 8] = [145, 131, 74, 136, 65, 137, 42, 38];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PoolType {
+    /// buys nfts
+    Token,
+    /// sells nfts
+    Nft,
+    /// buys and sells nfts
+    Trade,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CurveType {
+    Linear,
+    Exponential,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolConfig {
+    pub pool_type: PoolType,
+    pub curve_type: CurveType,
+    pub starting_price: u64,
+    pub delta: u64,
+    pub mm_compound_fees: bool,
+    /// market making fee of trade pools, 0 when not set
+    pub mm_fee_bps: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyNftInstruction {
+    pub config: PoolConfig,
+    pub max_price: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellNftInstruction {
+    pub config: PoolConfig,
+    pub min_price: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolInstruction {
+    pub config: PoolConfig,
+    pub lamports: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TSwapInstruction {
+    List(u64),
+    Delist,
+    EditSingleListing(u64),
+    BuySingleListing(u64),
+    BuyNft(BuyNftInstruction),
+    SellNftTokenPool(SellNftInstruction),
+    SellNftTradePool(SellNftInstruction),
+    DepositSol(SolInstruction),
+    WithdrawSol(SolInstruction),
+}
+
+impl TSwapInstruction {
+    /// Unpacks a byte buffer into a [TSwapInstruction](enum.TSwapInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (discriminator, rest) = anchor::split_discriminator(input)?;
+        Ok(match discriminator {
+            LIST => Self::List(anchor::deserialize(rest)?),
+            DELIST => Self::Delist,
+            EDIT_SINGLE_LISTING => Self::EditSingleListing(anchor::deserialize(rest)?),
+            BUY_SINGLE_LISTING => Self::BuySingleListing(anchor::deserialize(rest)?),
+            BUY_NFT => Self::BuyNft(anchor::deserialize(rest)?),
+            SELL_NFT_TOKEN_POOL => Self::SellNftTokenPool(anchor::deserialize(rest)?),
+            SELL_NFT_TRADE_POOL => Self::SellNftTradePool(anchor::deserialize(rest)?),
+            DEPOSIT_SOL => Self::DepositSol(anchor::deserialize(rest)?),
+            WITHDRAW_SOL => Self::WithdrawSol(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction discriminator")),
+        })
+    }
+}
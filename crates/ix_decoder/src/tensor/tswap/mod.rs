@@ -0,0 +1,204 @@
+//! Instruction decoding functions for the tensorswap program
+//!
+//! Pool trades only carry the buyer's maximum or seller's minimum price, the price paid
+//! and the royalties are logged through [events::log_events].
+
+use anyhow::Context;
+use ix::TSwapInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod events;
+pub mod ix;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TSWAPaqyCSx2KABk68Shruf4rp7CxcNi8hAsbdwmHbN");
+
+const SELL_NFT_ACCOUNTS: [&str; 11] = [
+    "tswap",
+    "feeVault",
+    "pool",
+    "whitelist",
+    "mintProof",
+    "nftSellerAcc",
+    "nftMint",
+    "nftMetadata",
+    "solEscrow",
+    "owner",
+    "seller",
+];
+
+const SOL_ACCOUNTS: [&str; 7] = [
+    "tswap",
+    "pool",
+    "whitelist",
+    "solEscrow",
+    "owner",
+    "systemProgram",
+    "rent",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TSwapDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl TSwapDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = TSwapInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            TSwapInstruction::List(price) => Ok(DecodedInstruction {
+                data: [("price".to_string(), serde_json::to_value(price)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "tswap",
+                    "nftSource",
+                    "nftMint",
+                    "nftEscrow",
+                    "singleListing",
+                    "owner",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "list".to_string(),
+            }),
+            TSwapInstruction::Delist => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "tswap",
+                    "nftDest",
+                    "nftMint",
+                    "nftEscrow",
+                    "singleListing",
+                    "owner",
+                    "tokenProgram",
+                    "systemProgram",
+                    "rent",
+                    "associatedTokenProgram",
+                ]),
+                name: "delist".to_string(),
+                ..Default::default()
+            }),
+            TSwapInstruction::EditSingleListing(price) => Ok(DecodedInstruction {
+                data: [("price".to_string(), serde_json::to_value(price)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self
+                    .ix
+                    .named_accounts(&["tswap", "singleListing", "nftMint", "owner"]),
+                name: "editSingleListing".to_string(),
+            }),
+            TSwapInstruction::BuySingleListing(max_price) => Ok(DecodedInstruction {
+                data: [("maxPrice".to_string(), serde_json::to_value(max_price)?)]
+                    .into_iter()
+                    .collect(),
+                accounts: self.ix.named_accounts(&[
+                    "tswap",
+                    "feeVault",
+                    "singleListing",
+                    "nftBuyerAcc",
+                    "nftMint",
+                    "nftMetadata",
+                    "nftEscrow",
+                    "owner",
+                    "buyer",
+                    "tokenProgram",
+                    "associatedTokenProgram",
+                    "systemProgram",
+                    "rent",
+                ]),
+                name: "buySingleListing".to_string(),
+            }),
+            TSwapInstruction::BuyNft(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "tswap",
+                    "feeVault",
+                    "pool",
+                    "whitelist",
+                    "nftBuyerAcc",
+                    "nftMint",
+                    "nftMetadata",
+                    "nftEscrow",
+                    "nftReceipt",
+                    "solEscrow",
+                    "owner",
+                    "buyer",
+                ]),
+                name: "buyNft".to_string(),
+            }),
+            TSwapInstruction::SellNftTokenPool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SELL_NFT_ACCOUNTS),
+                name: "sellNftTokenPool".to_string(),
+            }),
+            TSwapInstruction::SellNftTradePool(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SELL_NFT_ACCOUNTS),
+                name: "sellNftTradePool".to_string(),
+            }),
+            TSwapInstruction::DepositSol(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SOL_ACCOUNTS),
+                name: "depositSol".to_string(),
+            }),
+            TSwapInstruction::WithdrawSol(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&SOL_ACCOUNTS),
+                name: "withdrawSol".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use borsh::to_vec;
+
+    #[test]
+    fn test_decode_buy_nft_and_buy_sell_event() {
+        let buyer = Pubkey::new_unique();
+        let mut data = ix::BUY_NFT.to_vec();
+        data.extend([2, 1]);
+        data.extend(to_vec(&(1_000_000_000u64, 500u64, true, 150u16)).unwrap());
+        data.extend(to_vec(&(1_200_000_000u64, false, None::<u8>)).unwrap());
+        let decoded = TSwapDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: (0..11)
+                .map(|_| Pubkey::new_unique())
+                .chain([buyer])
+                .collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "buyNft");
+        assert_eq!(decoded.accounts["buyer"], buyer.to_string());
+        assert_eq!(decoded.data["maxPrice"], 1_200_000_000u64);
+        assert_eq!(decoded.data["config"]["poolType"], "trade");
+        assert_eq!(decoded.data["config"]["mmFeeBps"], 150);
+
+        let mut event = events::BUY_SELL_EVENT.to_vec();
+        event.extend(to_vec(&(1_150_000_000u64, 17_250_000u64, 0u64, 57_500_000u64)).unwrap());
+        let logs = vec![
+            format!("Program {} invoke [1]", PROGRAM_ID),
+            "Program log: Instruction: BuyNft".to_string(),
+            format!("Program data: {}", STANDARD.encode(&event)),
+            format!("Program {} success", PROGRAM_ID),
+        ];
+        let events = events::log_events(&logs);
+        assert_eq!(events.len(), 1);
+        let decoded = events[0].to_decoded_instruction().unwrap();
+        assert_eq!(decoded.name, "buySellEvent");
+        assert_eq!(decoded.data["currentPrice"], 1_150_000_000u64);
+        assert_eq!(decoded.data["creatorsFee"], 57_500_000u64);
+    }
+}
@@ -0,0 +1,935 @@
+//! Transaction and block level decoding, resolving the outer and inner instructions
+//! of a transaction before passing them through the program decoders
+
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{message::v0::MessageAddressTableLookup, pubkey::Pubkey};
+use solana_transaction_status::{
+    EncodedTransaction, EncodedTransactionWithStatusMeta, UiAddressTableLookup,
+    UiCompiledInstruction, UiConfirmedBlock, UiInnerInstructions, UiInstruction, UiLoadedAddresses,
+    UiMessage, UiParsedInstruction, UiTransactionStatusMeta,
+};
+
+use crate::{
+    anchor,
+    metaplex::bubblegum,
+    native::{
+        address_lookup_table::state::{resolve_loaded_addresses, LookupTable},
+        vote,
+    },
+    spl::{memo::Memo, noop},
+    tensor::marketplace,
+    try_instruction_decoder,
+    types::{DecodedInstruction, PartiallyDecodedInstruction},
+};
+
+/// Options used to control how transactions and blocks are decoded
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DecodeOptions {
+    /// when set vote transactions are skipped during block decoding, avoiding
+    /// the cost of decoding what is usually the majority of a block
+    pub skip_votes: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedTransaction {
+    pub signature: String,
+    /// outer instructions each followed by their inner instructions, in execution order
+    pub instructions: Vec<DecodedTransactionInstruction>,
+    /// memos attached to the transaction by either outer or inner instructions
+    pub memos: Vec<Memo>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedTransactionInstruction {
+    pub program_id: String,
+    /// index of the outer instruction, for inner instructions this is the index of their parent
+    pub outer_index: usize,
+    /// index within the parent's inner instructions, None for outer instructions
+    pub inner_index: Option<usize>,
+    pub stack_height: Option<u32>,
+    /// the decoded instruction, None if decoding failed
+    pub decoded: Option<DecodedInstruction>,
+    /// the reason decoding failed
+    pub error: Option<String>,
+    /// events the instruction emitted through anchor self cpi or tensor's `tcomp_noop`, decoded
+    /// from its inner instructions.
+    /// For bubblegum instructions these are the leaf schema and change log logged through noop
+    #[serde(default)]
+    pub events: Vec<DecodedInstruction>,
+}
+
+/// An account key of a transaction, or the reason an address loaded from a lookup table
+/// couldn't be resolved
+type AccountKey = Result<Pubkey, String>;
+
+/// An instruction of a transaction with its program id and accounts resolved
+struct ResolvedInstruction {
+    program_id: String,
+    outer_index: usize,
+    inner_index: Option<usize>,
+    stack_height: Option<u32>,
+    ix: anyhow::Result<PartiallyDecodedInstruction>,
+}
+
+/// Decodes every transaction in the block, returning the decoding result of each transaction
+/// along with its index in `block.transactions`, as skipped vote transactions leave gaps
+pub fn decode_block(
+    block: &UiConfirmedBlock,
+    options: DecodeOptions,
+) -> Vec<(usize, anyhow::Result<DecodedTransaction>)> {
+    block
+        .transactions
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter(|(_, tx)| !(options.skip_votes && is_vote_transaction(tx)))
+        .map(|(index, tx)| (index, decode_transaction(tx)))
+        .collect()
+}
+
+/// Decodes the outer and inner instructions of the transaction. Instructions which
+/// fail to decode are recorded with their error rather than failing the transaction
+pub fn decode_transaction(
+    tx: &EncodedTransactionWithStatusMeta,
+) -> anyhow::Result<DecodedTransaction> {
+    decode_transaction_with_lookup_tables(tx, &HashMap::new())
+}
+
+/// Decodes the transaction as with [decode_transaction], resolving any addresses loaded
+/// from lookup tables through `lookup_tables` when the transaction meta doesn't include them.
+/// Instructions referencing loaded addresses which can't be resolved are recorded with an error
+pub fn decode_transaction_with_lookup_tables(
+    tx: &EncodedTransactionWithStatusMeta,
+    lookup_tables: &HashMap<Pubkey, LookupTable>,
+) -> anyhow::Result<DecodedTransaction> {
+    let (signature, resolved_instructions) = resolve_instructions(tx, lookup_tables)?;
+    let mut instructions = Vec::with_capacity(resolved_instructions.len());
+    let mut memos = Vec::new();
+    // tracks which of the decoded instructions are self cpi events
+    let mut is_event = Vec::with_capacity(resolved_instructions.len());
+    for resolved in resolved_instructions {
+        if let Some(memo) = resolved.ix.as_ref().ok().and_then(Memo::from_instruction) {
+            memos.push(memo);
+        }
+        let event = resolved.inner_index.is_some()
+            && resolved.ix.as_ref().is_ok_and(|ix| {
+                anchor::strip_event_tag(&ix.data).is_some() || marketplace::is_event_instruction(ix)
+            });
+        let leaf_event = resolved
+            .ix
+            .as_ref()
+            .ok()
+            .filter(|ix| resolved.inner_index.is_some() && ix.program_id == noop::PROGRAM_ID)
+            .and_then(|ix| bubblegum::events::decode_noop_event(&ix.data).ok());
+        let decoded = resolved
+            .ix
+            .and_then(|ix| try_instruction_decoder(&ix)?.decode());
+        let (decoded, error) = match decoded {
+            Ok(decoded) => (Some(decoded), None),
+            Err(err) => (None, Some(format!("{err:#}"))),
+        };
+        let ix = DecodedTransactionInstruction {
+            program_id: resolved.program_id,
+            outer_index: resolved.outer_index,
+            inner_index: resolved.inner_index,
+            stack_height: resolved.stack_height,
+            decoded,
+            error,
+            events: Vec::new(),
+        };
+        if event {
+            if let (Some(parent), Some(decoded)) =
+                (event_parent(&instructions, &is_event, &ix), &ix.decoded)
+            {
+                instructions[parent].events.push(decoded.clone());
+            }
+        }
+        if let Some(leaf_event) = leaf_event {
+            if let Some(parent) = bubblegum_parent(&instructions, &ix) {
+                instructions[parent].events.push(leaf_event);
+            }
+        }
+        instructions.push(ix);
+        is_event.push(event);
+    }
+    Ok(DecodedTransaction {
+        signature,
+        instructions,
+        memos,
+    })
+}
+
+/// Returns the index of the instruction which emitted the self cpi event, which is the
+/// closest preceding instruction of the same program one level up the call stack
+fn event_parent(
+    instructions: &[DecodedTransactionInstruction],
+    is_event: &[bool],
+    event: &DecodedTransactionInstruction,
+) -> Option<usize> {
+    instructions
+        .iter()
+        .zip(is_event)
+        .rposition(|(ix, is_event)| {
+            !is_event
+                && ix.outer_index == event.outer_index
+                && ix.program_id == event.program_id
+                && match (ix.stack_height, event.stack_height) {
+                    (Some(parent), Some(event)) => parent + 1 == event,
+                    // outer instructions don't always record their stack height
+                    (None, Some(event)) => ix.inner_index.is_none() && event == 2,
+                    _ => true,
+                }
+        })
+}
+
+/// Returns the index of the bubblegum instruction a noop payload was logged for, which is
+/// the closest preceding bubblegum instruction higher up the call stack. The change log is
+/// logged by the compression program invoked by bubblegum, so may be more than one level down
+fn bubblegum_parent(
+    instructions: &[DecodedTransactionInstruction],
+    noop: &DecodedTransactionInstruction,
+) -> Option<usize> {
+    let bubblegum = bubblegum::PROGRAM_ID.to_string();
+    instructions.iter().rposition(|ix| {
+        ix.outer_index == noop.outer_index
+            && ix.program_id == bubblegum
+            && match (ix.stack_height, noop.stack_height) {
+                (Some(parent), Some(noop)) => parent < noop,
+                _ => true,
+            }
+    })
+}
+
+/// Returns true if every outer instruction of the transaction invokes the vote program.
+///
+/// Only program ids are inspected, no instruction data is decoded.
+pub fn is_vote_transaction(tx: &EncodedTransactionWithStatusMeta) -> bool {
+    let vote_program = vote::PROGRAM_ID.to_string();
+    match &tx.transaction {
+        EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
+            UiMessage::Raw(message) => {
+                !message.instructions.is_empty()
+                    && message.instructions.iter().all(|ix| {
+                        message.account_keys.get(ix.program_id_index as usize)
+                            == Some(&vote_program)
+                    })
+            }
+            UiMessage::Parsed(message) => {
+                !message.instructions.is_empty()
+                    && message.instructions.iter().all(|ix| match ix {
+                        UiInstruction::Compiled(ix) => {
+                            message
+                                .account_keys
+                                .get(ix.program_id_index as usize)
+                                .map(|account| &account.pubkey)
+                                == Some(&vote_program)
+                        }
+                        UiInstruction::Parsed(UiParsedInstruction::Parsed(ix)) => {
+                            ix.program_id == vote_program
+                        }
+                        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(ix)) => {
+                            ix.program_id == vote_program
+                        }
+                    })
+            }
+        },
+        EncodedTransaction::Accounts(_) => false,
+        encoded => encoded
+            .decode()
+            .map(|tx| {
+                let instructions = tx.message.instructions();
+                !instructions.is_empty()
+                    && instructions.iter().all(|ix| {
+                        ix.program_id(tx.message.static_account_keys()) == &vote::PROGRAM_ID
+                    })
+            })
+            .unwrap_or(false),
+    }
+}
+
+/// Returns the transaction signature and its resolved outer and inner instructions
+fn resolve_instructions(
+    tx: &EncodedTransactionWithStatusMeta,
+    lookup_tables: &HashMap<Pubkey, LookupTable>,
+) -> anyhow::Result<(String, Vec<ResolvedInstruction>)> {
+    // account keys loaded from lookup tables which couldn't be resolved hold the reason,
+    // failing only the instructions which reference them
+    let (signature, account_keys, outer_instructions) = match &tx.transaction {
+        EncodedTransaction::Json(ui_tx) => {
+            let signature = ui_tx.signatures.first().cloned().unwrap_or_default();
+            match &ui_tx.message {
+                UiMessage::Raw(message) => {
+                    let lookups = message
+                        .address_table_lookups
+                        .iter()
+                        .flatten()
+                        .map(parse_address_table_lookup)
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    let mut account_keys = parse_account_keys(message.account_keys.iter())?
+                        .into_iter()
+                        .map(Ok)
+                        .collect::<Vec<_>>();
+                    account_keys.extend(loaded_addresses(
+                        tx.meta.as_ref(),
+                        &lookups,
+                        lookup_tables,
+                    ));
+                    let instructions = message
+                        .instructions
+                        .iter()
+                        .cloned()
+                        .map(UiInstruction::Compiled)
+                        .collect::<Vec<_>>();
+                    (signature, account_keys, instructions)
+                }
+                UiMessage::Parsed(message) => {
+                    // parsed messages already include any addresses loaded from lookup tables
+                    let account_keys = parse_account_keys(
+                        message.account_keys.iter().map(|account| &account.pubkey),
+                    )?
+                    .into_iter()
+                    .map(Ok)
+                    .collect();
+                    (signature, account_keys, message.instructions.clone())
+                }
+            }
+        }
+        EncodedTransaction::Accounts(_) => {
+            return Err(anyhow!(
+                "transactions encoded as accounts do not contain instructions"
+            ))
+        }
+        encoded => {
+            let versioned_tx = encoded
+                .decode()
+                .ok_or_else(|| anyhow!("failed to decode transaction"))?;
+            let signature = versioned_tx
+                .signatures
+                .first()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let mut account_keys = versioned_tx
+                .message
+                .static_account_keys()
+                .iter()
+                .copied()
+                .map(Ok)
+                .collect::<Vec<_>>();
+            account_keys.extend(loaded_addresses(
+                tx.meta.as_ref(),
+                versioned_tx
+                    .message
+                    .address_table_lookups()
+                    .unwrap_or_default(),
+                lookup_tables,
+            ));
+            let instructions = versioned_tx
+                .message
+                .instructions()
+                .iter()
+                .map(|ix| {
+                    UiInstruction::Compiled(UiCompiledInstruction {
+                        program_id_index: ix.program_id_index,
+                        accounts: ix.accounts.clone(),
+                        data: bs58::encode(&ix.data).into_string(),
+                        stack_height: None,
+                    })
+                })
+                .collect::<Vec<_>>();
+            (signature, account_keys, instructions)
+        }
+    };
+
+    let inner_instructions: Option<&Vec<UiInnerInstructions>> = tx
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.inner_instructions.as_ref().into());
+    let inner_instructions = inner_instructions.map(Vec::as_slice).unwrap_or_default();
+
+    let mut resolved = Vec::with_capacity(outer_instructions.len());
+    for (outer_index, ix) in outer_instructions.iter().enumerate() {
+        resolved.push(resolve_instruction(ix, &account_keys, outer_index, None));
+        for inner in inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == outer_index)
+        {
+            for (inner_index, ix) in inner.instructions.iter().enumerate() {
+                resolved.push(resolve_instruction(
+                    ix,
+                    &account_keys,
+                    outer_index,
+                    Some(inner_index),
+                ));
+            }
+        }
+    }
+    Ok((signature, resolved))
+}
+
+fn resolve_instruction(
+    ix: &UiInstruction,
+    account_keys: &[AccountKey],
+    outer_index: usize,
+    inner_index: Option<usize>,
+) -> ResolvedInstruction {
+    let (program_id, stack_height, ix) = match ix {
+        UiInstruction::Compiled(ix) => {
+            let program_id = account_keys
+                .get(ix.program_id_index as usize)
+                .and_then(|key| key.as_ref().ok())
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            (
+                program_id,
+                ix.stack_height,
+                resolve_compiled(ix, account_keys),
+            )
+        }
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(ix)) => (
+            ix.program_id.clone(),
+            ix.stack_height,
+            PartiallyDecodedInstruction::try_from(ix.clone()),
+        ),
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(ix)) => (
+            ix.program_id.clone(),
+            ix.stack_height,
+            Err(anyhow!("unsupported instruction format")),
+        ),
+    };
+    ResolvedInstruction {
+        program_id,
+        outer_index,
+        inner_index,
+        stack_height,
+        ix,
+    }
+}
+
+fn resolve_compiled(
+    ix: &UiCompiledInstruction,
+    account_keys: &[AccountKey],
+) -> anyhow::Result<PartiallyDecodedInstruction> {
+    let account_key = |idx: u8| match account_keys.get(idx as usize) {
+        Some(Ok(key)) => Ok(*key),
+        Some(Err(err)) => Err(anyhow!(
+            "account index {idx} is loaded from a lookup table: {err}"
+        )),
+        None => Err(anyhow!("account index out of bounds")),
+    };
+    // the runtime doesn't allow program ids to be loaded from lookup tables
+    let program_id = account_keys
+        .get(ix.program_id_index as usize)
+        .and_then(|key| key.as_ref().ok())
+        .copied()
+        .ok_or_else(|| anyhow!("program id index out of bounds"))?;
+    let accounts = ix
+        .accounts
+        .iter()
+        .map(|idx| account_key(*idx))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let data = bs58::decode(&ix.data)
+        .into_vec()
+        .with_context(|| "failed to decode instruction data")?;
+    Ok(PartiallyDecodedInstruction {
+        program_id,
+        accounts,
+        data,
+        stack_height: ix.stack_height,
+    })
+}
+
+fn parse_account_keys<'a>(
+    account_keys: impl Iterator<Item = &'a String>,
+) -> anyhow::Result<Vec<Pubkey>> {
+    account_keys
+        .map(|account| Pubkey::from_str(account).with_context(|| "failed to parse account key"))
+        .collect()
+}
+
+fn parse_address_table_lookup(
+    lookup: &UiAddressTableLookup,
+) -> anyhow::Result<MessageAddressTableLookup> {
+    Ok(MessageAddressTableLookup {
+        account_key: Pubkey::from_str(&lookup.account_key)
+            .with_context(|| "failed to parse lookup table address")?,
+        writable_indexes: lookup.writable_indexes.clone(),
+        readonly_indexes: lookup.readonly_indexes.clone(),
+    })
+}
+
+/// Returns the writable followed by the readonly addresses loaded from address lookup tables.
+///
+/// The addresses recorded in the transaction meta are preferred, falling back to
+/// resolving the lookups against `lookup_tables`.
+fn loaded_addresses(
+    meta: Option<&UiTransactionStatusMeta>,
+    lookups: &[MessageAddressTableLookup],
+    lookup_tables: &HashMap<Pubkey, LookupTable>,
+) -> Vec<AccountKey> {
+    let loaded: Option<&UiLoadedAddresses> =
+        meta.and_then(|meta| meta.loaded_addresses.as_ref().into());
+    if let Some(loaded) = loaded {
+        return match parse_account_keys(loaded.writable.iter().chain(loaded.readonly.iter())) {
+            Ok(keys) => keys.into_iter().map(Ok).collect(),
+            Err(err) => {
+                let loaded_count = loaded.writable.len() + loaded.readonly.len();
+                vec![Err(format!("{err:#}")); loaded_count]
+            }
+        };
+    }
+    let loaded = resolve_loaded_addresses(lookups, lookup_tables);
+    loaded.writable.into_iter().chain(loaded.readonly).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        message::Message,
+        stake,
+        transaction::Transaction,
+        vote::{instruction as vote_instruction, state::Vote},
+    };
+    use solana_transaction_status::{Encodable, UiTransactionEncoding};
+
+    fn encode(ix: solana_sdk::instruction::Instruction) -> EncodedTransactionWithStatusMeta {
+        let payer = Pubkey::new_unique();
+        let tx = Transaction::new_unsigned(Message::new(&[ix], Some(&payer)));
+        EncodedTransactionWithStatusMeta {
+            transaction: tx.encode(UiTransactionEncoding::Base64),
+            meta: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_block_skip_votes() {
+        let vote_tx = encode(vote_instruction::vote(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            Vote::new(vec![1, 2, 3], Hash::default()),
+        ));
+        let stake_tx = encode(stake::instruction::deactivate_stake(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        ));
+        assert!(is_vote_transaction(&vote_tx));
+        assert!(!is_vote_transaction(&stake_tx));
+
+        let block = UiConfirmedBlock {
+            previous_blockhash: Default::default(),
+            blockhash: Default::default(),
+            parent_slot: 0,
+            transactions: Some(vec![vote_tx, stake_tx]),
+            signatures: None,
+            rewards: None,
+            block_time: None,
+            block_height: None,
+        };
+
+        let decoded = decode_block(&block, DecodeOptions::default());
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].0, 0);
+        let vote = decoded[0].1.as_ref().unwrap().instructions[0]
+            .decoded
+            .clone()
+            .unwrap();
+        assert_eq!(vote.name, "vote");
+        assert_eq!(vote.data["slots"], serde_json::json!([1, 2, 3]));
+
+        let decoded = decode_block(&block, DecodeOptions { skip_votes: true });
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].0, 1);
+        let deactivate = decoded[0].1.as_ref().unwrap().instructions[0]
+            .decoded
+            .clone()
+            .unwrap();
+        assert_eq!(deactivate.name, "deactivate");
+    }
+
+    #[test]
+    fn test_decode_block_keeps_indexes_of_failed_transactions() {
+        use solana_transaction_status::TransactionBinaryEncoding;
+
+        let vote_tx = encode(vote_instruction::vote(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            Vote::new(vec![1], Hash::default()),
+        ));
+        let invalid_tx = EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::Binary(
+                "not a transaction".to_string(),
+                TransactionBinaryEncoding::Base64,
+            ),
+            meta: None,
+            version: None,
+        };
+        let stake_tx = encode(stake::instruction::deactivate_stake(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        ));
+        assert!(!is_vote_transaction(&invalid_tx));
+
+        let block = UiConfirmedBlock {
+            previous_blockhash: Default::default(),
+            blockhash: Default::default(),
+            parent_slot: 0,
+            transactions: Some(vec![vote_tx, invalid_tx, stake_tx]),
+            signatures: None,
+            rewards: None,
+            block_time: None,
+            block_height: None,
+        };
+
+        let decoded = decode_block(&block, DecodeOptions { skip_votes: true });
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].0, 1);
+        assert_eq!(
+            decoded[0].1.as_ref().unwrap_err().to_string(),
+            "failed to decode transaction"
+        );
+        assert_eq!(decoded[1].0, 2);
+        assert!(decoded[1].1.is_ok());
+    }
+
+    #[test]
+    fn test_decode_transaction_with_lookup_tables() {
+        use solana_sdk::{
+            address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+            message::v0,
+            sysvar,
+        };
+        use solana_transaction_status::{UiRawMessage, UiTransaction};
+
+        let stake_account = Pubkey::new_unique();
+        let other_stake_account = Pubkey::new_unique();
+        let static_stake_account = Pubkey::new_unique();
+        let stake_authority = Pubkey::new_unique();
+        let table_address = Pubkey::new_unique();
+        let table_addresses = vec![Pubkey::new_unique(), stake_account];
+        // also provides the clock sysvar, loaded as readonly after every writable address
+        let other_table_address = Pubkey::new_unique();
+        let other_table_addresses = vec![other_stake_account, sysvar::clock::ID];
+
+        let message = v0::Message::try_compile(
+            &stake_authority,
+            &[
+                stake::instruction::deactivate_stake(&stake_account, &stake_authority),
+                stake::instruction::deactivate_stake(&other_stake_account, &stake_authority),
+                stake::instruction::deactivate_stake(&static_stake_account, &stake_authority),
+            ],
+            &[
+                AddressLookupTableAccount {
+                    key: table_address,
+                    addresses: table_addresses.clone(),
+                },
+                AddressLookupTableAccount {
+                    key: other_table_address,
+                    addresses: other_table_addresses.clone(),
+                },
+            ],
+            Hash::default(),
+        )
+        .unwrap();
+        assert_eq!(message.address_table_lookups.len(), 2);
+        let tx = EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::Json(UiTransaction {
+                signatures: vec![Default::default()],
+                message: UiMessage::Raw(UiRawMessage {
+                    header: message.header,
+                    account_keys: message
+                        .account_keys
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    recent_blockhash: message.recent_blockhash.to_string(),
+                    instructions: message
+                        .instructions
+                        .iter()
+                        .map(|ix| UiCompiledInstruction {
+                            program_id_index: ix.program_id_index,
+                            accounts: ix.accounts.clone(),
+                            data: bs58::encode(&ix.data).into_string(),
+                            stack_height: None,
+                        })
+                        .collect(),
+                    address_table_lookups: Some(
+                        message
+                            .address_table_lookups
+                            .iter()
+                            .map(|lookup| UiAddressTableLookup {
+                                account_key: lookup.account_key.to_string(),
+                                writable_indexes: lookup.writable_indexes.clone(),
+                                readonly_indexes: lookup.readonly_indexes.clone(),
+                            })
+                            .collect(),
+                    ),
+                }),
+            }),
+            meta: None,
+            version: None,
+        };
+        let unpack_table = |addresses: Vec<Pubkey>| {
+            let data = AddressLookupTable {
+                meta: Default::default(),
+                addresses: addresses.into(),
+            }
+            .serialize_for_tests()
+            .unwrap();
+            LookupTable::unpack(&data).unwrap()
+        };
+
+        // every instruction loads the clock sysvar, so none decode without the lookup tables
+        let decoded = decode_transaction(&tx).unwrap();
+        for ix in &decoded.instructions {
+            assert!(ix.decoded.is_none());
+            assert!(ix
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("loaded from a lookup table"));
+        }
+
+        // with one table missing the addresses of the other keep their positions
+        let lookup_tables = [(other_table_address, unpack_table(other_table_addresses))]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let decoded = decode_transaction_with_lookup_tables(&tx, &lookup_tables).unwrap();
+        assert!(decoded.instructions[0].decoded.is_none());
+        assert!(decoded.instructions[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains(&format!("missing lookup table {table_address}")));
+        let deactivate = decoded.instructions[1].decoded.clone().unwrap();
+        assert_eq!(deactivate.name, "deactivate");
+        assert_eq!(
+            deactivate.accounts["stakeAccount"],
+            other_stake_account.to_string()
+        );
+        assert_eq!(
+            deactivate.accounts["clockSysvar"],
+            sysvar::clock::ID.to_string()
+        );
+        let deactivate = decoded.instructions[2].decoded.clone().unwrap();
+        assert_eq!(
+            deactivate.accounts["stakeAccount"],
+            static_stake_account.to_string()
+        );
+
+        let mut lookup_tables = lookup_tables;
+        lookup_tables.insert(table_address, unpack_table(table_addresses));
+        let decoded = decode_transaction_with_lookup_tables(&tx, &lookup_tables).unwrap();
+        let deactivate = decoded.instructions[0].decoded.clone().unwrap();
+        assert_eq!(deactivate.name, "deactivate");
+        assert_eq!(
+            deactivate.accounts["stakeAccount"],
+            stake_account.to_string()
+        );
+    }
+
+    #[test]
+    fn test_decode_transaction_memos() {
+        let signer = Pubkey::new_unique();
+        let memo_ix = |data: &[u8]| solana_sdk::instruction::Instruction {
+            program_id: crate::spl::memo::PROGRAM_ID_V2,
+            accounts: vec![solana_sdk::instruction::AccountMeta::new_readonly(
+                signer, true,
+            )],
+            data: data.to_vec(),
+        };
+        let tx = Transaction::new_unsigned(Message::new(
+            &[memo_ix(b"deposit 1234"), memo_ix(&[0xff, 0xfe])],
+            Some(&signer),
+        ));
+        let tx = EncodedTransactionWithStatusMeta {
+            transaction: tx.encode(UiTransactionEncoding::Base64),
+            meta: None,
+            version: None,
+        };
+        let decoded = decode_transaction(&tx).unwrap();
+        assert_eq!(decoded.memos.len(), 2);
+        assert_eq!(decoded.memos[0].memo, "deposit 1234");
+        assert_eq!(decoded.memos[0].accounts, vec![signer.to_string()]);
+        assert_eq!(decoded.memos[1].memo, "fffe");
+        assert_eq!(
+            decoded.memos[1].encoding,
+            crate::spl::memo::MemoEncoding::Hex
+        );
+        assert_eq!(
+            decoded.instructions[0].decoded.as_ref().unwrap().data["memo"],
+            "deposit 1234"
+        );
+    }
+
+    #[test]
+    fn test_decode_transaction_attaches_events() {
+        use crate::pump::bonding_curve::{self as pump, events, ix as pump_ix};
+        use borsh::to_vec;
+        use solana_sdk::{instruction::AccountMeta, transaction::TransactionError};
+        use solana_transaction_status::{
+            option_serializer::OptionSerializer, TransactionStatusMeta,
+        };
+
+        let user = Pubkey::new_unique();
+        let event_authority = Pubkey::new_unique();
+        let mut buy_data = pump_ix::BUY.to_vec();
+        buy_data.extend(to_vec(&(42_000_000u64, 2_000_000_000u64)).unwrap());
+        let buy = solana_sdk::instruction::Instruction {
+            program_id: pump::PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(user, true),
+                AccountMeta::new_readonly(event_authority, false),
+            ],
+            data: buy_data,
+        };
+        let tx = Transaction::new_unsigned(Message::new(&[buy], Some(&user)));
+        let account_keys = &tx.message.account_keys;
+        let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+
+        let mut event_data = anchor::EVENT_IX_TAG.to_vec();
+        event_data.extend(events::TRADE_EVENT);
+        event_data.extend(
+            to_vec(&(
+                Pubkey::new_unique(),
+                1_500_000_000u64,
+                42_000_000u64,
+                true,
+                user,
+                0i64,
+                0u64,
+                0u64,
+            ))
+            .unwrap(),
+        );
+        let mut meta: UiTransactionStatusMeta = TransactionStatusMeta {
+            status: Ok::<(), TransactionError>(()),
+            ..Default::default()
+        }
+        .into();
+        meta.inner_instructions = OptionSerializer::Some(vec![UiInnerInstructions {
+            index: 0,
+            instructions: vec![UiInstruction::Compiled(UiCompiledInstruction {
+                program_id_index: index_of(&pump::PROGRAM_ID),
+                accounts: vec![index_of(&event_authority)],
+                data: bs58::encode(&event_data).into_string(),
+                stack_height: Some(2),
+            })],
+        }]);
+        let tx = EncodedTransactionWithStatusMeta {
+            transaction: tx.encode(UiTransactionEncoding::Base64),
+            meta: Some(meta),
+            version: None,
+        };
+
+        let decoded = decode_transaction(&tx).unwrap();
+        assert_eq!(decoded.instructions.len(), 2);
+        let buy = &decoded.instructions[0];
+        assert_eq!(buy.decoded.as_ref().unwrap().name, "buy");
+        assert_eq!(buy.events.len(), 1);
+        assert_eq!(buy.events[0].name, "tradeEvent");
+        assert_eq!(buy.events[0].data["solAmount"], 1_500_000_000u64);
+        assert!(decoded.instructions[1].events.is_empty());
+    }
+
+    #[test]
+    fn test_decode_transaction_attaches_bubblegum_leaves() {
+        use crate::{metaplex::bubblegum::ix as bubblegum_ix, spl::account_compression};
+        use borsh::to_vec;
+        use solana_sdk::{instruction::AccountMeta, transaction::TransactionError};
+        use solana_transaction_status::{
+            option_serializer::OptionSerializer, TransactionStatusMeta,
+        };
+
+        let owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let tree = Pubkey::new_unique();
+        let asset_id = bubblegum::state::get_asset_id(&tree, 3);
+        let mut transfer_data = bubblegum_ix::TRANSFER.to_vec();
+        transfer_data.extend([0u8; 96]);
+        transfer_data.extend(to_vec(&(3u64, 3u32)).unwrap());
+        let transfer = solana_sdk::instruction::Instruction {
+            program_id: bubblegum::PROGRAM_ID,
+            accounts: [Pubkey::new_unique(), owner, owner, new_owner, tree]
+                .into_iter()
+                .map(|account| AccountMeta::new(account, account == owner))
+                .chain([
+                    AccountMeta::new_readonly(noop::PROGRAM_ID, false),
+                    AccountMeta::new_readonly(account_compression::PROGRAM_ID, false),
+                ])
+                .collect(),
+            data: transfer_data,
+        };
+        let tx = Transaction::new_unsigned(Message::new(&[transfer], Some(&owner)));
+        let account_keys = &tx.message.account_keys;
+        let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+
+        // application data, v1 wrapping a v1 leaf schema event
+        let mut leaf = vec![1, 0];
+        let mut schema = vec![1, 0, 0];
+        schema.extend(to_vec(&(asset_id, new_owner, new_owner, 3u64)).unwrap());
+        schema.extend([5u8; 32]);
+        schema.extend([6u8; 32]);
+        schema.extend([7u8; 32]);
+        leaf.extend(to_vec(&schema).unwrap());
+        // change log, v1
+        let mut change_log = vec![0, 0];
+        change_log.extend(tree.to_bytes());
+        change_log.extend(to_vec(&(vec![([7u8; 32], 11u32)], 8u64, 3u32)).unwrap());
+        let mut replace_leaf = account_compression::ix::REPLACE_LEAF.to_vec();
+        replace_leaf.extend([0u8; 100]);
+        let inner = |program_id: &Pubkey, data: &[u8], stack_height: u32| {
+            UiInstruction::Compiled(UiCompiledInstruction {
+                program_id_index: index_of(program_id),
+                accounts: vec![],
+                data: bs58::encode(data).into_string(),
+                stack_height: Some(stack_height),
+            })
+        };
+        let mut meta: UiTransactionStatusMeta = TransactionStatusMeta {
+            status: Ok::<(), TransactionError>(()),
+            ..Default::default()
+        }
+        .into();
+        meta.inner_instructions = OptionSerializer::Some(vec![UiInnerInstructions {
+            index: 0,
+            instructions: vec![
+                inner(&noop::PROGRAM_ID, &leaf, 2),
+                inner(&account_compression::PROGRAM_ID, &replace_leaf, 2),
+                inner(&noop::PROGRAM_ID, &change_log, 3),
+            ],
+        }]);
+        let tx = EncodedTransactionWithStatusMeta {
+            transaction: tx.encode(UiTransactionEncoding::Base64),
+            meta: Some(meta),
+            version: None,
+        };
+
+        let decoded = decode_transaction(&tx).unwrap();
+        assert_eq!(decoded.instructions.len(), 4);
+        let transfer = &decoded.instructions[0];
+        assert_eq!(transfer.decoded.as_ref().unwrap().name, "transfer");
+        assert_eq!(transfer.events.len(), 2);
+        assert_eq!(transfer.events[0].name, "leafSchemaEvent");
+        assert_eq!(transfer.events[0].data["assetId"], asset_id.to_string());
+        assert_eq!(transfer.events[0].data["owner"], new_owner.to_string());
+        assert_eq!(transfer.events[0].data["delegate"], new_owner.to_string());
+        assert_eq!(transfer.events[0].data["dataHash"], hex::encode([5u8; 32]));
+        assert_eq!(transfer.events[1].name, "changeLog");
+        assert_eq!(transfer.events[1].data["id"], tree.to_string());
+        assert_eq!(
+            decoded.instructions[2].decoded.as_ref().unwrap().name,
+            "replaceLeaf"
+        );
+        assert_eq!(
+            decoded.instructions[1].decoded.as_ref().unwrap().name,
+            "applicationData"
+        );
+    }
+}
@@ -1,14 +1,61 @@
 use std::{collections::HashMap, str::FromStr, sync::Arc};
 
+use crate::allbridge::{Programs as AllbridgePrograms, ALLBRIDGE_DECODER};
+use crate::debridge::{Programs as DebridgePrograms, DEBRIDGE_DECODER};
+use crate::drift::{Programs as DriftPrograms, DRIFT_DECODER};
+use crate::jupiter::{Programs as JupiterPrograms, JUPITER_DECODER};
+use crate::kamino::{Programs as KaminoPrograms, KAMINO_DECODER};
+use crate::magic_eden::{Programs as MagicEdenPrograms, MAGIC_EDEN_DECODER};
+use crate::mango::{Programs as MangoPrograms, MANGO_DECODER};
+use crate::marginfi::{Programs as MarginfiPrograms, MARGINFI_DECODER};
+use crate::marinade::{Programs as MarinadePrograms, MARINADE_DECODER};
+use crate::mayan::{Programs as MayanPrograms, MAYAN_DECODER};
+use crate::metaplex::{Programs as MetaplexPrograms, METAPLEX_DECODER};
+use crate::meteora::{Programs as MeteoraPrograms, METEORA_DECODER};
+use crate::native::{Programs as NativePrograms, NATIVE_DECODER};
+use crate::openbook::{Programs as OpenbookPrograms, OPENBOOK_DECODER};
+use crate::orca::{Programs as OrcaPrograms, ORCA_DECODER};
+use crate::phoenix::{Programs as PhoenixPrograms, PHOENIX_DECODER};
+use crate::pump::{Programs as PumpPrograms, PUMP_DECODER};
 use crate::raydium::{Programs as RaydiumPrograms, RAYDIUM_DECODER};
-use anyhow::{anyhow, Context, Result};
+use crate::sanctum::{Programs as SanctumPrograms, SANCTUM_DECODER};
+use crate::solend::{Programs as SolendPrograms, SOLEND_DECODER};
+use crate::spl::{Programs as SplPrograms, SPL_DECODER};
+use crate::squads::{Programs as SquadsPrograms, SQUADS_DECODER};
+use crate::tensor::{Programs as TensorPrograms, TENSOR_DECODER};
+use crate::wormhole::{Programs as WormholePrograms, WORMHOLE_DECODER};
+use anyhow::{anyhow, Context};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize, Serializer};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 use solana_transaction_status::UiPartiallyDecodedInstruction;
 
-pub static PROGRAM_DECODER_MATCHERS: [Lazy<Arc<dyn ProgramDecoderMatcher>>; 1] =
-    [Lazy::new(|| RAYDIUM_DECODER.clone())];
+pub static PROGRAM_DECODER_MATCHERS: [Lazy<Arc<dyn ProgramDecoderMatcher>>; 24] = [
+    Lazy::new(|| RAYDIUM_DECODER.clone()),
+    Lazy::new(|| NATIVE_DECODER.clone()),
+    Lazy::new(|| SPL_DECODER.clone()),
+    Lazy::new(|| ORCA_DECODER.clone()),
+    Lazy::new(|| METEORA_DECODER.clone()),
+    Lazy::new(|| PUMP_DECODER.clone()),
+    Lazy::new(|| JUPITER_DECODER.clone()),
+    Lazy::new(|| OPENBOOK_DECODER.clone()),
+    Lazy::new(|| PHOENIX_DECODER.clone()),
+    Lazy::new(|| KAMINO_DECODER.clone()),
+    Lazy::new(|| MARGINFI_DECODER.clone()),
+    Lazy::new(|| SOLEND_DECODER.clone()),
+    Lazy::new(|| MARINADE_DECODER.clone()),
+    Lazy::new(|| SANCTUM_DECODER.clone()),
+    Lazy::new(|| DRIFT_DECODER.clone()),
+    Lazy::new(|| MANGO_DECODER.clone()),
+    Lazy::new(|| METAPLEX_DECODER.clone()),
+    Lazy::new(|| TENSOR_DECODER.clone()),
+    Lazy::new(|| MAGIC_EDEN_DECODER.clone()),
+    Lazy::new(|| WORMHOLE_DECODER.clone()),
+    Lazy::new(|| DEBRIDGE_DECODER.clone()),
+    Lazy::new(|| MAYAN_DECODER.clone()),
+    Lazy::new(|| ALLBRIDGE_DECODER.clone()),
+    Lazy::new(|| SQUADS_DECODER.clone()),
+];
 
 pub trait ProgramDecoder {
     fn decode(&self) -> anyhow::Result<DecodedInstruction>;
@@ -22,6 +69,29 @@ pub trait ProgramDecoderMatcher: Send + Sync {
 #[derive(Clone, PartialEq, Eq)]
 pub enum Protocols {
     Raydium(RaydiumPrograms),
+    Native(NativePrograms),
+    Spl(SplPrograms),
+    Orca(OrcaPrograms),
+    Meteora(MeteoraPrograms),
+    Pump(PumpPrograms),
+    Jupiter(JupiterPrograms),
+    Openbook(OpenbookPrograms),
+    Phoenix(PhoenixPrograms),
+    Kamino(KaminoPrograms),
+    Marginfi(MarginfiPrograms),
+    Solend(SolendPrograms),
+    Marinade(MarinadePrograms),
+    Sanctum(SanctumPrograms),
+    Drift(DriftPrograms),
+    Mango(MangoPrograms),
+    Metaplex(MetaplexPrograms),
+    Tensor(TensorPrograms),
+    MagicEden(MagicEdenPrograms),
+    Wormhole(WormholePrograms),
+    Debridge(DebridgePrograms),
+    Mayan(MayanPrograms),
+    Allbridge(AllbridgePrograms),
+    Squads(SquadsPrograms),
 }
 
 #[derive(Clone)]
@@ -40,6 +110,37 @@ pub struct DecodedInstruction {
     pub accounts: HashMap<String, serde_json::Value>,
 }
 
+/// An instruction carried by another rather than executed directly, such as the
+/// instructions of a multisig transaction or governance proposal, decoded through the
+/// program decoders
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedInstruction {
+    pub program_id: String,
+    /// the decoded instruction, None if decoding failed
+    pub decoded: Option<DecodedInstruction>,
+    /// the reason decoding failed
+    pub error: Option<String>,
+}
+
+impl EmbeddedInstruction {
+    /// Decodes the instruction, recording the error if it could not be resolved or decoded
+    pub fn decode(program_id: String, ix: anyhow::Result<PartiallyDecodedInstruction>) -> Self {
+        match ix.and_then(|ix| crate::try_instruction_decoder(&ix)?.decode()) {
+            Ok(decoded) => Self {
+                program_id,
+                decoded: Some(decoded),
+                error: None,
+            },
+            Err(err) => Self {
+                program_id,
+                decoded: None,
+                error: Some(format!("{err:#}")),
+            },
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
 pub struct IxAccountInput {
     pub index: u16,
@@ -60,6 +161,34 @@ pub struct PartiallyDecodedInstruction {
     pub stack_height: Option<u32>,
 }
 
+impl PartiallyDecodedInstruction {
+    /// Maps the instruction accounts to the given names by position, accounts
+    /// beyond the end of `names` are ignored
+    pub fn named_accounts(&self, names: &[&str]) -> HashMap<String, serde_json::Value> {
+        self.accounts
+            .iter()
+            .zip(names)
+            .map(|(account, name)| {
+                (
+                    name.to_string(),
+                    serde_json::Value::String(account.to_string()),
+                )
+            })
+            .collect()
+    }
+}
+
+impl From<Instruction> for PartiallyDecodedInstruction {
+    fn from(value: Instruction) -> Self {
+        Self {
+            program_id: value.program_id,
+            accounts: value.accounts.into_iter().map(|meta| meta.pubkey).collect(),
+            data: value.data,
+            stack_height: None,
+        }
+    }
+}
+
 impl TryFrom<UiPartiallyDecodedInstruction> for PartiallyDecodedInstruction {
     type Error = anyhow::Error;
     fn try_from(value: UiPartiallyDecodedInstruction) -> std::result::Result<Self, Self::Error> {
@@ -88,3 +217,123 @@ impl TryFrom<UiPartiallyDecodedInstruction> for PartiallyDecodedInstruction {
         })
     }
 }
+
+/// Converts instruction arguments into the data map of a [DecodedInstruction]
+pub fn to_data_map<T: Serialize>(args: &T) -> anyhow::Result<HashMap<String, serde_json::Value>> {
+    match serde_json::to_value(args)? {
+        serde_json::Value::Object(map) => Ok(map.into_iter().collect()),
+        serde_json::Value::Null => Ok(HashMap::new()),
+        _ => Err(anyhow!("instruction arguments must serialize to an object")),
+    }
+}
+
+/// Serializes a pubkey as its base58 string rather than as an array of bytes
+pub fn serialize_pubkey<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+pub fn serialize_option_pubkey<S: Serializer>(
+    pubkey: &Option<Pubkey>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match pubkey {
+        Some(pubkey) => serializer.collect_str(pubkey),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn serialize_pubkeys<S: Serializer>(
+    pubkeys: &[Pubkey],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(pubkeys.iter().map(ToString::to_string))
+}
+
+/// Serializes 128 bit integers as strings, as they can't be represented by json numbers
+pub fn serialize_u128<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Serializes optional 128 bit integers as strings
+pub fn serialize_option_u128<S: Serializer>(
+    value: &Option<u128>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn serialize_i128<S: Serializer>(value: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Serializes arrays longer than the 32 elements serde supports
+pub fn serialize_array<S: Serializer, T: Serialize, const N: usize>(
+    values: &[T; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(values)
+}
+
+pub fn serialize_u128_array<S: Serializer, const N: usize>(
+    values: &[u128; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(values.iter().map(ToString::to_string))
+}
+
+/// Serializes raw bytes as a hex string
+pub fn serialize_hex<S: Serializer, T: AsRef<[u8]>>(
+    bytes: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(bytes))
+}
+
+pub fn serialize_option_hex<S: Serializer, T: AsRef<[u8]>>(
+    bytes: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match bytes {
+        Some(bytes) => serializer.serialize_str(&hex::encode(bytes)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Address formats of the chains bridges send funds to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressFormat {
+    Solana,
+    Evm,
+    Hex,
+}
+
+/// Renders an address of another chain in its native format, evm addresses being the last
+/// 20 bytes when left padded to 32 bytes
+pub fn format_address(format: AddressFormat, address: &[u8]) -> String {
+    match format {
+        AddressFormat::Solana => bs58::encode(address).into_string(),
+        AddressFormat::Evm if address.len() >= 20 => {
+            format!("0x{}", hex::encode(&address[address.len() - 20..]))
+        }
+        _ => hex::encode(address),
+    }
+}
+
+/// The transfer a bridge instruction starts or completes, extracted so that flows can be
+/// traced the same way across bridges
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeTransfer {
+    /// chain id in the numbering used by the bridge
+    pub destination_chain_id: u64,
+    /// rendered in the format of the destination chain
+    pub destination_address: String,
+    /// token received on the destination chain
+    pub token: String,
+    /// the amount to receive for order based bridges, the amount sent for pool based ones,
+    /// as a decimal string since amounts of other chains may not fit a json number
+    pub amount: String,
+}
@@ -0,0 +1,102 @@
+//! Instruction types
+//!
+//! Instructions are borsh encoded, the variant index being the leading byte.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+
+use crate::{anchor, types::serialize_hex};
+
+/// Commitment the guardians wait for before observing a message
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConsistencyLevel {
+    Confirmed,
+    Finalized,
+}
+
+/// Ethereum style address of a guardian
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+pub struct GuardianKey(#[serde(serialize_with = "serialize_hex")] pub [u8; 20]);
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeInstruction {
+    pub guardian_set_expiration_time: u32,
+    pub fee: u64,
+    pub initial_guardians: Vec<GuardianKey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostMessageInstruction {
+    pub nonce: u32,
+    #[serde(serialize_with = "serialize_hex")]
+    pub payload: Vec<u8>,
+    pub consistency_level: ConsistencyLevel,
+}
+
+/// The body of a vaa whose signatures were verified through `verify_signatures`
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostVaaInstruction {
+    pub version: u8,
+    pub guardian_set_index: u32,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: u16,
+    #[serde(serialize_with = "serialize_hex")]
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub consistency_level: u8,
+    #[serde(serialize_with = "serialize_hex")]
+    pub payload: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeesInstruction {
+    pub fee: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifySignaturesInstruction {
+    /// index of each guardian in the secp256k1 instruction, -1 when it did not sign
+    pub signers: [i8; 19],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CoreBridgeInstruction {
+    Initialize(InitializeInstruction),
+    PostMessage(PostMessageInstruction),
+    PostVaa(PostVaaInstruction),
+    SetFees(SetFeesInstruction),
+    TransferFees,
+    UpgradeContract,
+    UpgradeGuardianSet,
+    VerifySignatures(VerifySignaturesInstruction),
+    PostMessageUnreliable(PostMessageInstruction),
+}
+
+impl CoreBridgeInstruction {
+    /// Unpacks a byte buffer into a [CoreBridgeInstruction](enum.CoreBridgeInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::Initialize(anchor::deserialize(rest)?),
+            1 => Self::PostMessage(anchor::deserialize(rest)?),
+            2 => Self::PostVaa(anchor::deserialize(rest)?),
+            3 => Self::SetFees(anchor::deserialize(rest)?),
+            4 => Self::TransferFees,
+            5 => Self::UpgradeContract,
+            6 => Self::UpgradeGuardianSet,
+            7 => Self::VerifySignatures(anchor::deserialize(rest)?),
+            8 => Self::PostMessageUnreliable(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
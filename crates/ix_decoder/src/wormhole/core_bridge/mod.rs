@@ -0,0 +1,194 @@
+//! Instruction decoding functions for the wormhole core bridge program
+//!
+//! Outbound messages are posted through `post_message`, inbound vaas have their guardian
+//! signatures checked by `verify_signatures` before `post_vaa` writes them to an account,
+//! see [state::MessageData].
+//!
+//! The decoder is not matched by program id, the mainnet deployment is yet to be added.
+//! Callers holding a core bridge instruction can use [CoreBridgeDecoder] directly.
+
+use anyhow::Context;
+use ix::CoreBridgeInstruction;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod state;
+
+const POST_MESSAGE_ACCOUNTS: [&str; 9] = [
+    "bridge",
+    "message",
+    "emitter",
+    "sequence",
+    "payer",
+    "feeCollector",
+    "clock",
+    "rent",
+    "systemProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CoreBridgeDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl CoreBridgeDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = CoreBridgeInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            CoreBridgeInstruction::Initialize(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "bridge",
+                    "guardianSet",
+                    "feeCollector",
+                    "payer",
+                    "clock",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "initialize".to_string(),
+            }),
+            CoreBridgeInstruction::PostMessage(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&POST_MESSAGE_ACCOUNTS),
+                name: "postMessage".to_string(),
+            }),
+            CoreBridgeInstruction::PostVaa(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "guardianSet",
+                    "bridge",
+                    "signatureSet",
+                    "postedVaa",
+                    "payer",
+                    "clock",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "postVaa".to_string(),
+            }),
+            CoreBridgeInstruction::SetFees(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "bridge",
+                    "vaa",
+                    "claim",
+                    "systemProgram",
+                ]),
+                name: "setFees".to_string(),
+            }),
+            CoreBridgeInstruction::TransferFees => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "bridge",
+                    "vaa",
+                    "claim",
+                    "feeCollector",
+                    "recipient",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "transferFees".to_string(),
+                ..Default::default()
+            }),
+            CoreBridgeInstruction::UpgradeContract => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "bridge",
+                    "vaa",
+                    "claim",
+                    "upgradeAuthority",
+                    "spill",
+                    "implementation",
+                    "programData",
+                    "wormholeProgram",
+                    "rent",
+                    "clock",
+                    "bpfLoaderUpgradeable",
+                    "systemProgram",
+                ]),
+                name: "upgradeContract".to_string(),
+                ..Default::default()
+            }),
+            CoreBridgeInstruction::UpgradeGuardianSet => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "bridge",
+                    "vaa",
+                    "claim",
+                    "guardianSetOld",
+                    "guardianSetNew",
+                    "systemProgram",
+                ]),
+                name: "upgradeGuardianSet".to_string(),
+                ..Default::default()
+            }),
+            CoreBridgeInstruction::VerifySignatures(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "guardianSet",
+                    "signatureSet",
+                    "instructions",
+                    "rent",
+                    "systemProgram",
+                ]),
+                name: "verifySignatures".to_string(),
+            }),
+            CoreBridgeInstruction::PostMessageUnreliable(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&POST_MESSAGE_ACCOUNTS),
+                name: "postMessageUnreliable".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+    use solana_sdk::pubkey::Pubkey;
+    use state::MessageData;
+
+    #[test]
+    fn test_decode_post_vaa_and_posted_vaa() {
+        let emitter_address = [7u8; 32];
+        let mut data = vec![2];
+        data.extend(to_vec(&(1u8, 4u32, 1_700_000_000u32, 0u32, 2u16)).unwrap());
+        data.extend(emitter_address);
+        data.extend(to_vec(&(42u64, 1u8, vec![1u8, 2, 3])).unwrap());
+        let decoded = CoreBridgeDecoder::new(PartiallyDecodedInstruction {
+            program_id: Pubkey::new_unique(),
+            accounts: (0..8).map(|_| Pubkey::new_unique()).collect(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "postVaa");
+        assert_eq!(decoded.data["emitterChain"], 2);
+        assert_eq!(decoded.data["emitterAddress"], hex::encode(emitter_address));
+        assert_eq!(decoded.data["sequence"], 42);
+        assert_eq!(decoded.data["payload"], "010203");
+        assert!(decoded.accounts.contains_key("postedVaa"));
+
+        let signature_set = Pubkey::new_unique();
+        let mut data = state::POSTED_VAA_MAGIC.to_vec();
+        data.extend(to_vec(&(1u8, 1u8, 1_700_000_000u32, signature_set, 0u32, 0u32)).unwrap());
+        data.extend(to_vec(&(42u64, 2u16, emitter_address, vec![1u8, 2, 3])).unwrap());
+        let message = MessageData::unpack(&data).unwrap();
+        assert_eq!(message.vaa_signature_account, signature_set);
+        assert_eq!(message.sequence, 42);
+        assert_eq!(message.emitter_chain, 2);
+        assert_eq!(message.emitter_address, emitter_address);
+        assert_eq!(message.payload, vec![1, 2, 3]);
+        assert!(MessageData::unpack(&data[3..]).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! Account types
+//!
+//! Posted messages and vaas share a layout, told apart by a three byte magic rather than
+//! an anchor discriminator.
+
+use anyhow::{anyhow, Context};
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{serialize_hex, serialize_pubkey};
+
+/// magic of a message posted through `post_message`
+pub const POSTED_MESSAGE_MAGIC: &[u8; 3] = b"msg";
+/// magic of a message posted through `post_message_unreliable`
+pub const POSTED_MESSAGE_UNRELIABLE_MAGIC: &[u8; 3] = b"msu";
+/// magic of a vaa posted through `post_vaa`
+pub const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+
+/// A message emitted from solana, or a vaa received from another chain. Addresses of
+/// other chains are left padded to 32 bytes.
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageData {
+    pub vaa_version: u8,
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub vaa_signature_account: Pubkey,
+    pub submission_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    #[serde(serialize_with = "serialize_hex")]
+    pub emitter_address: [u8; 32],
+    /// decoded by [crate::wormhole::token_bridge::payload] for token bridge messages
+    #[serde(serialize_with = "serialize_hex")]
+    pub payload: Vec<u8>,
+}
+
+impl MessageData {
+    /// Unpacks a posted message or posted vaa account
+    pub fn unpack(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 3 {
+            return Err(anyhow!("account data is too short"));
+        }
+        let (magic, mut input) = data.split_at(3);
+        if magic != POSTED_MESSAGE_MAGIC
+            && magic != POSTED_MESSAGE_UNRELIABLE_MAGIC
+            && magic != POSTED_VAA_MAGIC
+        {
+            return Err(anyhow!("account is not a posted message or vaa"));
+        }
+        Self::deserialize(&mut input).with_context(|| "failed to deserialize message data")
+    }
+}
@@ -0,0 +1,70 @@
+//! Instruction decoding functions for the wormhole programs
+
+use crate::types::{
+    AddressFormat, PartiallyDecodedInstruction, ProgramDecoder, ProgramDecoderMatcher,
+};
+use anyhow::Context;
+use core_bridge::CoreBridgeDecoder;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use token_bridge::TokenBridgeDecoder;
+
+pub mod core_bridge;
+pub mod token_bridge;
+
+/// the wormhole chain id of solana, bridges built on wormhole sharing its chain ids
+pub const SOLANA_CHAIN_ID: u16 = 1;
+
+pub static WORMHOLE_DECODER: Lazy<Arc<dyn ProgramDecoderMatcher>> =
+    Lazy::new(|| Arc::new(WormholeProgramDecoderMatcher {}) as Arc<dyn ProgramDecoderMatcher>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Programs {
+    CoreBridge(CoreBridgeDecoder),
+    TokenBridge(TokenBridgeDecoder),
+}
+
+/// used to handle iplementation of the ProgramDecoderMatcher trait
+/// which determines if the instruction can be decoded
+pub struct WormholeProgramDecoderMatcher {}
+
+impl ProgramDecoder for Programs {
+    fn decode(&self) -> anyhow::Result<crate::types::DecodedInstruction> {
+        match self {
+            Self::CoreBridge(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Wormhole::CoreBridge"),
+            Self::TokenBridge(decoder) => decoder
+                .decode()
+                .with_context(|| "failed to decode Wormhole::TokenBridge"),
+        }
+    }
+    fn debug(&self) -> String {
+        match self {
+            Self::CoreBridge(decoder) => format!("{:#?}", decoder),
+            Self::TokenBridge(decoder) => format!("{:#?}", decoder),
+        }
+    }
+}
+
+impl ProgramDecoderMatcher for WormholeProgramDecoderMatcher {
+    fn try_new(&self, ix: &PartiallyDecodedInstruction) -> Option<Box<dyn ProgramDecoder>> {
+        match ix.program_id {
+            token_bridge::PROGRAM_ID => Some(Box::new(Programs::TokenBridge(
+                TokenBridgeDecoder::new(ix.clone()),
+            ))),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the address format of a chain, by its wormhole chain id
+pub fn address_format(chain_id: u16) -> AddressFormat {
+    match chain_id {
+        SOLANA_CHAIN_ID => AddressFormat::Solana,
+        // ethereum, bsc, polygon, avalanche, fantom, celo, moonbeam, arbitrum, optimism
+        // and base
+        2 | 4 | 5 | 6 | 10 | 14 | 16 | 23 | 24 | 30 => AddressFormat::Evm,
+        _ => AddressFormat::Hex,
+    }
+}
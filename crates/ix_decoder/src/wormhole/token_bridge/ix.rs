@@ -0,0 +1,98 @@
+//! Instruction types
+//!
+//! Instructions are borsh encoded, the variant index being the leading byte. Completions
+//! take no arguments, the transfer being read from the posted vaa account.
+
+use anyhow::anyhow;
+use borsh::BorshDeserialize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor,
+    types::{serialize_hex, serialize_option_pubkey, serialize_pubkey},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeInstruction {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub wormhole: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestTokenInstruction {
+    pub nonce: u32,
+}
+
+/// Arguments of `transfer_native` and `transfer_wrapped`, the amount being in the units
+/// of the mint and the fee paid to the relayer completing the transfer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferInstruction {
+    pub nonce: u32,
+    pub amount: u64,
+    pub fee: u64,
+    #[serde(serialize_with = "serialize_hex")]
+    pub target_address: [u8; 32],
+    pub target_chain: u16,
+}
+
+/// Arguments of `transfer_native_with_payload` and `transfer_wrapped_with_payload`
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferWithPayloadInstruction {
+    pub nonce: u32,
+    pub amount: u64,
+    #[serde(serialize_with = "serialize_hex")]
+    pub target_address: [u8; 32],
+    pub target_chain: u16,
+    #[serde(serialize_with = "serialize_hex")]
+    pub payload: Vec<u8>,
+    /// program the transfer is sent on behalf of, the sender account being its pda
+    #[serde(serialize_with = "serialize_option_pubkey")]
+    pub cpi_program_id: Option<Pubkey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenBridgeInstruction {
+    Initialize(InitializeInstruction),
+    AttestToken(AttestTokenInstruction),
+    CompleteNative,
+    CompleteWrapped,
+    TransferWrapped(TransferInstruction),
+    TransferNative(TransferInstruction),
+    RegisterChain,
+    CreateWrapped,
+    UpgradeContract,
+    CompleteNativeWithPayload,
+    CompleteWrappedWithPayload,
+    TransferWrappedWithPayload(TransferWithPayloadInstruction),
+    TransferNativeWithPayload(TransferWithPayloadInstruction),
+}
+
+impl TokenBridgeInstruction {
+    /// Unpacks a byte buffer into a [TokenBridgeInstruction](enum.TokenBridgeInstruction.html).
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty instruction data"))?;
+        Ok(match tag {
+            0 => Self::Initialize(anchor::deserialize(rest)?),
+            1 => Self::AttestToken(anchor::deserialize(rest)?),
+            2 => Self::CompleteNative,
+            3 => Self::CompleteWrapped,
+            4 => Self::TransferWrapped(anchor::deserialize(rest)?),
+            5 => Self::TransferNative(anchor::deserialize(rest)?),
+            6 => Self::RegisterChain,
+            7 => Self::CreateWrapped,
+            8 => Self::UpgradeContract,
+            9 => Self::CompleteNativeWithPayload,
+            10 => Self::CompleteWrappedWithPayload,
+            11 => Self::TransferWrappedWithPayload(anchor::deserialize(rest)?),
+            12 => Self::TransferNativeWithPayload(anchor::deserialize(rest)?),
+            _ => return Err(anyhow!("unsupported instruction tag {tag}")),
+        })
+    }
+}
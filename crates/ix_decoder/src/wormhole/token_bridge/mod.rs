@@ -0,0 +1,351 @@
+//! Instruction decoding functions for the wormhole token bridge program
+//!
+//! Outbound transfers post a message through the core bridge, inbound transfers are
+//! completed against a posted vaa. The transfer itself is carried by the message
+//! payload, see [payload::TokenBridgePayload].
+
+use anyhow::Context;
+use ix::TokenBridgeInstruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::types::{to_data_map, DecodedInstruction, PartiallyDecodedInstruction};
+
+pub mod ix;
+pub mod payload;
+
+pub const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+
+const TRANSFER_NATIVE_ACCOUNTS: [&str; 17] = [
+    "payer",
+    "config",
+    "from",
+    "mint",
+    "custody",
+    "authoritySigner",
+    "custodySigner",
+    "bridgeConfig",
+    "message",
+    "emitter",
+    "sequence",
+    "feeCollector",
+    "clock",
+    "rent",
+    "systemProgram",
+    "wormholeProgram",
+    "tokenProgram",
+];
+
+const TRANSFER_NATIVE_WITH_PAYLOAD_ACCOUNTS: [&str; 18] = [
+    "payer",
+    "config",
+    "from",
+    "mint",
+    "custody",
+    "authoritySigner",
+    "custodySigner",
+    "bridgeConfig",
+    "message",
+    "emitter",
+    "sequence",
+    "feeCollector",
+    "clock",
+    "sender",
+    "rent",
+    "systemProgram",
+    "wormholeProgram",
+    "tokenProgram",
+];
+
+const TRANSFER_WRAPPED_ACCOUNTS: [&str; 17] = [
+    "payer",
+    "config",
+    "from",
+    "fromOwner",
+    "mint",
+    "wrappedMeta",
+    "authoritySigner",
+    "bridgeConfig",
+    "message",
+    "emitter",
+    "sequence",
+    "feeCollector",
+    "clock",
+    "rent",
+    "systemProgram",
+    "wormholeProgram",
+    "tokenProgram",
+];
+
+const TRANSFER_WRAPPED_WITH_PAYLOAD_ACCOUNTS: [&str; 18] = [
+    "payer",
+    "config",
+    "from",
+    "fromOwner",
+    "mint",
+    "wrappedMeta",
+    "authoritySigner",
+    "bridgeConfig",
+    "message",
+    "emitter",
+    "sequence",
+    "feeCollector",
+    "clock",
+    "sender",
+    "rent",
+    "systemProgram",
+    "wormholeProgram",
+    "tokenProgram",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TokenBridgeDecoder {
+    ix: PartiallyDecodedInstruction,
+}
+
+impl TokenBridgeDecoder {
+    pub fn new(ix: PartiallyDecodedInstruction) -> Self {
+        Self { ix }
+    }
+    pub fn decode(&self) -> anyhow::Result<DecodedInstruction> {
+        let ix = TokenBridgeInstruction::unpack(&self.ix.data)
+            .with_context(|| "failed to decode instruction")?;
+        match ix {
+            TokenBridgeInstruction::Initialize(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&["payer", "config", "rent", "systemProgram"]),
+                name: "initialize".to_string(),
+            }),
+            TokenBridgeInstruction::AttestToken(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "mint",
+                    "wrappedMeta",
+                    "splMetadata",
+                    "bridgeConfig",
+                    "message",
+                    "emitter",
+                    "sequence",
+                    "feeCollector",
+                    "clock",
+                    "rent",
+                    "systemProgram",
+                    "wormholeProgram",
+                ]),
+                name: "attestToken".to_string(),
+            }),
+            TokenBridgeInstruction::CompleteNative => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "vaa",
+                    "claim",
+                    "endpoint",
+                    "to",
+                    "toFees",
+                    "custody",
+                    "mint",
+                    "custodySigner",
+                    "rent",
+                    "systemProgram",
+                    "tokenProgram",
+                    "wormholeProgram",
+                ]),
+                name: "completeNative".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::CompleteWrapped => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "vaa",
+                    "claim",
+                    "endpoint",
+                    "to",
+                    "toFees",
+                    "mint",
+                    "wrappedMeta",
+                    "mintAuthority",
+                    "rent",
+                    "systemProgram",
+                    "tokenProgram",
+                    "wormholeProgram",
+                ]),
+                name: "completeWrapped".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::TransferWrapped(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TRANSFER_WRAPPED_ACCOUNTS),
+                name: "transferWrapped".to_string(),
+            }),
+            TokenBridgeInstruction::TransferNative(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self.ix.named_accounts(&TRANSFER_NATIVE_ACCOUNTS),
+                name: "transferNative".to_string(),
+            }),
+            TokenBridgeInstruction::RegisterChain => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "endpoint",
+                    "vaa",
+                    "claim",
+                    "rent",
+                    "systemProgram",
+                    "wormholeProgram",
+                ]),
+                name: "registerChain".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::CreateWrapped => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "endpoint",
+                    "vaa",
+                    "claim",
+                    "mint",
+                    "wrappedMeta",
+                    "splMetadata",
+                    "mintAuthority",
+                    "rent",
+                    "systemProgram",
+                    "tokenProgram",
+                    "splMetadataProgram",
+                    "wormholeProgram",
+                ]),
+                name: "createWrapped".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::UpgradeContract => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "vaa",
+                    "claim",
+                    "upgradeAuthority",
+                    "spill",
+                    "implementation",
+                    "programData",
+                    "tokenBridgeProgram",
+                    "rent",
+                    "clock",
+                    "bpfLoaderUpgradeable",
+                    "systemProgram",
+                ]),
+                name: "upgradeContract".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::CompleteNativeWithPayload => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "vaa",
+                    "claim",
+                    "endpoint",
+                    "to",
+                    "redeemer",
+                    "toFees",
+                    "custody",
+                    "mint",
+                    "custodySigner",
+                    "rent",
+                    "systemProgram",
+                    "tokenProgram",
+                    "wormholeProgram",
+                ]),
+                name: "completeNativeWithPayload".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::CompleteWrappedWithPayload => Ok(DecodedInstruction {
+                accounts: self.ix.named_accounts(&[
+                    "payer",
+                    "config",
+                    "vaa",
+                    "claim",
+                    "endpoint",
+                    "to",
+                    "redeemer",
+                    "toFees",
+                    "mint",
+                    "wrappedMeta",
+                    "mintAuthority",
+                    "rent",
+                    "systemProgram",
+                    "tokenProgram",
+                    "wormholeProgram",
+                ]),
+                name: "completeWrappedWithPayload".to_string(),
+                ..Default::default()
+            }),
+            TokenBridgeInstruction::TransferWrappedWithPayload(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&TRANSFER_WRAPPED_WITH_PAYLOAD_ACCOUNTS),
+                name: "transferWrappedWithPayload".to_string(),
+            }),
+            TokenBridgeInstruction::TransferNativeWithPayload(ix) => Ok(DecodedInstruction {
+                data: to_data_map(&ix)?,
+                accounts: self
+                    .ix
+                    .named_accounts(&TRANSFER_NATIVE_WITH_PAYLOAD_ACCOUNTS),
+                name: "transferNativeWithPayload".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::to_vec;
+    use payload::TokenBridgePayload;
+
+    #[test]
+    fn test_decode_transfer_native_and_transfer_payload() {
+        let from = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let target_address = [9u8; 32];
+        let mut data = vec![5];
+        data.extend(to_vec(&(0u32, 1_000_000u64, 0u64, target_address, 2u16)).unwrap());
+        let decoded = TokenBridgeDecoder::new(PartiallyDecodedInstruction {
+            program_id: PROGRAM_ID,
+            accounts: [Pubkey::new_unique(), Pubkey::new_unique(), from, mint].to_vec(),
+            data,
+            stack_height: None,
+        })
+        .decode()
+        .unwrap();
+        assert_eq!(decoded.name, "transferNative");
+        assert_eq!(decoded.accounts["from"], from.to_string());
+        assert_eq!(decoded.accounts["mint"], mint.to_string());
+        assert_eq!(decoded.data["amount"], 1_000_000);
+        assert_eq!(decoded.data["targetChain"], 2);
+        assert_eq!(decoded.data["targetAddress"], hex::encode(target_address));
+
+        // the payload posted for the transfer, with the amount as a big endian u256
+        let mut payload = vec![payload::TRANSFER];
+        payload.extend([0u8; 24]);
+        payload.extend(1_000_000u64.to_be_bytes());
+        payload.extend(mint.to_bytes());
+        payload.extend(1u16.to_be_bytes());
+        payload.extend(target_address);
+        payload.extend(2u16.to_be_bytes());
+        payload.extend([0u8; 32]);
+        let TokenBridgePayload::Transfer(transfer) = TokenBridgePayload::unpack(&payload).unwrap()
+        else {
+            panic!("expected a transfer payload");
+        };
+        assert_eq!(transfer.amount, 1_000_000);
+        assert_eq!(transfer.token_address, mint.to_bytes());
+        assert_eq!(transfer.token_chain, 1);
+        assert_eq!(transfer.to, target_address);
+        assert_eq!(transfer.to_chain, 2);
+        assert_eq!(transfer.fee, 0);
+        assert!(TokenBridgePayload::unpack(&payload[..100]).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Payloads of the messages the token bridge posts, carried by
+//! [MessageData](crate::wormhole::core_bridge::state::MessageData)
+//!
+//! Unlike the instructions these are big endian. Amounts are normalized to at most 8
+//! decimals, addresses of other chains are left padded to 32 bytes.
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::types::{serialize_hex, serialize_u128};
+
+pub const TRANSFER: u8 = 1;
+pub const ASSET_META: u8 = 2;
+pub const TRANSFER_WITH_PAYLOAD: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    #[serde(serialize_with = "serialize_u128")]
+    pub amount: u128,
+    #[serde(serialize_with = "serialize_hex")]
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    /// the token account receiving the transfer when sent to solana
+    #[serde(serialize_with = "serialize_hex")]
+    pub to: [u8; 32],
+    pub to_chain: u16,
+    /// paid to the relayer completing the transfer
+    #[serde(serialize_with = "serialize_u128")]
+    pub fee: u128,
+}
+
+/// Attestation of a token, used to create its wrapped mint on other chains
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetMeta {
+    #[serde(serialize_with = "serialize_hex")]
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    pub decimals: u8,
+    pub symbol: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferWithPayload {
+    #[serde(serialize_with = "serialize_u128")]
+    pub amount: u128,
+    #[serde(serialize_with = "serialize_hex")]
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    /// the program redeeming the transfer when sent to solana
+    #[serde(serialize_with = "serialize_hex")]
+    pub to: [u8; 32],
+    pub to_chain: u16,
+    #[serde(serialize_with = "serialize_hex")]
+    pub from_address: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub payload: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenBridgePayload {
+    Transfer(Transfer),
+    AssetMeta(AssetMeta),
+    TransferWithPayload(TransferWithPayload),
+}
+
+impl TokenBridgePayload {
+    /// Unpacks the payload of a token bridge message
+    pub fn unpack(input: &[u8]) -> anyhow::Result<Self> {
+        let (&id, rest) = input
+            .split_first()
+            .ok_or_else(|| anyhow!("empty payload"))?;
+        let mut reader = Reader(rest);
+        Ok(match id {
+            TRANSFER => Self::Transfer(Transfer {
+                amount: reader.amount()?,
+                token_address: reader.bytes()?,
+                token_chain: reader.u16()?,
+                to: reader.bytes()?,
+                to_chain: reader.u16()?,
+                fee: reader.amount()?,
+            }),
+            ASSET_META => Self::AssetMeta(AssetMeta {
+                token_address: reader.bytes()?,
+                token_chain: reader.u16()?,
+                decimals: reader.bytes::<1>()?[0],
+                symbol: reader.string()?,
+                name: reader.string()?,
+            }),
+            TRANSFER_WITH_PAYLOAD => Self::TransferWithPayload(TransferWithPayload {
+                amount: reader.amount()?,
+                token_address: reader.bytes()?,
+                token_chain: reader.u16()?,
+                to: reader.bytes()?,
+                to_chain: reader.u16()?,
+                from_address: reader.bytes()?,
+                payload: reader.0.to_vec(),
+            }),
+            _ => return Err(anyhow!("unsupported payload id {id}")),
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        if self.0.len() < N {
+            return Err(anyhow!("payload is too short"));
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into()?)
+    }
+    fn u16(&mut self) -> anyhow::Result<u16> {
+        Ok(u16::from_be_bytes(self.bytes()?))
+    }
+    /// amounts are encoded as u256, normalized amounts always fit in the lower half
+    fn amount(&mut self) -> anyhow::Result<u128> {
+        let bytes: [u8; 32] = self.bytes()?;
+        let (high, low) = bytes.split_at(16);
+        if high.iter().any(|&b| b != 0) {
+            return Err(anyhow!("amount overflows u128"));
+        }
+        Ok(u128::from_be_bytes(low.try_into()?))
+    }
+    /// strings are right padded with zeros to 32 bytes
+    fn string(&mut self) -> anyhow::Result<String> {
+        let bytes: [u8; 32] = self.bytes()?;
+        Ok(String::from_utf8_lossy(&bytes)
+            .trim_end_matches('\0')
+            .to_string())
+    }
+}